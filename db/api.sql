@@ -1,6 +1,16 @@
 CREATE SCHEMA IF NOT EXISTS api;
+DO $$
+DECLARE
+    func_ regprocedure;
+BEGIN
+    FOR func_ IN SELECT oid FROM pg_proc WHERE pronamespace = 'api'::regnamespace
+        LOOP
+            EXECUTE 'DROP FUNCTION ' || func_;
+        END LOOP;
+END
+$$;
 
-CREATE OR REPLACE FUNCTION api.fun_auth_signup(a_public_id bigint, a_username varchar, a_email varchar, a_phone varchar, a_password_hash bytea, a_password_salt bytea, a_age int, a_preferred_language varchar, a_agreed_tos boolean, a_agreed_privacy boolean, a_ip_address inet)
+CREATE OR REPLACE FUNCTION api.fun_auth_signup(a_public_id bigint, a_username varchar, a_email varchar, a_phone varchar, a_password_hash varchar, a_age int, a_preferred_language varchar, a_agreed_tos boolean, a_agreed_privacy boolean, a_ip_address inet)
 RETURNS table (
     "user_id" bigint
 )
@@ -22,7 +32,6 @@ BEGIN
                        email,
                        phone_number,
                        password_hash,
-                       password_salt,
                        age,
                        preferred_language,
                        agreed_tos,
@@ -33,7 +42,6 @@ BEGIN
           a_email,
           a_phone,
           a_password_hash,
-          a_password_salt,
           a_age,
           a_preferred_language,
           a_agreed_tos,
@@ -46,7 +54,7 @@ END
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_auth_authenticate(a_username varchar, a_password_hash bytea, a_service_code int, a_device_id varchar, a_device_os varchar, a_ip_address inet)
+CREATE OR REPLACE FUNCTION api.fun_auth_authenticate(a_username varchar, a_service_code int, a_device_id varchar, a_device_os varchar, a_ip_address inet)
 RETURNS table (
     "user_id" bigint,
     "user_public_id" bigint
@@ -56,33 +64,26 @@ AS $$
     
 DECLARE
     is_blocked_     boolean;
-    is_password_ok_ boolean;
     _user_id        bigint;
     _user_public_id bigint;
     _role           enum_role;
 BEGIN
     ASSERT (a_ip_address NOTNULL AND a_device_id NOTNULL AND a_device_os NOTNULL AND
-            a_username NOTNULL AND a_password_hash NOTNULL AND a_service_code NOTNULL);
+            a_username NOTNULL AND a_service_code NOTNULL);
 
-    -- Looking up the user.
-    SELECT pkey_id, u.public_id, is_blocked, (password_hash = a_password_hash), u.role
-    INTO _user_id, _user_public_id, is_blocked_, is_password_ok_, _role
+    -- Looking up the user. The password was verified against fun_auth_get_password_hash.
+    SELECT pkey_id, u.public_id, is_blocked, u.role
+    INTO _user_id, _user_public_id, is_blocked_, _role
     FROM tbl.user u
     WHERE username = a_username;
 
-    -- Log the login attempt.
-    INSERT INTO tbl.login_attempt(fkey_user, username, password_hash, ip_address,
-                                  is_password_ok)
-    VALUES (_user_id, a_username, a_password_hash, a_ip_address, is_password_ok_);
-
-    -- Checking the block status and password, and updating the login info if ok.
+    -- The attempt was logged by fun_auth_begin_login_attempt.
+    -- Checking the block status and role, and updating the login info if ok.
     IF (_user_id ISNULL) THEN
         RAISE SQLSTATE 'R0007'; -- UnknownUser
     END IF;
     IF (is_blocked_) THEN
         RAISE SQLSTATE 'R0008'; -- BlockedUser
-    ELSIF (NOT is_password_ok_) THEN
-        RAISE SQLSTATE 'R0009';
     ELSEIF (_role NOT IN ('admin', 'developer') AND
             a_service_code = (SELECT code FROM api.ADMIN_SERVICE())) OR
            (_role NOT IN ('user', 'admin', 'developer') AND
@@ -91,7 +92,7 @@ BEGIN
     END IF;
     UPDATE tbl.user -- ping
     SET last_ip      = a_ip_address,
-        last_login   = EXTRACT(EPOCH FROM (NOW()))::bigint,
+        last_login   = NOW(),
         logins_count = logins_count + 1
     WHERE pkey_id = _user_id;
 
@@ -107,44 +108,137 @@ END
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_auth_get_password_salt(a_username varchar)
+CREATE OR REPLACE FUNCTION api.fun_auth_get_password_hash(a_username varchar)
 RETURNS table (
-    "salt" bytea
+    "user_id" bigint,
+    "password_hash" varchar,
+    "password_salt" bytea,
+    "totp_secret" bytea,
+    "totp_pending_secret" bytea
 )
 LANGUAGE plpgsql
 AS $$
     
-DECLARE
-  user_id bigint;
 BEGIN
   ASSERT (a_username NOTNULL);
 
-  -- Looking up the user.
-  SELECT pkey_id, u.password_salt
-  INTO user_id, salt
-  FROM tbl.user u
-  WHERE username = a_username;
+  -- Looking up the user, no row if unknown. The salt is only set for legacy SHA-256 hashes.
+  RETURN QUERY SELECT u.pkey_id, u.password_hash, u.password_salt, u.totp_secret,
+                      u.totp_pending_secret
+               FROM tbl.user u
+               WHERE u.username = a_username;
+END
+            
+$$;
+        
 
-  IF (user_id ISNULL) THEN
-    RAISE SQLSTATE 'R0007'; -- UnknownUser
+CREATE OR REPLACE FUNCTION api.fun_auth_begin_login_attempt(a_username varchar, a_ip_address inet, a_device_id varchar, a_device_os varchar, a_username_window interval, a_ip_window interval)
+RETURNS table (
+    "attempt_id" bigint,
+    "username_failures" int,
+    "username_last_failure" timestamptz,
+    "ip_failures" int,
+    "ip_last_failure" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+  attempt_id_            bigint;
+  username_failures_     integer;
+  username_last_failure_ timestamptz;
+  ip_failures_           integer;
+  ip_last_failure_       timestamptz;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_ip_address NOTNULL AND a_username_window NOTNULL AND
+          a_ip_window NOTNULL);
+  -- Concurrent attempts on the username or from the address wait for each other, so each one
+  -- counts the attempts before it. Always locked in this order.
+  PERFORM pg_advisory_xact_lock(hashtext('login_attempt_username'), hashtext(a_username));
+  PERFORM pg_advisory_xact_lock(hashtext('login_attempt_ip'), hashtext(host(a_ip_address)));
+  -- A successful login forgives the earlier failures of the username, not of the address.
+  SELECT COUNT(*), MAX(moment)
+  INTO username_failures_, username_last_failure_
+  FROM tbl.login_attempt
+  WHERE username = a_username
+    AND is_password_ok = FALSE
+    AND NOT is_cleared
+    AND moment > NOW() - a_username_window
+    AND moment > COALESCE((SELECT MAX(moment)
+                           FROM tbl.login_attempt
+                           WHERE username = a_username
+                             AND is_password_ok), '-infinity');
+  SELECT COUNT(*), MAX(moment)
+  INTO ip_failures_, ip_last_failure_
+  FROM tbl.login_attempt
+  WHERE ip_address = a_ip_address
+    AND is_password_ok = FALSE
+    AND NOT is_cleared
+    AND moment > NOW() - a_ip_window;
+  -- Counted as a failure until fun_auth_finish_login_attempt tells otherwise.
+  INSERT INTO tbl.login_attempt(fkey_user, username, ip_address, device_id, device_os,
+                                is_password_ok)
+  VALUES ((SELECT pkey_id FROM tbl.user WHERE username = a_username), a_username,
+          a_ip_address, a_device_id, a_device_os, FALSE)
+  RETURNING pkey_id INTO attempt_id_;
+  RETURN QUERY SELECT attempt_id_, username_failures_, username_last_failure_, ip_failures_,
+                      ip_last_failure_;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_auth_finish_login_attempt(a_attempt_id bigint, a_is_password_ok boolean DEFAULT NULL)
+RETURNS void
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+  ASSERT (a_attempt_id NOTNULL);
+  -- NULL when the attempt was refused before the password was checked, which is not kept.
+  IF (a_is_password_ok ISNULL) THEN
+    DELETE FROM tbl.login_attempt WHERE pkey_id = a_attempt_id;
+  ELSE
+    UPDATE tbl.login_attempt SET is_password_ok = a_is_password_ok WHERE pkey_id = a_attempt_id;
   END IF;
-  RETURN QUERY SELECT salt;
 END
             
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_auth_set_token(a_user_id bigint, a_user_token uuid, a_admin_token uuid, a_service_code int)
+CREATE OR REPLACE FUNCTION api.fun_auth_rehash_password(a_user_id bigint, a_old_password_hash varchar, a_new_password_hash varchar)
 RETURNS void
 LANGUAGE plpgsql
 AS $$
     
+BEGIN
+  ASSERT (a_user_id NOTNULL AND a_old_password_hash NOTNULL AND a_new_password_hash NOTNULL);
+  -- Skipped if the password changed since it was verified.
+  UPDATE tbl.user
+  SET password_hash = a_new_password_hash,
+      password_salt = NULL
+  WHERE pkey_id = a_user_id
+    AND password_hash = a_old_password_hash;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_auth_create_session(a_user_id bigint, a_service enum_service, a_device_id varchar, a_device_os varchar, a_ip_address inet, a_access_token uuid, a_refresh_token uuid, a_access_token_lifetime interval, a_idle_timeout interval, a_absolute_timeout interval, a_is_two_factor_ok boolean)
+RETURNS table (
+    "access_expires_at" timestamptz,
+    "expires_at" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
 DECLARE
-  rc_         integer;
   is_blocked_ boolean;
 BEGIN
-  ASSERT (a_user_id NOTNULL AND a_service_code NOTNULL AND a_user_token NOTNULL AND
-          a_admin_token NOTNULL);
+  ASSERT (a_user_id NOTNULL AND a_service NOTNULL AND a_device_id NOTNULL AND
+          a_device_os NOTNULL AND a_ip_address NOTNULL AND a_access_token NOTNULL AND
+          a_refresh_token NOTNULL AND a_access_token_lifetime NOTNULL AND
+          a_idle_timeout NOTNULL AND a_absolute_timeout NOTNULL AND a_is_two_factor_ok NOTNULL);
   -- Looking up the user.
   SELECT is_blocked INTO is_blocked_ FROM tbl.user WHERE pkey_id = a_user_id;
   IF (is_blocked_ ISNULL) THEN
@@ -152,21 +246,30 @@ BEGIN
   ELSIF (is_blocked_) THEN
     RAISE SQLSTATE 'R0008'; -- BlockedUser
   END IF;
-
-  -- Setting up the token.
-  IF a_service_code = (SELECT code FROM api.USER_SERVICE()) THEN
-    UPDATE tbl.user
-    SET user_token = a_user_token
-    WHERE pkey_id = a_user_id;
-  END IF;
-  IF a_service_code = (SELECT code FROM api.ADMIN_SERVICE())  THEN
-    UPDATE tbl.user
-    SET admin_token = a_admin_token
-    WHERE pkey_id = a_user_id;
+  -- Admins may require the second factor for a service.
+  IF (NOT a_is_two_factor_ok AND
+      COALESCE((SELECT is_2fa_required FROM tbl.service_setting WHERE service = a_service),
+               FALSE)) THEN
+    RAISE SQLSTATE 'R0016'; -- TwoFactorRequired
   END IF;
 
-  GET DIAGNOSTICS rc_ := ROW_COUNT;
-  ASSERT (rc_ = 1);
+  -- Cleaning up the sessions of the user that ended, and the one of this device, which the new
+  -- session replaces. Deleting it closes its connections.
+  DELETE
+  FROM tbl.session AS s
+  WHERE s.fkey_user = a_user_id
+    AND (s.expires_at <= NOW() OR s.last_seen_at + s.idle_timeout <= NOW() OR
+         (s.service = a_service AND s.device_id = a_device_id));
+
+  RETURN QUERY INSERT INTO tbl.session AS s (fkey_user, service, device_id, device_os,
+                                             ip_address, access_token, refresh_token,
+                                             access_expires_at, idle_timeout, expires_at,
+                                             is_two_factor_ok)
+               VALUES (a_user_id, a_service, a_device_id, a_device_os, a_ip_address,
+                       a_access_token, a_refresh_token,
+                       LEAST(NOW() + a_access_token_lifetime, NOW() + a_absolute_timeout),
+                       a_idle_timeout, NOW() + a_absolute_timeout, a_is_two_factor_ok)
+               RETURNING s.access_expires_at, s.expires_at;
 END
             
 $$;
@@ -175,106 +278,229 @@ $$;
 CREATE OR REPLACE FUNCTION api.fun_auth_authorize(a_username varchar, a_token uuid, a_service enum_service, a_device_id varchar, a_device_os varchar, a_ip_address inet)
 RETURNS table (
     "user_id" bigint,
-    "role" enum_role
+    "role" enum_role,
+    "preferred_language" varchar,
+    "session_id" bigint
 )
 LANGUAGE plpgsql
 AS $$
     
 DECLARE
-    rc_          integer;
-    is_token_ok_ boolean;
-    user_id_     bigint;
-    role_        enum_role;
+    user_id_            bigint;
+    role_               enum_role;
+    preferred_language_ varchar;
+    session_id_         bigint;
+    is_session_ok_      boolean;
+    is_token_ok_        boolean;
+    is_blocked_         boolean;
 
 BEGIN
     ASSERT (a_username NOTNULL AND a_token NOTNULL AND a_service NOTNULL AND
             a_device_id NOTNULL AND a_device_os NOTNULL);
 
-    -- Looking up the user
-    CASE a_service
-        WHEN 'user'::enum_service
-            THEN SELECT pkey_id, u.role, (user_token = a_token)
-                 INTO user_id_, role_, is_token_ok_
-                 FROM tbl.user AS u
-                 WHERE username = a_username;
-        WHEN 'admin'::enum_service
-            THEN SELECT pkey_id, u.role, (admin_token = a_token)
-                 INTO user_id_, role_, is_token_ok_
-                 FROM tbl.user AS u
-                 WHERE username = a_username;
-        ELSE RAISE SQLSTATE 'R0001'; -- InvalidArgument
-        END CASE;
-    GET DIAGNOSTICS rc_ := ROW_COUNT;
-    IF (rc_ <> 1) THEN
+    -- Looking up the user, a blocked one keeps no access even with a valid token
+    SELECT pkey_id, u.role, u.preferred_language, u.is_blocked
+    INTO user_id_, role_, preferred_language_, is_blocked_
+    FROM tbl.user AS u
+    WHERE username = a_username;
+    IF (user_id_ ISNULL) THEN
         RAISE SQLSTATE 'R0007'; -- UnknownUser
     END IF;
+    IF (is_blocked_) THEN
+        RAISE SQLSTATE 'R0008'; -- BlockedUser
+    END IF;
+
+    -- Looking up the session of the token
+    SELECT s.pkey_id,
+           (s.last_seen_at + s.idle_timeout > NOW() AND s.expires_at > NOW()),
+           (s.access_expires_at > NOW())
+    INTO session_id_, is_session_ok_, is_token_ok_
+    FROM tbl.session AS s
+    WHERE s.fkey_user = user_id_
+      AND s.service = a_service
+      AND s.access_token = a_token;
 
     -- Log the authorization attempt
     INSERT INTO tbl.authorization_attempt(fkey_user, ip_address, is_token_ok)
-    VALUES (user_id_, a_ip_address, is_token_ok_ NOTNULL AND is_token_ok_);
+    VALUES (user_id_, a_ip_address, session_id_ NOTNULL AND is_session_ok_ AND is_token_ok_);
 
     -- Validating the token
-    IF NOT is_token_ok_ OR is_token_ok_ IS NULL THEN
+    IF (session_id_ ISNULL OR NOT is_session_ok_) THEN
         RAISE SQLSTATE 'R000A'; -- InvalidToken
+    ELSIF (NOT is_token_ok_) THEN
+        RAISE SQLSTATE 'R0013'; -- TokenExpired
     END IF;
 
-    -- Updating the device info
-    CASE a_service
-        WHEN 'user'::enum_service
-            THEN UPDATE tbl.user
-                 SET user_device_id = a_device_id
-                 WHERE pkey_id = user_id_
-                   AND user_token = a_token;
-        WHEN 'admin'::enum_service
-            THEN UPDATE tbl.user
-                 SET admin_device_id = a_device_id
-                 WHERE pkey_id = user_id_
-                   AND admin_token = a_token;
-        END CASE;
-    GET DIAGNOSTICS rc_ := ROW_COUNT;
-    ASSERT (rc_ = 1);
-    RETURN QUERY SELECT user_id_, role_;
+    -- Keeping the session alive
+    UPDATE tbl.session
+    SET last_seen_at = NOW(),
+        device_os    = a_device_os,
+        ip_address   = a_ip_address
+    WHERE pkey_id = session_id_;
+    RETURN QUERY SELECT user_id_, role_, preferred_language_, session_id_;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_auth_refresh(a_refresh_token uuid, a_device_id varchar, a_device_os varchar, a_ip_address inet, a_new_access_token uuid, a_new_refresh_token uuid, a_access_token_lifetime interval)
+RETURNS table (
+    "username" varchar,
+    "access_expires_at" timestamptz,
+    "expires_at" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+  session_id_    bigint;
+  username_      varchar;
+  is_session_ok_ boolean;
+  is_blocked_    boolean;
+BEGIN
+  ASSERT (a_refresh_token NOTNULL AND a_device_id NOTNULL AND a_device_os NOTNULL AND
+          a_ip_address NOTNULL AND a_new_access_token NOTNULL AND
+          a_new_refresh_token NOTNULL AND a_access_token_lifetime NOTNULL);
+  -- Looking up the session, which only the device it belongs to can refresh. Concurrent
+  -- refreshes wait here and then find the token replaced.
+  SELECT s.pkey_id,
+         u.username,
+         (s.last_seen_at + s.idle_timeout > NOW() AND s.expires_at > NOW()),
+         u.is_blocked
+  INTO session_id_, username_, is_session_ok_, is_blocked_
+  FROM tbl.session AS s
+         JOIN tbl.user AS u ON u.pkey_id = s.fkey_user
+  WHERE s.refresh_token = a_refresh_token
+    AND s.device_id = a_device_id
+  FOR UPDATE OF s;
+
+  IF (session_id_ ISNULL OR NOT is_session_ok_) THEN
+    RAISE SQLSTATE 'R000A'; -- InvalidToken
+  ELSIF (is_blocked_) THEN
+    RAISE SQLSTATE 'R0008'; -- BlockedUser
+  END IF;
+
+  -- Both tokens are replaced, so a refresh token works once.
+  RETURN QUERY UPDATE tbl.session AS s
+               SET access_token      = a_new_access_token,
+                   refresh_token     = a_new_refresh_token,
+                   access_expires_at = LEAST(NOW() + a_access_token_lifetime, s.expires_at),
+                   last_seen_at      = NOW(),
+                   device_os         = a_device_os,
+                   ip_address        = a_ip_address
+               WHERE s.pkey_id = session_id_
+               RETURNING username_, s.access_expires_at, s.expires_at;
 END
             
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_auth_change_password(a_username varchar, a_old_password_hash bytea, a_new_password_hash bytea, a_device_id varchar, a_device_os varchar, a_ip_address inet)
+CREATE OR REPLACE FUNCTION api.fun_auth_logout(a_username varchar, a_token uuid)
 RETURNS void
 LANGUAGE plpgsql
 AS $$
     
 DECLARE
-  is_blocked_     boolean;
-  is_password_ok_ boolean;
-  user_id_        bigint;
+  rc_ integer;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_token NOTNULL);
+  -- An expired access token still ends its session.
+  DELETE
+  FROM tbl.session AS s
+    USING tbl.user AS u
+  WHERE u.pkey_id = s.fkey_user
+    AND u.username = a_username
+    AND s.access_token = a_token;
+  GET DIAGNOSTICS rc_ := ROW_COUNT;
+  IF (rc_ <> 1) THEN
+    RAISE SQLSTATE 'R000A'; -- InvalidToken
+  END IF;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_auth_logout_all(a_username varchar, a_token uuid)
+RETURNS table (
+    "revoked_sessions" int
+)
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+  user_id_ bigint;
+  count_   integer;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_token NOTNULL);
+  -- Any session of the user that has not ended, its access token may be expired.
+  SELECT s.fkey_user
+  INTO user_id_
+  FROM tbl.session AS s
+         JOIN tbl.user AS u ON u.pkey_id = s.fkey_user
+  WHERE u.username = a_username
+    AND s.access_token = a_token
+    AND s.last_seen_at + s.idle_timeout > NOW()
+    AND s.expires_at > NOW();
+  IF (user_id_ ISNULL) THEN
+    RAISE SQLSTATE 'R000A'; -- InvalidToken
+  END IF;
+
+  DELETE FROM tbl.session WHERE fkey_user = user_id_;
+  GET DIAGNOSTICS count_ := ROW_COUNT;
+  RETURN QUERY SELECT count_;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_auth_filter_sessions(a_session_ids bigint[])
+RETURNS table (
+    "session_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+  ASSERT (a_session_ids NOTNULL);
+  -- The ones that still exist.
+  RETURN QUERY SELECT s.pkey_id
+               FROM tbl.session AS s
+               WHERE s.pkey_id = ANY (a_session_ids);
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_auth_change_password(a_username varchar, a_is_old_password_ok boolean, a_new_password_hash varchar, a_device_id varchar, a_device_os varchar, a_ip_address inet)
+RETURNS void
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+  is_blocked_ boolean;
+  user_id_    bigint;
 BEGIN
   ASSERT (a_ip_address NOTNULL AND a_device_id NOTNULL AND a_device_os NOTNULL AND
-          a_username NOTNULL AND a_old_password_hash NOTNULL AND
+          a_username NOTNULL AND a_is_old_password_ok NOTNULL AND
           a_new_password_hash NOTNULL);
-  -- Looking up the user.
-  SELECT pkey_id, is_blocked, (password_hash = a_old_password_hash)
-  INTO user_id_, is_blocked_, is_password_ok_
+  -- Looking up the user. The old password was verified against fun_auth_get_password_hash.
+  SELECT pkey_id, is_blocked
+  INTO user_id_, is_blocked_
   FROM tbl.user u
   WHERE username = a_username;
 
-  -- Log the login attempt.
-  INSERT INTO tbl.login_attempt(fkey_user, username, password_hash, ip_address,
-                                device_id, device_os, is_password_ok)
-  VALUES (user_id_, a_username, a_old_password_hash, a_ip_address, a_device_id,
-          a_device_os, is_password_ok_);
-
+  -- The attempt was logged by fun_auth_begin_login_attempt.
   -- Checking the block status and password, and updating the login info if ok.
   IF (user_id_ NOTNULL) THEN
     IF (is_blocked_) THEN
       RAISE SQLSTATE 'R0008'; -- BlockedUser
-    ELSIF (NOT is_password_ok_) THEN
+    ELSIF (NOT a_is_old_password_ok) THEN
       RAISE SQLSTATE 'R0009'; -- InvalidPassword
     END IF;
-    ASSERT (a_old_password_hash <> a_new_password_hash);
 
     UPDATE tbl.user
-    SET password_hash = a_new_password_hash
+    SET password_hash = a_new_password_hash,
+        password_salt = NULL
     WHERE username = a_username;
   ELSE
       RAISE SQLSTATE 'R0007'; -- UnknownUser
@@ -294,29 +520,35 @@ LANGUAGE plpgsql
 AS $$
     
 BEGIN
-  RETURN QUERY SELECT q.pkey_id,
+  RETURN QUERY SELECT q.pkey_id::int,
                       q.content,
                       q.category
-               FROM tbl.recovery_question_data q;
+               FROM tbl.recovery_question_data q
+               ORDER BY q.pkey_id;
 END
             
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_auth_set_recovery_questions(a_user_id bigint, a_question_ids int[], a_answers int[])
+CREATE OR REPLACE FUNCTION api.fun_auth_set_recovery_questions(a_user_id bigint, a_question_ids int[], a_answer_hashes varchar[])
 RETURNS void
 LANGUAGE plpgsql
 AS $$
     
-DECLARE
-  rc_ integer;
 BEGIN
-  ASSERT (a_user_id NOTNULL AND a_question_ids NOTNULL AND a_answers NOTNULL);
+  ASSERT (a_user_id NOTNULL AND a_question_ids NOTNULL AND a_answer_hashes NOTNULL);
+  -- Every question has to exist and be picked once, with one answer each.
+  IF (CARDINALITY(a_question_ids) = 0 OR
+      CARDINALITY(a_question_ids) <> CARDINALITY(a_answer_hashes) OR
+      (SELECT COUNT(DISTINCT x) FROM UNNEST(a_question_ids) x) <> CARDINALITY(a_question_ids) OR
+      (SELECT COUNT(*) FROM tbl.recovery_question_data WHERE pkey_id = ANY (a_question_ids)) <>
+      CARDINALITY(a_question_ids)) THEN
+    RAISE SQLSTATE 'R000R'; -- InvalidRecoveryQuestions
+  END IF;
   DELETE FROM tbl.recovery_question WHERE fkey_user = a_user_id;
   INSERT INTO tbl.recovery_question(fkey_user, fkey_question, answer)
-  VALUES (a_user_id, UNNEST(a_question_ids), UNNEST(a_answers));
-  GET DIAGNOSTICS rc_ := ROW_COUNT;
-  ASSERT (rc_ > 0);
+  SELECT a_user_id, t.question_id, t.answer_hash
+  FROM UNNEST(a_question_ids, a_answer_hashes) t(question_id, answer_hash);
 END
             
 $$;
@@ -324,7 +556,7 @@ $$;
 
 CREATE OR REPLACE FUNCTION api.fun_auth_basic_authenticate(a_username varchar, a_device_id varchar, a_device_os varchar, a_ip_address inet)
 RETURNS table (
-    "user_id" inet
+    "user_id" bigint
 )
 LANGUAGE plpgsql
 AS $$
@@ -339,9 +571,8 @@ BEGIN
   INTO user_id_, is_blocked_
   FROM tbl.user
   WHERE username = LOWER(a_username);
-  INSERT INTO tbl.login_attempt(fkey_user, username, password_hash, ip_address,
-                                device_id, device_os)
-  VALUES (user_id_, a_username, '', a_ip_address, a_device_id, a_device_os);
+  INSERT INTO tbl.login_attempt(fkey_user, username, ip_address, device_id, device_os)
+  VALUES (user_id_, a_username, a_ip_address, a_device_id, a_device_os);
   IF (user_id_ ISNULL) THEN
     RAISE SQLSTATE 'R0007'; -- UnknownUser
   ELSEIF (is_blocked_) THEN
@@ -353,7 +584,7 @@ END
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_auth_get_recovery_questions(a_user_id bigint)
+CREATE OR REPLACE FUNCTION api.fun_auth_get_recovery_questions(a_username varchar)
 RETURNS table (
     "question_id" int,
     "question" varchar
@@ -361,151 +592,1095 @@ RETURNS table (
 LANGUAGE plpgsql
 AS $$
     
+DECLARE
+  user_id_ bigint;
 BEGIN
-  ASSERT (a_user_id NOTNULL);
-  RETURN QUERY SELECT qd.pkey_id,
+  ASSERT (a_username NOTNULL);
+  SELECT pkey_id INTO user_id_ FROM tbl.user WHERE username = a_username;
+  IF (user_id_ ISNULL) THEN
+    RAISE SQLSTATE 'R0007'; -- UnknownUser
+  END IF;
+  IF NOT EXISTS(SELECT * FROM tbl.recovery_question WHERE fkey_user = user_id_) THEN
+    RAISE SQLSTATE 'R0010'; -- RecoveryQuestionsNotSet
+  END IF;
+  RETURN QUERY SELECT qd.pkey_id::int,
                       qd.content
                FROM tbl.recovery_question_data qd
                       JOIN tbl.recovery_question q ON qd.pkey_id = q.fkey_question
-               WHERE q.fkey_user = a_user_id;
+               WHERE q.fkey_user = user_id_
+               ORDER BY q.pkey_id;
 END
             
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_submit_recovery_answers(a_user_id bigint, a_question_ids int[], a_answers varchar[], a_password_reset_token uuid, a_token_valid int)
-RETURNS void
+CREATE OR REPLACE FUNCTION api.fun_auth_begin_recovery_attempt(a_username varchar, a_ip_address inet, a_max_failed_attempts int, a_attempt_window interval)
+RETURNS table (
+    "attempt_id" bigint,
+    "user_id" bigint,
+    "question_id" int,
+    "answer_hash" varchar
+)
 LANGUAGE plpgsql
 AS $$
     
 DECLARE
-  correct_answers_ varchar[];
+  user_id_    bigint;
+  attempt_id_ bigint;
 BEGIN
-  IF (SELECT COUNT(pkey_id) FROM tbl.recovery_question WHERE fkey_user = a_user_id) !=
-     CARDINALITY(a_question_ids) THEN
-    RAISE SQLSTATE 'R0011'; -- MustSubmitAllRecoveryQuestions
+  ASSERT (a_username NOTNULL AND a_ip_address NOTNULL AND a_max_failed_attempts NOTNULL AND
+          a_attempt_window NOTNULL);
+  -- Locked, so concurrent submissions for the user each count the ones before them.
+  SELECT pkey_id INTO user_id_ FROM tbl.user WHERE username = a_username FOR UPDATE;
+  IF (user_id_ ISNULL) THEN
+    RAISE SQLSTATE 'R0007'; -- UnknownUser
   END IF;
-  SELECT ARRAY_AGG(result.answer)
-  INTO correct_answers_
-  FROM (SELECT q.answer AS answer
-        FROM tbl.recovery_question q
-               JOIN UNNEST(a_question_ids) WITH ORDINALITY t(fkey_question, ord)
-                    USING (fkey_question)
-        WHERE fkey_user = a_user_id
-        ORDER BY t.ord) result;
-  IF a_answers != correct_answers_ THEN
-    RAISE SQLSTATE 'R000T'; -- WrongRecoveryAnswers
+  -- Guessing answers is limited per user, whatever the address.
+  IF (SELECT COUNT(*)
+      FROM tbl.password_reset_attempt
+      WHERE fkey_user = user_id_
+        AND NOT is_answers_ok
+        AND initiated_at > NOW() - a_attempt_window) >= a_max_failed_attempts THEN
+    RAISE SQLSTATE 'R000B'; -- TemporarilyUnavailable
   END IF;
-  UPDATE tbl.user
-  SET password_reset_token = a_password_reset_token,
-      reset_token_valid    = a_token_valid
-  WHERE pkey_id = a_user_id;
+  IF NOT EXISTS(SELECT * FROM tbl.recovery_question WHERE fkey_user = user_id_) THEN
+    RAISE SQLSTATE 'R0010'; -- RecoveryQuestionsNotSet
+  END IF;
+  -- Counted as wrong answers until fun_submit_recovery_answers tells otherwise.
+  INSERT INTO tbl.password_reset_attempt(fkey_user, ip_address, is_answers_ok)
+  VALUES (user_id_, a_ip_address, FALSE)
+  RETURNING pkey_id INTO attempt_id_;
+  RETURN QUERY SELECT attempt_id_, user_id_, q.fkey_question::int, q.answer
+               FROM tbl.recovery_question q
+               WHERE q.fkey_user = user_id_;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_submit_recovery_answers(a_attempt_id bigint, a_user_id bigint, a_is_answers_ok boolean, a_password_reset_token uuid, a_token_lifetime interval)
+RETURNS table (
+    "reset_token_valid" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+  valid_until_ timestamptz;
+BEGIN
+  ASSERT (a_attempt_id NOTNULL AND a_user_id NOTNULL AND a_is_answers_ok NOTNULL AND
+          a_password_reset_token NOTNULL AND a_token_lifetime NOTNULL);
+  -- The answers were verified against fun_auth_begin_recovery_attempt, which recorded the
+  -- attempt as wrong.
+  IF (a_is_answers_ok) THEN
+    UPDATE tbl.password_reset_attempt
+    SET is_answers_ok = TRUE
+    WHERE pkey_id = a_attempt_id
+      AND fkey_user = a_user_id;
+    valid_until_ := NOW() + a_token_lifetime;
+    UPDATE tbl.user
+    SET password_reset_token = a_password_reset_token,
+        reset_token_valid    = valid_until_
+    WHERE pkey_id = a_user_id;
+  END IF;
+  RETURN QUERY SELECT valid_until_;
 END
             
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_auth_reset_password(a_user_id bigint, a_new_password_hash bytea, a_new_password_salt bytea, a_reset_token uuid)
+CREATE OR REPLACE FUNCTION api.fun_auth_reset_password(a_username varchar, a_new_password_hash varchar, a_reset_token uuid)
 RETURNS void
 LANGUAGE plpgsql
 AS $$
     
 DECLARE
-  rc_ integer;
+  user_id_ bigint;
 BEGIN
-  ASSERT (a_user_id NOTNULL AND a_new_password_hash NOTNULL AND a_reset_token NOTNULL);
+  ASSERT (a_username NOTNULL AND a_new_password_hash NOTNULL AND a_reset_token NOTNULL);
   UPDATE tbl.user
   SET password_hash        = a_new_password_hash,
-      password_salt        = a_new_password_salt,
+      password_salt        = NULL,
       password_reset_token = NULL,
-      reset_token_valid    = NULL
-  WHERE pkey_id = a_user_id
+      reset_token_valid    = NULL,
+      last_password_reset  = NOW()
+  WHERE username = a_username
     AND password_reset_token = a_reset_token
-    AND reset_token_valid > EXTRACT(EPOCH FROM NOW())::bigint;
-  GET DIAGNOSTICS rc_ := ROW_COUNT;
-  IF (rc_ <> 1) THEN
+    AND reset_token_valid > NOW()
+  RETURNING pkey_id INTO user_id_;
+  IF (user_id_ ISNULL) THEN
     RAISE SQLSTATE 'R0012'; -- InvalidRecoveryToken
   END IF;
+  -- Whoever knew the old password is logged out.
+  DELETE FROM tbl.session WHERE fkey_user = user_id_;
 END
             
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_admin_list_users(a_offset int, a_limit int)
-RETURNS table (
-    "user_id" bigint,
-    "user_public_id" bigint,
-    "email" varchar,
-    "username" varchar,
-    "role" enum_role,
-    "updated_at" int,
-    "created_at" int
-)
+CREATE OR REPLACE FUNCTION api.fun_auth_enroll_totp(a_user_id bigint, a_secret bytea)
+RETURNS void
 LANGUAGE plpgsql
 AS $$
     
+DECLARE
+  totp_secret_ bytea;
 BEGIN
-    RETURN QUERY SELECT
-        u.pkey_id,
-        u.public_id,
-        u.email,
-        u.username,
-        u.role,
-        u.updated_at::int,
-        u.created_at::int
-    FROM tbl.user AS u
-    ORDER BY user_id
-    OFFSET a_offset
-    LIMIT a_limit;
+  ASSERT (a_user_id NOTNULL AND a_secret NOTNULL);
+  SELECT totp_secret INTO STRICT totp_secret_ FROM tbl.user WHERE pkey_id = a_user_id;
+  IF (totp_secret_ NOTNULL) THEN
+    RAISE SQLSTATE 'R001C'; -- TwoFactorAlreadyEnabled
+  END IF;
+  -- Replaces an enrollment that was never confirmed.
+  UPDATE tbl.user SET totp_pending_secret = a_secret WHERE pkey_id = a_user_id;
 END
-        
+            
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.fun_admin_assign_role(a_operator_user_id bigint, a_user_public_id bigint, a_new_role enum_role)
+CREATE OR REPLACE FUNCTION api.fun_auth_confirm_totp(a_user_id bigint, a_pending_secret bytea, a_step bigint, a_recovery_code_hashes varchar[])
 RETURNS void
 LANGUAGE plpgsql
 AS $$
     
 DECLARE
-    _operator_role enum_role;
+  rc_ integer;
 BEGIN
-    SELECT role FROM tbl.user WHERE pkey_id = a_operator_user_id INTO STRICT _operator_role;
-    IF _operator_role <> 'admin' THEN
-        RAISE SQLSTATE 'R000S'; -- InvalidRole
-    END IF;
-    UPDATE tbl.user SET role = a_new_role WHERE public_id = a_user_public_id;
+  ASSERT (a_user_id NOTNULL AND a_pending_secret NOTNULL AND a_step NOTNULL AND
+          a_recovery_code_hashes NOTNULL);
+  -- The code was verified against the pending secret, which may have been replaced since.
+  UPDATE tbl.user
+  SET totp_secret         = totp_pending_secret,
+      totp_pending_secret = NULL,
+      totp_last_step      = a_step
+  WHERE pkey_id = a_user_id
+    AND totp_secret ISNULL
+    AND totp_pending_secret = a_pending_secret;
+  GET DIAGNOSTICS rc_ := ROW_COUNT;
+  IF (rc_ <> 1) THEN
+    RAISE SQLSTATE 'R001D'; -- TwoFactorNotEnabled
+  END IF;
+  DELETE FROM tbl.totp_recovery_code WHERE fkey_user = a_user_id;
+  INSERT INTO tbl.totp_recovery_code(fkey_user, code_hash)
+  SELECT a_user_id, x
+  FROM UNNEST(a_recovery_code_hashes) x;
 END
+            
+$$;
         
+
+CREATE OR REPLACE FUNCTION api.fun_auth_disable_totp(a_user_id bigint)
+RETURNS void
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+  ASSERT (a_user_id NOTNULL);
+  UPDATE tbl.user
+  SET totp_secret         = NULL,
+      totp_pending_secret = NULL,
+      totp_last_step      = NULL
+  WHERE pkey_id = a_user_id;
+  DELETE FROM tbl.totp_recovery_code WHERE fkey_user = a_user_id;
+  DELETE FROM tbl.login_challenge WHERE fkey_user = a_user_id;
+  -- Sessions on services requiring the second factor no longer qualify.
+  DELETE
+  FROM tbl.session AS s
+  WHERE s.fkey_user = a_user_id
+    AND s.service IN (SELECT service FROM tbl.service_setting WHERE is_2fa_required);
+END
+            
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.AUTH_SERVICE()
+CREATE OR REPLACE FUNCTION api.fun_auth_use_two_factor_code(a_user_id bigint, a_ip_address inet, a_totp_step bigint, a_recovery_code_hash varchar, a_max_failed_attempts int, a_attempt_window interval)
 RETURNS table (
-    "code" int
+    "accepted" boolean
 )
 LANGUAGE plpgsql
 AS $$
-    BEGIN RETURN QUERY SELECT 1; END
+    
+DECLARE
+  rc_ integer := 0;
+BEGIN
+  ASSERT (a_user_id NOTNULL AND a_ip_address NOTNULL AND a_max_failed_attempts NOTNULL AND
+          a_attempt_window NOTNULL);
+  -- Locked, so concurrent codes for the user each count the failures before them.
+  PERFORM 1 FROM tbl.user WHERE pkey_id = a_user_id FOR UPDATE;
+  -- Guessing codes is limited per user, whatever the address or the login.
+  IF (SELECT COUNT(*)
+      FROM tbl.two_factor_failure
+      WHERE fkey_user = a_user_id
+        AND moment > NOW() - a_attempt_window) >= a_max_failed_attempts THEN
+    RAISE SQLSTATE 'R000B'; -- TemporarilyUnavailable
+  END IF;
+  -- A TOTP code verified for a step is accepted once, later steps only. A recovery code is
+  -- used up. Without either the code was wrong.
+  IF (a_totp_step NOTNULL) THEN
+    UPDATE tbl.user
+    SET totp_last_step = a_totp_step
+    WHERE pkey_id = a_user_id
+      AND totp_secret NOTNULL
+      AND (totp_last_step ISNULL OR totp_last_step < a_totp_step);
+    GET DIAGNOSTICS rc_ := ROW_COUNT;
+  ELSIF (a_recovery_code_hash NOTNULL) THEN
+    UPDATE tbl.totp_recovery_code
+    SET used_at = NOW()
+    WHERE fkey_user = a_user_id
+      AND code_hash = a_recovery_code_hash
+      AND used_at ISNULL;
+    GET DIAGNOSTICS rc_ := ROW_COUNT;
+  END IF;
+  -- Reported rather than raised, which would roll the record back.
+  IF (rc_ <> 1) THEN
+    INSERT INTO tbl.two_factor_failure(fkey_user, ip_address) VALUES (a_user_id, a_ip_address);
+  END IF;
+  RETURN QUERY SELECT rc_ = 1;
+END
+            
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.USER_SERVICE()
+CREATE OR REPLACE FUNCTION api.fun_auth_create_login_challenge(a_user_id bigint, a_service enum_service, a_device_id varchar, a_device_os varchar, a_ip_address inet, a_token uuid, a_lifetime interval)
 RETURNS table (
-    "code" int
+    "expires_at" timestamptz
 )
 LANGUAGE plpgsql
 AS $$
-    BEGIN RETURN QUERY SELECT 2; END
+    
+BEGIN
+  ASSERT (a_user_id NOTNULL AND a_service NOTNULL AND a_device_id NOTNULL AND
+          a_device_os NOTNULL AND a_ip_address NOTNULL AND a_token NOTNULL AND
+          a_lifetime NOTNULL);
+  DELETE FROM tbl.login_challenge AS c WHERE c.fkey_user = a_user_id AND c.expires_at <= NOW();
+  RETURN QUERY INSERT INTO tbl.login_challenge AS c (fkey_user, token, service, device_id,
+                                                     device_os, ip_address, expires_at)
+               VALUES (a_user_id, a_token, a_service, a_device_id, a_device_os, a_ip_address,
+                       NOW() + a_lifetime)
+               RETURNING c.expires_at;
+END
+            
 $$;
         
 
-CREATE OR REPLACE FUNCTION api.ADMIN_SERVICE()
+CREATE OR REPLACE FUNCTION api.fun_auth_get_login_challenge(a_username varchar, a_token uuid)
 RETURNS table (
-    "code" int
+    "challenge_id" bigint,
+    "user_id" bigint,
+    "user_public_id" bigint,
+    "service" enum_service,
+    "device_id" varchar,
+    "device_os" varchar,
+    "totp_secret" bytea
 )
 LANGUAGE plpgsql
 AS $$
-    BEGIN RETURN QUERY SELECT 3; END
+    
+DECLARE
+  challenge_id_   bigint;
+  user_id_        bigint;
+  user_public_id_ bigint;
+  service_        enum_service;
+  device_id_      varchar;
+  device_os_      varchar;
+  totp_secret_    bytea;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_token NOTNULL);
+  SELECT c.pkey_id, u.pkey_id, u.public_id, c.service, c.device_id, c.device_os, u.totp_secret
+  INTO challenge_id_, user_id_, user_public_id_, service_, device_id_, device_os_, totp_secret_
+  FROM tbl.login_challenge AS c
+         JOIN tbl.user AS u ON u.pkey_id = c.fkey_user
+  WHERE u.username = a_username
+    AND c.token = a_token
+    AND c.expires_at > NOW();
+  IF (challenge_id_ ISNULL OR totp_secret_ ISNULL) THEN
+    RAISE SQLSTATE 'R0017'; -- InvalidLoginChallenge
+  END IF;
+  RETURN QUERY SELECT challenge_id_, user_id_, user_public_id_, service_, device_id_,
+                      device_os_, totp_secret_;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_auth_complete_login_challenge(a_challenge_id bigint)
+RETURNS void
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+  rc_ integer;
+BEGIN
+  ASSERT (a_challenge_id NOTNULL);
+  -- A challenge completes once.
+  DELETE FROM tbl.login_challenge WHERE pkey_id = a_challenge_id;
+  GET DIAGNOSTICS rc_ := ROW_COUNT;
+  IF (rc_ <> 1) THEN
+    RAISE SQLSTATE 'R0017'; -- InvalidLoginChallenge
+  END IF;
+END
+            
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_admin_list_users(a_offset int, a_limit int)
+RETURNS table (
+    "users" struct_list_users_response_row[]
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT COALESCE(array_agg((
+        u.public_id,
+        u.username,
+        u.email,
+        u.family_name,
+        u.given_name,
+        u.role,
+        u.last_login,
+        u.created_at,
+        u.updated_at
+    )::struct_list_users_response_row ORDER BY u.pkey_id), '{}')
+    FROM (
+        SELECT * FROM tbl.user
+        ORDER BY pkey_id
+        OFFSET a_offset
+        LIMIT a_limit
+    ) AS u;
+END
+        
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_admin_assign_role(a_operator_user_id bigint, a_user_public_id bigint, a_new_role enum_role)
+RETURNS void
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+    _operator_role enum_role;
+BEGIN
+    SELECT role FROM tbl.user WHERE pkey_id = a_operator_user_id INTO STRICT _operator_role;
+    IF _operator_role <> 'admin' THEN
+        RAISE SQLSTATE 'R000S'; -- InvalidRole
+    END IF;
+    UPDATE tbl.user SET role = a_new_role WHERE public_id = a_user_public_id;
+END
+        
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_admin_revoke_sessions(a_operator_user_id bigint, a_user_public_id bigint, a_service enum_service DEFAULT NULL)
+RETURNS table (
+    "revoked_sessions" int
+)
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+    _operator_role enum_role;
+    _user_id       bigint;
+    _count         integer;
+BEGIN
+    SELECT role FROM tbl.user WHERE pkey_id = a_operator_user_id INTO STRICT _operator_role;
+    IF _operator_role <> 'admin' THEN
+        RAISE SQLSTATE 'R000S'; -- InvalidRole
+    END IF;
+    SELECT pkey_id FROM tbl.user WHERE public_id = a_user_public_id INTO _user_id;
+    IF _user_id ISNULL THEN
+        RAISE SQLSTATE 'R0007'; -- UnknownUser
+    END IF;
+    -- Deleting the sessions closes their connections.
+    DELETE
+    FROM tbl.session AS s
+    WHERE s.fkey_user = _user_id
+      AND (a_service ISNULL OR s.service = a_service);
+    GET DIAGNOSTICS _count := ROW_COUNT;
+    RETURN QUERY SELECT _count;
+END
+        
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_admin_list_login_failures(a_username_window interval, a_ip_window interval, a_min_username_failures int, a_min_ip_failures int)
+RETURNS table (
+    "username" varchar,
+    "ip_address" inet,
+    "failures" int,
+    "last_failure" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    -- Counted as in fun_auth_begin_login_attempt.
+    RETURN QUERY
+        SELECT a.username::varchar, NULL::inet, COUNT(*)::int, MAX(a.moment)
+        FROM tbl.login_attempt AS a
+        WHERE a.is_password_ok = FALSE
+          AND NOT a.is_cleared
+          AND a.moment > NOW() - a_username_window
+          AND a.moment > COALESCE((SELECT MAX(s.moment)
+                                   FROM tbl.login_attempt AS s
+                                   WHERE s.username = a.username
+                                     AND s.is_password_ok), '-infinity')
+        GROUP BY a.username
+        HAVING COUNT(*) >= a_min_username_failures
+        UNION ALL
+        SELECT NULL::varchar, a.ip_address, COUNT(*)::int, MAX(a.moment)
+        FROM tbl.login_attempt AS a
+        WHERE a.is_password_ok = FALSE
+          AND NOT a.is_cleared
+          AND a.moment > NOW() - a_ip_window
+        GROUP BY a.ip_address
+        HAVING COUNT(*) >= a_min_ip_failures
+        ORDER BY 4 DESC;
+END
+        
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_admin_clear_login_failures(a_operator_user_id bigint, a_username varchar DEFAULT NULL, a_ip_address inet DEFAULT NULL)
+RETURNS table (
+    "cleared" int
+)
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+    _operator_role enum_role;
+    _count         integer;
+BEGIN
+    SELECT role FROM tbl.user WHERE pkey_id = a_operator_user_id INTO STRICT _operator_role;
+    IF _operator_role <> 'admin' THEN
+        RAISE SQLSTATE 'R000S'; -- InvalidRole
+    END IF;
+    IF a_username ISNULL AND a_ip_address ISNULL THEN
+        RAISE SQLSTATE 'R0001'; -- InvalidArgument
+    END IF;
+    UPDATE tbl.login_attempt
+    SET is_cleared = TRUE
+    WHERE is_password_ok = FALSE
+      AND NOT is_cleared
+      AND (a_username ISNULL OR username = a_username)
+      AND (a_ip_address ISNULL OR ip_address = a_ip_address);
+    GET DIAGNOSTICS _count := ROW_COUNT;
+    RETURN QUERY SELECT _count;
+END
+        
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_admin_set_two_factor_required(a_operator_user_id bigint, a_service enum_service, a_required boolean)
+RETURNS table (
+    "revoked_sessions" int
+)
+LANGUAGE plpgsql
+AS $$
+    
+DECLARE
+    _operator_role enum_role;
+    _operator_totp bytea;
+    _count         integer := 0;
+BEGIN
+    SELECT role, totp_secret
+    FROM tbl.user
+    WHERE pkey_id = a_operator_user_id
+    INTO STRICT _operator_role, _operator_totp;
+    IF _operator_role <> 'admin' THEN
+        RAISE SQLSTATE 'R000S'; -- InvalidRole
+    END IF;
+    -- Requiring what the operator has not enabled would lock them out.
+    IF a_required AND _operator_totp ISNULL THEN
+        RAISE SQLSTATE 'R0016'; -- TwoFactorRequired
+    END IF;
+    INSERT INTO tbl.service_setting(service, is_2fa_required, fkey_updated_by)
+    VALUES (a_service, a_required, a_operator_user_id)
+    ON CONFLICT (service) DO UPDATE SET is_2fa_required = a_required,
+                                        fkey_updated_by = a_operator_user_id,
+                                        updated_at      = NOW();
+    -- Ends the sessions opened without a second factor, even of users who enabled it since.
+    IF a_required THEN
+        DELETE
+        FROM tbl.session AS s
+        WHERE s.service = a_service
+          AND NOT s.is_two_factor_ok;
+        GET DIAGNOSTICS _count := ROW_COUNT;
+    END IF;
+    RETURN QUERY SELECT _count;
+END
+        
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_organization_get(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint,
+    "name" varchar,
+    "country" varchar,
+    "tax_id" varchar,
+    "address" varchar,
+    "note" varchar
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."name", t."country", t."tax_id", t."address", t."note"
+                 FROM tbl."organization" AS t
+                 WHERE t."pkey_id" = a_pkey_id;
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_organization_list(a_offset int, a_limit int, a_name varchar DEFAULT NULL, a_country varchar DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "name" varchar,
+    "country" varchar,
+    "tax_id" varchar,
+    "address" varchar,
+    "note" varchar
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."name", t."country", t."tax_id", t."address", t."note"
+                 FROM tbl."organization" AS t
+                 WHERE (a_name ISNULL OR t."name" = a_name)
+                   AND (a_country ISNULL OR t."country" = a_country)
+                 ORDER BY t."pkey_id"
+                 OFFSET a_offset LIMIT a_limit;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_organization_insert(a_name varchar, a_country varchar, a_tax_id varchar, a_address varchar, a_note varchar)
+RETURNS table (
+    "pkey_id" bigint,
+    "name" varchar,
+    "country" varchar,
+    "tax_id" varchar,
+    "address" varchar,
+    "note" varchar
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY INSERT INTO tbl."organization" AS t ("name", "country", "tax_id", "address", "note")
+                 VALUES (a_name, a_country, a_tax_id, a_address, a_note)
+                 RETURNING t."pkey_id", t."name", t."country", t."tax_id", t."address", t."note";
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_organization_update(a_pkey_id bigint, a_name varchar DEFAULT NULL, a_country varchar DEFAULT NULL, a_tax_id varchar DEFAULT NULL, a_address varchar DEFAULT NULL, a_note varchar DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "name" varchar,
+    "country" varchar,
+    "tax_id" varchar,
+    "address" varchar,
+    "note" varchar
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY UPDATE tbl."organization" AS t
+                 SET "name" = COALESCE(a_name, t."name"),
+                     "country" = COALESCE(a_country, t."country"),
+                     "tax_id" = COALESCE(a_tax_id, t."tax_id"),
+                     "address" = COALESCE(a_address, t."address"),
+                     "note" = COALESCE(a_note, t."note")
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id", t."name", t."country", t."tax_id", t."address", t."note";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_organization_delete(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY DELETE
+                 FROM tbl."organization" AS t
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_asset_get(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint,
+    "short_name" varchar,
+    "long_name" varchar,
+    "description" varchar,
+    "network_type" varchar,
+    "contract_address" varchar,
+    "precision" int,
+    "fkey_prototype" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."short_name", t."long_name", t."description", t."network_type", t."contract_address", t."precision", t."fkey_prototype"
+                 FROM tbl."asset" AS t
+                 WHERE t."pkey_id" = a_pkey_id;
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_asset_list(a_offset int, a_limit int, a_short_name varchar DEFAULT NULL, a_network_type varchar DEFAULT NULL, a_fkey_prototype bigint DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "short_name" varchar,
+    "long_name" varchar,
+    "description" varchar,
+    "network_type" varchar,
+    "contract_address" varchar,
+    "precision" int,
+    "fkey_prototype" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."short_name", t."long_name", t."description", t."network_type", t."contract_address", t."precision", t."fkey_prototype"
+                 FROM tbl."asset" AS t
+                 WHERE (a_short_name ISNULL OR t."short_name" = a_short_name)
+                   AND (a_network_type ISNULL OR t."network_type" = a_network_type)
+                   AND (a_fkey_prototype ISNULL OR t."fkey_prototype" = a_fkey_prototype)
+                 ORDER BY t."pkey_id"
+                 OFFSET a_offset LIMIT a_limit;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_asset_insert(a_short_name varchar, a_long_name varchar, a_description varchar, a_network_type varchar, a_contract_address varchar, a_precision int, a_fkey_prototype bigint DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "short_name" varchar,
+    "long_name" varchar,
+    "description" varchar,
+    "network_type" varchar,
+    "contract_address" varchar,
+    "precision" int,
+    "fkey_prototype" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY INSERT INTO tbl."asset" AS t ("short_name", "long_name", "description", "network_type", "contract_address", "precision", "fkey_prototype")
+                 VALUES (a_short_name, a_long_name, a_description, a_network_type, a_contract_address, a_precision, a_fkey_prototype)
+                 RETURNING t."pkey_id", t."short_name", t."long_name", t."description", t."network_type", t."contract_address", t."precision", t."fkey_prototype";
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_asset_update(a_pkey_id bigint, a_short_name varchar DEFAULT NULL, a_long_name varchar DEFAULT NULL, a_description varchar DEFAULT NULL, a_network_type varchar DEFAULT NULL, a_contract_address varchar DEFAULT NULL, a_precision int DEFAULT NULL, a_fkey_prototype bigint DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "short_name" varchar,
+    "long_name" varchar,
+    "description" varchar,
+    "network_type" varchar,
+    "contract_address" varchar,
+    "precision" int,
+    "fkey_prototype" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY UPDATE tbl."asset" AS t
+                 SET "short_name" = COALESCE(a_short_name, t."short_name"),
+                     "long_name" = COALESCE(a_long_name, t."long_name"),
+                     "description" = COALESCE(a_description, t."description"),
+                     "network_type" = COALESCE(a_network_type, t."network_type"),
+                     "contract_address" = COALESCE(a_contract_address, t."contract_address"),
+                     "precision" = COALESCE(a_precision, t."precision"),
+                     "fkey_prototype" = COALESCE(a_fkey_prototype, t."fkey_prototype")
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id", t."short_name", t."long_name", t."description", t."network_type", t."contract_address", t."precision", t."fkey_prototype";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_asset_delete(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY DELETE
+                 FROM tbl."asset" AS t
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_support_ticket_get(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_handler_user" bigint,
+    "content" varchar,
+    "response" varchar,
+    "created_at" timestamptz,
+    "updated_at" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."fkey_user", t."fkey_handler_user", t."content", t."response", t."created_at", t."updated_at"
+                 FROM tbl."support_ticket" AS t
+                 WHERE t."pkey_id" = a_pkey_id;
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_support_ticket_list(a_offset int, a_limit int, a_fkey_user bigint DEFAULT NULL, a_fkey_handler_user bigint DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_handler_user" bigint,
+    "content" varchar,
+    "response" varchar,
+    "created_at" timestamptz,
+    "updated_at" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."fkey_user", t."fkey_handler_user", t."content", t."response", t."created_at", t."updated_at"
+                 FROM tbl."support_ticket" AS t
+                 WHERE (a_fkey_user ISNULL OR t."fkey_user" = a_fkey_user)
+                   AND (a_fkey_handler_user ISNULL OR t."fkey_handler_user" = a_fkey_handler_user)
+                 ORDER BY t."pkey_id"
+                 OFFSET a_offset LIMIT a_limit;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_support_ticket_insert(a_fkey_user bigint, a_content varchar, a_response varchar, a_fkey_handler_user bigint DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_handler_user" bigint,
+    "content" varchar,
+    "response" varchar,
+    "created_at" timestamptz,
+    "updated_at" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY INSERT INTO tbl."support_ticket" AS t ("fkey_user", "fkey_handler_user", "content", "response")
+                 VALUES (a_fkey_user, a_fkey_handler_user, a_content, a_response)
+                 RETURNING t."pkey_id", t."fkey_user", t."fkey_handler_user", t."content", t."response", t."created_at", t."updated_at";
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_support_ticket_update(a_pkey_id bigint, a_fkey_user bigint DEFAULT NULL, a_fkey_handler_user bigint DEFAULT NULL, a_content varchar DEFAULT NULL, a_response varchar DEFAULT NULL, a_created_at timestamptz DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_handler_user" bigint,
+    "content" varchar,
+    "response" varchar,
+    "created_at" timestamptz,
+    "updated_at" timestamptz
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY UPDATE tbl."support_ticket" AS t
+                 SET "fkey_user" = COALESCE(a_fkey_user, t."fkey_user"),
+                     "fkey_handler_user" = COALESCE(a_fkey_handler_user, t."fkey_handler_user"),
+                     "content" = COALESCE(a_content, t."content"),
+                     "response" = COALESCE(a_response, t."response"),
+                     "created_at" = COALESCE(a_created_at, t."created_at"),
+                     "updated_at" = NOW()
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id", t."fkey_user", t."fkey_handler_user", t."content", t."response", t."created_at", t."updated_at";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_support_ticket_delete(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY DELETE
+                 FROM tbl."support_ticket" AS t
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_favorite_wallet_get(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_wallet" bigint,
+    "user_pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."fkey_user", t."fkey_wallet", t."user_pkey_id"
+                 FROM tbl."favorite_wallet" AS t
+                 WHERE t."pkey_id" = a_pkey_id;
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_favorite_wallet_list(a_offset int, a_limit int, a_fkey_user bigint DEFAULT NULL, a_fkey_wallet bigint DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_wallet" bigint,
+    "user_pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY SELECT t."pkey_id", t."fkey_user", t."fkey_wallet", t."user_pkey_id"
+                 FROM tbl."favorite_wallet" AS t
+                 WHERE (a_fkey_user ISNULL OR t."fkey_user" = a_fkey_user)
+                   AND (a_fkey_wallet ISNULL OR t."fkey_wallet" = a_fkey_wallet)
+                 ORDER BY t."pkey_id"
+                 OFFSET a_offset LIMIT a_limit;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_favorite_wallet_insert(a_fkey_user bigint, a_fkey_wallet bigint, a_user_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_wallet" bigint,
+    "user_pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY INSERT INTO tbl."favorite_wallet" AS t ("fkey_user", "fkey_wallet", "user_pkey_id")
+                 VALUES (a_fkey_user, a_fkey_wallet, a_user_pkey_id)
+                 RETURNING t."pkey_id", t."fkey_user", t."fkey_wallet", t."user_pkey_id";
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_favorite_wallet_update(a_pkey_id bigint, a_fkey_user bigint DEFAULT NULL, a_fkey_wallet bigint DEFAULT NULL, a_user_pkey_id bigint DEFAULT NULL)
+RETURNS table (
+    "pkey_id" bigint,
+    "fkey_user" bigint,
+    "fkey_wallet" bigint,
+    "user_pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY UPDATE tbl."favorite_wallet" AS t
+                 SET "fkey_user" = COALESCE(a_fkey_user, t."fkey_user"),
+                     "fkey_wallet" = COALESCE(a_fkey_wallet, t."fkey_wallet"),
+                     "user_pkey_id" = COALESCE(a_user_pkey_id, t."user_pkey_id")
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id", t."fkey_user", t."fkey_wallet", t."user_pkey_id";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.fun_favorite_wallet_delete(a_pkey_id bigint)
+RETURNS table (
+    "pkey_id" bigint
+)
+LANGUAGE plpgsql
+AS $$
+    
+BEGIN
+    RETURN QUERY DELETE
+                 FROM tbl."favorite_wallet" AS t
+                 WHERE t."pkey_id" = a_pkey_id
+                 RETURNING t."pkey_id";
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;
+END
+
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.AUTH_SERVICE()
+RETURNS table (
+    "code" int
+)
+LANGUAGE plpgsql
+AS $$
+    BEGIN RETURN QUERY SELECT 1; END
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.USER_SERVICE()
+RETURNS table (
+    "code" int
+)
+LANGUAGE plpgsql
+AS $$
+    BEGIN RETURN QUERY SELECT 2; END
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.ADMIN_SERVICE()
+RETURNS table (
+    "code" int
+)
+LANGUAGE plpgsql
+AS $$
+    BEGIN RETURN QUERY SELECT 3; END
+$$;
+        
+
+CREATE OR REPLACE FUNCTION api.ERROR_CODES()
+RETURNS table (
+    "name" varchar,
+    "sqlstate" varchar,
+    "code" int,
+    "http_status" int,
+    "description" varchar
+)
+LANGUAGE plpgsql
+AS $$
+    BEGIN
+    RETURN QUERY SELECT * FROM (VALUES
+        ('InvalidEnumLevel'::varchar, '22P02'::varchar, 3484946, 400, 'Value is not a valid enum variant'::varchar),
+        ('Error'::varchar, 'R0000'::varchar, 45349632, 500, 'Unspecified error'::varchar),
+        ('InvalidArgument'::varchar, 'R0001'::varchar, 45349633, 400, 'An argument is invalid'::varchar),
+        ('InvalidState'::varchar, 'R0002'::varchar, 45349634, 409, 'Operation not allowed in the current state'::varchar),
+        ('InvalidSeq'::varchar, 'R0003'::varchar, 45349635, 400, 'Request sequence number is invalid'::varchar),
+        ('InvalidMethod'::varchar, 'R0004'::varchar, 45349636, 400, 'Unknown method code'::varchar),
+        ('ProtocolViolation'::varchar, 'R0005'::varchar, 45349637, 400, 'Message violates the protocol'::varchar),
+        ('MalformedRequest'::varchar, 'R0006'::varchar, 45349638, 400, 'Request could not be parsed'::varchar),
+        ('UnknownUser'::varchar, 'R0007'::varchar, 45349639, 404, 'User does not exist'::varchar),
+        ('BlockedUser'::varchar, 'R0008'::varchar, 45349640, 403, 'User is blocked'::varchar),
+        ('InvalidPassword'::varchar, 'R0009'::varchar, 45349641, 401, 'Password is incorrect'::varchar),
+        ('InvalidToken'::varchar, 'R000A'::varchar, 45349642, 401, 'Token is invalid or expired'::varchar),
+        ('TemporarilyUnavailable'::varchar, 'R000B'::varchar, 45349643, 503, 'Try again later'::varchar),
+        ('UnexpectedException'::varchar, 'R000C'::varchar, 45349644, 500, 'Unexpected exception'::varchar),
+        ('BackPressureIncreased'::varchar, 'R000D'::varchar, 45349645, 503, 'Server is overloaded'::varchar),
+        ('InvalidPublicId'::varchar, 'R000E'::varchar, 45349646, 400, 'Public id is invalid'::varchar),
+        ('InvalidRange'::varchar, 'R000F'::varchar, 45349647, 400, 'Value is out of range'::varchar),
+        ('BankAccountAlreadyExists'::varchar, 'R000G'::varchar, 45349648, 409, 'Bank account already exists'::varchar),
+        ('InsufficientFunds'::varchar, 'R000H'::varchar, 45349649, 400, 'Balance is too low'::varchar),
+        ('LogicalError'::varchar, 'R000M'::varchar, 45349654, 500, 'Internal logic error'::varchar),
+        ('RestrictedUserPrivileges'::varchar, 'R000N'::varchar, 45349655, 403, 'User lacks the privileges'::varchar),
+        ('IdenticalReplacement'::varchar, 'R000O'::varchar, 45349656, 400, 'New value equals the old one'::varchar),
+        ('InvalidRecoveryQuestions'::varchar, 'R000R'::varchar, 45349659, 400, 'Recovery questions are invalid'::varchar),
+        ('InvalidRole'::varchar, 'R000S'::varchar, 45349660, 403, 'Role not allowed for this service'::varchar),
+        ('WrongRecoveryAnswers'::varchar, 'R000T'::varchar, 45349661, 401, 'Recovery answers do not match'::varchar),
+        ('MessageNotDelivered'::varchar, 'R000U'::varchar, 45349662, 502, 'Message could not be delivered'::varchar),
+        ('NoReply'::varchar, 'R000V'::varchar, 45349663, 504, 'No reply received'::varchar),
+        ('NullAttribute'::varchar, 'R000W'::varchar, 45349664, 400, 'A required attribute is null'::varchar),
+        ('ConsentMissing'::varchar, 'R000X'::varchar, 45349665, 400, 'Terms of service or privacy policy not agreed'::varchar),
+        ('ActiveSubscriptionRequired'::varchar, 'R000Y'::varchar, 45349666, 402, 'Requires an active subscription'::varchar),
+        ('UsernameAlreadyRegistered'::varchar, 'R000Z'::varchar, 45349667, 409, 'Username is taken'::varchar),
+        ('RecoveryQuestionsNotSet'::varchar, 'R0010'::varchar, 45349668, 400, 'User has no recovery questions'::varchar),
+        ('MustSubmitAllRecoveryQuestions'::varchar, 'R0011'::varchar, 45349669, 400, 'Every recovery question must be answered'::varchar),
+        ('InvalidRecoveryToken'::varchar, 'R0012'::varchar, 45349670, 401, 'Recovery token is invalid or expired'::varchar),
+        ('TokenExpired'::varchar, 'R0013'::varchar, 45349671, 401, 'Access token expired, the session can be refreshed'::varchar),
+        ('CaptchaRequired'::varchar, 'R0014'::varchar, 45349672, 429, 'Too many failed logins, a CAPTCHA has to be solved'::varchar),
+        ('InvalidTwoFactorCode'::varchar, 'R0015'::varchar, 45349673, 401, 'Two-factor code or recovery code is wrong or already used'::varchar),
+        ('TwoFactorRequired'::varchar, 'R0016'::varchar, 45349674, 403, 'The service requires two-factor authentication, which the user has not enabled'::varchar),
+        ('InvalidLoginChallenge'::varchar, 'R0017'::varchar, 45349675, 401, 'Login challenge is invalid, expired or already completed'::varchar),
+        ('RoutingError'::varchar, 'R0018'::varchar, 45349676, 502, 'Message could not be routed'::varchar),
+        ('UnauthorizedMessage'::varchar, 'R0019'::varchar, 45349677, 403, 'Not allowed to send this message'::varchar),
+        ('AuthError'::varchar, 'R001B'::varchar, 45349679, 401, 'Authentication failed'::varchar),
+        ('TwoFactorAlreadyEnabled'::varchar, 'R001C'::varchar, 45349680, 409, 'Two-factor authentication is already enabled'::varchar),
+        ('TwoFactorNotEnabled'::varchar, 'R001D'::varchar, 45349681, 409, 'Two-factor authentication is not enabled, or has no pending enrollment to confirm'::varchar),
+        ('InternalError'::varchar, 'R001G'::varchar, 45349684, 500, 'Internal server error'::varchar),
+        ('NotFound'::varchar, 'R001H'::varchar, 45349685, 404, 'Record does not exist'::varchar)
+    ) AS t;
+END
 $$;
         
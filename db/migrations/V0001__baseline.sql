@@ -1,6 +1,205 @@
 -- Created by Vertabelo (http://vertabelo.com)
 -- Last modification date: 2022-09-13 11:05:50.045
 
+CREATE SCHEMA IF NOT EXISTS tbl;
+
+-- sequences
+-- Sequence: seq_address_id
+CREATE SEQUENCE tbl.seq_address_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_asset_id
+CREATE SEQUENCE tbl.seq_asset_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_asset_prototype_id
+CREATE SEQUENCE tbl.seq_asset_prototype_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_asset_tokenomics_id
+CREATE SEQUENCE tbl.seq_asset_tokenomics_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_asset_transfer_plan_id
+CREATE SEQUENCE tbl.seq_asset_transfer_plan_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_authorization_attempt_id
+CREATE SEQUENCE tbl.seq_authorization_attempt_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_bad_request_id
+CREATE SEQUENCE tbl.seq_bad_request_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_bucket_id
+CREATE SEQUENCE tbl.seq_bucket_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_favorite_wallet_id
+CREATE SEQUENCE tbl.seq_favorite_wallet_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_fiat_account_id
+CREATE SEQUENCE tbl.seq_fiat_account_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_internal_wallet_id
+CREATE SEQUENCE tbl.seq_internal_wallet_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_listing_id
+CREATE SEQUENCE tbl.seq_listing_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_login_attempt_id
+CREATE SEQUENCE tbl.seq_login_attempt_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_organization_id
+CREATE SEQUENCE tbl.seq_organization_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_organization_membership_id
+CREATE SEQUENCE tbl.seq_organization_membership_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_password_reset_attempt_id
+CREATE SEQUENCE tbl.seq_password_reset_attempt_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_recovery_question_id
+CREATE SEQUENCE tbl.seq_recovery_question_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_shipping_provider_id
+CREATE SEQUENCE tbl.seq_shipping_provider_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_support_ticket_id
+CREATE SEQUENCE tbl.seq_support_ticket_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_transfer_id
+CREATE SEQUENCE tbl.seq_transfer_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_user_id
+CREATE SEQUENCE tbl.seq_user_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_vault_id
+CREATE SEQUENCE tbl.seq_vault_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_vault_metadata_id
+CREATE SEQUENCE tbl.seq_vault_metadata_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_vault_wallet_id
+CREATE SEQUENCE tbl.seq_vault_wallet_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
+-- Sequence: seq_ver_id
+CREATE SEQUENCE tbl.seq_ver_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+-- Sequence: seq_wallet_blacklist_id
+CREATE SEQUENCE tbl.seq_wallet_blacklist_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+;
+
 -- tables
 -- Table: asset
 CREATE TABLE tbl.asset (
@@ -438,202 +637,5 @@ ALTER TABLE tbl.vault_wallet ADD CONSTRAINT vault_wallet_vault_metadata
     INITIALLY IMMEDIATE
 ;
 
--- sequences
--- Sequence: seq_address_id
-CREATE SEQUENCE tbl.seq_address_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_asset_id
-CREATE SEQUENCE tbl.seq_asset_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_asset_prototype_id
-CREATE SEQUENCE tbl.seq_asset_prototype_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_asset_tokenomics_id
-CREATE SEQUENCE tbl.seq_asset_tokenomics_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_asset_transfer_plan_id
-CREATE SEQUENCE tbl.seq_asset_transfer_plan_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_authorization_attempt_id
-CREATE SEQUENCE tbl.seq_authorization_attempt_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_bad_request_id
-CREATE SEQUENCE tbl.seq_bad_request_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_bucket_id
-CREATE SEQUENCE tbl.seq_bucket_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_favorite_wallet_id
-CREATE SEQUENCE tbl.seq_favorite_wallet_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_fiat_account_id
-CREATE SEQUENCE tbl.seq_fiat_account_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_internal_wallet_id
-CREATE SEQUENCE tbl.seq_internal_wallet_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_listing_id
-CREATE SEQUENCE tbl.seq_listing_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_login_attempt_id
-CREATE SEQUENCE tbl.seq_login_attempt_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_organization_id
-CREATE SEQUENCE tbl.seq_organization_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_organization_membership_id
-CREATE SEQUENCE tbl.seq_organization_membership_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_password_reset_attempt_id
-CREATE SEQUENCE tbl.seq_password_reset_attempt_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_recovery_question_id
-CREATE SEQUENCE tbl.seq_recovery_question_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_shipping_provider_id
-CREATE SEQUENCE tbl.seq_shipping_provider_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_support_ticket_id
-CREATE SEQUENCE tbl.seq_support_ticket_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_transfer_id
-CREATE SEQUENCE tbl.seq_transfer_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_user_id
-CREATE SEQUENCE tbl.seq_user_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_vault_id
-CREATE SEQUENCE tbl.seq_vault_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_vault_metadata_id
-CREATE SEQUENCE tbl.seq_vault_metadata_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_vault_wallet_id
-CREATE SEQUENCE tbl.seq_vault_wallet_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
--- Sequence: seq_ver_id
-CREATE SEQUENCE tbl.seq_ver_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-      AS bigint
-;
-
--- Sequence: seq_wallet_blacklist_id
-CREATE SEQUENCE tbl.seq_wallet_blacklist_id
-      NO MINVALUE
-      NO MAXVALUE
-      NO CYCLE
-;
-
 -- End of file.
 
@@ -0,0 +1,47 @@
+-- Time columns were unix seconds stored as oid; they become timestamptz
+ALTER TABLE tbl.asset_transfer_plan
+    ALTER COLUMN updated_at TYPE timestamptz USING to_timestamp(updated_at::bigint),
+    ALTER COLUMN created_at TYPE timestamptz USING to_timestamp(created_at::bigint);
+
+ALTER TABLE tbl.authorization_attempt
+    ALTER COLUMN moment DROP DEFAULT,
+    ALTER COLUMN moment TYPE timestamptz USING to_timestamp(moment::bigint),
+    ALTER COLUMN moment SET DEFAULT NOW();
+
+ALTER TABLE tbl.bad_request
+    ALTER COLUMN moment DROP DEFAULT,
+    ALTER COLUMN moment TYPE timestamptz USING to_timestamp(moment::bigint),
+    ALTER COLUMN moment SET DEFAULT NOW();
+
+ALTER TABLE tbl.login_attempt
+    ALTER COLUMN moment DROP DEFAULT,
+    ALTER COLUMN moment TYPE timestamptz USING to_timestamp(moment::bigint),
+    ALTER COLUMN moment SET DEFAULT NOW();
+
+ALTER TABLE tbl.password_reset_attempt
+    ALTER COLUMN initiated_at DROP DEFAULT,
+    ALTER COLUMN initiated_at TYPE timestamptz USING to_timestamp(initiated_at::bigint),
+    ALTER COLUMN initiated_at SET DEFAULT NOW(),
+    ALTER COLUMN valid_until DROP DEFAULT,
+    ALTER COLUMN valid_until TYPE timestamptz USING to_timestamp(valid_until::bigint),
+    ALTER COLUMN valid_until SET DEFAULT NOW() + interval '1 day';
+
+ALTER TABLE tbl.support_ticket
+    ALTER COLUMN created_at TYPE timestamptz USING to_timestamp(created_at::bigint),
+    ALTER COLUMN updated_at TYPE timestamptz USING to_timestamp(updated_at::bigint);
+
+ALTER TABLE tbl.transfer
+    ALTER COLUMN created_at DROP DEFAULT,
+    ALTER COLUMN created_at TYPE timestamptz USING to_timestamp(created_at::bigint),
+    ALTER COLUMN created_at SET DEFAULT NOW(),
+    ALTER COLUMN updated_at TYPE timestamptz USING to_timestamp(updated_at::bigint);
+
+ALTER TABLE tbl.user
+    ALTER COLUMN created_at DROP DEFAULT,
+    ALTER COLUMN created_at TYPE timestamptz USING to_timestamp(created_at::bigint),
+    ALTER COLUMN created_at SET DEFAULT NOW(),
+    ALTER COLUMN updated_at DROP DEFAULT,
+    ALTER COLUMN updated_at TYPE timestamptz USING to_timestamp(updated_at::bigint),
+    ALTER COLUMN updated_at SET DEFAULT NOW(),
+    ALTER COLUMN last_login TYPE timestamptz USING to_timestamp(last_login::bigint),
+    ALTER COLUMN last_password_reset TYPE timestamptz USING to_timestamp(last_password_reset::bigint);
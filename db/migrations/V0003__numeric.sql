@@ -0,0 +1,12 @@
+-- Amounts were stored as real, which rounds them; they become numeric(38,18)
+ALTER TABLE tbl.asset_tokenomics
+    ALTER COLUMN value TYPE numeric(38,18) USING value::numeric(38,18);
+
+ALTER TABLE tbl.asset_transfer_plan
+    ALTER COLUMN transfer_value TYPE numeric(38,18) USING transfer_value::numeric(38,18);
+
+ALTER TABLE tbl.transfer
+    ALTER COLUMN quantity TYPE numeric(38,18) USING quantity::numeric(38,18);
+
+ALTER TABLE tbl.vault_wallet
+    ALTER COLUMN balance TYPE numeric(38,18) USING balance::numeric(38,18);
@@ -0,0 +1,4 @@
+-- tbl.support_ticket is declared in src/service/tables.rs with these defaults
+ALTER TABLE tbl.support_ticket
+    ALTER COLUMN created_at SET DEFAULT NOW(),
+    ALTER COLUMN updated_at SET DEFAULT NOW();
@@ -0,0 +1,8 @@
+-- Password hashes become argon2id PHC strings. SHA-256 hashes are kept hex encoded along with
+-- their salt until the user logs in again, when they are replaced and the salt cleared
+ALTER TABLE tbl.user
+    ALTER COLUMN password_hash TYPE varchar USING encode(password_hash, 'hex'),
+    ALTER COLUMN password_salt DROP NOT NULL;
+-- the hash of a failed attempt is of no use and exposes what was typed
+ALTER TABLE tbl.login_attempt
+    DROP COLUMN password_hash;
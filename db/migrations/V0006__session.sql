@@ -0,0 +1,42 @@
+-- Login sessions, one per user, service and device. They replace the single user_token and
+-- admin_token of tbl.user, with which a login on one device ended the session on the other
+CREATE SEQUENCE tbl.seq_session_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+CREATE TABLE tbl.session (
+    pkey_id bigint  NOT NULL DEFAULT nextval( 'tbl.seq_session_id' ),
+    fkey_user bigint  NOT NULL,
+    service enum_service  NOT NULL,
+    device_id varchar(256)  NOT NULL,
+    device_os varchar(64)  NOT NULL,
+    ip_address inet  NOT NULL,
+    access_token uuid  NOT NULL,
+    refresh_token uuid  NOT NULL,
+    created_at timestamptz  NOT NULL DEFAULT NOW(),
+    last_seen_at timestamptz  NOT NULL DEFAULT NOW(),
+    -- when the access token has to be refreshed
+    access_expires_at timestamptz  NOT NULL,
+    -- the session ends once unused for idle_timeout, or at expires_at
+    idle_timeout interval  NOT NULL,
+    expires_at timestamptz  NOT NULL,
+    CONSTRAINT session_pk PRIMARY KEY (pkey_id),
+    CONSTRAINT uidx_session_device UNIQUE (fkey_user, service, device_id),
+    CONSTRAINT uidx_session_access_token UNIQUE (access_token),
+    CONSTRAINT uidx_session_refresh_token UNIQUE (refresh_token)
+);
+
+ALTER TABLE tbl.session ADD CONSTRAINT user_session
+    FOREIGN KEY (fkey_user)
+    REFERENCES tbl.user (pkey_id)
+    ON DELETE CASCADE
+    NOT DEFERRABLE
+    INITIALLY IMMEDIATE
+;
+
+ALTER TABLE tbl.user
+    DROP COLUMN user_token,
+    DROP COLUMN admin_token;
@@ -0,0 +1,17 @@
+-- Every deleted session is announced on the session_revoked channel with its id, so that the
+-- services close the connections it authorized. Sessions are deleted by a logout, a revocation,
+-- a new login on the same device or the deletion of their user
+CREATE OR REPLACE FUNCTION tbl.notify_session_revoked()
+    RETURNS trigger
+    LANGUAGE plpgsql
+AS $$
+BEGIN
+    PERFORM pg_notify('session_revoked', OLD.pkey_id::text);
+    RETURN OLD;
+END
+$$;
+
+CREATE TRIGGER session_revoked
+    AFTER DELETE ON tbl.session
+    FOR EACH ROW
+EXECUTE FUNCTION tbl.notify_session_revoked();
@@ -0,0 +1,18 @@
+-- The expiry of the password reset token was declared as uuid
+ALTER TABLE tbl.user DROP COLUMN reset_token_valid;
+ALTER TABLE tbl.user ADD COLUMN reset_token_valid timestamptz  NULL;
+
+-- Answers are stored as argon2 hashes. The rows stored so far could only hold numbers, which
+-- never match a hash, so they are dropped and users set their questions again
+DELETE FROM tbl.recovery_question;
+ALTER TABLE tbl.recovery_question ADD CONSTRAINT uidx_recovery_question_user_question
+    UNIQUE (fkey_user, fkey_question) NOT DEFERRABLE  INITIALLY IMMEDIATE;
+
+-- One row per submission of recovery answers, failed ones within a window are rate limited
+DELETE FROM tbl.password_reset_attempt;
+ALTER TABLE tbl.password_reset_attempt
+    DROP COLUMN valid_until,
+    DROP COLUMN code,
+    ADD COLUMN ip_address inet  NOT NULL,
+    ADD COLUMN is_answers_ok boolean  NOT NULL;
+CREATE INDEX idx_password_reset_attempt_user ON tbl.password_reset_attempt (fkey_user, initiated_at);
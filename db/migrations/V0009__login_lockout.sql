@@ -0,0 +1,5 @@
+-- Failed password checks count towards a lockout until they leave the window of its policy, a
+-- later login succeeds, or an admin clears them
+ALTER TABLE tbl.login_attempt ADD COLUMN is_cleared boolean  NOT NULL DEFAULT false;
+CREATE INDEX idx_login_attempt_username ON tbl.login_attempt (username, moment);
+CREATE INDEX idx_login_attempt_ip_address ON tbl.login_attempt (ip_address, moment);
@@ -0,0 +1,98 @@
+-- TOTP two-factor authentication. An enrollment keeps its secret pending until confirmed with a
+-- code, and totp_last_step keeps a code from being accepted twice
+ALTER TABLE tbl.user
+    ADD COLUMN totp_secret bytea  NULL,
+    ADD COLUMN totp_pending_secret bytea  NULL,
+    ADD COLUMN totp_last_step bigint  NULL;
+
+-- Whether the session was opened with a second factor, which services requiring it check
+ALTER TABLE tbl.session ADD COLUMN is_two_factor_ok boolean  NOT NULL DEFAULT false;
+
+-- One-time codes standing in for a TOTP code, stored as SHA-256 hashes
+CREATE SEQUENCE tbl.seq_totp_recovery_code_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+CREATE TABLE tbl.totp_recovery_code (
+    pkey_id bigint  NOT NULL DEFAULT nextval( 'tbl.seq_totp_recovery_code_id' ),
+    fkey_user bigint  NOT NULL,
+    code_hash varchar  NOT NULL,
+    used_at timestamptz  NULL,
+    CONSTRAINT totp_recovery_code_pk PRIMARY KEY (pkey_id),
+    CONSTRAINT uidx_totp_recovery_code UNIQUE (fkey_user, code_hash)
+);
+
+ALTER TABLE tbl.totp_recovery_code ADD CONSTRAINT user_totp_recovery_code
+    FOREIGN KEY (fkey_user)
+    REFERENCES tbl.user (pkey_id)
+    ON DELETE CASCADE
+    NOT DEFERRABLE
+    INITIALLY IMMEDIATE
+;
+
+-- A login whose password was right, waiting for the second factor
+CREATE SEQUENCE tbl.seq_login_challenge_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+CREATE TABLE tbl.login_challenge (
+    pkey_id bigint  NOT NULL DEFAULT nextval( 'tbl.seq_login_challenge_id' ),
+    fkey_user bigint  NOT NULL,
+    token uuid  NOT NULL,
+    service enum_service  NOT NULL,
+    device_id varchar(256)  NOT NULL,
+    device_os varchar(64)  NOT NULL,
+    ip_address inet  NOT NULL,
+    created_at timestamptz  NOT NULL DEFAULT NOW(),
+    expires_at timestamptz  NOT NULL,
+    CONSTRAINT login_challenge_pk PRIMARY KEY (pkey_id),
+    CONSTRAINT uidx_login_challenge_token UNIQUE (token)
+);
+
+ALTER TABLE tbl.login_challenge ADD CONSTRAINT user_login_challenge
+    FOREIGN KEY (fkey_user)
+    REFERENCES tbl.user (pkey_id)
+    ON DELETE CASCADE
+    NOT DEFERRABLE
+    INITIALLY IMMEDIATE
+;
+
+-- Wrong TOTP and recovery codes, rate limited per user within a window
+CREATE SEQUENCE tbl.seq_two_factor_failure_id
+      NO MINVALUE
+      NO MAXVALUE
+      NO CYCLE
+      AS bigint
+;
+
+CREATE TABLE tbl.two_factor_failure (
+    pkey_id bigint  NOT NULL DEFAULT nextval( 'tbl.seq_two_factor_failure_id' ),
+    fkey_user bigint  NOT NULL,
+    ip_address inet  NOT NULL,
+    moment timestamptz  NOT NULL DEFAULT NOW(),
+    CONSTRAINT two_factor_failure_pk PRIMARY KEY (pkey_id)
+);
+CREATE INDEX idx_two_factor_failure_user ON tbl.two_factor_failure (fkey_user, moment);
+
+ALTER TABLE tbl.two_factor_failure ADD CONSTRAINT user_two_factor_failure
+    FOREIGN KEY (fkey_user)
+    REFERENCES tbl.user (pkey_id)
+    ON DELETE CASCADE
+    NOT DEFERRABLE
+    INITIALLY IMMEDIATE
+;
+
+-- Settings admins change per service, a missing row means the defaults
+CREATE TABLE tbl.service_setting (
+    service enum_service  NOT NULL,
+    is_2fa_required boolean  NOT NULL DEFAULT false,
+    fkey_updated_by bigint  NULL REFERENCES tbl.user (pkey_id) ON DELETE SET NULL,
+    updated_at timestamptz  NOT NULL DEFAULT NOW(),
+    CONSTRAINT service_setting_pk PRIMARY KEY (service)
+);
@@ -1,3 +1,69 @@
-CREATE TYPE enum_role AS ENUM ('guest', 'user', 'admin', 'developer');
-CREATE TYPE enum_recovery_question_category AS ENUM ('childhood', 'education', 'family', 'favorite', 'first', 'personal', 'pet', 'work', 'historical');
-CREATE TYPE enum_service AS ENUM ('auth', 'user', 'admin');
+DO $$
+BEGIN
+    CREATE TYPE enum_role AS ENUM ('guest', 'user', 'admin', 'developer');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END
+$$;
+ALTER TYPE enum_role ADD VALUE IF NOT EXISTS 'guest';
+ALTER TYPE enum_role ADD VALUE IF NOT EXISTS 'user';
+ALTER TYPE enum_role ADD VALUE IF NOT EXISTS 'admin';
+ALTER TYPE enum_role ADD VALUE IF NOT EXISTS 'developer';
+DO $$
+BEGIN
+    CREATE TYPE enum_recovery_question_category AS ENUM ('childhood', 'education', 'family', 'favorite', 'first', 'personal', 'pet', 'work', 'historical');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END
+$$;
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'childhood';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'education';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'family';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'favorite';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'first';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'personal';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'pet';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'work';
+ALTER TYPE enum_recovery_question_category ADD VALUE IF NOT EXISTS 'historical';
+DO $$
+BEGIN
+    CREATE TYPE enum_service AS ENUM ('auth', 'user', 'admin');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END
+$$;
+ALTER TYPE enum_service ADD VALUE IF NOT EXISTS 'auth';
+ALTER TYPE enum_service ADD VALUE IF NOT EXISTS 'user';
+ALTER TYPE enum_service ADD VALUE IF NOT EXISTS 'admin';
+DO $$
+BEGIN
+    CREATE TYPE struct_list_users_response_row AS (
+        "user_public_id" bigint,
+        "username" varchar,
+        "email" varchar,
+        "family_name" varchar,
+        "given_name" varchar,
+        "role" enum_role,
+        "last_login" timestamptz,
+        "created_at" timestamptz,
+        "updated_at" timestamptz
+    );
+EXCEPTION
+    WHEN duplicate_object THEN
+        IF ARRAY(
+            SELECT a.attname::varchar
+            FROM pg_attribute a
+            JOIN pg_type t ON t.typrelid = a.attrelid
+            WHERE t.oid = 'struct_list_users_response_row'::regtype AND a.attnum > 0 AND NOT a.attisdropped
+            ORDER BY a.attnum
+        ) <> ARRAY['user_public_id', 'username', 'email', 'family_name', 'given_name', 'role', 'last_login', 'created_at', 'updated_at']::varchar[] OR ARRAY(
+            SELECT a.atttypid
+            FROM pg_attribute a
+            JOIN pg_type t ON t.typrelid = a.attrelid
+            WHERE t.oid = 'struct_list_users_response_row'::regtype AND a.attnum > 0 AND NOT a.attisdropped
+            ORDER BY a.attnum
+        ) <> ARRAY['bigint', 'varchar', 'varchar', 'varchar', 'varchar', 'enum_role', 'timestamptz', 'timestamptz', 'timestamptz']::regtype[]::oid[] THEN
+            RAISE EXCEPTION 'type struct_list_users_response_row differs from the model, change it in a versioned migration';
+        END IF;
+END
+$$;
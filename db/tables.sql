@@ -0,0 +1,50 @@
+CREATE SCHEMA IF NOT EXISTS tbl;
+
+CREATE SEQUENCE IF NOT EXISTS tbl.seq_organization_id;
+CREATE TABLE IF NOT EXISTS tbl."organization" (
+    "pkey_id" bigint NOT NULL DEFAULT nextval('tbl.seq_organization_id'),
+    "name" varchar NOT NULL,
+    "country" varchar NOT NULL,
+    "tax_id" varchar NOT NULL,
+    "address" varchar NOT NULL,
+    "note" varchar NOT NULL,
+    CONSTRAINT organization_pk PRIMARY KEY ("pkey_id")
+);
+
+
+CREATE SEQUENCE IF NOT EXISTS tbl.seq_asset_id;
+CREATE TABLE IF NOT EXISTS tbl."asset" (
+    "pkey_id" bigint NOT NULL DEFAULT nextval('tbl.seq_asset_id'),
+    "short_name" varchar NOT NULL,
+    "long_name" varchar NOT NULL,
+    "description" varchar NOT NULL,
+    "network_type" varchar NOT NULL,
+    "contract_address" varchar NOT NULL,
+    "precision" int NOT NULL,
+    "fkey_prototype" bigint NULL REFERENCES tbl."asset_prototype" ("pkey_id"),
+    CONSTRAINT asset_pk PRIMARY KEY ("pkey_id")
+);
+
+
+CREATE SEQUENCE IF NOT EXISTS tbl.seq_support_ticket_id;
+CREATE TABLE IF NOT EXISTS tbl."support_ticket" (
+    "pkey_id" bigint NOT NULL DEFAULT nextval('tbl.seq_support_ticket_id'),
+    "fkey_user" bigint NOT NULL REFERENCES tbl."user" ("pkey_id"),
+    "fkey_handler_user" bigint NULL REFERENCES tbl."user" ("pkey_id"),
+    "content" varchar NOT NULL,
+    "response" varchar NOT NULL,
+    "created_at" timestamptz NOT NULL DEFAULT NOW(),
+    "updated_at" timestamptz NOT NULL DEFAULT NOW(),
+    CONSTRAINT support_ticket_pk PRIMARY KEY ("pkey_id")
+);
+
+
+CREATE SEQUENCE IF NOT EXISTS tbl.seq_favorite_wallet_id;
+CREATE TABLE IF NOT EXISTS tbl."favorite_wallet" (
+    "pkey_id" bigint NOT NULL DEFAULT nextval('tbl.seq_favorite_wallet_id'),
+    "fkey_user" bigint NOT NULL REFERENCES tbl."user" ("pkey_id"),
+    "fkey_wallet" bigint NOT NULL REFERENCES tbl."vault_wallet" ("pkey_id"),
+    "user_pkey_id" bigint NOT NULL,
+    CONSTRAINT favorite_wallet_pk PRIMARY KEY ("pkey_id")
+);
+
@@ -0,0 +1,166 @@
+// Generated from model::error_code::ERROR_CODES, do not edit.
+
+export enum ErrorCode {
+  /** Value is not a valid enum variant */
+  InvalidEnumLevel = 3484946,
+  /** Unspecified error */
+  Error = 45349632,
+  /** An argument is invalid */
+  InvalidArgument = 45349633,
+  /** Operation not allowed in the current state */
+  InvalidState = 45349634,
+  /** Request sequence number is invalid */
+  InvalidSeq = 45349635,
+  /** Unknown method code */
+  InvalidMethod = 45349636,
+  /** Message violates the protocol */
+  ProtocolViolation = 45349637,
+  /** Request could not be parsed */
+  MalformedRequest = 45349638,
+  /** User does not exist */
+  UnknownUser = 45349639,
+  /** User is blocked */
+  BlockedUser = 45349640,
+  /** Password is incorrect */
+  InvalidPassword = 45349641,
+  /** Token is invalid or expired */
+  InvalidToken = 45349642,
+  /** Try again later */
+  TemporarilyUnavailable = 45349643,
+  /** Unexpected exception */
+  UnexpectedException = 45349644,
+  /** Server is overloaded */
+  BackPressureIncreased = 45349645,
+  /** Public id is invalid */
+  InvalidPublicId = 45349646,
+  /** Value is out of range */
+  InvalidRange = 45349647,
+  /** Bank account already exists */
+  BankAccountAlreadyExists = 45349648,
+  /** Balance is too low */
+  InsufficientFunds = 45349649,
+  /** Internal logic error */
+  LogicalError = 45349654,
+  /** User lacks the privileges */
+  RestrictedUserPrivileges = 45349655,
+  /** New value equals the old one */
+  IdenticalReplacement = 45349656,
+  /** Recovery questions are invalid */
+  InvalidRecoveryQuestions = 45349659,
+  /** Role not allowed for this service */
+  InvalidRole = 45349660,
+  /** Recovery answers do not match */
+  WrongRecoveryAnswers = 45349661,
+  /** Message could not be delivered */
+  MessageNotDelivered = 45349662,
+  /** No reply received */
+  NoReply = 45349663,
+  /** A required attribute is null */
+  NullAttribute = 45349664,
+  /** Terms of service or privacy policy not agreed */
+  ConsentMissing = 45349665,
+  /** Requires an active subscription */
+  ActiveSubscriptionRequired = 45349666,
+  /** Username is taken */
+  UsernameAlreadyRegistered = 45349667,
+  /** User has no recovery questions */
+  RecoveryQuestionsNotSet = 45349668,
+  /** Every recovery question must be answered */
+  MustSubmitAllRecoveryQuestions = 45349669,
+  /** Recovery token is invalid or expired */
+  InvalidRecoveryToken = 45349670,
+  /** Access token expired, the session can be refreshed */
+  TokenExpired = 45349671,
+  /** Too many failed logins, a CAPTCHA has to be solved */
+  CaptchaRequired = 45349672,
+  /** Two-factor code or recovery code is wrong or already used */
+  InvalidTwoFactorCode = 45349673,
+  /** The service requires two-factor authentication, which the user has not enabled */
+  TwoFactorRequired = 45349674,
+  /** Login challenge is invalid, expired or already completed */
+  InvalidLoginChallenge = 45349675,
+  /** Message could not be routed */
+  RoutingError = 45349676,
+  /** Not allowed to send this message */
+  UnauthorizedMessage = 45349677,
+  /** Authentication failed */
+  AuthError = 45349679,
+  /** Two-factor authentication is already enabled */
+  TwoFactorAlreadyEnabled = 45349680,
+  /** Two-factor authentication is not enabled, or has no pending enrollment to confirm */
+  TwoFactorNotEnabled = 45349681,
+  /** Internal server error */
+  InternalError = 45349684,
+  /** Record does not exist */
+  NotFound = 45349685,
+}
+
+export type Locale = "en" | "zh";
+
+export interface ErrorCodeInfo {
+  name: string;
+  sqlstate: string;
+  httpStatus: number;
+  description: string;
+  messages: Record<Locale, string>;
+}
+
+export const ERROR_CODES: Record<ErrorCode, ErrorCodeInfo> = {
+  [ErrorCode.InvalidEnumLevel]: { name: "InvalidEnumLevel", sqlstate: "22P02", httpStatus: 400, description: "Value is not a valid enum variant", messages: { en: "The value is not allowed.", zh: "该值无效。" } },
+  [ErrorCode.Error]: { name: "Error", sqlstate: "R0000", httpStatus: 500, description: "Unspecified error", messages: { en: "Something went wrong.", zh: "出现错误。" } },
+  [ErrorCode.InvalidArgument]: { name: "InvalidArgument", sqlstate: "R0001", httpStatus: 400, description: "An argument is invalid", messages: { en: "Some of the provided information is invalid.", zh: "提供的信息有误。" } },
+  [ErrorCode.InvalidState]: { name: "InvalidState", sqlstate: "R0002", httpStatus: 409, description: "Operation not allowed in the current state", messages: { en: "This action is not possible right now.", zh: "当前无法执行此操作。" } },
+  [ErrorCode.InvalidSeq]: { name: "InvalidSeq", sqlstate: "R0003", httpStatus: 400, description: "Request sequence number is invalid", messages: { en: "The request is out of order.", zh: "请求顺序错误。" } },
+  [ErrorCode.InvalidMethod]: { name: "InvalidMethod", sqlstate: "R0004", httpStatus: 400, description: "Unknown method code", messages: { en: "This action is not supported.", zh: "不支持此操作。" } },
+  [ErrorCode.ProtocolViolation]: { name: "ProtocolViolation", sqlstate: "R0005", httpStatus: 400, description: "Message violates the protocol", messages: { en: "The request is not valid.", zh: "请求无效。" } },
+  [ErrorCode.MalformedRequest]: { name: "MalformedRequest", sqlstate: "R0006", httpStatus: 400, description: "Request could not be parsed", messages: { en: "The request could not be read.", zh: "无法解析请求。" } },
+  [ErrorCode.UnknownUser]: { name: "UnknownUser", sqlstate: "R0007", httpStatus: 404, description: "User does not exist", messages: { en: "The username or password is incorrect.", zh: "用户名或密码错误。" } },
+  [ErrorCode.BlockedUser]: { name: "BlockedUser", sqlstate: "R0008", httpStatus: 403, description: "User is blocked", messages: { en: "This account has been blocked.", zh: "该账户已被封禁。" } },
+  [ErrorCode.InvalidPassword]: { name: "InvalidPassword", sqlstate: "R0009", httpStatus: 401, description: "Password is incorrect", messages: { en: "The username or password is incorrect.", zh: "用户名或密码错误。" } },
+  [ErrorCode.InvalidToken]: { name: "InvalidToken", sqlstate: "R000A", httpStatus: 401, description: "Token is invalid or expired", messages: { en: "Your session has expired, please log in again.", zh: "会话已过期，请重新登录。" } },
+  [ErrorCode.TemporarilyUnavailable]: { name: "TemporarilyUnavailable", sqlstate: "R000B", httpStatus: 503, description: "Try again later", messages: { en: "The service is temporarily unavailable, please try again later.", zh: "服务暂时不可用，请稍后再试。" } },
+  [ErrorCode.UnexpectedException]: { name: "UnexpectedException", sqlstate: "R000C", httpStatus: 500, description: "Unexpected exception", messages: { en: "Something went wrong.", zh: "出现错误。" } },
+  [ErrorCode.BackPressureIncreased]: { name: "BackPressureIncreased", sqlstate: "R000D", httpStatus: 503, description: "Server is overloaded", messages: { en: "The service is busy, please try again later.", zh: "服务繁忙，请稍后再试。" } },
+  [ErrorCode.InvalidPublicId]: { name: "InvalidPublicId", sqlstate: "R000E", httpStatus: 400, description: "Public id is invalid", messages: { en: "The identifier is invalid.", zh: "标识符无效。" } },
+  [ErrorCode.InvalidRange]: { name: "InvalidRange", sqlstate: "R000F", httpStatus: 400, description: "Value is out of range", messages: { en: "The value is out of range.", zh: "数值超出范围。" } },
+  [ErrorCode.BankAccountAlreadyExists]: { name: "BankAccountAlreadyExists", sqlstate: "R000G", httpStatus: 409, description: "Bank account already exists", messages: { en: "This bank account has already been added.", zh: "该银行账户已存在。" } },
+  [ErrorCode.InsufficientFunds]: { name: "InsufficientFunds", sqlstate: "R000H", httpStatus: 400, description: "Balance is too low", messages: { en: "Your balance is too low.", zh: "余额不足。" } },
+  [ErrorCode.LogicalError]: { name: "LogicalError", sqlstate: "R000M", httpStatus: 500, description: "Internal logic error", messages: { en: "Something went wrong.", zh: "出现错误。" } },
+  [ErrorCode.RestrictedUserPrivileges]: { name: "RestrictedUserPrivileges", sqlstate: "R000N", httpStatus: 403, description: "User lacks the privileges", messages: { en: "You are not allowed to do this.", zh: "您没有执行此操作的权限。" } },
+  [ErrorCode.IdenticalReplacement]: { name: "IdenticalReplacement", sqlstate: "R000O", httpStatus: 400, description: "New value equals the old one", messages: { en: "The new value must differ from the current one.", zh: "新值不能与当前值相同。" } },
+  [ErrorCode.InvalidRecoveryQuestions]: { name: "InvalidRecoveryQuestions", sqlstate: "R000R", httpStatus: 400, description: "Recovery questions are invalid", messages: { en: "The recovery questions are invalid.", zh: "安全问题无效。" } },
+  [ErrorCode.InvalidRole]: { name: "InvalidRole", sqlstate: "R000S", httpStatus: 403, description: "Role not allowed for this service", messages: { en: "Your account cannot use this service.", zh: "您的账户无法使用此服务。" } },
+  [ErrorCode.WrongRecoveryAnswers]: { name: "WrongRecoveryAnswers", sqlstate: "R000T", httpStatus: 401, description: "Recovery answers do not match", messages: { en: "The answers do not match.", zh: "答案不正确。" } },
+  [ErrorCode.MessageNotDelivered]: { name: "MessageNotDelivered", sqlstate: "R000U", httpStatus: 502, description: "Message could not be delivered", messages: { en: "The message could not be delivered.", zh: "消息发送失败。" } },
+  [ErrorCode.NoReply]: { name: "NoReply", sqlstate: "R000V", httpStatus: 504, description: "No reply received", messages: { en: "No reply was received, please try again.", zh: "未收到回复，请重试。" } },
+  [ErrorCode.NullAttribute]: { name: "NullAttribute", sqlstate: "R000W", httpStatus: 400, description: "A required attribute is null", messages: { en: "A required field is missing.", zh: "缺少必填项。" } },
+  [ErrorCode.ConsentMissing]: { name: "ConsentMissing", sqlstate: "R000X", httpStatus: 400, description: "Terms of service or privacy policy not agreed", messages: { en: "You must agree to the terms of service and the privacy policy.", zh: "您必须同意服务条款和隐私政策。" } },
+  [ErrorCode.ActiveSubscriptionRequired]: { name: "ActiveSubscriptionRequired", sqlstate: "R000Y", httpStatus: 402, description: "Requires an active subscription", messages: { en: "This requires an active subscription.", zh: "此功能需要有效订阅。" } },
+  [ErrorCode.UsernameAlreadyRegistered]: { name: "UsernameAlreadyRegistered", sqlstate: "R000Z", httpStatus: 409, description: "Username is taken", messages: { en: "This username is already taken.", zh: "该用户名已被占用。" } },
+  [ErrorCode.RecoveryQuestionsNotSet]: { name: "RecoveryQuestionsNotSet", sqlstate: "R0010", httpStatus: 400, description: "User has no recovery questions", messages: { en: "No recovery questions have been set for this account.", zh: "该账户未设置安全问题。" } },
+  [ErrorCode.MustSubmitAllRecoveryQuestions]: { name: "MustSubmitAllRecoveryQuestions", sqlstate: "R0011", httpStatus: 400, description: "Every recovery question must be answered", messages: { en: "Please answer every recovery question.", zh: "请回答所有安全问题。" } },
+  [ErrorCode.InvalidRecoveryToken]: { name: "InvalidRecoveryToken", sqlstate: "R0012", httpStatus: 401, description: "Recovery token is invalid or expired", messages: { en: "The recovery link is invalid or has expired.", zh: "恢复链接无效或已过期。" } },
+  [ErrorCode.TokenExpired]: { name: "TokenExpired", sqlstate: "R0013", httpStatus: 401, description: "Access token expired, the session can be refreshed", messages: { en: "Your session needs to be renewed.", zh: "您的会话需要续期。" } },
+  [ErrorCode.CaptchaRequired]: { name: "CaptchaRequired", sqlstate: "R0014", httpStatus: 429, description: "Too many failed logins, a CAPTCHA has to be solved", messages: { en: "Please complete the CAPTCHA to continue.", zh: "请完成人机验证后继续。" } },
+  [ErrorCode.InvalidTwoFactorCode]: { name: "InvalidTwoFactorCode", sqlstate: "R0015", httpStatus: 401, description: "Two-factor code or recovery code is wrong or already used", messages: { en: "The verification code is incorrect.", zh: "验证码不正确。" } },
+  [ErrorCode.TwoFactorRequired]: { name: "TwoFactorRequired", sqlstate: "R0016", httpStatus: 403, description: "The service requires two-factor authentication, which the user has not enabled", messages: { en: "Please enable two-factor authentication to use this service.", zh: "请先启用双重验证再使用此服务。" } },
+  [ErrorCode.InvalidLoginChallenge]: { name: "InvalidLoginChallenge", sqlstate: "R0017", httpStatus: 401, description: "Login challenge is invalid, expired or already completed", messages: { en: "Your login has expired, please log in again.", zh: "登录已过期，请重新登录。" } },
+  [ErrorCode.RoutingError]: { name: "RoutingError", sqlstate: "R0018", httpStatus: 502, description: "Message could not be routed", messages: { en: "The message could not be delivered.", zh: "消息发送失败。" } },
+  [ErrorCode.UnauthorizedMessage]: { name: "UnauthorizedMessage", sqlstate: "R0019", httpStatus: 403, description: "Not allowed to send this message", messages: { en: "You are not allowed to send this message.", zh: "您无权发送此消息。" } },
+  [ErrorCode.AuthError]: { name: "AuthError", sqlstate: "R001B", httpStatus: 401, description: "Authentication failed", messages: { en: "Authentication failed, please log in again.", zh: "认证失败，请重新登录。" } },
+  [ErrorCode.TwoFactorAlreadyEnabled]: { name: "TwoFactorAlreadyEnabled", sqlstate: "R001C", httpStatus: 409, description: "Two-factor authentication is already enabled", messages: { en: "Two-factor authentication is already enabled.", zh: "双重验证已启用。" } },
+  [ErrorCode.TwoFactorNotEnabled]: { name: "TwoFactorNotEnabled", sqlstate: "R001D", httpStatus: 409, description: "Two-factor authentication is not enabled, or has no pending enrollment to confirm", messages: { en: "Two-factor authentication is not enabled.", zh: "双重验证未启用。" } },
+  [ErrorCode.InternalError]: { name: "InternalError", sqlstate: "R001G", httpStatus: 500, description: "Internal server error", messages: { en: "Something went wrong.", zh: "出现错误。" } },
+  [ErrorCode.NotFound]: { name: "NotFound", sqlstate: "R001H", httpStatus: 404, description: "Record does not exist", messages: { en: "The requested record does not exist.", zh: "请求的记录不存在。" } },
+};
+
+export const HTTP_ERROR_MESSAGES: Record<number, Record<Locale, string>> = {
+  400: { en: "The request is invalid.", zh: "请求无效。" },
+  401: { en: "Please log in first.", zh: "请先登录。" },
+  403: { en: "You are not allowed to do this.", zh: "您没有执行此操作的权限。" },
+  404: { en: "The requested resource does not exist.", zh: "请求的资源不存在。" },
+  409: { en: "The request conflicts with the current state.", zh: "请求与当前状态冲突。" },
+  429: { en: "Too many requests, please try again later.", zh: "请求过于频繁，请稍后再试。" },
+  500: { en: "Something went wrong.", zh: "出现错误。" },
+  503: { en: "The service is temporarily unavailable, please try again later.", zh: "服务暂时不可用，请稍后再试。" },
+};
@@ -0,0 +1,16 @@
+#![no_main]
+//! Decoding arbitrary headers must not panic, and whatever decodes must encode to a header that
+//! decodes to the same parameters
+use lib::ws::{decode_header, encode_header};
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../schema.rs"]
+mod schema;
+
+fuzz_target!(|header: &str| {
+    let schema = schema::schema();
+    if let Ok(params) = decode_header(header, &schema) {
+        let encoded = encode_header(&params, schema::schema()).expect("failed to encode");
+        assert_eq!(decode_header(&encoded, &schema).ok(), Some(params));
+    }
+});
@@ -0,0 +1,47 @@
+#![no_main]
+//! Every request encodes to a header that decodes to exactly the same parameters
+use lib::ws::{decode_header, encode_header};
+use libfuzzer_sys::fuzz_target;
+use serde_json::json;
+
+#[path = "../schema.rs"]
+mod schema;
+
+/// name, id, count, flag, nickname, created_at, whether the service is admin, tags, salt
+type Request = (
+    String,
+    i64,
+    i32,
+    bool,
+    Option<String>,
+    Option<i64>,
+    bool,
+    Vec<String>,
+    Vec<u8>,
+);
+
+fuzz_target!(|input: Request| {
+    let (name, id, count, flag, nickname, created_at, admin, tags, salt) = input;
+    let mut req = json!({
+        "name": name,
+        "id": id,
+        "count": count,
+        "flag": flag,
+        "service": if admin { "Admin" } else { "User" },
+        "tags": tags,
+        "salt": salt,
+    });
+    let req = req.as_object_mut().unwrap();
+    if let Some(nickname) = nickname {
+        req.insert("nickname".into(), nickname.into());
+    }
+    if let Some(created_at) = created_at {
+        req.insert("createdAt".into(), created_at.into());
+    }
+    let schema = schema::schema();
+    let header = encode_header(&*req, schema::schema()).expect("failed to encode");
+    assert_eq!(
+        &decode_header(&header, &schema).expect("failed to decode"),
+        req
+    );
+});
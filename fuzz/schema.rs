@@ -0,0 +1,30 @@
+use model::endpoint::EndpointSchema;
+use model::types::{EnumVariant, Field, TimeFormat, Type};
+
+/// An endpoint with a parameter of each kind of value, shared with the property tests in
+/// `tests/test_header.rs`
+pub fn schema() -> EndpointSchema {
+    EndpointSchema::new(
+        "Everything",
+        1,
+        vec![
+            Field::new("name", Type::String),
+            Field::new("id", Type::BigInt),
+            Field::new("count", Type::Int),
+            Field::new("flag", Type::Boolean),
+            Field::new("nickname", Type::optional(Type::String)),
+            Field::new("created_at", Type::optional(Type::TimestampTz))
+                .time_format(TimeFormat::EpochMillis),
+            Field::new(
+                "service",
+                Type::enum_(
+                    "service",
+                    vec![EnumVariant::new("user", 1), EnumVariant::new("admin", 2)],
+                ),
+            ),
+            Field::new("tags", Type::vec(Type::String)),
+            Field::new("salt", Type::Bytea),
+        ],
+        vec![],
+    )
+}
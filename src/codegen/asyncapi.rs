@@ -0,0 +1,208 @@
+use convert_case::{Case, Casing};
+use model::endpoint::EndpointSchema;
+use model::json_schema::ToJsonSchema;
+use model::service::Service;
+use model::types::*;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+pub const ASYNCAPI_VERSION: &str = "2.6.0";
+
+fn get_params_schema(e: &EndpointSchema, key: &str, suffix: &str) -> Value {
+    let mut schema = e.json_schema[key].clone();
+    schema["title"] = Value::String(format!("{}{}", e.name, suffix));
+    schema
+}
+
+fn get_request_message(e: &EndpointSchema) -> Value {
+    json!({
+        "name": format!("{}Request", e.name),
+        "title": format!("{} request", e.name),
+        "contentType": "application/json",
+        "x-method-code": e.code,
+        "payload": {
+            "type": "object",
+            "properties": {
+                "method": {"type": "integer", "const": e.code},
+                "seq": {"type": "integer", "minimum": 0},
+                "params": get_params_schema(e, "parameters", "Request")
+            },
+            "required": ["method", "seq", "params"]
+        }
+    })
+}
+
+fn get_response_message(e: &EndpointSchema) -> Value {
+    json!({
+        "name": format!("{}Response", e.name),
+        "title": format!("{} response", e.name),
+        "contentType": "application/json",
+        "x-method-code": e.code,
+        "payload": {
+            "type": "object",
+            "properties": {
+                "method": {"type": "integer", "const": e.code},
+                "seq": {"type": "integer", "minimum": 0},
+                "params": get_params_schema(e, "returns", "Response")
+            },
+            "required": ["method", "seq", "params"]
+        }
+    })
+}
+
+fn get_error_message() -> Value {
+    json!({
+        "name": "Error",
+        "title": "Error response",
+        "summary": "Sent instead of a response when a request fails",
+        "contentType": "application/json",
+        "payload": {
+            "type": "object",
+            "properties": {
+                "method": {"type": "integer", "description": "method code of the failed request, 0 during handshake"},
+                "code": {"type": "integer", "description": "HTTP status code or base36-decoded SQLSTATE, see error_codes.md"},
+                "seq": {"type": "integer", "minimum": 0},
+                "reason": {"type": "string", "description": "message for users, localized from the user's preferred language or the handshake's Accept-Language"},
+                "log_id": {"type": "integer", "description": "id the error details are logged under"},
+                "retry_after": {"type": "integer", "minimum": 0, "description": "seconds to wait before trying again, for TemporarilyUnavailable"},
+                "details": {
+                    "type": "array",
+                    "description": "the fields an invalid request was rejected for",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "path": {"type": "string", "description": "path of the field, $ for the whole request"},
+                            "message": {"type": "string"}
+                        },
+                        "required": ["path", "message"]
+                    }
+                }
+            },
+            "required": ["method", "code", "seq", "reason", "log_id"]
+        }
+    })
+}
+
+fn get_log_message() -> Value {
+    json!({
+        "name": "Log",
+        "title": "Log message",
+        "summary": "Diagnostics attached to a request, may arrive before its response",
+        "contentType": "application/json",
+        "payload": {
+            "type": "object",
+            "properties": {
+                "seq": {"type": "integer", "minimum": 0},
+                "log_id": {"type": "integer"},
+                "level": {"type": "string", "enum": ["Off", "Trace", "Debug", "Info", "Warn", "Error"]},
+                "message": {"type": "string"}
+            },
+            "required": ["seq", "log_id", "level", "message"]
+        }
+    })
+}
+
+fn get_message_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/messages/{}", name) })
+}
+
+pub fn get_asyncapi_doc(
+    app_name: &str,
+    services: &[Service],
+    enums: &[Type],
+    hosts: &HashMap<String, String>,
+    port: u16,
+) -> Value {
+    let mut servers = Map::new();
+    let mut channels = Map::new();
+    let mut messages = Map::new();
+    let mut schemas = Map::new();
+
+    for e in enums {
+        if let Type::Enum { name, .. } = e {
+            schemas.insert(
+                format!("Enum{}", name.to_case(Case::Pascal)),
+                e.to_json_schema(),
+            );
+        }
+    }
+    messages.insert("Error".to_owned(), get_error_message());
+    messages.insert("Log".to_owned(), get_log_message());
+
+    for srv in services {
+        let host = hosts
+            .get(&srv.name)
+            .cloned()
+            .unwrap_or_else(|| srv.name.clone());
+        servers.insert(
+            srv.name.clone(),
+            json!({
+                "url": format!("{}:{}", host, port),
+                "protocol": "wss",
+                "description": format!("{} {} service", app_name, srv.name)
+            }),
+        );
+        let mut publish = vec![];
+        let mut subscribe = vec![];
+        for e in &srv.endpoints {
+            let req = format!("{}Request", e.name);
+            let resp = format!("{}Response", e.name);
+            messages.insert(req.clone(), get_request_message(e));
+            messages.insert(resp.clone(), get_response_message(e));
+            publish.push(get_message_ref(&req));
+            subscribe.push(get_message_ref(&resp));
+        }
+        subscribe.push(get_message_ref("Error"));
+        subscribe.push(get_message_ref("Log"));
+
+        channels.insert(
+            format!("/{}", srv.name),
+            json!({
+                "description": format!(
+                    "WebSocket connection to the {} service. Handshake endpoints are invoked \
+                     through the Sec-WebSocket-Protocol header as `0<method>, 1<param1>, 2<param2>...`, \
+                     the rest as JSON text frames.",
+                    srv.name
+                ),
+                "servers": [srv.name.clone()],
+                "bindings": {
+                    "ws": {
+                        "method": "GET",
+                        "headers": {
+                            "type": "object",
+                            "properties": {
+                                "Sec-WebSocket-Protocol": {
+                                    "type": "string",
+                                    "description": "url-encoded handshake request"
+                                }
+                            }
+                        }
+                    }
+                },
+                "publish": {
+                    "operationId": format!("{}Request", srv.name.to_case(Case::Camel)),
+                    "message": {"oneOf": publish}
+                },
+                "subscribe": {
+                    "operationId": format!("{}Response", srv.name.to_case(Case::Camel)),
+                    "message": {"oneOf": subscribe}
+                }
+            }),
+        );
+    }
+
+    json!({
+        "asyncapi": ASYNCAPI_VERSION,
+        "info": {
+            "title": app_name,
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "defaultContentType": "application/json",
+        "servers": servers,
+        "channels": channels,
+        "components": {
+            "messages": messages,
+            "schemas": schemas
+        }
+    })
+}
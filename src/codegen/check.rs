@@ -0,0 +1,349 @@
+use crate::enums::get_enums;
+use crate::get_api_functions;
+use crate::sql::ToSql;
+use crate::tables::get_tables;
+use crate::SYMBOL;
+use eyre::*;
+use itertools::Itertools;
+use model::types::*;
+use postgres::{Client, NoTls};
+use serde::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct CheckArgs {
+    /// Config file with the `db` section of the services
+    pub config: PathBuf,
+    /// Also call every function with NULL arguments in a rolled back transaction
+    pub run: bool,
+}
+impl CheckArgs {
+    pub fn parse(root: &str, mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut this = Self {
+            config: PathBuf::from(root).join("etc/config.json"),
+            run: false,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--run" => this.run = true,
+                "--config" => this.config = args.next().context("--config needs a path")?.into(),
+                _ => bail!(
+                    "unknown argument {}, usage: codegen check [--config PATH] [--run]",
+                    arg
+                ),
+            }
+        }
+        Ok(this)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DatabaseConfig {
+    host: String,
+    port: u16,
+    user: String,
+    password: String,
+    dbname: String,
+}
+#[derive(Debug, Clone, Deserialize)]
+struct Config {
+    db: DatabaseConfig,
+}
+
+fn connect(path: &PathBuf) -> Result<Client> {
+    let config: Config = serde_json::from_str(
+        &std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+    )?;
+    let db = config.db;
+    Ok(postgres::Config::new()
+        .host(&db.host)
+        .port(db.port)
+        .user(&db.user)
+        .password(&db.password)
+        .dbname(&db.dbname)
+        .connect(NoTls)?)
+}
+
+/// A function of the `api` schema as described by `pg_proc`, types as named by `format_type`
+struct DbFunction {
+    parameters: Vec<(String, String)>,
+    returns: Vec<(String, String)>,
+}
+
+fn get_db_functions(client: &mut Client) -> Result<HashMap<String, Vec<DbFunction>>> {
+    let rows = client.query(
+        "SELECT p.proname::text,
+       COALESCE(p.proargnames, '{}'),
+       COALESCE(p.proargmodes::text[], '{}'),
+       ARRAY(SELECT format_type(x.oid, NULL)
+             FROM unnest(COALESCE(p.proallargtypes, p.proargtypes::oid[])) WITH ORDINALITY AS x(oid, i)
+             ORDER BY x.i)
+FROM pg_proc p
+WHERE p.pronamespace = 'api'::regnamespace",
+        &[],
+    )?;
+    let mut result: HashMap<String, Vec<DbFunction>> = HashMap::new();
+    for row in rows {
+        let names: Vec<String> = row.get(1);
+        let modes: Vec<String> = row.get(2);
+        let types: Vec<String> = row.get(3);
+        let mut func = DbFunction {
+            parameters: vec![],
+            returns: vec![],
+        };
+        for (i, ty) in types.into_iter().enumerate() {
+            let name = names.get(i).cloned().unwrap_or_default();
+            // no modes means every argument is an input
+            match modes.get(i).map(|x| x.as_str()) {
+                None | Some("i") => func.parameters.push((name, ty)),
+                Some(_) => func.returns.push((name, ty)),
+            }
+        }
+        result.entry(row.get(0)).or_default().push(func);
+    }
+    Ok(result)
+}
+
+/// Resolves the name postgres gives a type, e.g. `character varying` for `varchar`, so types
+/// written differently compare equal. Types the database does not know are kept as written
+struct TypeNames {
+    cache: HashMap<String, Option<String>>,
+}
+impl TypeNames {
+    fn resolve(&mut self, client: &mut Client, ty: &Type) -> Result<String> {
+        let sql = ty.to_sql();
+        if !self.cache.contains_key(&sql) {
+            let name: Option<String> = client
+                .query_one("SELECT format_type(to_regtype($1), NULL)", &[&sql])?
+                .get(0);
+            self.cache.insert(sql.clone(), name);
+        }
+        Ok(self.cache[&sql]
+            .clone()
+            .unwrap_or_else(|| format!("{} (missing)", sql)))
+    }
+}
+
+fn check_function(
+    client: &mut Client,
+    types: &mut TypeNames,
+    func: &ProceduralFunction,
+    found: &[DbFunction],
+    problems: &mut Vec<String>,
+) -> Result<()> {
+    let db = match found {
+        [] => {
+            problems.push(format!("api.{} is missing", func.name));
+            return Ok(());
+        }
+        [x] => x,
+        _ => {
+            problems.push(format!("api.{} has {} overloads", func.name, found.len()));
+            return Ok(());
+        }
+    };
+    let mut compare =
+        |what: &str, prefix: &str, fields: &[Field], db: &[(String, String)]| -> Result<()> {
+            let expected = fields
+                .iter()
+                .map(|x| {
+                    Ok((
+                        format!("{}{}", prefix, x.name),
+                        types.resolve(client, &x.ty)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if expected != db {
+                let show = |x: &[(String, String)]| {
+                    x.iter().map(|(n, t)| format!("{} {}", n, t)).join(", ")
+                };
+                problems.push(format!(
+                    "api.{} {} differ\n    model:    ({})\n    database: ({})",
+                    func.name,
+                    what,
+                    show(&expected),
+                    show(db)
+                ));
+            }
+            Ok(())
+        };
+    compare("parameters", SYMBOL, &func.parameters, &db.parameters)?;
+    compare("returns", "", &func.returns, &db.returns)?;
+    Ok(())
+}
+
+fn check_enums(client: &mut Client, problems: &mut Vec<String>) -> Result<()> {
+    let rows = client.query(
+        "SELECT t.typname::text, array_agg(e.enumlabel::text ORDER BY e.enumsortorder)
+FROM pg_type t
+JOIN pg_enum e ON e.enumtypid = t.oid
+GROUP BY t.typname",
+        &[],
+    )?;
+    let db: HashMap<String, Vec<String>> = rows.into_iter().map(|x| (x.get(0), x.get(1))).collect();
+    for e in get_enums() {
+        let Type::Enum { name, variants } = e else {
+            unreachable!()
+        };
+        let name = format!("enum_{}", name);
+        let Some(labels) = db.get(&name) else {
+            problems.push(format!("{} is missing", name));
+            continue;
+        };
+        let missing = variants
+            .iter()
+            .filter(|x| !labels.contains(&x.name))
+            .map(|x| &x.name)
+            .join(", ");
+        if !missing.is_empty() {
+            problems.push(format!("{} is missing variants {}", name, missing));
+        }
+        let extra = labels
+            .iter()
+            .filter(|x| !variants.iter().any(|v| &v.name == *x))
+            .join(", ");
+        if !extra.is_empty() {
+            problems.push(format!("{} has variants not in the model: {}", name, extra));
+        }
+    }
+    Ok(())
+}
+
+/// Compares the columns of the tables in `src/service/tables.rs` with the ones the migrations
+/// created. Columns the model leaves out are fine as long as inserts can leave them out too
+fn check_tables(
+    client: &mut Client,
+    types: &mut TypeNames,
+    problems: &mut Vec<String>,
+) -> Result<()> {
+    let rows = client.query(
+        "SELECT c.relname::text, a.attname::text, format_type(a.atttypid, NULL), NOT a.attnotnull, a.atthasdef
+FROM pg_attribute a
+JOIN pg_class c ON c.oid = a.attrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = 'tbl' AND c.relkind = 'r' AND a.attnum > 0 AND NOT a.attisdropped",
+        &[],
+    )?;
+    let mut db: HashMap<String, Vec<(String, String, bool, bool)>> = HashMap::new();
+    for row in rows {
+        db.entry(row.get(0))
+            .or_default()
+            .push((row.get(1), row.get(2), row.get(3), row.get(4)));
+    }
+    for table in get_tables() {
+        let Some(columns) = db.get(&table.name) else {
+            problems.push(format!("tbl.{} is missing", table.name));
+            continue;
+        };
+        for column in &table.columns {
+            let Some((_, ty, nullable, _)) = columns.iter().find(|x| x.0 == column.name) else {
+                problems.push(format!("tbl.{}.{} is missing", table.name, column.name));
+                continue;
+            };
+            let expected = types.resolve(client, &column.ty)?;
+            if &expected != ty {
+                problems.push(format!(
+                    "tbl.{}.{} is {} in the database, {} in the model",
+                    table.name, column.name, ty, expected
+                ));
+            }
+            if *nullable != matches!(column.ty, Type::Optional(_)) {
+                problems.push(format!(
+                    "tbl.{}.{} is {} in the database but not in the model",
+                    table.name,
+                    column.name,
+                    if *nullable { "nullable" } else { "NOT NULL" }
+                ));
+            }
+        }
+        for (name, _, nullable, has_default) in columns {
+            if !nullable && !has_default && !table.columns.iter().any(|x| &x.name == name) {
+                problems.push(format!(
+                    "tbl.{}.{} is NOT NULL without a default but not in the model",
+                    table.name, name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Calls every function with NULL arguments inside a transaction that is rolled back. Errors
+/// raised on purpose are expected, those of class 42 (undefined table, column or function,
+/// mismatched result types) are reported. plpgsql only plans a statement when reaching it, so
+/// this catches problems up to the function's first check of its arguments
+fn run_functions(
+    client: &mut Client,
+    funcs: &[ProceduralFunction],
+    problems: &mut Vec<String>,
+) -> Result<()> {
+    let mut tx = client.transaction()?;
+    tx.batch_execute("SET LOCAL statement_timeout = '5s'")?;
+    for func in funcs {
+        let arguments = func
+            .parameters
+            .iter()
+            .map(|x| format!("{}{} => NULL::{}", SYMBOL, x.name, x.ty.to_sql()))
+            .join(", ");
+        let sql = format!("SELECT * FROM api.{}({})", func.name, arguments);
+        let mut savepoint = tx.savepoint("run_function")?;
+        if let Err(err) = savepoint.batch_execute(&sql) {
+            let code = err.code().map(|x| x.code()).unwrap_or_default();
+            if code.starts_with("42") {
+                problems.push(format!(
+                    "api.{} failed: {}",
+                    func.name,
+                    err.as_db_error()
+                        .map_or(err.to_string(), |x| x.message().to_owned())
+                ));
+            }
+        }
+        savepoint.rollback()?;
+    }
+    tx.rollback()?;
+    Ok(())
+}
+
+/// `codegen check`: compares the `api` functions, enum types and declared tables of a live
+/// database with the model, to catch an `api.sql` that was not applied or drifted from the
+/// generated code
+pub fn check_database(args: &CheckArgs) -> Result<()> {
+    let mut client = connect(&args.config)?;
+    let funcs = get_api_functions();
+    let mut db_funcs = get_db_functions(&mut client)?;
+    let mut types = TypeNames {
+        cache: HashMap::new(),
+    };
+    let mut problems = vec![];
+    for func in &funcs {
+        // unquoted names are folded to lower case
+        let found = db_funcs
+            .remove(&func.name.to_lowercase())
+            .unwrap_or_default();
+        check_function(&mut client, &mut types, func, &found, &mut problems)?;
+    }
+    for name in db_funcs.keys().sorted() {
+        problems.push(format!("api.{} is not in the model", name));
+    }
+    check_enums(&mut client, &mut problems)?;
+    check_tables(&mut client, &mut types, &mut problems)?;
+    if args.run {
+        run_functions(&mut client, &funcs, &mut problems)?;
+    }
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if !problems.is_empty() {
+        bail!(
+            "the database does not match the model, {} problems",
+            problems.len()
+        );
+    }
+    println!(
+        "the database matches the model, {} functions checked",
+        funcs.len()
+    );
+    Ok(())
+}
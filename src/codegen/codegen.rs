@@ -1,8 +1,13 @@
+pub mod asyncapi;
+pub mod check;
+pub mod crud;
+pub mod error_code;
 pub mod rust;
 pub mod service;
 pub mod sql;
 
-use crate::rust::{to_rust_decl, to_rust_type_decl, ToRust};
+use crate::asyncapi::get_asyncapi_doc;
+use crate::rust::{to_rust_db_api, to_rust_decl, to_rust_type_decl, ToRust};
 use crate::service::get_systemd_service;
 use crate::sql::ToSql;
 use convert_case::{Case, Casing};
@@ -24,6 +29,9 @@ mod services;
 #[path = "../service/enums.rs"]
 mod enums;
 
+#[path = "../service/tables.rs"]
+mod tables;
+
 pub fn collect_rust_recursive_types(t: Type) -> Vec<Type> {
     match t {
         Type::Object { ref fields, .. } => {
@@ -41,6 +49,51 @@ pub fn collect_rust_recursive_types(t: Type) -> Vec<Type> {
     }
 }
 
+fn get_type_name(t: &Type) -> Option<&str> {
+    match t {
+        Type::Object { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// Objects and DataTables used by pg functions, dependencies first. Each becomes a composite
+/// type in SQL and a struct with `ToSql`/`FromSql` in the generated model
+pub fn get_composite_types() -> Result<Vec<Type>> {
+    let mut types: Vec<Type> = vec![];
+    for func in services::get_proc_functions() {
+        for field in func.parameters.iter().chain(func.returns.iter()) {
+            let mut nested = collect_rust_recursive_types(field.ty.clone());
+            nested.reverse();
+            for t in nested {
+                add_unique_type(&mut types, t)?;
+            }
+        }
+    }
+    Ok(types)
+}
+
+fn add_unique_type(types: &mut Vec<Type>, t: Type) -> Result<()> {
+    match types.iter().find(|x| get_type_name(x) == get_type_name(&t)) {
+        Some(x) if format!("{:?}", x) != format!("{:?}", t) => {
+            bail!("conflicting definitions of type {:?}", get_type_name(&t))
+        }
+        Some(_) => {}
+        None => types.push(t),
+    }
+    Ok(())
+}
+
+pub fn get_documented_services() -> Vec<Service> {
+    let enums = enums::get_enums();
+    let mut services = services::get_services();
+    for s in &mut services {
+        for e in &mut s.endpoints {
+            e.resolve_enums(&enums);
+        }
+    }
+    services
+}
+
 pub fn check_endpoint_codes() -> Result<()> {
     let mut codes = HashMap::new();
     for s in services::get_services() {
@@ -74,32 +127,57 @@ use strum_macros::EnumString;
         write!(&mut f, "{}", e.to_rust_decl())?;
     }
 
+    let composites = get_composite_types()?;
+    let mut types = vec![];
     for s in services::get_services() {
         for e in s.endpoints {
             let req = Type::object(format!("{}Request", e.name), e.parameters);
             let resp = Type::object(format!("{}Response", e.name), e.returns);
-            let ss = vec![
+            for t in [
                 collect_rust_recursive_types(req),
                 collect_rust_recursive_types(resp),
             ]
-            .concat();
-            for s in ss {
-                write!(
-                    &mut f,
-                    r#"#[derive(Serialize, Deserialize, Debug)]
-                    #[serde(rename_all = "camelCase")]
-                    {}"#,
-                    s.to_rust_decl()
-                )?;
+            .concat()
+            {
+                add_unique_type(&mut types, t)?;
             }
         }
     }
+    for t in composites.iter().cloned() {
+        add_unique_type(&mut types, t)?;
+    }
+    for s in types {
+        let postgres = if composites
+            .iter()
+            .any(|x| get_type_name(x) == get_type_name(&s))
+        {
+            format!(
+                "#[derive(ToSql, FromSql)]\n#[postgres(name = \"{}\")]\n",
+                s.to_sql()
+            )
+        } else {
+            "".to_owned()
+        };
+        write!(
+            &mut f,
+            r#"#[derive(Serialize, Deserialize, Debug)]
+            #[serde(rename_all = "camelCase")]
+            {}{}
+            {}"#,
+            postgres,
+            rust::to_rust_model_decl(&s),
+            rust::to_rust_validate(&s)
+        )?;
+    }
     f.flush()?;
     drop(f);
     rustfmt(&db_filename)?;
 
     Ok(())
 }
+/// Types for the database. Written to be re-applied by the migration runner: enums only gain
+/// variants, and composite types are only created, failing if an existing one differs from the
+/// model since functions and columns may depend on it
 pub fn gen_model_sql(root: &str) -> Result<()> {
     let db_filename = format!("{}/db/model.sql", root);
     let mut f = File::create(db_filename)?;
@@ -109,17 +187,42 @@ pub fn gen_model_sql(root: &str) -> Result<()> {
             Type::Enum { name, variants } => {
                 writeln!(
                     &mut f,
-                    "CREATE TYPE enum_{} AS ENUM ({});",
+                    "DO $$\nBEGIN\n    CREATE TYPE enum_{} AS ENUM ({});\nEXCEPTION\n    WHEN duplicate_object THEN NULL;\nEND\n$$;",
                     name,
                     variants
-                        .into_iter()
+                        .iter()
                         .map(|x| format!("'{}'", x.name))
                         .join(", ")
                 )?;
+                for x in variants {
+                    writeln!(
+                        &mut f,
+                        "ALTER TYPE enum_{} ADD VALUE IF NOT EXISTS '{}';",
+                        name, x.name
+                    )?;
+                }
             }
             _ => unreachable!(),
         }
     }
+    for t in get_composite_types()? {
+        if let Type::Object { fields, .. } = &t {
+            writeln!(
+                &mut f,
+                "DO $$\nBEGIN\n    CREATE TYPE {name} AS (\n{}\n    );\nEXCEPTION\n    WHEN duplicate_object THEN\n        IF ARRAY(\n            SELECT a.attname::varchar\n            FROM pg_attribute a\n            JOIN pg_type t ON t.typrelid = a.attrelid\n            WHERE t.oid = '{name}'::regtype AND a.attnum > 0 AND NOT a.attisdropped\n            ORDER BY a.attnum\n        ) <> ARRAY[{}]::varchar[] OR ARRAY(\n            SELECT a.atttypid\n            FROM pg_attribute a\n            JOIN pg_type t ON t.typrelid = a.attrelid\n            WHERE t.oid = '{name}'::regtype AND a.attnum > 0 AND NOT a.attisdropped\n            ORDER BY a.attnum\n        ) <> ARRAY[{}]::regtype[]::oid[] THEN\n            RAISE EXCEPTION 'type {name} differs from the model, change it in a versioned migration';\n        END IF;\nEND\n$$;",
+                fields
+                    .iter()
+                    .map(|x| format!("        \"{}\" {}", x.name, x.ty.to_sql()))
+                    .join(",\n"),
+                fields.iter().map(|x| format!("'{}'", x.name)).join(", "),
+                fields
+                    .iter()
+                    .map(|x| format!("'{}'", x.ty.to_sql()))
+                    .join(", "),
+                name = t.to_sql(),
+            )?;
+        }
+    }
     f.flush()?;
     drop(f);
     Ok(())
@@ -147,17 +250,39 @@ pub fn gen_db_rs(dir: &str) -> Result<()> {
         "{}",
         r#"
 use eyre::*;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use lib::database::*;
+use std::sync::Arc;
 use crate::model::*;
 
+/// Where the generated methods send calls: postgres, or a `DbApi` such as `MockDbClient`
 #[derive(Clone)]
-pub struct DbClient {
-    client: SimpleDbClient
+enum DbBackend<C> {
+    Pg(C),
+    Api(Arc<dyn DbApi>),
 }
+
+/// The generated `fun_*` methods, over a `SimpleDbClient` or a `SimpleDbTransaction`
+#[derive(Clone)]
+pub struct Db<C> {
+    backend: DbBackend<C>
+}
+pub type DbClient = Db<SimpleDbClient>;
+/// Rolled back if dropped without `commit`. Commit and rollback do nothing with a `DbApi`
+pub type DbTransaction = Db<SimpleDbTransaction>;
+
 impl DbClient {
     pub fn new(client: SimpleDbClient) -> Self {
         Self {
-            client
+            backend: DbBackend::Pg(client)
+        }
+    }
+    /// Sends every call to `api` instead of postgres, e.g. a `MockDbClient` in tests
+    pub fn with_api(api: Arc<dyn DbApi>) -> Self {
+        Self {
+            backend: DbBackend::Api(api)
         }
     }
 }
@@ -165,55 +290,131 @@ impl From<SimpleDbClient> for DbClient {
     fn from(client: SimpleDbClient) -> Self {
         Self::new(client)
     }
+}
+impl DbClient {
+    /// Starts a transaction, the `fun_*` calls made through it are committed together
+    pub async fn begin(&self, isolation: IsolationLevel) -> Result<DbTransaction> {
+        let backend = match &self.backend {
+            DbBackend::Pg(client) => DbBackend::Pg(client.begin(isolation).await?),
+            DbBackend::Api(api) => DbBackend::Api(api.clone()),
+        };
+        Ok(DbTransaction { backend })
+    }
+}
+impl DbTransaction {
+    pub async fn commit(self) -> Result<()> {
+        match self.backend {
+            DbBackend::Pg(client) => client.commit().await,
+            DbBackend::Api(_) => Ok(()),
+        }
+    }
+    pub async fn rollback(self) -> Result<()> {
+        match self.backend {
+            DbBackend::Pg(client) => client.rollback().await,
+            DbBackend::Api(_) => Ok(()),
+        }
+    }
 }
     "#
     )?;
-    for func in funcs {
+    for func in &funcs {
         write!(
             &mut db,
             "
 {}
-impl DbClient {{ 
+impl<C: DbQuery> Db<C> {{
     #[allow(unused_variables)]
-    {}
+    {decl}
 }}",
-            to_rust_type_decl(&func),
-            to_rust_decl(&func)
+            to_rust_type_decl(func),
+            decl = to_rust_decl(func)
         )?;
     }
+    write!(&mut db, "{}", to_rust_db_api(&funcs))?;
     db.flush()?;
     drop(db);
     rustfmt(&db_filename)?;
     Ok(())
 }
 
-pub fn gen_db_sql(root: &str) -> Result<()> {
-    let funcs = services::get_proc_functions();
+/// Every function in the `api` schema: the services' procedural functions, one
+/// `<SERVICE>_SERVICE()` per service returning its id, and `ERROR_CODES()`
+pub fn get_api_functions() -> Vec<ProceduralFunction> {
+    let mut funcs = services::get_proc_functions();
+    for srv in services::get_services() {
+        funcs.push(ProceduralFunction::new(
+            format!("{}_SERVICE", srv.name.to_case(Case::ScreamingSnake)),
+            vec![],
+            vec![Field::new("code", Type::Int)],
+            format!("BEGIN RETURN QUERY SELECT {}; END", srv.id),
+        )
+        .cardinality(Cardinality::One));
+    }
+    funcs.push(error_code::get_error_codes_function());
+    funcs
+}
 
+pub fn gen_db_sql(root: &str) -> Result<()> {
     let db_filename = format!("{}/db/api.sql", root);
     let mut f = File::create(&db_filename)?;
     writeln!(&mut f, "{}", r#"CREATE SCHEMA IF NOT EXISTS api;"#)?;
-    for func in funcs {
+    // drop everything first, so changed signatures and removed functions do not linger
+    writeln!(
+        &mut f,
+        r#"DO $$
+DECLARE
+    func_ regprocedure;
+BEGIN
+    FOR func_ IN SELECT oid FROM pg_proc WHERE pronamespace = 'api'::regnamespace
+        LOOP
+            EXECUTE 'DROP FUNCTION ' || func_;
+        END LOOP;
+END
+$$;"#
+    )?;
+    for func in get_api_functions() {
         writeln!(&mut f, "{}", func.to_sql())?;
     }
-    for srv in services::get_services() {
-        writeln!(
-            &mut f,
-            "{}",
-            ProceduralFunction::new(
-                format!("{}_SERVICE", srv.name.to_case(Case::ScreamingSnake)),
-                vec![],
-                vec![Field::new("code", Type::Int)],
-                format!("BEGIN RETURN QUERY SELECT {}; END", srv.id),
-            )
-            .to_sql()
-        )?;
-    }
     f.flush()?;
     drop(f);
 
     Ok(())
 }
+
+pub fn gen_tables_sql(root: &str) -> Result<()> {
+    let mut f = File::create(format!("{}/db/tables.sql", root))?;
+    writeln!(&mut f, "CREATE SCHEMA IF NOT EXISTS tbl;")?;
+    for table in tables::get_tables() {
+        writeln!(&mut f, "\n{}", table.to_sql())?;
+    }
+    f.flush()?;
+    Ok(())
+}
+pub fn gen_crud_rs(dir: &str) -> Result<()> {
+    let filename = format!("{}/crud.rs", dir);
+    let mut f = File::create(&filename)?;
+    write!(&mut f, "{}", crud::to_rust_crud_handlers(&tables::get_tables()))?;
+    f.flush()?;
+    drop(f);
+    rustfmt(&filename)?;
+    Ok(())
+}
+pub fn gen_error_code_rs(dir: &str) -> Result<()> {
+    let filename = format!("{}/error_code.rs", dir);
+    let mut f = File::create(&filename)?;
+    write!(&mut f, "{}", error_code::to_rust_error_code_enum())?;
+    f.flush()?;
+    drop(f);
+    rustfmt(&filename)?;
+    Ok(())
+}
+pub fn gen_error_code_docs(root: &str) -> Result<()> {
+    let mut f = File::create(format!("{}/docs/error_codes.md", root))?;
+    write!(&mut f, "{}", error_code::get_error_codes_markdown())?;
+    let mut f = File::create(format!("{}/docs/error_codes.ts", root))?;
+    write!(&mut f, "{}", error_code::get_error_codes_ts())?;
+    Ok(())
+}
 #[derive(Debug, Serialize, Deserialize)]
 struct Docs {
     services: Vec<Service>,
@@ -221,7 +422,7 @@ struct Docs {
 }
 pub fn gen_docs(root: &str) -> Result<()> {
     let docs = Docs {
-        services: services::get_services(),
+        services: get_documented_services(),
         enums: enums::get_enums(),
     };
     let docs_filename = format!("{}/docs/services.json", root);
@@ -230,6 +431,20 @@ pub fn gen_docs(root: &str) -> Result<()> {
     Ok(())
 }
 
+pub fn gen_asyncapi(root: &str, app_name: &str, host: &HashMap<String, String>) -> Result<()> {
+    let doc = get_asyncapi_doc(
+        app_name,
+        &get_documented_services(),
+        &enums::get_enums(),
+        host,
+        443,
+    );
+    let docs_filename = format!("{}/docs/asyncapi.json", root);
+    let mut docs_file = File::create(docs_filename)?;
+    serde_json::to_writer_pretty(&mut docs_file, &doc)?;
+    Ok(())
+}
+
 pub fn gen_systemd_services(
     root: &str,
     app_name: &str,
@@ -256,6 +471,7 @@ pub fn gen_systemd_services(
 }
 pub fn main() -> Result<()> {
     check_endpoint_codes()?;
+    error_code::check_error_codes(&services::get_proc_functions())?;
     let mut root = env::current_dir()?;
     loop {
         if root.join(".cargo").exists() {
@@ -264,25 +480,30 @@ pub fn main() -> Result<()> {
         root = root.parent().unwrap().to_owned();
     }
     let root = root.to_str().unwrap();
+    let mut args = env::args().skip(1);
+    if args.next().as_deref() == Some("check") {
+        return check::check_database(&check::CheckArgs::parse(root, args)?);
+    }
     // let dir = env::var_os("OUT_DIR")
     //     .map(|x| x.to_str().unwrap().to_owned())
     //     .unwrap_or(format!("{}/target/gen", root));
     let dir = format!("{}/src/gen", root);
     create_dir_all(&dir)?;
+    let hosts = HashMap::from([
+        ("auth".to_owned(), "auth.iloverust".to_owned()),
+        ("user".to_owned(), "user.iloverust".to_owned()),
+        ("admin".to_owned(), "admin.iloverust".to_owned()),
+    ]);
     gen_docs(root)?;
+    gen_error_code_docs(root)?;
+    gen_asyncapi(root, "iloverust", &hosts)?;
     gen_model_rs(&dir)?;
     gen_model_sql(root)?;
     gen_db_sql(root)?;
+    gen_tables_sql(root)?;
     gen_db_rs(&dir)?;
-    gen_systemd_services(
-        root,
-        "iloverust",
-        "ilr",
-        HashMap::from([
-            ("auth".to_owned(), "auth.iloverust".to_owned()),
-            ("user".to_owned(), "user.iloverust".to_owned()),
-            ("admin".to_owned(), "admin.iloverust".to_owned()),
-        ]),
-    )?;
+    gen_error_code_rs(&dir)?;
+    gen_crud_rs(&dir)?;
+    gen_systemd_services(root, "iloverust", "ilr", hosts)?;
     Ok(())
 }
@@ -0,0 +1,118 @@
+use convert_case::{Case, Casing};
+use itertools::Itertools;
+use model::crud::*;
+use model::types::*;
+
+fn to_rust_struct_init(name: &str, fields: &[Field], from: &str) -> String {
+    format!(
+        "{} {{ {} }}",
+        name,
+        fields
+            .iter()
+            .map(|x| format!("{}: {}.{}", x.name, from, x.name))
+            .join(", ")
+    )
+}
+
+fn to_rust_crud_handler(table: &Table, op: CrudOperation) -> String {
+    let endpoint = op.endpoint_name(table);
+    let function = op.function_name(table);
+    let function_type = function.to_case(Case::Pascal);
+    let (suffix, response) = match op {
+        CrudOperation::List => (
+            "",
+            format!(
+                "Ok({}Response {{ rows: resp.rows.into_iter().map(|row| {}).collect() }})",
+                endpoint,
+                to_rust_struct_init(&get_row_type_name(table), &op.returns(table), "row")
+            ),
+        ),
+        _ => (
+            "_one",
+            format!(
+                "Ok({})",
+                to_rust_struct_init(&format!("{}Response", endpoint), &op.returns(table), "resp")
+            ),
+        ),
+    };
+    let call = format!(
+        "db.{}{}({}).await?",
+        function,
+        suffix,
+        to_rust_struct_init(
+            &format!("{}Req", function_type),
+            &op.parameters(table),
+            "req"
+        )
+    );
+    format!(
+        r#"
+pub struct {endpoint}Handler;
+impl RequestHandler for {endpoint}Handler {{
+    type Request = {endpoint}Request;
+    type Response = {endpoint}Response;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {{
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {{
+            let db = db?;
+            let resp = {call};
+            {response}
+        }});
+    }}
+}}
+"#,
+        endpoint = endpoint,
+        call = call,
+        response = response
+    )
+}
+
+/// Handlers of the endpoints from `get_crud_endpoints`, and `get_crud_handler` to look them up
+pub fn to_rust_crud_handlers(tables: &[Table]) -> String {
+    let tables = tables
+        .iter()
+        .filter(|x| x.endpoint_code.is_some())
+        .collect::<Vec<_>>();
+    let handlers = tables
+        .iter()
+        .flat_map(|t| CrudOperation::ALL.map(|op| to_rust_crud_handler(t, op)))
+        .join("");
+    let arms = tables
+        .iter()
+        .flat_map(|t| {
+            CrudOperation::ALL.map(|op| {
+                let endpoint = op.endpoint_name(t);
+                format!(
+                    "\"{}\" => Some(Arc::new({}Handler)),",
+                    endpoint, endpoint
+                )
+            })
+        })
+        .join("\n");
+    format!(
+        r#"use crate::database::*;
+use crate::model::*;
+use lib::handler::{{RequestHandler, RequestHandlerErased}};
+use lib::toolbox::*;
+use lib::ws::*;
+use std::sync::Arc;
+{handlers}
+/// Handler of a generated CRUD endpoint, by endpoint name
+pub fn get_crud_handler(name: &str) -> Option<Arc<dyn RequestHandlerErased>> {{
+    match name {{
+        {arms}
+        _ => None,
+    }}
+}}
+"#,
+        handlers = handlers,
+        arms = arms
+    )
+}
@@ -0,0 +1,226 @@
+use eyre::*;
+use itertools::Itertools;
+use model::error_code::*;
+use model::types::*;
+use std::collections::HashSet;
+
+fn check_messages(name: &str, messages: LocalizedMessages) -> Result<()> {
+    for locale in LOCALES {
+        if !messages.iter().any(|(l, _)| l == locale) {
+            bail!("error code {} has no message for locale {}", name, locale);
+        }
+    }
+    for (locale, _) in messages {
+        if !LOCALES.contains(locale) {
+            bail!(
+                "error code {} has a message for unknown locale {}",
+                name,
+                locale
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn check_error_codes(funcs: &[ProceduralFunction]) -> Result<()> {
+    let mut names = HashSet::new();
+    let mut sqlstates = HashSet::new();
+    for x in ERROR_CODES {
+        if !names.insert(x.name) {
+            bail!("duplicate error code name: {}", x.name);
+        }
+        if !sqlstates.insert(x.sqlstate) {
+            bail!("duplicate error code SQLSTATE: {} {}", x.sqlstate, x.name);
+        }
+        if x.sqlstate.len() != 5 || u32::from_str_radix(x.sqlstate, 36).is_err() {
+            bail!("invalid SQLSTATE: {} {}", x.sqlstate, x.name);
+        }
+        check_messages(x.name, x.messages)?;
+    }
+    for (status, messages) in HTTP_ERROR_MESSAGES {
+        check_messages(&status.to_string(), messages)?;
+    }
+    for func in funcs {
+        for state in get_raised_sqlstates(&func.body) {
+            if !sqlstates.contains(state.as_str()) {
+                bail!(
+                    "{} raises SQLSTATE {} which is not in model::error_code::ERROR_CODES",
+                    func.name,
+                    state
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn to_rust_error_code_enum() -> String {
+    let variants = ERROR_CODES
+        .iter()
+        .map(|x| {
+            format!(
+                "/// {} ({}, HTTP {})\n{} = {}",
+                x.description,
+                x.sqlstate,
+                x.http_status,
+                x.name,
+                x.code()
+            )
+        })
+        .join(",\n");
+    format!(
+        r#"use lib::error_code::ErrorCode;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use serde::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, FromPrimitive)]
+#[repr(u32)]
+pub enum EnumErrorCode {{
+{variants}
+}}
+impl EnumErrorCode {{
+    pub fn code(self) -> u32 {{
+        self as u32
+    }}
+}}
+impl TryFrom<u32> for EnumErrorCode {{
+    type Error = u32;
+
+    fn try_from(code: u32) -> Result<Self, u32> {{
+        Self::from_u32(code).ok_or(code)
+    }}
+}}
+impl From<EnumErrorCode> for ErrorCode {{
+    fn from(code: EnumErrorCode) -> Self {{
+        ErrorCode::new(code as u32)
+    }}
+}}
+"#,
+        variants = variants
+    )
+}
+
+/// `api.ERROR_CODES()`, the registry as a table for use in SQL
+pub fn get_error_codes_function() -> ProceduralFunction {
+    let quote = |x: &str| format!("'{}'", x.replace('\'', "''"));
+    let rows = ERROR_CODES
+        .iter()
+        .map(|x| {
+            format!(
+                "        ({}::varchar, {}::varchar, {}, {}, {}::varchar)",
+                quote(x.name),
+                quote(x.sqlstate),
+                x.code(),
+                x.http_status,
+                quote(x.description)
+            )
+        })
+        .join(",\n");
+    ProceduralFunction::new(
+        "ERROR_CODES",
+        vec![],
+        vec![
+            Field::new("name", Type::String),
+            Field::new("sqlstate", Type::String),
+            Field::new("code", Type::Int),
+            Field::new("http_status", Type::Int),
+            Field::new("description", Type::String),
+        ],
+        format!(
+            "BEGIN\n    RETURN QUERY SELECT * FROM (VALUES\n{}\n    ) AS t;\nEND",
+            rows
+        ),
+    )
+}
+
+pub fn get_error_codes_markdown() -> String {
+    let rows = ERROR_CODES
+        .iter()
+        .map(|x| {
+            format!(
+                "| {} | `{}` | {} | {} | {} | {} |",
+                x.name,
+                x.sqlstate,
+                x.code(),
+                x.http_status,
+                x.description,
+                LOCALES
+                    .iter()
+                    .map(|l| x.message(l).unwrap_or(""))
+                    .join(" | ")
+            )
+        })
+        .join("\n");
+    let http_rows = HTTP_ERROR_MESSAGES
+        .iter()
+        .map(|(status, messages)| {
+            format!(
+                "| {} | {} |",
+                status,
+                LOCALES
+                    .iter()
+                    .map(|l| messages.iter().find(|(x, _)| x == l).map_or("", |x| x.1))
+                    .join(" | ")
+            )
+        })
+        .join("\n");
+    let locales = LOCALES
+        .iter()
+        .map(|x| format!("Message ({})", x))
+        .join(" | ");
+    let separators = LOCALES.iter().map(|_| "---").join("|");
+    format!(
+        "# Error codes\n\n\
+         Generated from `model::error_code::ERROR_CODES`, do not edit.\n\n\
+         HTTP status codes are sent as is. Application errors are sent as their SQLSTATE read as a \
+         base-36 number.\n\n\
+         The `reason` of an error response is the message in the user's preferred language, or the \
+         one from the handshake's `Accept-Language`, falling back to `{}`. Error details are only \
+         logged, under the response's `log_id`.\n\n\
+         | Name | SQLSTATE | Code | HTTP | Description | {} |\n\
+         |------|----------|------|------|-------------|{}|\n\
+         {}\n\n\
+         ## HTTP status codes\n\n\
+         | HTTP | {} |\n\
+         |------|{}|\n\
+         {}\n",
+        LOCALES[0], locales, separators, rows, locales, separators, http_rows
+    )
+}
+
+pub fn get_error_codes_ts() -> String {
+    let variants = ERROR_CODES
+        .iter()
+        .map(|x| format!("  /** {} */\n  {} = {},", x.description, x.name, x.code()))
+        .join("\n");
+    let to_ts_messages = |messages: LocalizedMessages| {
+        messages
+            .iter()
+            .map(|(locale, message)| format!("{}: {:?}", locale, message))
+            .join(", ")
+    };
+    let infos = ERROR_CODES
+        .iter()
+        .map(|x| {
+            format!(
+                "  [ErrorCode.{}]: {{ name: {:?}, sqlstate: {:?}, httpStatus: {}, description: {:?}, messages: {{ {} }} }},",
+                x.name, x.name, x.sqlstate, x.http_status, x.description, to_ts_messages(x.messages)
+            )
+        })
+        .join("\n");
+    let http_messages = HTTP_ERROR_MESSAGES
+        .iter()
+        .map(|(status, messages)| format!("  {}: {{ {} }},", status, to_ts_messages(messages)))
+        .join("\n");
+    let locales = LOCALES.iter().map(|x| format!("{:?}", x)).join(" | ");
+    format!(
+        "// Generated from model::error_code::ERROR_CODES, do not edit.\n\n\
+         export enum ErrorCode {{\n{}\n}}\n\n\
+         export type Locale = {};\n\n\
+         export interface ErrorCodeInfo {{\n  name: string;\n  sqlstate: string;\n  httpStatus: number;\n  description: string;\n  messages: Record<Locale, string>;\n}}\n\n\
+         export const ERROR_CODES: Record<ErrorCode, ErrorCodeInfo> = {{\n{}\n}};\n\n\
+         export const HTTP_ERROR_MESSAGES: Record<number, Record<Locale, string>> = {{\n{}\n}};\n",
+        variants, locales, infos, http_messages
+    )
+}
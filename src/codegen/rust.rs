@@ -12,12 +12,15 @@ pub trait ToRust {
 impl ToRust for Type {
     fn to_rust_ref(&self) -> String {
         match self {
-            Type::Second => "u32".to_owned(),
-            Type::MilliSecond => "u64".to_owned(),
-            Type::Date => "u32".to_owned(), // TODO: resolve date
+            Type::Second => "i64".to_owned(),
+            Type::MilliSecond => "i64".to_owned(),
+            Type::Date => "chrono::NaiveDate".to_owned(),
+            Type::Timestamp => "chrono::NaiveDateTime".to_owned(),
+            Type::TimestampTz => "chrono::DateTime<chrono::Utc>".to_owned(),
+            Type::Duration => "lib::time::Interval".to_owned(),
             Type::Int => "i32".to_owned(),
             Type::BigInt => "i64".to_owned(),
-            Type::Numeric => "f32".to_owned(),
+            Type::Numeric { .. } => "rust_decimal::Decimal".to_owned(),
             Type::Object { name, .. } => name.clone(),
             Type::DataTable { name, .. } => format!("Vec<{}>", name),
             Type::Vec(ele) => {
@@ -38,12 +41,7 @@ impl ToRust for Type {
 
     fn to_rust_decl(&self) -> String {
         match self {
-            Type::Object { name, fields } => {
-                let mut fields = fields
-                    .iter()
-                    .map(|x| format!("pub {}: {}", x.name, x.ty.to_rust_ref()));
-                format!("pub struct {} {{{}}}", name, fields.join(","))
-            }
+            Type::Object { name, fields } => to_rust_struct_decl(name, fields, false),
             Type::Enum {
                 name,
                 variants: fields,
@@ -68,6 +66,53 @@ impl ToRust for Type {
     }
 }
 
+fn is_time_type(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Date | Type::Timestamp | Type::TimestampTz | Type::Duration
+    )
+}
+fn to_rust_serde_attr(field: &Field) -> String {
+    let mut attrs = vec![];
+    let inner = match &field.ty {
+        Type::Optional(inner) => {
+            attrs.push(r#"default, skip_serializing_if = "Option::is_none""#);
+            Some(inner)
+        }
+        _ => None,
+    };
+    if field.time_format == TimeFormat::EpochMillis {
+        match inner {
+            Some(x) if is_time_type(x) => attrs.push(r#"with = "lib::time::epoch_millis::option""#),
+            None if is_time_type(&field.ty) => attrs.push(r#"with = "lib::time::epoch_millis""#),
+            _ => {}
+        }
+    }
+    if attrs.is_empty() {
+        "".to_owned()
+    } else {
+        format!("#[serde({})]", attrs.join(", "))
+    }
+}
+fn to_rust_struct_decl(name: &str, fields: &[Field], serde: bool) -> String {
+    let mut fields = fields.iter().map(|x| {
+        let attr = if serde {
+            to_rust_serde_attr(x)
+        } else {
+            "".to_owned()
+        };
+        format!("{}pub {}: {}", attr, x.name, x.ty.to_rust_ref())
+    });
+    format!("pub struct {} {{{}}}", name, fields.join(","))
+}
+/// Like `to_rust_decl`, but annotates the struct fields for serde. Absent optional fields are
+/// left out of the serialized JSON instead of being sent as `null`
+pub fn to_rust_model_decl(this: &Type) -> String {
+    match this {
+        Type::Object { name, fields } => to_rust_struct_decl(name, fields, true),
+        x => x.to_rust_decl(),
+    }
+}
 pub fn get_parameter_type(this: &ProceduralFunction) -> Type {
     Type::object(
         format!("{}Req", this.name.to_case(Case::Pascal)),
@@ -117,9 +162,54 @@ pub fn to_rust_decl(this: &ProceduralFunction) -> String {
         .enumerate()
         .map(|(i, x)| format!("{}: row.try_get({})?", x.name, i))
         .join(",\n");
+    let helper = match this.cardinality {
+        Cardinality::None => "".to_owned(),
+        Cardinality::One => format!(
+            "pub async fn {name_raw}_one(&self, req: {name}Req) -> Result<{name}RespRow> {{
+              expect_one(\"{name_raw}\", self.{name_raw}(req).await?.rows)
+            }}",
+            name_raw = this.name,
+            name = this.name.to_case(Case::Pascal),
+        ),
+        Cardinality::Optional => format!(
+            "pub async fn {name_raw}_opt(&self, req: {name}Req) -> Result<Option<{name}RespRow>> {{
+              expect_optional(\"{name_raw}\", self.{name_raw}(req).await?.rows)
+            }}",
+            name_raw = this.name,
+            name = this.name.to_case(Case::Pascal),
+        ),
+        Cardinality::Many => format!(
+            "#[allow(unused_variables)]
+            pub async fn {name_raw}_stream(&self, req: {name}Req) -> Result<BoxStream<'_, Result<{name}RespRow>>> {{
+              let client = match &self.backend {{
+                DbBackend::Pg(client) => client,
+                DbBackend::Api(api) => {{
+                  let rows = api.{name_raw}(req).await?.rows;
+                  return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+                }}
+              }};
+              let rows = client.query_stream(\"{sql}\", &[{pg_params}]).await?;
+              Ok(rows.map(|row| {{
+                let row = row?;
+                Ok({name}RespRow {{
+                  {row_getter}
+                }})
+              }}).boxed())
+            }}",
+            name_raw = this.name,
+            name = this.name.to_case(Case::Pascal),
+            sql = sql,
+            pg_params = pg_params,
+            row_getter = row_getter
+        ),
+    };
     format!(
         "pub async fn {name_raw}(&self, req: {name}Req) -> Result<{name}Resp> {{
-          let rows = self.client.query(\"{sql}\", &[{pg_params}]).await?;
+          let client = match &self.backend {{
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.{name_raw}(req).await,
+          }};
+          let rows = client.query(\"{sql}\", &[{pg_params}]).await?;
           let mut resp = {name}Resp {{
               rows: Vec::with_capacity(rows.len())
           }};
@@ -130,11 +220,230 @@ pub fn to_rust_decl(this: &ProceduralFunction) -> String {
             resp.rows.push(r);
           }}
           Ok(resp)
-        }}",
+        }}
+        {helper}",
         name_raw = this.name,
         name = this.name.to_case(Case::Pascal),
         sql = sql,
         pg_params = pg_params,
-        row_getter = row_getter
+        row_getter = row_getter,
+        helper = helper
+    )
+}
+
+/// `DbApi` with every function, implemented by `DbClient`, `DbTransaction` and `MockDbClient`
+pub fn to_rust_db_api(funcs: &[ProceduralFunction]) -> String {
+    let names = funcs
+        .iter()
+        .map(|x| (x.name.clone(), x.name.to_case(Case::Pascal)))
+        .collect::<Vec<_>>();
+    let signatures = names
+        .iter()
+        .map(|(name_raw, name)| {
+            format!(
+                "fn {}(&self, req: {}Req) -> BoxFuture<'_, Result<{}Resp>>",
+                name_raw, name, name
+            )
+        })
+        .collect::<Vec<_>>();
+    let forward = |ty: &str| {
+        names
+            .iter()
+            .zip(&signatures)
+            .map(|((name_raw, _), signature)| {
+                format!("{} {{ Box::pin({}::{}(self, req)) }}", signature, ty, name_raw)
+            })
+            .join("\n")
+    };
+    let mock_fields = names
+        .iter()
+        .map(|(name_raw, name)| format!("{}: Option<MockFn<{}Req, {}Resp>>,", name_raw, name, name))
+        .join("\n");
+    let mock_setters = names
+        .iter()
+        .map(|(name_raw, name)| {
+            format!(
+                "pub fn on_{name_raw}(mut self, f: impl Fn({name}Req) -> Result<{name}Resp> + Send + Sync + 'static) -> Self {{
+                    self.{name_raw} = Some(Box::new(f));
+                    self
+                }}",
+                name_raw = name_raw,
+                name = name
+            )
+        })
+        .join("\n");
+    let mock_impls = names
+        .iter()
+        .zip(&signatures)
+        .map(|((name_raw, _), signature)| {
+            format!(
+                "{signature} {{
+                    let result = match &self.{name_raw} {{
+                        Some(f) => f(req),
+                        None => Err(eyre!(\"MockDbClient has no response for {name_raw}\")),
+                    }};
+                    Box::pin(async move {{ result }})
+                }}",
+                signature = signature,
+                name_raw = name_raw
+            )
+        })
+        .join("\n");
+    format!(
+        "
+/// Every `fun_*` method, so handlers can be tested against a `MockDbClient`
+pub trait DbApi: Send + Sync {{
+    {signatures}
+}}
+impl DbApi for DbClient {{
+    {client}
+}}
+impl DbApi for DbTransaction {{
+    {transaction}
+}}
+
+type MockFn<Req, Resp> = Box<dyn Fn(Req) -> Result<Resp> + Send + Sync>;
+
+/// A `DbApi` answering from a closure set per function with `on_fun_*`. Calling a function
+/// without one fails
+#[derive(Default)]
+pub struct MockDbClient {{
+    {mock_fields}
+}}
+impl MockDbClient {{
+    pub fn new() -> Self {{
+        Self::default()
+    }}
+    {mock_setters}
+}}
+impl DbApi for MockDbClient {{
+    {mock_impls}
+}}
+",
+        signatures = signatures.iter().map(|x| format!("{};", x)).join("\n"),
+        client = forward("DbClient"),
+        transaction = forward("DbTransaction"),
+        mock_fields = mock_fields,
+        mock_setters = mock_setters,
+        mock_impls = mock_impls
+    )
+}
+
+fn to_rust_field_check(value: &str, ty: &Type, c: &FieldConstraints) -> String {
+    let mut checks = vec![];
+    match ty {
+        Type::Optional(inner) => {
+            let check = to_rust_field_check(value, inner, c);
+            if check.is_empty() {
+                return check;
+            }
+            return format!(
+                "if let Some({value}) = {value} {{ {} }}",
+                check,
+                value = value
+            );
+        }
+        Type::String => {
+            if c.get_min_length().is_some() || c.max_length.is_some() {
+                checks.push(format!(
+                    "lib::validation::check_length(&p, {}, {:?}, {:?}, errors);",
+                    value,
+                    c.get_min_length(),
+                    c.max_length
+                ));
+            }
+            if let Some(pattern) = &c.pattern {
+                checks.push(format!(
+                    "lib::validation::check_pattern(&p, {}, {:?}, errors);",
+                    value, pattern
+                ));
+            }
+            if let Some(format) = c.format {
+                checks.push(format!(
+                    "lib::validation::check_format(&p, {}, {:?}, errors);",
+                    value,
+                    format.as_str()
+                ));
+            }
+        }
+        Type::Numeric { .. } if c.minimum.is_some() || c.maximum.is_some() => {
+            checks.push(format!(
+                "lib::validation::check_decimal_range(&p, {}, {:?}, {:?}, errors);",
+                value, c.minimum, c.maximum
+            ));
+        }
+        Type::Int | Type::BigInt | Type::Second | Type::MilliSecond
+            if c.minimum.is_some() || c.maximum.is_some() =>
+        {
+            checks.push(format!(
+                "lib::validation::check_range(&p, *{} as f64, {:?}, {:?}, errors);",
+                value, c.minimum, c.maximum
+            ));
+        }
+        Type::Object { .. } => {
+            checks.push(format!(
+                "lib::validation::Validate::validate_at({}, &p, errors);",
+                value
+            ));
+        }
+        Type::Vec(_) | Type::DataTable { .. } | Type::Bytea => {
+            if c.get_min_length().is_some() || c.max_length.is_some() {
+                checks.push(format!(
+                    "lib::validation::check_items(&p, {}.len(), {:?}, {:?}, errors);",
+                    value,
+                    c.get_min_length(),
+                    c.max_length
+                ));
+            }
+            if matches!(ty, Type::DataTable { .. })
+                || matches!(ty, Type::Vec(x) if matches!(**x, Type::Object { .. }))
+            {
+                checks.push(format!(
+                    "for (i, x) in {}.iter().enumerate() {{ lib::validation::Validate::validate_at(x, &format!(\"{{}}[{{}}]\", p, i), errors); }}",
+                    value
+                ));
+            }
+        }
+        _ => {}
+    }
+    checks.join("\n")
+}
+
+/// Generates `lib::validation::Validate` for an object, checking the constraints of every field
+pub fn to_rust_validate(this: &Type) -> String {
+    let (name, fields) = match this {
+        Type::Object { name, fields } => (name, fields),
+        _ => return "".to_owned(),
+    };
+    let checks = fields
+        .iter()
+        .filter_map(|x| {
+            let check = to_rust_field_check("v", &x.ty, &x.constraints);
+            if check.is_empty() {
+                return None;
+            }
+            Some(format!(
+                "{{ let p = lib::validation::join_path(path, \"{}\"); let v = &self.{}; {} }}",
+                x.name.to_case(Case::Camel),
+                x.name,
+                check
+            ))
+        })
+        .join("\n");
+    let (path, errors) = if checks.is_empty() {
+        ("_path", "_errors")
+    } else {
+        ("path", "errors")
+    };
+    format!(
+        "impl lib::validation::Validate for {name} {{
+            fn validate_at(&self, {path}: &str, {errors}: &mut Vec<lib::json_schema::SchemaError>) {{
+                {checks}
+            }}
+        }}",
+        name = name,
+        path = path,
+        errors = errors,
+        checks = checks
     )
 }
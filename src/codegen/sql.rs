@@ -1,4 +1,5 @@
 use crate::SYMBOL;
+use convert_case::{Case, Casing};
 use itertools::Itertools;
 use model::types::*;
 
@@ -9,31 +10,22 @@ pub trait ToSql {
 impl ToSql for Type {
     fn to_sql(&self) -> String {
         match self {
-            Type::Second => "oid".to_owned(),
-            Type::MilliSecond => "int".to_owned(),
-            Type::Date => "int".to_owned(), // TODO: fix things
+            Type::Second => "bigint".to_owned(),
+            Type::MilliSecond => "bigint".to_owned(),
+            Type::Date => "date".to_owned(),
+            Type::Timestamp => "timestamp".to_owned(),
+            Type::TimestampTz => "timestamptz".to_owned(),
+            Type::Duration => "interval".to_owned(),
             Type::Int => "int".to_owned(),
             Type::BigInt => "bigint".to_owned(),
-            Type::Numeric => "real".to_owned(),
-            Type::Object { fields, .. } => {
-                let fields = fields
-                    .iter()
-                    .map(|x| format!("\"{}\" {}", x.name, x.ty.to_sql()));
-                format!(
-                    "table (\n{}\n)",
-                    fields.map(|x| format!("    {}", x)).join(",\n")
-                )
-            }
-            Type::DataTable { .. } => {
-                todo!()
-            }
+            Type::Numeric { precision, scale } => format!("numeric({},{})", precision, scale),
+            Type::Object { name, .. } => format!("struct_{}", name.to_case(Case::Snake)),
+            Type::DataTable { name, .. } => format!("struct_{}[]", name.to_case(Case::Snake)),
             Type::Vec(fields) => {
                 format!("{}[]", fields.to_sql())
             }
             Type::Unit => "void".to_owned(),
-            Type::Optional(t) => {
-                format!("Option<{}>", t.to_sql())
-            }
+            Type::Optional(t) => t.to_sql(),
             Type::Boolean => "boolean".to_owned(),
             Type::String => "varchar".to_owned(),
             Type::Bytea => "bytea".to_owned(),
@@ -45,20 +37,35 @@ impl ToSql for Type {
 }
 impl ToSql for ProceduralFunction {
     fn to_sql(&self) -> String {
+        // postgres only allows defaults on trailing parameters; optional parameters in the
+        // middle are still nullable, callers just have to pass them (generated code always does)
+        let first_default = self
+            .parameters
+            .iter()
+            .rposition(|x| !matches!(x.ty, Type::Optional(_)))
+            .map(|x| x + 1)
+            .unwrap_or(0);
         let params = self
             .parameters
             .iter()
-            .map(|x| match &x.ty {
-                Type::Optional(y) => {
+            .enumerate()
+            .map(|(i, x)| match &x.ty {
+                Type::Optional(y) if i >= first_default => {
                     format!("{}{} {} DEFAULT NULL", SYMBOL, x.name, y.to_sql())
                 }
                 y => format!("{}{} {}", SYMBOL, x.name, y.to_sql()),
             })
             .join(", ");
-        let returns = if self.returns.len() == 0 {
+        let returns = if self.returns.is_empty() {
             "void".to_owned()
         } else {
-            Type::object("", self.returns.clone()).to_sql()
+            format!(
+                "table (\n{}\n)",
+                self.returns
+                    .iter()
+                    .map(|x| format!("    \"{}\" {}", x.name, x.ty.to_sql()))
+                    .join(",\n")
+            )
         };
         format!(
             "
@@ -76,3 +83,44 @@ $$;
         )
     }
 }
+impl ToSql for Table {
+    /// DDL that only creates what is missing, changes to existing tables need a migration
+    fn to_sql(&self) -> String {
+        let sequences = self
+            .columns
+            .iter()
+            .filter_map(|x| x.sequence.as_ref())
+            .map(|x| format!("CREATE SEQUENCE IF NOT EXISTS tbl.{};\n", x))
+            .join("");
+        let columns = self
+            .columns
+            .iter()
+            .map(|x| {
+                let mut column = format!(
+                    "    \"{}\" {} {}",
+                    x.name,
+                    x.ty.to_sql(),
+                    if matches!(x.ty, Type::Optional(_)) {
+                        "NULL"
+                    } else {
+                        "NOT NULL"
+                    }
+                );
+                if let Some(default) = x.get_default() {
+                    column += &format!(" DEFAULT {}", default);
+                }
+                if let Some((table, key)) = &x.references {
+                    column += &format!(" REFERENCES tbl.\"{}\" (\"{}\")", table, key);
+                }
+                column
+            })
+            .join(",\n");
+        format!(
+            "{sequences}CREATE TABLE IF NOT EXISTS tbl.\"{name}\" (\n{columns},\n    CONSTRAINT {name}_pk PRIMARY KEY (\"{key}\")\n);\n",
+            sequences = sequences,
+            name = self.name,
+            columns = columns,
+            key = self.primary_key
+        )
+    }
+}
@@ -0,0 +1,158 @@
+use codegen::check::{check_database, CheckArgs};
+use eyre::*;
+use postgres::{Client, NoTls};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+/// A database built from `db/` on the server of `TESTKIT_DATABASE`, dropped on drop
+struct CheckDatabase {
+    admin: postgres::Config,
+    name: String,
+    config: PathBuf,
+}
+impl CheckDatabase {
+    fn create(name: &str) -> Result<Option<Self>> {
+        let Some(admin) = std::env::var("TESTKIT_DATABASE").ok() else {
+            eprintln!("skipped, set TESTKIT_DATABASE to a server to create databases on");
+            return Ok(None);
+        };
+        let mut admin: postgres::Config = admin.parse()?;
+        if admin.get_dbname().is_none() {
+            admin.dbname("postgres");
+        }
+        let name = format!("codegen_{}_{}", name, std::process::id());
+        let mut client = admin.connect(NoTls)?;
+        client.batch_execute(&format!("DROP DATABASE IF EXISTS {}", name))?;
+        client.batch_execute(&format!("CREATE DATABASE {}", name))?;
+        let config = std::env::temp_dir().join(format!("{}.json", name));
+        let this = Self {
+            admin,
+            name,
+            config,
+        };
+        let mut client = this.connect()?;
+        let db = Path::new(ROOT).join("db");
+        let mut files = vec![db.join("model.sql")];
+        let mut migrations = std::fs::read_dir(db.join("migrations"))?
+            .map(|x| Ok(x?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        migrations.sort();
+        files.extend(migrations);
+        files.push(db.join("tables.sql"));
+        files.push(db.join("api.sql"));
+        for file in files {
+            client
+                .batch_execute(&std::fs::read_to_string(&file)?)
+                .with_context(|| format!("failed to apply {}", file.display()))?;
+        }
+        let host = this.admin.get_hosts().first().map(|x| match x {
+            postgres::config::Host::Tcp(host) => host.clone(),
+            postgres::config::Host::Unix(path) => path.display().to_string(),
+        });
+        let password = this
+            .admin
+            .get_password()
+            .map(|x| String::from_utf8_lossy(x).into_owned());
+        let config = json!({
+            "db": {
+                "host": host.unwrap_or_else(|| "localhost".to_owned()),
+                "port": this.admin.get_ports().first().copied().unwrap_or(5432),
+                "user": this.admin.get_user().unwrap_or("postgres"),
+                "password": password.unwrap_or_default(),
+                "dbname": this.name,
+            }
+        });
+        std::fs::write(&this.config, config.to_string())?;
+        Ok(Some(this))
+    }
+    fn connect(&self) -> Result<Client> {
+        let mut config = self.admin.clone();
+        config.dbname(&self.name);
+        Ok(config.connect(NoTls)?)
+    }
+    fn args(&self, run: bool) -> CheckArgs {
+        CheckArgs {
+            config: self.config.clone(),
+            run,
+        }
+    }
+    /// Runs `codegen check` the way the CI does, returning whether it succeeded and its stdout
+    fn run_binary(&self) -> Result<(bool, String)> {
+        let output = Command::new(env!("CARGO_BIN_EXE_codegen"))
+            .current_dir(ROOT)
+            .arg("check")
+            .arg("--config")
+            .arg(&self.config)
+            .output()?;
+        Ok((
+            output.status.success(),
+            String::from_utf8_lossy(&output.stdout).into_owned(),
+        ))
+    }
+}
+impl Drop for CheckDatabase {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.config);
+        let result = self.admin.connect(NoTls).and_then(|mut client| {
+            client.batch_execute(&format!(
+                "DROP DATABASE IF EXISTS {} WITH (FORCE)",
+                self.name
+            ))
+        });
+        if let Err(err) = result {
+            eprintln!("failed to drop test database {}: {:?}", self.name, err);
+        }
+    }
+}
+
+#[test]
+fn test_check_matching_database() -> Result<()> {
+    let Some(database) = CheckDatabase::create("matching")? else {
+        return Ok(());
+    };
+    check_database(&database.args(true))?;
+    let (success, stdout) = database.run_binary()?;
+    assert!(success, "{}", stdout);
+    assert!(stdout.contains("the database matches the model"));
+    Ok(())
+}
+
+#[test]
+fn test_check_drift() -> Result<()> {
+    let Some(database) = CheckDatabase::create("drift")? else {
+        return Ok(());
+    };
+    database.connect()?.batch_execute(
+        "DROP FUNCTION api.fun_auth_authorize;
+        CREATE FUNCTION api.fun_auth_stray() RETURNS void LANGUAGE sql AS '';
+        ALTER TYPE enum_role ADD VALUE 'stray';",
+    )?;
+    let err = check_database(&database.args(false)).unwrap_err();
+    assert!(
+        err.to_string().contains("does not match the model"),
+        "{}",
+        err
+    );
+    // a drifted database fails the build step rather than only printing
+    let (success, stdout) = database.run_binary()?;
+    assert!(!success, "{}", stdout);
+    assert!(
+        stdout.contains("api.fun_auth_authorize is missing"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("api.fun_auth_stray is not in the model"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("enum_role has variants not in the model: stray"),
+        "{}",
+        stdout
+    );
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use codegen::get_composite_types;
+use codegen::rust::{to_rust_model_decl, ToRust};
+use codegen::sql::ToSql;
+use eyre::*;
+use model::types::*;
+
+fn row() -> Vec<Field> {
+    vec![
+        Field::new("user_id", Type::BigInt),
+        Field::new("email", Type::optional(Type::String)),
+    ]
+}
+
+#[test]
+fn test_composite_sql() {
+    assert_eq!(Type::object("UserRow", row()).to_sql(), "struct_user_row");
+    assert_eq!(
+        Type::data_table("UserRow", row()).to_sql(),
+        "struct_user_row[]"
+    );
+    assert_eq!(
+        Type::vec(Type::object("UserRow", row())).to_sql(),
+        "struct_user_row[]"
+    );
+    let func = ProceduralFunction::new(
+        "fun_list_users",
+        vec![],
+        vec![Field::new("users", Type::data_table("UserRow", row()))],
+        "BEGIN END",
+    );
+    assert!(func.to_sql().contains("\"users\" struct_user_row[]"));
+}
+
+#[test]
+fn test_composite_rust() {
+    assert_eq!(Type::object("UserRow", row()).to_rust_ref(), "UserRow");
+    assert_eq!(
+        Type::data_table("UserRow", row()).to_rust_ref(),
+        "Vec<UserRow>"
+    );
+    let decl = to_rust_model_decl(&Type::object("UserRow", row()));
+    assert!(decl.starts_with("pub struct UserRow {"), "{}", decl);
+    assert!(decl.contains("pub user_id: i64"), "{}", decl);
+    assert!(decl.contains("pub email: Option<String>"), "{}", decl);
+}
+
+#[test]
+fn test_composite_types() -> Result<()> {
+    // data tables of pg functions become composite types, each declared once
+    let types = get_composite_types()?;
+    let rows = types
+        .iter()
+        .filter(|x| matches!(x, Type::Object { name, .. } if name == "ListUsersResponseRow"))
+        .count();
+    assert_eq!(rows, 1);
+    Ok(())
+}
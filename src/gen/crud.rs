@@ -0,0 +1,563 @@
+use crate::database::*;
+use crate::model::*;
+use lib::handler::{RequestHandler, RequestHandlerErased};
+use lib::toolbox::*;
+use lib::ws::*;
+use std::sync::Arc;
+
+pub struct GetOrganizationHandler;
+impl RequestHandler for GetOrganizationHandler {
+    type Request = GetOrganizationRequest;
+    type Response = GetOrganizationResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_organization_get_one(FunOrganizationGetReq {
+                    pkey_id: req.pkey_id,
+                })
+                .await?;
+            Ok(GetOrganizationResponse {
+                pkey_id: resp.pkey_id,
+                name: resp.name,
+                country: resp.country,
+                tax_id: resp.tax_id,
+                address: resp.address,
+                note: resp.note,
+            })
+        });
+    }
+}
+
+pub struct ListOrganizationsHandler;
+impl RequestHandler for ListOrganizationsHandler {
+    type Request = ListOrganizationsRequest;
+    type Response = ListOrganizationsResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_organization_list(FunOrganizationListReq {
+                    offset: req.offset,
+                    limit: req.limit,
+                    name: req.name,
+                    country: req.country,
+                })
+                .await?;
+            Ok(ListOrganizationsResponse {
+                rows: resp
+                    .rows
+                    .into_iter()
+                    .map(|row| OrganizationRow {
+                        pkey_id: row.pkey_id,
+                        name: row.name,
+                        country: row.country,
+                        tax_id: row.tax_id,
+                        address: row.address,
+                        note: row.note,
+                    })
+                    .collect(),
+            })
+        });
+    }
+}
+
+pub struct CreateOrganizationHandler;
+impl RequestHandler for CreateOrganizationHandler {
+    type Request = CreateOrganizationRequest;
+    type Response = CreateOrganizationResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_organization_insert_one(FunOrganizationInsertReq {
+                    name: req.name,
+                    country: req.country,
+                    tax_id: req.tax_id,
+                    address: req.address,
+                    note: req.note,
+                })
+                .await?;
+            Ok(CreateOrganizationResponse {
+                pkey_id: resp.pkey_id,
+                name: resp.name,
+                country: resp.country,
+                tax_id: resp.tax_id,
+                address: resp.address,
+                note: resp.note,
+            })
+        });
+    }
+}
+
+pub struct UpdateOrganizationHandler;
+impl RequestHandler for UpdateOrganizationHandler {
+    type Request = UpdateOrganizationRequest;
+    type Response = UpdateOrganizationResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_organization_update_one(FunOrganizationUpdateReq {
+                    pkey_id: req.pkey_id,
+                    name: req.name,
+                    country: req.country,
+                    tax_id: req.tax_id,
+                    address: req.address,
+                    note: req.note,
+                })
+                .await?;
+            Ok(UpdateOrganizationResponse {
+                pkey_id: resp.pkey_id,
+                name: resp.name,
+                country: resp.country,
+                tax_id: resp.tax_id,
+                address: resp.address,
+                note: resp.note,
+            })
+        });
+    }
+}
+
+pub struct DeleteOrganizationHandler;
+impl RequestHandler for DeleteOrganizationHandler {
+    type Request = DeleteOrganizationRequest;
+    type Response = DeleteOrganizationResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_organization_delete_one(FunOrganizationDeleteReq {
+                    pkey_id: req.pkey_id,
+                })
+                .await?;
+            Ok(DeleteOrganizationResponse {
+                pkey_id: resp.pkey_id,
+            })
+        });
+    }
+}
+
+pub struct GetAssetHandler;
+impl RequestHandler for GetAssetHandler {
+    type Request = GetAssetRequest;
+    type Response = GetAssetResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_asset_get_one(FunAssetGetReq {
+                    pkey_id: req.pkey_id,
+                })
+                .await?;
+            Ok(GetAssetResponse {
+                pkey_id: resp.pkey_id,
+                short_name: resp.short_name,
+                long_name: resp.long_name,
+                description: resp.description,
+                network_type: resp.network_type,
+                contract_address: resp.contract_address,
+                precision: resp.precision,
+                fkey_prototype: resp.fkey_prototype,
+            })
+        });
+    }
+}
+
+pub struct ListAssetsHandler;
+impl RequestHandler for ListAssetsHandler {
+    type Request = ListAssetsRequest;
+    type Response = ListAssetsResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_asset_list(FunAssetListReq {
+                    offset: req.offset,
+                    limit: req.limit,
+                    short_name: req.short_name,
+                    network_type: req.network_type,
+                    fkey_prototype: req.fkey_prototype,
+                })
+                .await?;
+            Ok(ListAssetsResponse {
+                rows: resp
+                    .rows
+                    .into_iter()
+                    .map(|row| AssetRow {
+                        pkey_id: row.pkey_id,
+                        short_name: row.short_name,
+                        long_name: row.long_name,
+                        description: row.description,
+                        network_type: row.network_type,
+                        contract_address: row.contract_address,
+                        precision: row.precision,
+                        fkey_prototype: row.fkey_prototype,
+                    })
+                    .collect(),
+            })
+        });
+    }
+}
+
+pub struct CreateAssetHandler;
+impl RequestHandler for CreateAssetHandler {
+    type Request = CreateAssetRequest;
+    type Response = CreateAssetResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_asset_insert_one(FunAssetInsertReq {
+                    short_name: req.short_name,
+                    long_name: req.long_name,
+                    description: req.description,
+                    network_type: req.network_type,
+                    contract_address: req.contract_address,
+                    precision: req.precision,
+                    fkey_prototype: req.fkey_prototype,
+                })
+                .await?;
+            Ok(CreateAssetResponse {
+                pkey_id: resp.pkey_id,
+                short_name: resp.short_name,
+                long_name: resp.long_name,
+                description: resp.description,
+                network_type: resp.network_type,
+                contract_address: resp.contract_address,
+                precision: resp.precision,
+                fkey_prototype: resp.fkey_prototype,
+            })
+        });
+    }
+}
+
+pub struct UpdateAssetHandler;
+impl RequestHandler for UpdateAssetHandler {
+    type Request = UpdateAssetRequest;
+    type Response = UpdateAssetResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_asset_update_one(FunAssetUpdateReq {
+                    pkey_id: req.pkey_id,
+                    short_name: req.short_name,
+                    long_name: req.long_name,
+                    description: req.description,
+                    network_type: req.network_type,
+                    contract_address: req.contract_address,
+                    precision: req.precision,
+                    fkey_prototype: req.fkey_prototype,
+                })
+                .await?;
+            Ok(UpdateAssetResponse {
+                pkey_id: resp.pkey_id,
+                short_name: resp.short_name,
+                long_name: resp.long_name,
+                description: resp.description,
+                network_type: resp.network_type,
+                contract_address: resp.contract_address,
+                precision: resp.precision,
+                fkey_prototype: resp.fkey_prototype,
+            })
+        });
+    }
+}
+
+pub struct DeleteAssetHandler;
+impl RequestHandler for DeleteAssetHandler {
+    type Request = DeleteAssetRequest;
+    type Response = DeleteAssetResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_asset_delete_one(FunAssetDeleteReq {
+                    pkey_id: req.pkey_id,
+                })
+                .await?;
+            Ok(DeleteAssetResponse {
+                pkey_id: resp.pkey_id,
+            })
+        });
+    }
+}
+
+pub struct GetSupportTicketHandler;
+impl RequestHandler for GetSupportTicketHandler {
+    type Request = GetSupportTicketRequest;
+    type Response = GetSupportTicketResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_support_ticket_get_one(FunSupportTicketGetReq {
+                    pkey_id: req.pkey_id,
+                })
+                .await?;
+            Ok(GetSupportTicketResponse {
+                pkey_id: resp.pkey_id,
+                fkey_user: resp.fkey_user,
+                fkey_handler_user: resp.fkey_handler_user,
+                content: resp.content,
+                response: resp.response,
+                created_at: resp.created_at,
+                updated_at: resp.updated_at,
+            })
+        });
+    }
+}
+
+pub struct ListSupportTicketsHandler;
+impl RequestHandler for ListSupportTicketsHandler {
+    type Request = ListSupportTicketsRequest;
+    type Response = ListSupportTicketsResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_support_ticket_list(FunSupportTicketListReq {
+                    offset: req.offset,
+                    limit: req.limit,
+                    fkey_user: req.fkey_user,
+                    fkey_handler_user: req.fkey_handler_user,
+                })
+                .await?;
+            Ok(ListSupportTicketsResponse {
+                rows: resp
+                    .rows
+                    .into_iter()
+                    .map(|row| SupportTicketRow {
+                        pkey_id: row.pkey_id,
+                        fkey_user: row.fkey_user,
+                        fkey_handler_user: row.fkey_handler_user,
+                        content: row.content,
+                        response: row.response,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                    })
+                    .collect(),
+            })
+        });
+    }
+}
+
+pub struct CreateSupportTicketHandler;
+impl RequestHandler for CreateSupportTicketHandler {
+    type Request = CreateSupportTicketRequest;
+    type Response = CreateSupportTicketResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_support_ticket_insert_one(FunSupportTicketInsertReq {
+                    fkey_user: req.fkey_user,
+                    content: req.content,
+                    response: req.response,
+                    fkey_handler_user: req.fkey_handler_user,
+                })
+                .await?;
+            Ok(CreateSupportTicketResponse {
+                pkey_id: resp.pkey_id,
+                fkey_user: resp.fkey_user,
+                fkey_handler_user: resp.fkey_handler_user,
+                content: resp.content,
+                response: resp.response,
+                created_at: resp.created_at,
+                updated_at: resp.updated_at,
+            })
+        });
+    }
+}
+
+pub struct UpdateSupportTicketHandler;
+impl RequestHandler for UpdateSupportTicketHandler {
+    type Request = UpdateSupportTicketRequest;
+    type Response = UpdateSupportTicketResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_support_ticket_update_one(FunSupportTicketUpdateReq {
+                    pkey_id: req.pkey_id,
+                    fkey_user: req.fkey_user,
+                    fkey_handler_user: req.fkey_handler_user,
+                    content: req.content,
+                    response: req.response,
+                    created_at: req.created_at,
+                })
+                .await?;
+            Ok(UpdateSupportTicketResponse {
+                pkey_id: resp.pkey_id,
+                fkey_user: resp.fkey_user,
+                fkey_handler_user: resp.fkey_handler_user,
+                content: resp.content,
+                response: resp.response,
+                created_at: resp.created_at,
+                updated_at: resp.updated_at,
+            })
+        });
+    }
+}
+
+pub struct DeleteSupportTicketHandler;
+impl RequestHandler for DeleteSupportTicketHandler {
+    type Request = DeleteSupportTicketRequest;
+    type Response = DeleteSupportTicketResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let resp = db
+                .fun_support_ticket_delete_one(FunSupportTicketDeleteReq {
+                    pkey_id: req.pkey_id,
+                })
+                .await?;
+            Ok(DeleteSupportTicketResponse {
+                pkey_id: resp.pkey_id,
+            })
+        });
+    }
+}
+
+/// Handler of a generated CRUD endpoint, by endpoint name
+pub fn get_crud_handler(name: &str) -> Option<Arc<dyn RequestHandlerErased>> {
+    match name {
+        "GetOrganization" => Some(Arc::new(GetOrganizationHandler)),
+        "ListOrganizations" => Some(Arc::new(ListOrganizationsHandler)),
+        "CreateOrganization" => Some(Arc::new(CreateOrganizationHandler)),
+        "UpdateOrganization" => Some(Arc::new(UpdateOrganizationHandler)),
+        "DeleteOrganization" => Some(Arc::new(DeleteOrganizationHandler)),
+        "GetAsset" => Some(Arc::new(GetAssetHandler)),
+        "ListAssets" => Some(Arc::new(ListAssetsHandler)),
+        "CreateAsset" => Some(Arc::new(CreateAssetHandler)),
+        "UpdateAsset" => Some(Arc::new(UpdateAssetHandler)),
+        "DeleteAsset" => Some(Arc::new(DeleteAssetHandler)),
+        "GetSupportTicket" => Some(Arc::new(GetSupportTicketHandler)),
+        "ListSupportTickets" => Some(Arc::new(ListSupportTicketsHandler)),
+        "CreateSupportTicket" => Some(Arc::new(CreateSupportTicketHandler)),
+        "UpdateSupportTicket" => Some(Arc::new(UpdateSupportTicketHandler)),
+        "DeleteSupportTicket" => Some(Arc::new(DeleteSupportTicketHandler)),
+        _ => None,
+    }
+}
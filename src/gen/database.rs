@@ -1,14 +1,38 @@
 use crate::model::*;
 use eyre::*;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use lib::database::*;
+use std::sync::Arc;
 
+/// Where the generated methods send calls: postgres, or a `DbApi` such as `MockDbClient`
 #[derive(Clone)]
-pub struct DbClient {
-    client: SimpleDbClient,
+enum DbBackend<C> {
+    Pg(C),
+    Api(Arc<dyn DbApi>),
 }
+
+/// The generated `fun_*` methods, over a `SimpleDbClient` or a `SimpleDbTransaction`
+#[derive(Clone)]
+pub struct Db<C> {
+    backend: DbBackend<C>,
+}
+pub type DbClient = Db<SimpleDbClient>;
+/// Rolled back if dropped without `commit`. Commit and rollback do nothing with a `DbApi`
+pub type DbTransaction = Db<SimpleDbTransaction>;
+
 impl DbClient {
     pub fn new(client: SimpleDbClient) -> Self {
-        Self { client }
+        Self {
+            backend: DbBackend::Pg(client),
+        }
+    }
+    /// Sends every call to `api` instead of postgres, e.g. a `MockDbClient` in tests
+    pub fn with_api(api: Arc<dyn DbApi>) -> Self {
+        Self {
+            backend: DbBackend::Api(api),
+        }
     }
 }
 impl From<SimpleDbClient> for DbClient {
@@ -16,14 +40,37 @@ impl From<SimpleDbClient> for DbClient {
         Self::new(client)
     }
 }
+impl DbClient {
+    /// Starts a transaction, the `fun_*` calls made through it are committed together
+    pub async fn begin(&self, isolation: IsolationLevel) -> Result<DbTransaction> {
+        let backend = match &self.backend {
+            DbBackend::Pg(client) => DbBackend::Pg(client.begin(isolation).await?),
+            DbBackend::Api(api) => DbBackend::Api(api.clone()),
+        };
+        Ok(DbTransaction { backend })
+    }
+}
+impl DbTransaction {
+    pub async fn commit(self) -> Result<()> {
+        match self.backend {
+            DbBackend::Pg(client) => client.commit().await,
+            DbBackend::Api(_) => Ok(()),
+        }
+    }
+    pub async fn rollback(self) -> Result<()> {
+        match self.backend {
+            DbBackend::Pg(client) => client.rollback().await,
+            DbBackend::Api(_) => Ok(()),
+        }
+    }
+}
 
 pub struct FunAuthSignupReq {
     pub public_id: i64,
     pub username: String,
     pub email: String,
     pub phone: String,
-    pub password_hash: Vec<u8>,
-    pub password_salt: Vec<u8>,
+    pub password_hash: String,
     pub age: i32,
     pub preferred_language: String,
     pub agreed_tos: bool,
@@ -36,10 +83,14 @@ pub struct FunAuthSignupRespRow {
 pub struct FunAuthSignupResp {
     pub rows: Vec<FunAuthSignupRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_signup(&self, req: FunAuthSignupReq) -> Result<FunAuthSignupResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_signup(a_public_id => $1::bigint, a_username => $2::varchar, a_email => $3::varchar, a_phone => $4::varchar, a_password_hash => $5::bytea, a_password_salt => $6::bytea, a_age => $7::int, a_preferred_language => $8::varchar, a_agreed_tos => $9::boolean, a_agreed_privacy => $10::boolean, a_ip_address => $11::inet);", &[&req.public_id, &req.username, &req.email, &req.phone, &req.password_hash, &req.password_salt, &req.age, &req.preferred_language, &req.agreed_tos, &req.agreed_privacy, &req.ip_address]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_signup(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_signup(a_public_id => $1::bigint, a_username => $2::varchar, a_email => $3::varchar, a_phone => $4::varchar, a_password_hash => $5::varchar, a_age => $6::int, a_preferred_language => $7::varchar, a_agreed_tos => $8::boolean, a_agreed_privacy => $9::boolean, a_ip_address => $10::inet);", &[&req.public_id, &req.username, &req.email, &req.phone, &req.password_hash, &req.age, &req.preferred_language, &req.agreed_tos, &req.agreed_privacy, &req.ip_address]).await?;
         let mut resp = FunAuthSignupResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -51,10 +102,12 @@ impl DbClient {
         }
         Ok(resp)
     }
+    pub async fn fun_auth_signup_one(&self, req: FunAuthSignupReq) -> Result<FunAuthSignupRespRow> {
+        expect_one("fun_auth_signup", self.fun_auth_signup(req).await?.rows)
+    }
 }
 pub struct FunAuthAuthenticateReq {
     pub username: String,
-    pub password_hash: Vec<u8>,
     pub service_code: i32,
     pub device_id: String,
     pub device_os: String,
@@ -67,13 +120,17 @@ pub struct FunAuthAuthenticateRespRow {
 pub struct FunAuthAuthenticateResp {
     pub rows: Vec<FunAuthAuthenticateRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_authenticate(
         &self,
         req: FunAuthAuthenticateReq,
     ) -> Result<FunAuthAuthenticateResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_authenticate(a_username => $1::varchar, a_password_hash => $2::bytea, a_service_code => $3::int, a_device_id => $4::varchar, a_device_os => $5::varchar, a_ip_address => $6::inet);", &[&req.username, &req.password_hash, &req.service_code, &req.device_id, &req.device_os, &req.ip_address]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_authenticate(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_authenticate(a_username => $1::varchar, a_service_code => $2::int, a_device_id => $3::varchar, a_device_os => $4::varchar, a_ip_address => $5::inet);", &[&req.username, &req.service_code, &req.device_id, &req.device_os, &req.ip_address]).await?;
         let mut resp = FunAuthAuthenticateResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -86,64 +143,235 @@ impl DbClient {
         }
         Ok(resp)
     }
+    pub async fn fun_auth_authenticate_one(
+        &self,
+        req: FunAuthAuthenticateReq,
+    ) -> Result<FunAuthAuthenticateRespRow> {
+        expect_one(
+            "fun_auth_authenticate",
+            self.fun_auth_authenticate(req).await?.rows,
+        )
+    }
 }
-pub struct FunAuthGetPasswordSaltReq {
+pub struct FunAuthGetPasswordHashReq {
     pub username: String,
 }
-pub struct FunAuthGetPasswordSaltRespRow {
-    pub salt: Vec<u8>,
+pub struct FunAuthGetPasswordHashRespRow {
+    pub user_id: i64,
+    pub password_hash: String,
+    pub password_salt: Option<Vec<u8>>,
+    pub totp_secret: Option<Vec<u8>>,
+    pub totp_pending_secret: Option<Vec<u8>>,
 }
-pub struct FunAuthGetPasswordSaltResp {
-    pub rows: Vec<FunAuthGetPasswordSaltRespRow>,
+pub struct FunAuthGetPasswordHashResp {
+    pub rows: Vec<FunAuthGetPasswordHashRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
-    pub async fn fun_auth_get_password_salt(
+    pub async fn fun_auth_get_password_hash(
         &self,
-        req: FunAuthGetPasswordSaltReq,
-    ) -> Result<FunAuthGetPasswordSaltResp> {
-        let rows = self
-            .client
+        req: FunAuthGetPasswordHashReq,
+    ) -> Result<FunAuthGetPasswordHashResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_get_password_hash(req).await,
+        };
+        let rows = client
             .query(
-                "SELECT * FROM api.fun_auth_get_password_salt(a_username => $1::varchar);",
+                "SELECT * FROM api.fun_auth_get_password_hash(a_username => $1::varchar);",
                 &[&req.username],
             )
             .await?;
-        let mut resp = FunAuthGetPasswordSaltResp {
+        let mut resp = FunAuthGetPasswordHashResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthGetPasswordHashRespRow {
+                user_id: row.try_get(0)?,
+                password_hash: row.try_get(1)?,
+                password_salt: row.try_get(2)?,
+                totp_secret: row.try_get(3)?,
+                totp_pending_secret: row.try_get(4)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_auth_get_password_hash_opt(
+        &self,
+        req: FunAuthGetPasswordHashReq,
+    ) -> Result<Option<FunAuthGetPasswordHashRespRow>> {
+        expect_optional(
+            "fun_auth_get_password_hash",
+            self.fun_auth_get_password_hash(req).await?.rows,
+        )
+    }
+}
+pub struct FunAuthBeginLoginAttemptReq {
+    pub username: String,
+    pub ip_address: std::net::IpAddr,
+    pub device_id: Option<String>,
+    pub device_os: Option<String>,
+    pub username_window: lib::time::Interval,
+    pub ip_window: lib::time::Interval,
+}
+pub struct FunAuthBeginLoginAttemptRespRow {
+    pub attempt_id: i64,
+    pub username_failures: i32,
+    pub username_last_failure: Option<chrono::DateTime<chrono::Utc>>,
+    pub ip_failures: i32,
+    pub ip_last_failure: Option<chrono::DateTime<chrono::Utc>>,
+}
+pub struct FunAuthBeginLoginAttemptResp {
+    pub rows: Vec<FunAuthBeginLoginAttemptRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_begin_login_attempt(
+        &self,
+        req: FunAuthBeginLoginAttemptReq,
+    ) -> Result<FunAuthBeginLoginAttemptResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_begin_login_attempt(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_begin_login_attempt(a_username => $1::varchar, a_ip_address => $2::inet, a_device_id => $3::varchar, a_device_os => $4::varchar, a_username_window => $5::interval, a_ip_window => $6::interval);", &[&req.username, &req.ip_address, &req.device_id, &req.device_os, &req.username_window, &req.ip_window]).await?;
+        let mut resp = FunAuthBeginLoginAttemptResp {
             rows: Vec::with_capacity(rows.len()),
         };
         for row in rows {
-            let r = FunAuthGetPasswordSaltRespRow {
-                salt: row.try_get(0)?,
+            let r = FunAuthBeginLoginAttemptRespRow {
+                attempt_id: row.try_get(0)?,
+                username_failures: row.try_get(1)?,
+                username_last_failure: row.try_get(2)?,
+                ip_failures: row.try_get(3)?,
+                ip_last_failure: row.try_get(4)?,
             };
             resp.rows.push(r);
         }
         Ok(resp)
     }
+    pub async fn fun_auth_begin_login_attempt_one(
+        &self,
+        req: FunAuthBeginLoginAttemptReq,
+    ) -> Result<FunAuthBeginLoginAttemptRespRow> {
+        expect_one(
+            "fun_auth_begin_login_attempt",
+            self.fun_auth_begin_login_attempt(req).await?.rows,
+        )
+    }
+}
+pub struct FunAuthFinishLoginAttemptReq {
+    pub attempt_id: i64,
+    pub is_password_ok: Option<bool>,
+}
+pub struct FunAuthFinishLoginAttemptRespRow {}
+pub struct FunAuthFinishLoginAttemptResp {
+    pub rows: Vec<FunAuthFinishLoginAttemptRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_finish_login_attempt(
+        &self,
+        req: FunAuthFinishLoginAttemptReq,
+    ) -> Result<FunAuthFinishLoginAttemptResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_finish_login_attempt(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_finish_login_attempt(a_attempt_id => $1::bigint, a_is_password_ok => $2::boolean);", &[&req.attempt_id, &req.is_password_ok]).await?;
+        let mut resp = FunAuthFinishLoginAttemptResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthFinishLoginAttemptRespRow {};
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
 }
-pub struct FunAuthSetTokenReq {
+pub struct FunAuthRehashPasswordReq {
     pub user_id: i64,
-    pub user_token: uuid::Uuid,
-    pub admin_token: uuid::Uuid,
-    pub service_code: i32,
+    pub old_password_hash: String,
+    pub new_password_hash: String,
 }
-pub struct FunAuthSetTokenRespRow {}
-pub struct FunAuthSetTokenResp {
-    pub rows: Vec<FunAuthSetTokenRespRow>,
+pub struct FunAuthRehashPasswordRespRow {}
+pub struct FunAuthRehashPasswordResp {
+    pub rows: Vec<FunAuthRehashPasswordRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
-    pub async fn fun_auth_set_token(&self, req: FunAuthSetTokenReq) -> Result<FunAuthSetTokenResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_set_token(a_user_id => $1::bigint, a_user_token => $2::uuid, a_admin_token => $3::uuid, a_service_code => $4::int);", &[&req.user_id, &req.user_token, &req.admin_token, &req.service_code]).await?;
-        let mut resp = FunAuthSetTokenResp {
+    pub async fn fun_auth_rehash_password(
+        &self,
+        req: FunAuthRehashPasswordReq,
+    ) -> Result<FunAuthRehashPasswordResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_rehash_password(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_rehash_password(a_user_id => $1::bigint, a_old_password_hash => $2::varchar, a_new_password_hash => $3::varchar);", &[&req.user_id, &req.old_password_hash, &req.new_password_hash]).await?;
+        let mut resp = FunAuthRehashPasswordResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthRehashPasswordRespRow {};
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+}
+pub struct FunAuthCreateSessionReq {
+    pub user_id: i64,
+    pub service: EnumService,
+    pub device_id: String,
+    pub device_os: String,
+    pub ip_address: std::net::IpAddr,
+    pub access_token: uuid::Uuid,
+    pub refresh_token: uuid::Uuid,
+    pub access_token_lifetime: lib::time::Interval,
+    pub idle_timeout: lib::time::Interval,
+    pub absolute_timeout: lib::time::Interval,
+    pub is_two_factor_ok: bool,
+}
+pub struct FunAuthCreateSessionRespRow {
+    pub access_expires_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunAuthCreateSessionResp {
+    pub rows: Vec<FunAuthCreateSessionRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_create_session(
+        &self,
+        req: FunAuthCreateSessionReq,
+    ) -> Result<FunAuthCreateSessionResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_create_session(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_create_session(a_user_id => $1::bigint, a_service => $2::enum_service, a_device_id => $3::varchar, a_device_os => $4::varchar, a_ip_address => $5::inet, a_access_token => $6::uuid, a_refresh_token => $7::uuid, a_access_token_lifetime => $8::interval, a_idle_timeout => $9::interval, a_absolute_timeout => $10::interval, a_is_two_factor_ok => $11::boolean);", &[&req.user_id, &req.service, &req.device_id, &req.device_os, &req.ip_address, &req.access_token, &req.refresh_token, &req.access_token_lifetime, &req.idle_timeout, &req.absolute_timeout, &req.is_two_factor_ok]).await?;
+        let mut resp = FunAuthCreateSessionResp {
             rows: Vec::with_capacity(rows.len()),
         };
         for row in rows {
-            let r = FunAuthSetTokenRespRow {};
+            let r = FunAuthCreateSessionRespRow {
+                access_expires_at: row.try_get(0)?,
+                expires_at: row.try_get(1)?,
+            };
             resp.rows.push(r);
         }
         Ok(resp)
     }
+    pub async fn fun_auth_create_session_one(
+        &self,
+        req: FunAuthCreateSessionReq,
+    ) -> Result<FunAuthCreateSessionRespRow> {
+        expect_one(
+            "fun_auth_create_session",
+            self.fun_auth_create_session(req).await?.rows,
+        )
+    }
 }
 pub struct FunAuthAuthorizeReq {
     pub username: String,
@@ -156,17 +384,23 @@ pub struct FunAuthAuthorizeReq {
 pub struct FunAuthAuthorizeRespRow {
     pub user_id: i64,
     pub role: EnumRole,
+    pub preferred_language: String,
+    pub session_id: i64,
 }
 pub struct FunAuthAuthorizeResp {
     pub rows: Vec<FunAuthAuthorizeRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_authorize(
         &self,
         req: FunAuthAuthorizeReq,
     ) -> Result<FunAuthAuthorizeResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_authorize(a_username => $1::varchar, a_token => $2::uuid, a_service => $3::enum_service, a_device_id => $4::varchar, a_device_os => $5::varchar, a_ip_address => $6::inet);", &[&req.username, &req.token, &req.service, &req.device_id, &req.device_os, &req.ip_address]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_authorize(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_authorize(a_username => $1::varchar, a_token => $2::uuid, a_service => $3::enum_service, a_device_id => $4::varchar, a_device_os => $5::varchar, a_ip_address => $6::inet);", &[&req.username, &req.token, &req.service, &req.device_id, &req.device_os, &req.ip_address]).await?;
         let mut resp = FunAuthAuthorizeResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -174,16 +408,204 @@ impl DbClient {
             let r = FunAuthAuthorizeRespRow {
                 user_id: row.try_get(0)?,
                 role: row.try_get(1)?,
+                preferred_language: row.try_get(2)?,
+                session_id: row.try_get(3)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_auth_authorize_one(
+        &self,
+        req: FunAuthAuthorizeReq,
+    ) -> Result<FunAuthAuthorizeRespRow> {
+        expect_one(
+            "fun_auth_authorize",
+            self.fun_auth_authorize(req).await?.rows,
+        )
+    }
+}
+pub struct FunAuthRefreshReq {
+    pub refresh_token: uuid::Uuid,
+    pub device_id: String,
+    pub device_os: String,
+    pub ip_address: std::net::IpAddr,
+    pub new_access_token: uuid::Uuid,
+    pub new_refresh_token: uuid::Uuid,
+    pub access_token_lifetime: lib::time::Interval,
+}
+pub struct FunAuthRefreshRespRow {
+    pub username: String,
+    pub access_expires_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunAuthRefreshResp {
+    pub rows: Vec<FunAuthRefreshRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_refresh(&self, req: FunAuthRefreshReq) -> Result<FunAuthRefreshResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_refresh(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_refresh(a_refresh_token => $1::uuid, a_device_id => $2::varchar, a_device_os => $3::varchar, a_ip_address => $4::inet, a_new_access_token => $5::uuid, a_new_refresh_token => $6::uuid, a_access_token_lifetime => $7::interval);", &[&req.refresh_token, &req.device_id, &req.device_os, &req.ip_address, &req.new_access_token, &req.new_refresh_token, &req.access_token_lifetime]).await?;
+        let mut resp = FunAuthRefreshResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthRefreshRespRow {
+                username: row.try_get(0)?,
+                access_expires_at: row.try_get(1)?,
+                expires_at: row.try_get(2)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_auth_refresh_one(
+        &self,
+        req: FunAuthRefreshReq,
+    ) -> Result<FunAuthRefreshRespRow> {
+        expect_one("fun_auth_refresh", self.fun_auth_refresh(req).await?.rows)
+    }
+}
+pub struct FunAuthLogoutReq {
+    pub username: String,
+    pub token: uuid::Uuid,
+}
+pub struct FunAuthLogoutRespRow {}
+pub struct FunAuthLogoutResp {
+    pub rows: Vec<FunAuthLogoutRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_logout(&self, req: FunAuthLogoutReq) -> Result<FunAuthLogoutResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_logout(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_logout(a_username => $1::varchar, a_token => $2::uuid);", &[&req.username, &req.token]).await?;
+        let mut resp = FunAuthLogoutResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthLogoutRespRow {};
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+}
+pub struct FunAuthLogoutAllReq {
+    pub username: String,
+    pub token: uuid::Uuid,
+}
+pub struct FunAuthLogoutAllRespRow {
+    pub revoked_sessions: i32,
+}
+pub struct FunAuthLogoutAllResp {
+    pub rows: Vec<FunAuthLogoutAllRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_logout_all(
+        &self,
+        req: FunAuthLogoutAllReq,
+    ) -> Result<FunAuthLogoutAllResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_logout_all(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_logout_all(a_username => $1::varchar, a_token => $2::uuid);", &[&req.username, &req.token]).await?;
+        let mut resp = FunAuthLogoutAllResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthLogoutAllRespRow {
+                revoked_sessions: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_auth_logout_all_one(
+        &self,
+        req: FunAuthLogoutAllReq,
+    ) -> Result<FunAuthLogoutAllRespRow> {
+        expect_one(
+            "fun_auth_logout_all",
+            self.fun_auth_logout_all(req).await?.rows,
+        )
+    }
+}
+pub struct FunAuthFilterSessionsReq {
+    pub session_ids: Vec<i64>,
+}
+pub struct FunAuthFilterSessionsRespRow {
+    pub session_id: i64,
+}
+pub struct FunAuthFilterSessionsResp {
+    pub rows: Vec<FunAuthFilterSessionsRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_filter_sessions(
+        &self,
+        req: FunAuthFilterSessionsReq,
+    ) -> Result<FunAuthFilterSessionsResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_filter_sessions(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_auth_filter_sessions(a_session_ids => $1::bigint[]);",
+                &[&req.session_ids],
+            )
+            .await?;
+        let mut resp = FunAuthFilterSessionsResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthFilterSessionsRespRow {
+                session_id: row.try_get(0)?,
             };
             resp.rows.push(r);
         }
         Ok(resp)
     }
+    #[allow(unused_variables)]
+    pub async fn fun_auth_filter_sessions_stream(
+        &self,
+        req: FunAuthFilterSessionsReq,
+    ) -> Result<BoxStream<'_, Result<FunAuthFilterSessionsRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_auth_filter_sessions(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client
+            .query_stream(
+                "SELECT * FROM api.fun_auth_filter_sessions(a_session_ids => $1::bigint[]);",
+                &[&req.session_ids],
+            )
+            .await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunAuthFilterSessionsRespRow {
+                    session_id: row.try_get(0)?,
+                })
+            })
+            .boxed())
+    }
 }
 pub struct FunAuthChangePasswordReq {
     pub username: String,
-    pub old_password_hash: Vec<u8>,
-    pub new_password_hash: Vec<u8>,
+    pub is_old_password_ok: bool,
+    pub new_password_hash: String,
     pub device_id: String,
     pub device_os: String,
     pub ip_address: std::net::IpAddr,
@@ -192,13 +614,17 @@ pub struct FunAuthChangePasswordRespRow {}
 pub struct FunAuthChangePasswordResp {
     pub rows: Vec<FunAuthChangePasswordRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_change_password(
         &self,
         req: FunAuthChangePasswordReq,
     ) -> Result<FunAuthChangePasswordResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_change_password(a_username => $1::varchar, a_old_password_hash => $2::bytea, a_new_password_hash => $3::bytea, a_device_id => $4::varchar, a_device_os => $5::varchar, a_ip_address => $6::inet);", &[&req.username, &req.old_password_hash, &req.new_password_hash, &req.device_id, &req.device_os, &req.ip_address]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_change_password(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_change_password(a_username => $1::varchar, a_is_old_password_ok => $2::boolean, a_new_password_hash => $3::varchar, a_device_id => $4::varchar, a_device_os => $5::varchar, a_ip_address => $6::inet);", &[&req.username, &req.is_old_password_ok, &req.new_password_hash, &req.device_id, &req.device_os, &req.ip_address]).await?;
         let mut resp = FunAuthChangePasswordResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -218,14 +644,17 @@ pub struct FunGetRecoveryQuestionDataRespRow {
 pub struct FunGetRecoveryQuestionDataResp {
     pub rows: Vec<FunGetRecoveryQuestionDataRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_get_recovery_question_data(
         &self,
         req: FunGetRecoveryQuestionDataReq,
     ) -> Result<FunGetRecoveryQuestionDataResp> {
-        let rows = self
-            .client
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_get_recovery_question_data(req).await,
+        };
+        let rows = client
             .query("SELECT * FROM api.fun_get_recovery_question_data();", &[])
             .await?;
         let mut resp = FunGetRecoveryQuestionDataResp {
@@ -241,23 +670,53 @@ impl DbClient {
         }
         Ok(resp)
     }
+    #[allow(unused_variables)]
+    pub async fn fun_get_recovery_question_data_stream(
+        &self,
+        req: FunGetRecoveryQuestionDataReq,
+    ) -> Result<BoxStream<'_, Result<FunGetRecoveryQuestionDataRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_get_recovery_question_data(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client
+            .query_stream("SELECT * FROM api.fun_get_recovery_question_data();", &[])
+            .await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunGetRecoveryQuestionDataRespRow {
+                    question_id: row.try_get(0)?,
+                    content: row.try_get(1)?,
+                    category: row.try_get(2)?,
+                })
+            })
+            .boxed())
+    }
 }
 pub struct FunAuthSetRecoveryQuestionsReq {
     pub user_id: i64,
     pub question_ids: Vec<i32>,
-    pub answers: Vec<i32>,
+    pub answer_hashes: Vec<String>,
 }
 pub struct FunAuthSetRecoveryQuestionsRespRow {}
 pub struct FunAuthSetRecoveryQuestionsResp {
     pub rows: Vec<FunAuthSetRecoveryQuestionsRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_set_recovery_questions(
         &self,
         req: FunAuthSetRecoveryQuestionsReq,
     ) -> Result<FunAuthSetRecoveryQuestionsResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_set_recovery_questions(a_user_id => $1::bigint, a_question_ids => $2::int[], a_answers => $3::int[]);", &[&req.user_id, &req.question_ids, &req.answers]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_set_recovery_questions(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_set_recovery_questions(a_user_id => $1::bigint, a_question_ids => $2::int[], a_answer_hashes => $3::varchar[]);", &[&req.user_id, &req.question_ids, &req.answer_hashes]).await?;
         let mut resp = FunAuthSetRecoveryQuestionsResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -275,18 +734,22 @@ pub struct FunAuthBasicAuthenticateReq {
     pub ip_address: std::net::IpAddr,
 }
 pub struct FunAuthBasicAuthenticateRespRow {
-    pub user_id: std::net::IpAddr,
+    pub user_id: i64,
 }
 pub struct FunAuthBasicAuthenticateResp {
     pub rows: Vec<FunAuthBasicAuthenticateRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_basic_authenticate(
         &self,
         req: FunAuthBasicAuthenticateReq,
     ) -> Result<FunAuthBasicAuthenticateResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_basic_authenticate(a_username => $1::varchar, a_device_id => $2::varchar, a_device_os => $3::varchar, a_ip_address => $4::inet);", &[&req.username, &req.device_id, &req.device_os, &req.ip_address]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_basic_authenticate(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_basic_authenticate(a_username => $1::varchar, a_device_id => $2::varchar, a_device_os => $3::varchar, a_ip_address => $4::inet);", &[&req.username, &req.device_id, &req.device_os, &req.ip_address]).await?;
         let mut resp = FunAuthBasicAuthenticateResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -298,9 +761,18 @@ impl DbClient {
         }
         Ok(resp)
     }
+    pub async fn fun_auth_basic_authenticate_one(
+        &self,
+        req: FunAuthBasicAuthenticateReq,
+    ) -> Result<FunAuthBasicAuthenticateRespRow> {
+        expect_one(
+            "fun_auth_basic_authenticate",
+            self.fun_auth_basic_authenticate(req).await?.rows,
+        )
+    }
 }
 pub struct FunAuthGetRecoveryQuestionsReq {
-    pub user_id: i64,
+    pub username: String,
 }
 pub struct FunAuthGetRecoveryQuestionsRespRow {
     pub question_id: i32,
@@ -309,17 +781,20 @@ pub struct FunAuthGetRecoveryQuestionsRespRow {
 pub struct FunAuthGetRecoveryQuestionsResp {
     pub rows: Vec<FunAuthGetRecoveryQuestionsRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_get_recovery_questions(
         &self,
         req: FunAuthGetRecoveryQuestionsReq,
     ) -> Result<FunAuthGetRecoveryQuestionsResp> {
-        let rows = self
-            .client
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_get_recovery_questions(req).await,
+        };
+        let rows = client
             .query(
-                "SELECT * FROM api.fun_auth_get_recovery_questions(a_user_id => $1::bigint);",
-                &[&req.user_id],
+                "SELECT * FROM api.fun_auth_get_recovery_questions(a_username => $1::varchar);",
+                &[&req.username],
             )
             .await?;
         let mut resp = FunAuthGetRecoveryQuestionsResp {
@@ -334,52 +809,166 @@ impl DbClient {
         }
         Ok(resp)
     }
+    #[allow(unused_variables)]
+    pub async fn fun_auth_get_recovery_questions_stream(
+        &self,
+        req: FunAuthGetRecoveryQuestionsReq,
+    ) -> Result<BoxStream<'_, Result<FunAuthGetRecoveryQuestionsRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_auth_get_recovery_questions(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client
+            .query_stream(
+                "SELECT * FROM api.fun_auth_get_recovery_questions(a_username => $1::varchar);",
+                &[&req.username],
+            )
+            .await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunAuthGetRecoveryQuestionsRespRow {
+                    question_id: row.try_get(0)?,
+                    question: row.try_get(1)?,
+                })
+            })
+            .boxed())
+    }
+}
+pub struct FunAuthBeginRecoveryAttemptReq {
+    pub username: String,
+    pub ip_address: std::net::IpAddr,
+    pub max_failed_attempts: i32,
+    pub attempt_window: lib::time::Interval,
+}
+pub struct FunAuthBeginRecoveryAttemptRespRow {
+    pub attempt_id: i64,
+    pub user_id: i64,
+    pub question_id: i32,
+    pub answer_hash: String,
+}
+pub struct FunAuthBeginRecoveryAttemptResp {
+    pub rows: Vec<FunAuthBeginRecoveryAttemptRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_begin_recovery_attempt(
+        &self,
+        req: FunAuthBeginRecoveryAttemptReq,
+    ) -> Result<FunAuthBeginRecoveryAttemptResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_begin_recovery_attempt(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_begin_recovery_attempt(a_username => $1::varchar, a_ip_address => $2::inet, a_max_failed_attempts => $3::int, a_attempt_window => $4::interval);", &[&req.username, &req.ip_address, &req.max_failed_attempts, &req.attempt_window]).await?;
+        let mut resp = FunAuthBeginRecoveryAttemptResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthBeginRecoveryAttemptRespRow {
+                attempt_id: row.try_get(0)?,
+                user_id: row.try_get(1)?,
+                question_id: row.try_get(2)?,
+                answer_hash: row.try_get(3)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    #[allow(unused_variables)]
+    pub async fn fun_auth_begin_recovery_attempt_stream(
+        &self,
+        req: FunAuthBeginRecoveryAttemptReq,
+    ) -> Result<BoxStream<'_, Result<FunAuthBeginRecoveryAttemptRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_auth_begin_recovery_attempt(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client.query_stream("SELECT * FROM api.fun_auth_begin_recovery_attempt(a_username => $1::varchar, a_ip_address => $2::inet, a_max_failed_attempts => $3::int, a_attempt_window => $4::interval);", &[&req.username, &req.ip_address, &req.max_failed_attempts, &req.attempt_window]).await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunAuthBeginRecoveryAttemptRespRow {
+                    attempt_id: row.try_get(0)?,
+                    user_id: row.try_get(1)?,
+                    question_id: row.try_get(2)?,
+                    answer_hash: row.try_get(3)?,
+                })
+            })
+            .boxed())
+    }
 }
 pub struct FunSubmitRecoveryAnswersReq {
+    pub attempt_id: i64,
     pub user_id: i64,
-    pub question_ids: Vec<i32>,
-    pub answers: Vec<String>,
+    pub is_answers_ok: bool,
     pub password_reset_token: uuid::Uuid,
-    pub token_valid: i32,
+    pub token_lifetime: lib::time::Interval,
+}
+pub struct FunSubmitRecoveryAnswersRespRow {
+    pub reset_token_valid: Option<chrono::DateTime<chrono::Utc>>,
 }
-pub struct FunSubmitRecoveryAnswersRespRow {}
 pub struct FunSubmitRecoveryAnswersResp {
     pub rows: Vec<FunSubmitRecoveryAnswersRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_submit_recovery_answers(
         &self,
         req: FunSubmitRecoveryAnswersReq,
     ) -> Result<FunSubmitRecoveryAnswersResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_submit_recovery_answers(a_user_id => $1::bigint, a_question_ids => $2::int[], a_answers => $3::varchar[], a_password_reset_token => $4::uuid, a_token_valid => $5::int);", &[&req.user_id, &req.question_ids, &req.answers, &req.password_reset_token, &req.token_valid]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_submit_recovery_answers(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_submit_recovery_answers(a_attempt_id => $1::bigint, a_user_id => $2::bigint, a_is_answers_ok => $3::boolean, a_password_reset_token => $4::uuid, a_token_lifetime => $5::interval);", &[&req.attempt_id, &req.user_id, &req.is_answers_ok, &req.password_reset_token, &req.token_lifetime]).await?;
         let mut resp = FunSubmitRecoveryAnswersResp {
             rows: Vec::with_capacity(rows.len()),
         };
         for row in rows {
-            let r = FunSubmitRecoveryAnswersRespRow {};
+            let r = FunSubmitRecoveryAnswersRespRow {
+                reset_token_valid: row.try_get(0)?,
+            };
             resp.rows.push(r);
         }
         Ok(resp)
     }
+    pub async fn fun_submit_recovery_answers_one(
+        &self,
+        req: FunSubmitRecoveryAnswersReq,
+    ) -> Result<FunSubmitRecoveryAnswersRespRow> {
+        expect_one(
+            "fun_submit_recovery_answers",
+            self.fun_submit_recovery_answers(req).await?.rows,
+        )
+    }
 }
 pub struct FunAuthResetPasswordReq {
-    pub user_id: i64,
-    pub new_password_hash: Vec<u8>,
-    pub new_password_salt: Vec<u8>,
+    pub username: String,
+    pub new_password_hash: String,
     pub reset_token: uuid::Uuid,
 }
 pub struct FunAuthResetPasswordRespRow {}
 pub struct FunAuthResetPasswordResp {
     pub rows: Vec<FunAuthResetPasswordRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_auth_reset_password(
         &self,
         req: FunAuthResetPasswordReq,
     ) -> Result<FunAuthResetPasswordResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_auth_reset_password(a_user_id => $1::bigint, a_new_password_hash => $2::bytea, a_new_password_salt => $3::bytea, a_reset_token => $4::uuid);", &[&req.user_id, &req.new_password_hash, &req.new_password_salt, &req.reset_token]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_reset_password(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_reset_password(a_username => $1::varchar, a_new_password_hash => $2::varchar, a_reset_token => $3::uuid);", &[&req.username, &req.new_password_hash, &req.reset_token]).await?;
         let mut resp = FunAuthResetPasswordResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -390,33 +979,298 @@ impl DbClient {
         Ok(resp)
     }
 }
-pub struct FunAdminListUsersReq {
-    pub offset: i32,
-    pub limit: i32,
-}
-pub struct FunAdminListUsersRespRow {
+pub struct FunAuthEnrollTotpReq {
     pub user_id: i64,
-    pub user_public_id: i64,
-    pub email: String,
-    pub username: String,
-    pub role: EnumRole,
-    pub updated_at: i32,
-    pub created_at: i32,
+    pub secret: Vec<u8>,
 }
-pub struct FunAdminListUsersResp {
-    pub rows: Vec<FunAdminListUsersRespRow>,
+pub struct FunAuthEnrollTotpRespRow {}
+pub struct FunAuthEnrollTotpResp {
+    pub rows: Vec<FunAuthEnrollTotpRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
-    pub async fn fun_admin_list_users(
+    pub async fn fun_auth_enroll_totp(
         &self,
-        req: FunAdminListUsersReq,
-    ) -> Result<FunAdminListUsersResp> {
-        let rows = self
-            .client
-            .query(
-                "SELECT * FROM api.fun_admin_list_users(a_offset => $1::int, a_limit => $2::int);",
-                &[&req.offset, &req.limit],
+        req: FunAuthEnrollTotpReq,
+    ) -> Result<FunAuthEnrollTotpResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_enroll_totp(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_enroll_totp(a_user_id => $1::bigint, a_secret => $2::bytea);", &[&req.user_id, &req.secret]).await?;
+        let mut resp = FunAuthEnrollTotpResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthEnrollTotpRespRow {};
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+}
+pub struct FunAuthConfirmTotpReq {
+    pub user_id: i64,
+    pub pending_secret: Vec<u8>,
+    pub step: i64,
+    pub recovery_code_hashes: Vec<String>,
+}
+pub struct FunAuthConfirmTotpRespRow {}
+pub struct FunAuthConfirmTotpResp {
+    pub rows: Vec<FunAuthConfirmTotpRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_confirm_totp(
+        &self,
+        req: FunAuthConfirmTotpReq,
+    ) -> Result<FunAuthConfirmTotpResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_confirm_totp(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_confirm_totp(a_user_id => $1::bigint, a_pending_secret => $2::bytea, a_step => $3::bigint, a_recovery_code_hashes => $4::varchar[]);", &[&req.user_id, &req.pending_secret, &req.step, &req.recovery_code_hashes]).await?;
+        let mut resp = FunAuthConfirmTotpResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthConfirmTotpRespRow {};
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+}
+pub struct FunAuthDisableTotpReq {
+    pub user_id: i64,
+}
+pub struct FunAuthDisableTotpRespRow {}
+pub struct FunAuthDisableTotpResp {
+    pub rows: Vec<FunAuthDisableTotpRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_disable_totp(
+        &self,
+        req: FunAuthDisableTotpReq,
+    ) -> Result<FunAuthDisableTotpResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_disable_totp(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_auth_disable_totp(a_user_id => $1::bigint);",
+                &[&req.user_id],
+            )
+            .await?;
+        let mut resp = FunAuthDisableTotpResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthDisableTotpRespRow {};
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+}
+pub struct FunAuthUseTwoFactorCodeReq {
+    pub user_id: i64,
+    pub ip_address: std::net::IpAddr,
+    pub totp_step: Option<i64>,
+    pub recovery_code_hash: Option<String>,
+    pub max_failed_attempts: i32,
+    pub attempt_window: lib::time::Interval,
+}
+pub struct FunAuthUseTwoFactorCodeRespRow {
+    pub accepted: bool,
+}
+pub struct FunAuthUseTwoFactorCodeResp {
+    pub rows: Vec<FunAuthUseTwoFactorCodeRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_use_two_factor_code(
+        &self,
+        req: FunAuthUseTwoFactorCodeReq,
+    ) -> Result<FunAuthUseTwoFactorCodeResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_use_two_factor_code(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_use_two_factor_code(a_user_id => $1::bigint, a_ip_address => $2::inet, a_totp_step => $3::bigint, a_recovery_code_hash => $4::varchar, a_max_failed_attempts => $5::int, a_attempt_window => $6::interval);", &[&req.user_id, &req.ip_address, &req.totp_step, &req.recovery_code_hash, &req.max_failed_attempts, &req.attempt_window]).await?;
+        let mut resp = FunAuthUseTwoFactorCodeResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthUseTwoFactorCodeRespRow {
+                accepted: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_auth_use_two_factor_code_one(
+        &self,
+        req: FunAuthUseTwoFactorCodeReq,
+    ) -> Result<FunAuthUseTwoFactorCodeRespRow> {
+        expect_one(
+            "fun_auth_use_two_factor_code",
+            self.fun_auth_use_two_factor_code(req).await?.rows,
+        )
+    }
+}
+pub struct FunAuthCreateLoginChallengeReq {
+    pub user_id: i64,
+    pub service: EnumService,
+    pub device_id: String,
+    pub device_os: String,
+    pub ip_address: std::net::IpAddr,
+    pub token: uuid::Uuid,
+    pub lifetime: lib::time::Interval,
+}
+pub struct FunAuthCreateLoginChallengeRespRow {
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunAuthCreateLoginChallengeResp {
+    pub rows: Vec<FunAuthCreateLoginChallengeRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_create_login_challenge(
+        &self,
+        req: FunAuthCreateLoginChallengeReq,
+    ) -> Result<FunAuthCreateLoginChallengeResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_create_login_challenge(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_create_login_challenge(a_user_id => $1::bigint, a_service => $2::enum_service, a_device_id => $3::varchar, a_device_os => $4::varchar, a_ip_address => $5::inet, a_token => $6::uuid, a_lifetime => $7::interval);", &[&req.user_id, &req.service, &req.device_id, &req.device_os, &req.ip_address, &req.token, &req.lifetime]).await?;
+        let mut resp = FunAuthCreateLoginChallengeResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthCreateLoginChallengeRespRow {
+                expires_at: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_auth_create_login_challenge_one(
+        &self,
+        req: FunAuthCreateLoginChallengeReq,
+    ) -> Result<FunAuthCreateLoginChallengeRespRow> {
+        expect_one(
+            "fun_auth_create_login_challenge",
+            self.fun_auth_create_login_challenge(req).await?.rows,
+        )
+    }
+}
+pub struct FunAuthGetLoginChallengeReq {
+    pub username: String,
+    pub token: uuid::Uuid,
+}
+pub struct FunAuthGetLoginChallengeRespRow {
+    pub challenge_id: i64,
+    pub user_id: i64,
+    pub user_public_id: i64,
+    pub service: EnumService,
+    pub device_id: String,
+    pub device_os: String,
+    pub totp_secret: Vec<u8>,
+}
+pub struct FunAuthGetLoginChallengeResp {
+    pub rows: Vec<FunAuthGetLoginChallengeRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_get_login_challenge(
+        &self,
+        req: FunAuthGetLoginChallengeReq,
+    ) -> Result<FunAuthGetLoginChallengeResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_get_login_challenge(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_get_login_challenge(a_username => $1::varchar, a_token => $2::uuid);", &[&req.username, &req.token]).await?;
+        let mut resp = FunAuthGetLoginChallengeResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthGetLoginChallengeRespRow {
+                challenge_id: row.try_get(0)?,
+                user_id: row.try_get(1)?,
+                user_public_id: row.try_get(2)?,
+                service: row.try_get(3)?,
+                device_id: row.try_get(4)?,
+                device_os: row.try_get(5)?,
+                totp_secret: row.try_get(6)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_auth_get_login_challenge_one(
+        &self,
+        req: FunAuthGetLoginChallengeReq,
+    ) -> Result<FunAuthGetLoginChallengeRespRow> {
+        expect_one(
+            "fun_auth_get_login_challenge",
+            self.fun_auth_get_login_challenge(req).await?.rows,
+        )
+    }
+}
+pub struct FunAuthCompleteLoginChallengeReq {
+    pub challenge_id: i64,
+}
+pub struct FunAuthCompleteLoginChallengeRespRow {}
+pub struct FunAuthCompleteLoginChallengeResp {
+    pub rows: Vec<FunAuthCompleteLoginChallengeRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_auth_complete_login_challenge(
+        &self,
+        req: FunAuthCompleteLoginChallengeReq,
+    ) -> Result<FunAuthCompleteLoginChallengeResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_auth_complete_login_challenge(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_auth_complete_login_challenge(a_challenge_id => $1::bigint);", &[&req.challenge_id]).await?;
+        let mut resp = FunAuthCompleteLoginChallengeResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAuthCompleteLoginChallengeRespRow {};
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+}
+pub struct FunAdminListUsersReq {
+    pub offset: i32,
+    pub limit: i32,
+}
+pub struct FunAdminListUsersRespRow {
+    pub users: Vec<ListUsersResponseRow>,
+}
+pub struct FunAdminListUsersResp {
+    pub rows: Vec<FunAdminListUsersRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_admin_list_users(
+        &self,
+        req: FunAdminListUsersReq,
+    ) -> Result<FunAdminListUsersResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_admin_list_users(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_admin_list_users(a_offset => $1::int, a_limit => $2::int);",
+                &[&req.offset, &req.limit],
             )
             .await?;
         let mut resp = FunAdminListUsersResp {
@@ -424,18 +1278,21 @@ impl DbClient {
         };
         for row in rows {
             let r = FunAdminListUsersRespRow {
-                user_id: row.try_get(0)?,
-                user_public_id: row.try_get(1)?,
-                email: row.try_get(2)?,
-                username: row.try_get(3)?,
-                role: row.try_get(4)?,
-                updated_at: row.try_get(5)?,
-                created_at: row.try_get(6)?,
+                users: row.try_get(0)?,
             };
             resp.rows.push(r);
         }
         Ok(resp)
     }
+    pub async fn fun_admin_list_users_one(
+        &self,
+        req: FunAdminListUsersReq,
+    ) -> Result<FunAdminListUsersRespRow> {
+        expect_one(
+            "fun_admin_list_users",
+            self.fun_admin_list_users(req).await?.rows,
+        )
+    }
 }
 pub struct FunAdminAssignRoleReq {
     pub operator_user_id: i64,
@@ -446,13 +1303,17 @@ pub struct FunAdminAssignRoleRespRow {}
 pub struct FunAdminAssignRoleResp {
     pub rows: Vec<FunAdminAssignRoleRespRow>,
 }
-impl DbClient {
+impl<C: DbQuery> Db<C> {
     #[allow(unused_variables)]
     pub async fn fun_admin_assign_role(
         &self,
         req: FunAdminAssignRoleReq,
     ) -> Result<FunAdminAssignRoleResp> {
-        let rows = self.client.query("SELECT * FROM api.fun_admin_assign_role(a_operator_user_id => $1::bigint, a_user_public_id => $2::bigint, a_new_role => $3::enum_role);", &[&req.operator_user_id, &req.user_public_id, &req.new_role]).await?;
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_admin_assign_role(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_admin_assign_role(a_operator_user_id => $1::bigint, a_user_public_id => $2::bigint, a_new_role => $3::enum_role);", &[&req.operator_user_id, &req.user_public_id, &req.new_role]).await?;
         let mut resp = FunAdminAssignRoleResp {
             rows: Vec::with_capacity(rows.len()),
         };
@@ -463,3 +1324,3286 @@ impl DbClient {
         Ok(resp)
     }
 }
+pub struct FunAdminRevokeSessionsReq {
+    pub operator_user_id: i64,
+    pub user_public_id: i64,
+    pub service: Option<EnumService>,
+}
+pub struct FunAdminRevokeSessionsRespRow {
+    pub revoked_sessions: i32,
+}
+pub struct FunAdminRevokeSessionsResp {
+    pub rows: Vec<FunAdminRevokeSessionsRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_admin_revoke_sessions(
+        &self,
+        req: FunAdminRevokeSessionsReq,
+    ) -> Result<FunAdminRevokeSessionsResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_admin_revoke_sessions(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_admin_revoke_sessions(a_operator_user_id => $1::bigint, a_user_public_id => $2::bigint, a_service => $3::enum_service);", &[&req.operator_user_id, &req.user_public_id, &req.service]).await?;
+        let mut resp = FunAdminRevokeSessionsResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAdminRevokeSessionsRespRow {
+                revoked_sessions: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_admin_revoke_sessions_one(
+        &self,
+        req: FunAdminRevokeSessionsReq,
+    ) -> Result<FunAdminRevokeSessionsRespRow> {
+        expect_one(
+            "fun_admin_revoke_sessions",
+            self.fun_admin_revoke_sessions(req).await?.rows,
+        )
+    }
+}
+pub struct FunAdminListLoginFailuresReq {
+    pub username_window: lib::time::Interval,
+    pub ip_window: lib::time::Interval,
+    pub min_username_failures: i32,
+    pub min_ip_failures: i32,
+}
+pub struct FunAdminListLoginFailuresRespRow {
+    pub username: Option<String>,
+    pub ip_address: Option<std::net::IpAddr>,
+    pub failures: i32,
+    pub last_failure: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunAdminListLoginFailuresResp {
+    pub rows: Vec<FunAdminListLoginFailuresRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_admin_list_login_failures(
+        &self,
+        req: FunAdminListLoginFailuresReq,
+    ) -> Result<FunAdminListLoginFailuresResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_admin_list_login_failures(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_admin_list_login_failures(a_username_window => $1::interval, a_ip_window => $2::interval, a_min_username_failures => $3::int, a_min_ip_failures => $4::int);", &[&req.username_window, &req.ip_window, &req.min_username_failures, &req.min_ip_failures]).await?;
+        let mut resp = FunAdminListLoginFailuresResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAdminListLoginFailuresRespRow {
+                username: row.try_get(0)?,
+                ip_address: row.try_get(1)?,
+                failures: row.try_get(2)?,
+                last_failure: row.try_get(3)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    #[allow(unused_variables)]
+    pub async fn fun_admin_list_login_failures_stream(
+        &self,
+        req: FunAdminListLoginFailuresReq,
+    ) -> Result<BoxStream<'_, Result<FunAdminListLoginFailuresRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_admin_list_login_failures(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client.query_stream("SELECT * FROM api.fun_admin_list_login_failures(a_username_window => $1::interval, a_ip_window => $2::interval, a_min_username_failures => $3::int, a_min_ip_failures => $4::int);", &[&req.username_window, &req.ip_window, &req.min_username_failures, &req.min_ip_failures]).await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunAdminListLoginFailuresRespRow {
+                    username: row.try_get(0)?,
+                    ip_address: row.try_get(1)?,
+                    failures: row.try_get(2)?,
+                    last_failure: row.try_get(3)?,
+                })
+            })
+            .boxed())
+    }
+}
+pub struct FunAdminClearLoginFailuresReq {
+    pub operator_user_id: i64,
+    pub username: Option<String>,
+    pub ip_address: Option<std::net::IpAddr>,
+}
+pub struct FunAdminClearLoginFailuresRespRow {
+    pub cleared: i32,
+}
+pub struct FunAdminClearLoginFailuresResp {
+    pub rows: Vec<FunAdminClearLoginFailuresRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_admin_clear_login_failures(
+        &self,
+        req: FunAdminClearLoginFailuresReq,
+    ) -> Result<FunAdminClearLoginFailuresResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_admin_clear_login_failures(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_admin_clear_login_failures(a_operator_user_id => $1::bigint, a_username => $2::varchar, a_ip_address => $3::inet);", &[&req.operator_user_id, &req.username, &req.ip_address]).await?;
+        let mut resp = FunAdminClearLoginFailuresResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAdminClearLoginFailuresRespRow {
+                cleared: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_admin_clear_login_failures_one(
+        &self,
+        req: FunAdminClearLoginFailuresReq,
+    ) -> Result<FunAdminClearLoginFailuresRespRow> {
+        expect_one(
+            "fun_admin_clear_login_failures",
+            self.fun_admin_clear_login_failures(req).await?.rows,
+        )
+    }
+}
+pub struct FunAdminSetTwoFactorRequiredReq {
+    pub operator_user_id: i64,
+    pub service: EnumService,
+    pub required: bool,
+}
+pub struct FunAdminSetTwoFactorRequiredRespRow {
+    pub revoked_sessions: i32,
+}
+pub struct FunAdminSetTwoFactorRequiredResp {
+    pub rows: Vec<FunAdminSetTwoFactorRequiredRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_admin_set_two_factor_required(
+        &self,
+        req: FunAdminSetTwoFactorRequiredReq,
+    ) -> Result<FunAdminSetTwoFactorRequiredResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_admin_set_two_factor_required(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_admin_set_two_factor_required(a_operator_user_id => $1::bigint, a_service => $2::enum_service, a_required => $3::boolean);", &[&req.operator_user_id, &req.service, &req.required]).await?;
+        let mut resp = FunAdminSetTwoFactorRequiredResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAdminSetTwoFactorRequiredRespRow {
+                revoked_sessions: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_admin_set_two_factor_required_one(
+        &self,
+        req: FunAdminSetTwoFactorRequiredReq,
+    ) -> Result<FunAdminSetTwoFactorRequiredRespRow> {
+        expect_one(
+            "fun_admin_set_two_factor_required",
+            self.fun_admin_set_two_factor_required(req).await?.rows,
+        )
+    }
+}
+pub struct FunOrganizationGetReq {
+    pub pkey_id: i64,
+}
+pub struct FunOrganizationGetRespRow {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+pub struct FunOrganizationGetResp {
+    pub rows: Vec<FunOrganizationGetRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_organization_get(
+        &self,
+        req: FunOrganizationGetReq,
+    ) -> Result<FunOrganizationGetResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_organization_get(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_organization_get(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunOrganizationGetResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunOrganizationGetRespRow {
+                pkey_id: row.try_get(0)?,
+                name: row.try_get(1)?,
+                country: row.try_get(2)?,
+                tax_id: row.try_get(3)?,
+                address: row.try_get(4)?,
+                note: row.try_get(5)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_organization_get_one(
+        &self,
+        req: FunOrganizationGetReq,
+    ) -> Result<FunOrganizationGetRespRow> {
+        expect_one(
+            "fun_organization_get",
+            self.fun_organization_get(req).await?.rows,
+        )
+    }
+}
+pub struct FunOrganizationListReq {
+    pub offset: i32,
+    pub limit: i32,
+    pub name: Option<String>,
+    pub country: Option<String>,
+}
+pub struct FunOrganizationListRespRow {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+pub struct FunOrganizationListResp {
+    pub rows: Vec<FunOrganizationListRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_organization_list(
+        &self,
+        req: FunOrganizationListReq,
+    ) -> Result<FunOrganizationListResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_organization_list(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_organization_list(a_offset => $1::int, a_limit => $2::int, a_name => $3::varchar, a_country => $4::varchar);", &[&req.offset, &req.limit, &req.name, &req.country]).await?;
+        let mut resp = FunOrganizationListResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunOrganizationListRespRow {
+                pkey_id: row.try_get(0)?,
+                name: row.try_get(1)?,
+                country: row.try_get(2)?,
+                tax_id: row.try_get(3)?,
+                address: row.try_get(4)?,
+                note: row.try_get(5)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    #[allow(unused_variables)]
+    pub async fn fun_organization_list_stream(
+        &self,
+        req: FunOrganizationListReq,
+    ) -> Result<BoxStream<'_, Result<FunOrganizationListRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_organization_list(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client.query_stream("SELECT * FROM api.fun_organization_list(a_offset => $1::int, a_limit => $2::int, a_name => $3::varchar, a_country => $4::varchar);", &[&req.offset, &req.limit, &req.name, &req.country]).await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunOrganizationListRespRow {
+                    pkey_id: row.try_get(0)?,
+                    name: row.try_get(1)?,
+                    country: row.try_get(2)?,
+                    tax_id: row.try_get(3)?,
+                    address: row.try_get(4)?,
+                    note: row.try_get(5)?,
+                })
+            })
+            .boxed())
+    }
+}
+pub struct FunOrganizationInsertReq {
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+pub struct FunOrganizationInsertRespRow {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+pub struct FunOrganizationInsertResp {
+    pub rows: Vec<FunOrganizationInsertRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_organization_insert(
+        &self,
+        req: FunOrganizationInsertReq,
+    ) -> Result<FunOrganizationInsertResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_organization_insert(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_organization_insert(a_name => $1::varchar, a_country => $2::varchar, a_tax_id => $3::varchar, a_address => $4::varchar, a_note => $5::varchar);", &[&req.name, &req.country, &req.tax_id, &req.address, &req.note]).await?;
+        let mut resp = FunOrganizationInsertResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunOrganizationInsertRespRow {
+                pkey_id: row.try_get(0)?,
+                name: row.try_get(1)?,
+                country: row.try_get(2)?,
+                tax_id: row.try_get(3)?,
+                address: row.try_get(4)?,
+                note: row.try_get(5)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_organization_insert_one(
+        &self,
+        req: FunOrganizationInsertReq,
+    ) -> Result<FunOrganizationInsertRespRow> {
+        expect_one(
+            "fun_organization_insert",
+            self.fun_organization_insert(req).await?.rows,
+        )
+    }
+}
+pub struct FunOrganizationUpdateReq {
+    pub pkey_id: i64,
+    pub name: Option<String>,
+    pub country: Option<String>,
+    pub tax_id: Option<String>,
+    pub address: Option<String>,
+    pub note: Option<String>,
+}
+pub struct FunOrganizationUpdateRespRow {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+pub struct FunOrganizationUpdateResp {
+    pub rows: Vec<FunOrganizationUpdateRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_organization_update(
+        &self,
+        req: FunOrganizationUpdateReq,
+    ) -> Result<FunOrganizationUpdateResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_organization_update(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_organization_update(a_pkey_id => $1::bigint, a_name => $2::varchar, a_country => $3::varchar, a_tax_id => $4::varchar, a_address => $5::varchar, a_note => $6::varchar);", &[&req.pkey_id, &req.name, &req.country, &req.tax_id, &req.address, &req.note]).await?;
+        let mut resp = FunOrganizationUpdateResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunOrganizationUpdateRespRow {
+                pkey_id: row.try_get(0)?,
+                name: row.try_get(1)?,
+                country: row.try_get(2)?,
+                tax_id: row.try_get(3)?,
+                address: row.try_get(4)?,
+                note: row.try_get(5)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_organization_update_one(
+        &self,
+        req: FunOrganizationUpdateReq,
+    ) -> Result<FunOrganizationUpdateRespRow> {
+        expect_one(
+            "fun_organization_update",
+            self.fun_organization_update(req).await?.rows,
+        )
+    }
+}
+pub struct FunOrganizationDeleteReq {
+    pub pkey_id: i64,
+}
+pub struct FunOrganizationDeleteRespRow {
+    pub pkey_id: i64,
+}
+pub struct FunOrganizationDeleteResp {
+    pub rows: Vec<FunOrganizationDeleteRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_organization_delete(
+        &self,
+        req: FunOrganizationDeleteReq,
+    ) -> Result<FunOrganizationDeleteResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_organization_delete(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_organization_delete(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunOrganizationDeleteResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunOrganizationDeleteRespRow {
+                pkey_id: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_organization_delete_one(
+        &self,
+        req: FunOrganizationDeleteReq,
+    ) -> Result<FunOrganizationDeleteRespRow> {
+        expect_one(
+            "fun_organization_delete",
+            self.fun_organization_delete(req).await?.rows,
+        )
+    }
+}
+pub struct FunAssetGetReq {
+    pub pkey_id: i64,
+}
+pub struct FunAssetGetRespRow {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    pub fkey_prototype: Option<i64>,
+}
+pub struct FunAssetGetResp {
+    pub rows: Vec<FunAssetGetRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_asset_get(&self, req: FunAssetGetReq) -> Result<FunAssetGetResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_asset_get(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_asset_get(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunAssetGetResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAssetGetRespRow {
+                pkey_id: row.try_get(0)?,
+                short_name: row.try_get(1)?,
+                long_name: row.try_get(2)?,
+                description: row.try_get(3)?,
+                network_type: row.try_get(4)?,
+                contract_address: row.try_get(5)?,
+                precision: row.try_get(6)?,
+                fkey_prototype: row.try_get(7)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_asset_get_one(&self, req: FunAssetGetReq) -> Result<FunAssetGetRespRow> {
+        expect_one("fun_asset_get", self.fun_asset_get(req).await?.rows)
+    }
+}
+pub struct FunAssetListReq {
+    pub offset: i32,
+    pub limit: i32,
+    pub short_name: Option<String>,
+    pub network_type: Option<String>,
+    pub fkey_prototype: Option<i64>,
+}
+pub struct FunAssetListRespRow {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    pub fkey_prototype: Option<i64>,
+}
+pub struct FunAssetListResp {
+    pub rows: Vec<FunAssetListRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_asset_list(&self, req: FunAssetListReq) -> Result<FunAssetListResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_asset_list(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_asset_list(a_offset => $1::int, a_limit => $2::int, a_short_name => $3::varchar, a_network_type => $4::varchar, a_fkey_prototype => $5::bigint);", &[&req.offset, &req.limit, &req.short_name, &req.network_type, &req.fkey_prototype]).await?;
+        let mut resp = FunAssetListResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAssetListRespRow {
+                pkey_id: row.try_get(0)?,
+                short_name: row.try_get(1)?,
+                long_name: row.try_get(2)?,
+                description: row.try_get(3)?,
+                network_type: row.try_get(4)?,
+                contract_address: row.try_get(5)?,
+                precision: row.try_get(6)?,
+                fkey_prototype: row.try_get(7)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    #[allow(unused_variables)]
+    pub async fn fun_asset_list_stream(
+        &self,
+        req: FunAssetListReq,
+    ) -> Result<BoxStream<'_, Result<FunAssetListRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_asset_list(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client.query_stream("SELECT * FROM api.fun_asset_list(a_offset => $1::int, a_limit => $2::int, a_short_name => $3::varchar, a_network_type => $4::varchar, a_fkey_prototype => $5::bigint);", &[&req.offset, &req.limit, &req.short_name, &req.network_type, &req.fkey_prototype]).await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunAssetListRespRow {
+                    pkey_id: row.try_get(0)?,
+                    short_name: row.try_get(1)?,
+                    long_name: row.try_get(2)?,
+                    description: row.try_get(3)?,
+                    network_type: row.try_get(4)?,
+                    contract_address: row.try_get(5)?,
+                    precision: row.try_get(6)?,
+                    fkey_prototype: row.try_get(7)?,
+                })
+            })
+            .boxed())
+    }
+}
+pub struct FunAssetInsertReq {
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    pub fkey_prototype: Option<i64>,
+}
+pub struct FunAssetInsertRespRow {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    pub fkey_prototype: Option<i64>,
+}
+pub struct FunAssetInsertResp {
+    pub rows: Vec<FunAssetInsertRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_asset_insert(&self, req: FunAssetInsertReq) -> Result<FunAssetInsertResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_asset_insert(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_asset_insert(a_short_name => $1::varchar, a_long_name => $2::varchar, a_description => $3::varchar, a_network_type => $4::varchar, a_contract_address => $5::varchar, a_precision => $6::int, a_fkey_prototype => $7::bigint);", &[&req.short_name, &req.long_name, &req.description, &req.network_type, &req.contract_address, &req.precision, &req.fkey_prototype]).await?;
+        let mut resp = FunAssetInsertResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAssetInsertRespRow {
+                pkey_id: row.try_get(0)?,
+                short_name: row.try_get(1)?,
+                long_name: row.try_get(2)?,
+                description: row.try_get(3)?,
+                network_type: row.try_get(4)?,
+                contract_address: row.try_get(5)?,
+                precision: row.try_get(6)?,
+                fkey_prototype: row.try_get(7)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_asset_insert_one(
+        &self,
+        req: FunAssetInsertReq,
+    ) -> Result<FunAssetInsertRespRow> {
+        expect_one("fun_asset_insert", self.fun_asset_insert(req).await?.rows)
+    }
+}
+pub struct FunAssetUpdateReq {
+    pub pkey_id: i64,
+    pub short_name: Option<String>,
+    pub long_name: Option<String>,
+    pub description: Option<String>,
+    pub network_type: Option<String>,
+    pub contract_address: Option<String>,
+    pub precision: Option<i32>,
+    pub fkey_prototype: Option<i64>,
+}
+pub struct FunAssetUpdateRespRow {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    pub fkey_prototype: Option<i64>,
+}
+pub struct FunAssetUpdateResp {
+    pub rows: Vec<FunAssetUpdateRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_asset_update(&self, req: FunAssetUpdateReq) -> Result<FunAssetUpdateResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_asset_update(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_asset_update(a_pkey_id => $1::bigint, a_short_name => $2::varchar, a_long_name => $3::varchar, a_description => $4::varchar, a_network_type => $5::varchar, a_contract_address => $6::varchar, a_precision => $7::int, a_fkey_prototype => $8::bigint);", &[&req.pkey_id, &req.short_name, &req.long_name, &req.description, &req.network_type, &req.contract_address, &req.precision, &req.fkey_prototype]).await?;
+        let mut resp = FunAssetUpdateResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAssetUpdateRespRow {
+                pkey_id: row.try_get(0)?,
+                short_name: row.try_get(1)?,
+                long_name: row.try_get(2)?,
+                description: row.try_get(3)?,
+                network_type: row.try_get(4)?,
+                contract_address: row.try_get(5)?,
+                precision: row.try_get(6)?,
+                fkey_prototype: row.try_get(7)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_asset_update_one(
+        &self,
+        req: FunAssetUpdateReq,
+    ) -> Result<FunAssetUpdateRespRow> {
+        expect_one("fun_asset_update", self.fun_asset_update(req).await?.rows)
+    }
+}
+pub struct FunAssetDeleteReq {
+    pub pkey_id: i64,
+}
+pub struct FunAssetDeleteRespRow {
+    pub pkey_id: i64,
+}
+pub struct FunAssetDeleteResp {
+    pub rows: Vec<FunAssetDeleteRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_asset_delete(&self, req: FunAssetDeleteReq) -> Result<FunAssetDeleteResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_asset_delete(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_asset_delete(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunAssetDeleteResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunAssetDeleteRespRow {
+                pkey_id: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_asset_delete_one(
+        &self,
+        req: FunAssetDeleteReq,
+    ) -> Result<FunAssetDeleteRespRow> {
+        expect_one("fun_asset_delete", self.fun_asset_delete(req).await?.rows)
+    }
+}
+pub struct FunSupportTicketGetReq {
+    pub pkey_id: i64,
+}
+pub struct FunSupportTicketGetRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunSupportTicketGetResp {
+    pub rows: Vec<FunSupportTicketGetRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_support_ticket_get(
+        &self,
+        req: FunSupportTicketGetReq,
+    ) -> Result<FunSupportTicketGetResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_support_ticket_get(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_support_ticket_get(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunSupportTicketGetResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunSupportTicketGetRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_handler_user: row.try_get(2)?,
+                content: row.try_get(3)?,
+                response: row.try_get(4)?,
+                created_at: row.try_get(5)?,
+                updated_at: row.try_get(6)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_support_ticket_get_one(
+        &self,
+        req: FunSupportTicketGetReq,
+    ) -> Result<FunSupportTicketGetRespRow> {
+        expect_one(
+            "fun_support_ticket_get",
+            self.fun_support_ticket_get(req).await?.rows,
+        )
+    }
+}
+pub struct FunSupportTicketListReq {
+    pub offset: i32,
+    pub limit: i32,
+    pub fkey_user: Option<i64>,
+    pub fkey_handler_user: Option<i64>,
+}
+pub struct FunSupportTicketListRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunSupportTicketListResp {
+    pub rows: Vec<FunSupportTicketListRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_support_ticket_list(
+        &self,
+        req: FunSupportTicketListReq,
+    ) -> Result<FunSupportTicketListResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_support_ticket_list(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_support_ticket_list(a_offset => $1::int, a_limit => $2::int, a_fkey_user => $3::bigint, a_fkey_handler_user => $4::bigint);", &[&req.offset, &req.limit, &req.fkey_user, &req.fkey_handler_user]).await?;
+        let mut resp = FunSupportTicketListResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunSupportTicketListRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_handler_user: row.try_get(2)?,
+                content: row.try_get(3)?,
+                response: row.try_get(4)?,
+                created_at: row.try_get(5)?,
+                updated_at: row.try_get(6)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    #[allow(unused_variables)]
+    pub async fn fun_support_ticket_list_stream(
+        &self,
+        req: FunSupportTicketListReq,
+    ) -> Result<BoxStream<'_, Result<FunSupportTicketListRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_support_ticket_list(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client.query_stream("SELECT * FROM api.fun_support_ticket_list(a_offset => $1::int, a_limit => $2::int, a_fkey_user => $3::bigint, a_fkey_handler_user => $4::bigint);", &[&req.offset, &req.limit, &req.fkey_user, &req.fkey_handler_user]).await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunSupportTicketListRespRow {
+                    pkey_id: row.try_get(0)?,
+                    fkey_user: row.try_get(1)?,
+                    fkey_handler_user: row.try_get(2)?,
+                    content: row.try_get(3)?,
+                    response: row.try_get(4)?,
+                    created_at: row.try_get(5)?,
+                    updated_at: row.try_get(6)?,
+                })
+            })
+            .boxed())
+    }
+}
+pub struct FunSupportTicketInsertReq {
+    pub fkey_user: i64,
+    pub content: String,
+    pub response: String,
+    pub fkey_handler_user: Option<i64>,
+}
+pub struct FunSupportTicketInsertRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunSupportTicketInsertResp {
+    pub rows: Vec<FunSupportTicketInsertRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_support_ticket_insert(
+        &self,
+        req: FunSupportTicketInsertReq,
+    ) -> Result<FunSupportTicketInsertResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_support_ticket_insert(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_support_ticket_insert(a_fkey_user => $1::bigint, a_content => $2::varchar, a_response => $3::varchar, a_fkey_handler_user => $4::bigint);", &[&req.fkey_user, &req.content, &req.response, &req.fkey_handler_user]).await?;
+        let mut resp = FunSupportTicketInsertResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunSupportTicketInsertRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_handler_user: row.try_get(2)?,
+                content: row.try_get(3)?,
+                response: row.try_get(4)?,
+                created_at: row.try_get(5)?,
+                updated_at: row.try_get(6)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_support_ticket_insert_one(
+        &self,
+        req: FunSupportTicketInsertReq,
+    ) -> Result<FunSupportTicketInsertRespRow> {
+        expect_one(
+            "fun_support_ticket_insert",
+            self.fun_support_ticket_insert(req).await?.rows,
+        )
+    }
+}
+pub struct FunSupportTicketUpdateReq {
+    pub pkey_id: i64,
+    pub fkey_user: Option<i64>,
+    pub fkey_handler_user: Option<i64>,
+    pub content: Option<String>,
+    pub response: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+pub struct FunSupportTicketUpdateRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+pub struct FunSupportTicketUpdateResp {
+    pub rows: Vec<FunSupportTicketUpdateRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_support_ticket_update(
+        &self,
+        req: FunSupportTicketUpdateReq,
+    ) -> Result<FunSupportTicketUpdateResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_support_ticket_update(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_support_ticket_update(a_pkey_id => $1::bigint, a_fkey_user => $2::bigint, a_fkey_handler_user => $3::bigint, a_content => $4::varchar, a_response => $5::varchar, a_created_at => $6::timestamptz);", &[&req.pkey_id, &req.fkey_user, &req.fkey_handler_user, &req.content, &req.response, &req.created_at]).await?;
+        let mut resp = FunSupportTicketUpdateResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunSupportTicketUpdateRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_handler_user: row.try_get(2)?,
+                content: row.try_get(3)?,
+                response: row.try_get(4)?,
+                created_at: row.try_get(5)?,
+                updated_at: row.try_get(6)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_support_ticket_update_one(
+        &self,
+        req: FunSupportTicketUpdateReq,
+    ) -> Result<FunSupportTicketUpdateRespRow> {
+        expect_one(
+            "fun_support_ticket_update",
+            self.fun_support_ticket_update(req).await?.rows,
+        )
+    }
+}
+pub struct FunSupportTicketDeleteReq {
+    pub pkey_id: i64,
+}
+pub struct FunSupportTicketDeleteRespRow {
+    pub pkey_id: i64,
+}
+pub struct FunSupportTicketDeleteResp {
+    pub rows: Vec<FunSupportTicketDeleteRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_support_ticket_delete(
+        &self,
+        req: FunSupportTicketDeleteReq,
+    ) -> Result<FunSupportTicketDeleteResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_support_ticket_delete(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_support_ticket_delete(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunSupportTicketDeleteResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunSupportTicketDeleteRespRow {
+                pkey_id: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_support_ticket_delete_one(
+        &self,
+        req: FunSupportTicketDeleteReq,
+    ) -> Result<FunSupportTicketDeleteRespRow> {
+        expect_one(
+            "fun_support_ticket_delete",
+            self.fun_support_ticket_delete(req).await?.rows,
+        )
+    }
+}
+pub struct FunFavoriteWalletGetReq {
+    pub pkey_id: i64,
+}
+pub struct FunFavoriteWalletGetRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_wallet: i64,
+    pub user_pkey_id: i64,
+}
+pub struct FunFavoriteWalletGetResp {
+    pub rows: Vec<FunFavoriteWalletGetRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_favorite_wallet_get(
+        &self,
+        req: FunFavoriteWalletGetReq,
+    ) -> Result<FunFavoriteWalletGetResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_favorite_wallet_get(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_favorite_wallet_get(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunFavoriteWalletGetResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunFavoriteWalletGetRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_wallet: row.try_get(2)?,
+                user_pkey_id: row.try_get(3)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_favorite_wallet_get_one(
+        &self,
+        req: FunFavoriteWalletGetReq,
+    ) -> Result<FunFavoriteWalletGetRespRow> {
+        expect_one(
+            "fun_favorite_wallet_get",
+            self.fun_favorite_wallet_get(req).await?.rows,
+        )
+    }
+}
+pub struct FunFavoriteWalletListReq {
+    pub offset: i32,
+    pub limit: i32,
+    pub fkey_user: Option<i64>,
+    pub fkey_wallet: Option<i64>,
+}
+pub struct FunFavoriteWalletListRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_wallet: i64,
+    pub user_pkey_id: i64,
+}
+pub struct FunFavoriteWalletListResp {
+    pub rows: Vec<FunFavoriteWalletListRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_favorite_wallet_list(
+        &self,
+        req: FunFavoriteWalletListReq,
+    ) -> Result<FunFavoriteWalletListResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_favorite_wallet_list(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_favorite_wallet_list(a_offset => $1::int, a_limit => $2::int, a_fkey_user => $3::bigint, a_fkey_wallet => $4::bigint);", &[&req.offset, &req.limit, &req.fkey_user, &req.fkey_wallet]).await?;
+        let mut resp = FunFavoriteWalletListResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunFavoriteWalletListRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_wallet: row.try_get(2)?,
+                user_pkey_id: row.try_get(3)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    #[allow(unused_variables)]
+    pub async fn fun_favorite_wallet_list_stream(
+        &self,
+        req: FunFavoriteWalletListReq,
+    ) -> Result<BoxStream<'_, Result<FunFavoriteWalletListRespRow>>> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => {
+                let rows = api.fun_favorite_wallet_list(req).await?.rows;
+                return Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed());
+            }
+        };
+        let rows = client.query_stream("SELECT * FROM api.fun_favorite_wallet_list(a_offset => $1::int, a_limit => $2::int, a_fkey_user => $3::bigint, a_fkey_wallet => $4::bigint);", &[&req.offset, &req.limit, &req.fkey_user, &req.fkey_wallet]).await?;
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+                Ok(FunFavoriteWalletListRespRow {
+                    pkey_id: row.try_get(0)?,
+                    fkey_user: row.try_get(1)?,
+                    fkey_wallet: row.try_get(2)?,
+                    user_pkey_id: row.try_get(3)?,
+                })
+            })
+            .boxed())
+    }
+}
+pub struct FunFavoriteWalletInsertReq {
+    pub fkey_user: i64,
+    pub fkey_wallet: i64,
+    pub user_pkey_id: i64,
+}
+pub struct FunFavoriteWalletInsertRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_wallet: i64,
+    pub user_pkey_id: i64,
+}
+pub struct FunFavoriteWalletInsertResp {
+    pub rows: Vec<FunFavoriteWalletInsertRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_favorite_wallet_insert(
+        &self,
+        req: FunFavoriteWalletInsertReq,
+    ) -> Result<FunFavoriteWalletInsertResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_favorite_wallet_insert(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_favorite_wallet_insert(a_fkey_user => $1::bigint, a_fkey_wallet => $2::bigint, a_user_pkey_id => $3::bigint);", &[&req.fkey_user, &req.fkey_wallet, &req.user_pkey_id]).await?;
+        let mut resp = FunFavoriteWalletInsertResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunFavoriteWalletInsertRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_wallet: row.try_get(2)?,
+                user_pkey_id: row.try_get(3)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_favorite_wallet_insert_one(
+        &self,
+        req: FunFavoriteWalletInsertReq,
+    ) -> Result<FunFavoriteWalletInsertRespRow> {
+        expect_one(
+            "fun_favorite_wallet_insert",
+            self.fun_favorite_wallet_insert(req).await?.rows,
+        )
+    }
+}
+pub struct FunFavoriteWalletUpdateReq {
+    pub pkey_id: i64,
+    pub fkey_user: Option<i64>,
+    pub fkey_wallet: Option<i64>,
+    pub user_pkey_id: Option<i64>,
+}
+pub struct FunFavoriteWalletUpdateRespRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    pub fkey_wallet: i64,
+    pub user_pkey_id: i64,
+}
+pub struct FunFavoriteWalletUpdateResp {
+    pub rows: Vec<FunFavoriteWalletUpdateRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_favorite_wallet_update(
+        &self,
+        req: FunFavoriteWalletUpdateReq,
+    ) -> Result<FunFavoriteWalletUpdateResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_favorite_wallet_update(req).await,
+        };
+        let rows = client.query("SELECT * FROM api.fun_favorite_wallet_update(a_pkey_id => $1::bigint, a_fkey_user => $2::bigint, a_fkey_wallet => $3::bigint, a_user_pkey_id => $4::bigint);", &[&req.pkey_id, &req.fkey_user, &req.fkey_wallet, &req.user_pkey_id]).await?;
+        let mut resp = FunFavoriteWalletUpdateResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunFavoriteWalletUpdateRespRow {
+                pkey_id: row.try_get(0)?,
+                fkey_user: row.try_get(1)?,
+                fkey_wallet: row.try_get(2)?,
+                user_pkey_id: row.try_get(3)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_favorite_wallet_update_one(
+        &self,
+        req: FunFavoriteWalletUpdateReq,
+    ) -> Result<FunFavoriteWalletUpdateRespRow> {
+        expect_one(
+            "fun_favorite_wallet_update",
+            self.fun_favorite_wallet_update(req).await?.rows,
+        )
+    }
+}
+pub struct FunFavoriteWalletDeleteReq {
+    pub pkey_id: i64,
+}
+pub struct FunFavoriteWalletDeleteRespRow {
+    pub pkey_id: i64,
+}
+pub struct FunFavoriteWalletDeleteResp {
+    pub rows: Vec<FunFavoriteWalletDeleteRespRow>,
+}
+impl<C: DbQuery> Db<C> {
+    #[allow(unused_variables)]
+    pub async fn fun_favorite_wallet_delete(
+        &self,
+        req: FunFavoriteWalletDeleteReq,
+    ) -> Result<FunFavoriteWalletDeleteResp> {
+        let client = match &self.backend {
+            DbBackend::Pg(client) => client,
+            DbBackend::Api(api) => return api.fun_favorite_wallet_delete(req).await,
+        };
+        let rows = client
+            .query(
+                "SELECT * FROM api.fun_favorite_wallet_delete(a_pkey_id => $1::bigint);",
+                &[&req.pkey_id],
+            )
+            .await?;
+        let mut resp = FunFavoriteWalletDeleteResp {
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let r = FunFavoriteWalletDeleteRespRow {
+                pkey_id: row.try_get(0)?,
+            };
+            resp.rows.push(r);
+        }
+        Ok(resp)
+    }
+    pub async fn fun_favorite_wallet_delete_one(
+        &self,
+        req: FunFavoriteWalletDeleteReq,
+    ) -> Result<FunFavoriteWalletDeleteRespRow> {
+        expect_one(
+            "fun_favorite_wallet_delete",
+            self.fun_favorite_wallet_delete(req).await?.rows,
+        )
+    }
+}
+/// Every `fun_*` method, so handlers can be tested against a `MockDbClient`
+pub trait DbApi: Send + Sync {
+    fn fun_auth_signup(&self, req: FunAuthSignupReq) -> BoxFuture<'_, Result<FunAuthSignupResp>>;
+    fn fun_auth_authenticate(
+        &self,
+        req: FunAuthAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthenticateResp>>;
+    fn fun_auth_get_password_hash(
+        &self,
+        req: FunAuthGetPasswordHashReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetPasswordHashResp>>;
+    fn fun_auth_begin_login_attempt(
+        &self,
+        req: FunAuthBeginLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginLoginAttemptResp>>;
+    fn fun_auth_finish_login_attempt(
+        &self,
+        req: FunAuthFinishLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthFinishLoginAttemptResp>>;
+    fn fun_auth_rehash_password(
+        &self,
+        req: FunAuthRehashPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthRehashPasswordResp>>;
+    fn fun_auth_create_session(
+        &self,
+        req: FunAuthCreateSessionReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateSessionResp>>;
+    fn fun_auth_authorize(
+        &self,
+        req: FunAuthAuthorizeReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthorizeResp>>;
+    fn fun_auth_refresh(&self, req: FunAuthRefreshReq)
+        -> BoxFuture<'_, Result<FunAuthRefreshResp>>;
+    fn fun_auth_logout(&self, req: FunAuthLogoutReq) -> BoxFuture<'_, Result<FunAuthLogoutResp>>;
+    fn fun_auth_logout_all(
+        &self,
+        req: FunAuthLogoutAllReq,
+    ) -> BoxFuture<'_, Result<FunAuthLogoutAllResp>>;
+    fn fun_auth_filter_sessions(
+        &self,
+        req: FunAuthFilterSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthFilterSessionsResp>>;
+    fn fun_auth_change_password(
+        &self,
+        req: FunAuthChangePasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthChangePasswordResp>>;
+    fn fun_get_recovery_question_data(
+        &self,
+        req: FunGetRecoveryQuestionDataReq,
+    ) -> BoxFuture<'_, Result<FunGetRecoveryQuestionDataResp>>;
+    fn fun_auth_set_recovery_questions(
+        &self,
+        req: FunAuthSetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthSetRecoveryQuestionsResp>>;
+    fn fun_auth_basic_authenticate(
+        &self,
+        req: FunAuthBasicAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthBasicAuthenticateResp>>;
+    fn fun_auth_get_recovery_questions(
+        &self,
+        req: FunAuthGetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetRecoveryQuestionsResp>>;
+    fn fun_auth_begin_recovery_attempt(
+        &self,
+        req: FunAuthBeginRecoveryAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginRecoveryAttemptResp>>;
+    fn fun_submit_recovery_answers(
+        &self,
+        req: FunSubmitRecoveryAnswersReq,
+    ) -> BoxFuture<'_, Result<FunSubmitRecoveryAnswersResp>>;
+    fn fun_auth_reset_password(
+        &self,
+        req: FunAuthResetPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthResetPasswordResp>>;
+    fn fun_auth_enroll_totp(
+        &self,
+        req: FunAuthEnrollTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthEnrollTotpResp>>;
+    fn fun_auth_confirm_totp(
+        &self,
+        req: FunAuthConfirmTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthConfirmTotpResp>>;
+    fn fun_auth_disable_totp(
+        &self,
+        req: FunAuthDisableTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthDisableTotpResp>>;
+    fn fun_auth_use_two_factor_code(
+        &self,
+        req: FunAuthUseTwoFactorCodeReq,
+    ) -> BoxFuture<'_, Result<FunAuthUseTwoFactorCodeResp>>;
+    fn fun_auth_create_login_challenge(
+        &self,
+        req: FunAuthCreateLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateLoginChallengeResp>>;
+    fn fun_auth_get_login_challenge(
+        &self,
+        req: FunAuthGetLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetLoginChallengeResp>>;
+    fn fun_auth_complete_login_challenge(
+        &self,
+        req: FunAuthCompleteLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCompleteLoginChallengeResp>>;
+    fn fun_admin_list_users(
+        &self,
+        req: FunAdminListUsersReq,
+    ) -> BoxFuture<'_, Result<FunAdminListUsersResp>>;
+    fn fun_admin_assign_role(
+        &self,
+        req: FunAdminAssignRoleReq,
+    ) -> BoxFuture<'_, Result<FunAdminAssignRoleResp>>;
+    fn fun_admin_revoke_sessions(
+        &self,
+        req: FunAdminRevokeSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAdminRevokeSessionsResp>>;
+    fn fun_admin_list_login_failures(
+        &self,
+        req: FunAdminListLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminListLoginFailuresResp>>;
+    fn fun_admin_clear_login_failures(
+        &self,
+        req: FunAdminClearLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminClearLoginFailuresResp>>;
+    fn fun_admin_set_two_factor_required(
+        &self,
+        req: FunAdminSetTwoFactorRequiredReq,
+    ) -> BoxFuture<'_, Result<FunAdminSetTwoFactorRequiredResp>>;
+    fn fun_organization_get(
+        &self,
+        req: FunOrganizationGetReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationGetResp>>;
+    fn fun_organization_list(
+        &self,
+        req: FunOrganizationListReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationListResp>>;
+    fn fun_organization_insert(
+        &self,
+        req: FunOrganizationInsertReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationInsertResp>>;
+    fn fun_organization_update(
+        &self,
+        req: FunOrganizationUpdateReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationUpdateResp>>;
+    fn fun_organization_delete(
+        &self,
+        req: FunOrganizationDeleteReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationDeleteResp>>;
+    fn fun_asset_get(&self, req: FunAssetGetReq) -> BoxFuture<'_, Result<FunAssetGetResp>>;
+    fn fun_asset_list(&self, req: FunAssetListReq) -> BoxFuture<'_, Result<FunAssetListResp>>;
+    fn fun_asset_insert(&self, req: FunAssetInsertReq)
+        -> BoxFuture<'_, Result<FunAssetInsertResp>>;
+    fn fun_asset_update(&self, req: FunAssetUpdateReq)
+        -> BoxFuture<'_, Result<FunAssetUpdateResp>>;
+    fn fun_asset_delete(&self, req: FunAssetDeleteReq)
+        -> BoxFuture<'_, Result<FunAssetDeleteResp>>;
+    fn fun_support_ticket_get(
+        &self,
+        req: FunSupportTicketGetReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketGetResp>>;
+    fn fun_support_ticket_list(
+        &self,
+        req: FunSupportTicketListReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketListResp>>;
+    fn fun_support_ticket_insert(
+        &self,
+        req: FunSupportTicketInsertReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketInsertResp>>;
+    fn fun_support_ticket_update(
+        &self,
+        req: FunSupportTicketUpdateReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketUpdateResp>>;
+    fn fun_support_ticket_delete(
+        &self,
+        req: FunSupportTicketDeleteReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketDeleteResp>>;
+    fn fun_favorite_wallet_get(
+        &self,
+        req: FunFavoriteWalletGetReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletGetResp>>;
+    fn fun_favorite_wallet_list(
+        &self,
+        req: FunFavoriteWalletListReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletListResp>>;
+    fn fun_favorite_wallet_insert(
+        &self,
+        req: FunFavoriteWalletInsertReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletInsertResp>>;
+    fn fun_favorite_wallet_update(
+        &self,
+        req: FunFavoriteWalletUpdateReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletUpdateResp>>;
+    fn fun_favorite_wallet_delete(
+        &self,
+        req: FunFavoriteWalletDeleteReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletDeleteResp>>;
+}
+impl DbApi for DbClient {
+    fn fun_auth_signup(&self, req: FunAuthSignupReq) -> BoxFuture<'_, Result<FunAuthSignupResp>> {
+        Box::pin(DbClient::fun_auth_signup(self, req))
+    }
+    fn fun_auth_authenticate(
+        &self,
+        req: FunAuthAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthenticateResp>> {
+        Box::pin(DbClient::fun_auth_authenticate(self, req))
+    }
+    fn fun_auth_get_password_hash(
+        &self,
+        req: FunAuthGetPasswordHashReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetPasswordHashResp>> {
+        Box::pin(DbClient::fun_auth_get_password_hash(self, req))
+    }
+    fn fun_auth_begin_login_attempt(
+        &self,
+        req: FunAuthBeginLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginLoginAttemptResp>> {
+        Box::pin(DbClient::fun_auth_begin_login_attempt(self, req))
+    }
+    fn fun_auth_finish_login_attempt(
+        &self,
+        req: FunAuthFinishLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthFinishLoginAttemptResp>> {
+        Box::pin(DbClient::fun_auth_finish_login_attempt(self, req))
+    }
+    fn fun_auth_rehash_password(
+        &self,
+        req: FunAuthRehashPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthRehashPasswordResp>> {
+        Box::pin(DbClient::fun_auth_rehash_password(self, req))
+    }
+    fn fun_auth_create_session(
+        &self,
+        req: FunAuthCreateSessionReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateSessionResp>> {
+        Box::pin(DbClient::fun_auth_create_session(self, req))
+    }
+    fn fun_auth_authorize(
+        &self,
+        req: FunAuthAuthorizeReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthorizeResp>> {
+        Box::pin(DbClient::fun_auth_authorize(self, req))
+    }
+    fn fun_auth_refresh(
+        &self,
+        req: FunAuthRefreshReq,
+    ) -> BoxFuture<'_, Result<FunAuthRefreshResp>> {
+        Box::pin(DbClient::fun_auth_refresh(self, req))
+    }
+    fn fun_auth_logout(&self, req: FunAuthLogoutReq) -> BoxFuture<'_, Result<FunAuthLogoutResp>> {
+        Box::pin(DbClient::fun_auth_logout(self, req))
+    }
+    fn fun_auth_logout_all(
+        &self,
+        req: FunAuthLogoutAllReq,
+    ) -> BoxFuture<'_, Result<FunAuthLogoutAllResp>> {
+        Box::pin(DbClient::fun_auth_logout_all(self, req))
+    }
+    fn fun_auth_filter_sessions(
+        &self,
+        req: FunAuthFilterSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthFilterSessionsResp>> {
+        Box::pin(DbClient::fun_auth_filter_sessions(self, req))
+    }
+    fn fun_auth_change_password(
+        &self,
+        req: FunAuthChangePasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthChangePasswordResp>> {
+        Box::pin(DbClient::fun_auth_change_password(self, req))
+    }
+    fn fun_get_recovery_question_data(
+        &self,
+        req: FunGetRecoveryQuestionDataReq,
+    ) -> BoxFuture<'_, Result<FunGetRecoveryQuestionDataResp>> {
+        Box::pin(DbClient::fun_get_recovery_question_data(self, req))
+    }
+    fn fun_auth_set_recovery_questions(
+        &self,
+        req: FunAuthSetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthSetRecoveryQuestionsResp>> {
+        Box::pin(DbClient::fun_auth_set_recovery_questions(self, req))
+    }
+    fn fun_auth_basic_authenticate(
+        &self,
+        req: FunAuthBasicAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthBasicAuthenticateResp>> {
+        Box::pin(DbClient::fun_auth_basic_authenticate(self, req))
+    }
+    fn fun_auth_get_recovery_questions(
+        &self,
+        req: FunAuthGetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetRecoveryQuestionsResp>> {
+        Box::pin(DbClient::fun_auth_get_recovery_questions(self, req))
+    }
+    fn fun_auth_begin_recovery_attempt(
+        &self,
+        req: FunAuthBeginRecoveryAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginRecoveryAttemptResp>> {
+        Box::pin(DbClient::fun_auth_begin_recovery_attempt(self, req))
+    }
+    fn fun_submit_recovery_answers(
+        &self,
+        req: FunSubmitRecoveryAnswersReq,
+    ) -> BoxFuture<'_, Result<FunSubmitRecoveryAnswersResp>> {
+        Box::pin(DbClient::fun_submit_recovery_answers(self, req))
+    }
+    fn fun_auth_reset_password(
+        &self,
+        req: FunAuthResetPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthResetPasswordResp>> {
+        Box::pin(DbClient::fun_auth_reset_password(self, req))
+    }
+    fn fun_auth_enroll_totp(
+        &self,
+        req: FunAuthEnrollTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthEnrollTotpResp>> {
+        Box::pin(DbClient::fun_auth_enroll_totp(self, req))
+    }
+    fn fun_auth_confirm_totp(
+        &self,
+        req: FunAuthConfirmTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthConfirmTotpResp>> {
+        Box::pin(DbClient::fun_auth_confirm_totp(self, req))
+    }
+    fn fun_auth_disable_totp(
+        &self,
+        req: FunAuthDisableTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthDisableTotpResp>> {
+        Box::pin(DbClient::fun_auth_disable_totp(self, req))
+    }
+    fn fun_auth_use_two_factor_code(
+        &self,
+        req: FunAuthUseTwoFactorCodeReq,
+    ) -> BoxFuture<'_, Result<FunAuthUseTwoFactorCodeResp>> {
+        Box::pin(DbClient::fun_auth_use_two_factor_code(self, req))
+    }
+    fn fun_auth_create_login_challenge(
+        &self,
+        req: FunAuthCreateLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateLoginChallengeResp>> {
+        Box::pin(DbClient::fun_auth_create_login_challenge(self, req))
+    }
+    fn fun_auth_get_login_challenge(
+        &self,
+        req: FunAuthGetLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetLoginChallengeResp>> {
+        Box::pin(DbClient::fun_auth_get_login_challenge(self, req))
+    }
+    fn fun_auth_complete_login_challenge(
+        &self,
+        req: FunAuthCompleteLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCompleteLoginChallengeResp>> {
+        Box::pin(DbClient::fun_auth_complete_login_challenge(self, req))
+    }
+    fn fun_admin_list_users(
+        &self,
+        req: FunAdminListUsersReq,
+    ) -> BoxFuture<'_, Result<FunAdminListUsersResp>> {
+        Box::pin(DbClient::fun_admin_list_users(self, req))
+    }
+    fn fun_admin_assign_role(
+        &self,
+        req: FunAdminAssignRoleReq,
+    ) -> BoxFuture<'_, Result<FunAdminAssignRoleResp>> {
+        Box::pin(DbClient::fun_admin_assign_role(self, req))
+    }
+    fn fun_admin_revoke_sessions(
+        &self,
+        req: FunAdminRevokeSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAdminRevokeSessionsResp>> {
+        Box::pin(DbClient::fun_admin_revoke_sessions(self, req))
+    }
+    fn fun_admin_list_login_failures(
+        &self,
+        req: FunAdminListLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminListLoginFailuresResp>> {
+        Box::pin(DbClient::fun_admin_list_login_failures(self, req))
+    }
+    fn fun_admin_clear_login_failures(
+        &self,
+        req: FunAdminClearLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminClearLoginFailuresResp>> {
+        Box::pin(DbClient::fun_admin_clear_login_failures(self, req))
+    }
+    fn fun_admin_set_two_factor_required(
+        &self,
+        req: FunAdminSetTwoFactorRequiredReq,
+    ) -> BoxFuture<'_, Result<FunAdminSetTwoFactorRequiredResp>> {
+        Box::pin(DbClient::fun_admin_set_two_factor_required(self, req))
+    }
+    fn fun_organization_get(
+        &self,
+        req: FunOrganizationGetReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationGetResp>> {
+        Box::pin(DbClient::fun_organization_get(self, req))
+    }
+    fn fun_organization_list(
+        &self,
+        req: FunOrganizationListReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationListResp>> {
+        Box::pin(DbClient::fun_organization_list(self, req))
+    }
+    fn fun_organization_insert(
+        &self,
+        req: FunOrganizationInsertReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationInsertResp>> {
+        Box::pin(DbClient::fun_organization_insert(self, req))
+    }
+    fn fun_organization_update(
+        &self,
+        req: FunOrganizationUpdateReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationUpdateResp>> {
+        Box::pin(DbClient::fun_organization_update(self, req))
+    }
+    fn fun_organization_delete(
+        &self,
+        req: FunOrganizationDeleteReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationDeleteResp>> {
+        Box::pin(DbClient::fun_organization_delete(self, req))
+    }
+    fn fun_asset_get(&self, req: FunAssetGetReq) -> BoxFuture<'_, Result<FunAssetGetResp>> {
+        Box::pin(DbClient::fun_asset_get(self, req))
+    }
+    fn fun_asset_list(&self, req: FunAssetListReq) -> BoxFuture<'_, Result<FunAssetListResp>> {
+        Box::pin(DbClient::fun_asset_list(self, req))
+    }
+    fn fun_asset_insert(
+        &self,
+        req: FunAssetInsertReq,
+    ) -> BoxFuture<'_, Result<FunAssetInsertResp>> {
+        Box::pin(DbClient::fun_asset_insert(self, req))
+    }
+    fn fun_asset_update(
+        &self,
+        req: FunAssetUpdateReq,
+    ) -> BoxFuture<'_, Result<FunAssetUpdateResp>> {
+        Box::pin(DbClient::fun_asset_update(self, req))
+    }
+    fn fun_asset_delete(
+        &self,
+        req: FunAssetDeleteReq,
+    ) -> BoxFuture<'_, Result<FunAssetDeleteResp>> {
+        Box::pin(DbClient::fun_asset_delete(self, req))
+    }
+    fn fun_support_ticket_get(
+        &self,
+        req: FunSupportTicketGetReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketGetResp>> {
+        Box::pin(DbClient::fun_support_ticket_get(self, req))
+    }
+    fn fun_support_ticket_list(
+        &self,
+        req: FunSupportTicketListReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketListResp>> {
+        Box::pin(DbClient::fun_support_ticket_list(self, req))
+    }
+    fn fun_support_ticket_insert(
+        &self,
+        req: FunSupportTicketInsertReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketInsertResp>> {
+        Box::pin(DbClient::fun_support_ticket_insert(self, req))
+    }
+    fn fun_support_ticket_update(
+        &self,
+        req: FunSupportTicketUpdateReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketUpdateResp>> {
+        Box::pin(DbClient::fun_support_ticket_update(self, req))
+    }
+    fn fun_support_ticket_delete(
+        &self,
+        req: FunSupportTicketDeleteReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketDeleteResp>> {
+        Box::pin(DbClient::fun_support_ticket_delete(self, req))
+    }
+    fn fun_favorite_wallet_get(
+        &self,
+        req: FunFavoriteWalletGetReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletGetResp>> {
+        Box::pin(DbClient::fun_favorite_wallet_get(self, req))
+    }
+    fn fun_favorite_wallet_list(
+        &self,
+        req: FunFavoriteWalletListReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletListResp>> {
+        Box::pin(DbClient::fun_favorite_wallet_list(self, req))
+    }
+    fn fun_favorite_wallet_insert(
+        &self,
+        req: FunFavoriteWalletInsertReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletInsertResp>> {
+        Box::pin(DbClient::fun_favorite_wallet_insert(self, req))
+    }
+    fn fun_favorite_wallet_update(
+        &self,
+        req: FunFavoriteWalletUpdateReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletUpdateResp>> {
+        Box::pin(DbClient::fun_favorite_wallet_update(self, req))
+    }
+    fn fun_favorite_wallet_delete(
+        &self,
+        req: FunFavoriteWalletDeleteReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletDeleteResp>> {
+        Box::pin(DbClient::fun_favorite_wallet_delete(self, req))
+    }
+}
+impl DbApi for DbTransaction {
+    fn fun_auth_signup(&self, req: FunAuthSignupReq) -> BoxFuture<'_, Result<FunAuthSignupResp>> {
+        Box::pin(DbTransaction::fun_auth_signup(self, req))
+    }
+    fn fun_auth_authenticate(
+        &self,
+        req: FunAuthAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthenticateResp>> {
+        Box::pin(DbTransaction::fun_auth_authenticate(self, req))
+    }
+    fn fun_auth_get_password_hash(
+        &self,
+        req: FunAuthGetPasswordHashReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetPasswordHashResp>> {
+        Box::pin(DbTransaction::fun_auth_get_password_hash(self, req))
+    }
+    fn fun_auth_begin_login_attempt(
+        &self,
+        req: FunAuthBeginLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginLoginAttemptResp>> {
+        Box::pin(DbTransaction::fun_auth_begin_login_attempt(self, req))
+    }
+    fn fun_auth_finish_login_attempt(
+        &self,
+        req: FunAuthFinishLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthFinishLoginAttemptResp>> {
+        Box::pin(DbTransaction::fun_auth_finish_login_attempt(self, req))
+    }
+    fn fun_auth_rehash_password(
+        &self,
+        req: FunAuthRehashPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthRehashPasswordResp>> {
+        Box::pin(DbTransaction::fun_auth_rehash_password(self, req))
+    }
+    fn fun_auth_create_session(
+        &self,
+        req: FunAuthCreateSessionReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateSessionResp>> {
+        Box::pin(DbTransaction::fun_auth_create_session(self, req))
+    }
+    fn fun_auth_authorize(
+        &self,
+        req: FunAuthAuthorizeReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthorizeResp>> {
+        Box::pin(DbTransaction::fun_auth_authorize(self, req))
+    }
+    fn fun_auth_refresh(
+        &self,
+        req: FunAuthRefreshReq,
+    ) -> BoxFuture<'_, Result<FunAuthRefreshResp>> {
+        Box::pin(DbTransaction::fun_auth_refresh(self, req))
+    }
+    fn fun_auth_logout(&self, req: FunAuthLogoutReq) -> BoxFuture<'_, Result<FunAuthLogoutResp>> {
+        Box::pin(DbTransaction::fun_auth_logout(self, req))
+    }
+    fn fun_auth_logout_all(
+        &self,
+        req: FunAuthLogoutAllReq,
+    ) -> BoxFuture<'_, Result<FunAuthLogoutAllResp>> {
+        Box::pin(DbTransaction::fun_auth_logout_all(self, req))
+    }
+    fn fun_auth_filter_sessions(
+        &self,
+        req: FunAuthFilterSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthFilterSessionsResp>> {
+        Box::pin(DbTransaction::fun_auth_filter_sessions(self, req))
+    }
+    fn fun_auth_change_password(
+        &self,
+        req: FunAuthChangePasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthChangePasswordResp>> {
+        Box::pin(DbTransaction::fun_auth_change_password(self, req))
+    }
+    fn fun_get_recovery_question_data(
+        &self,
+        req: FunGetRecoveryQuestionDataReq,
+    ) -> BoxFuture<'_, Result<FunGetRecoveryQuestionDataResp>> {
+        Box::pin(DbTransaction::fun_get_recovery_question_data(self, req))
+    }
+    fn fun_auth_set_recovery_questions(
+        &self,
+        req: FunAuthSetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthSetRecoveryQuestionsResp>> {
+        Box::pin(DbTransaction::fun_auth_set_recovery_questions(self, req))
+    }
+    fn fun_auth_basic_authenticate(
+        &self,
+        req: FunAuthBasicAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthBasicAuthenticateResp>> {
+        Box::pin(DbTransaction::fun_auth_basic_authenticate(self, req))
+    }
+    fn fun_auth_get_recovery_questions(
+        &self,
+        req: FunAuthGetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetRecoveryQuestionsResp>> {
+        Box::pin(DbTransaction::fun_auth_get_recovery_questions(self, req))
+    }
+    fn fun_auth_begin_recovery_attempt(
+        &self,
+        req: FunAuthBeginRecoveryAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginRecoveryAttemptResp>> {
+        Box::pin(DbTransaction::fun_auth_begin_recovery_attempt(self, req))
+    }
+    fn fun_submit_recovery_answers(
+        &self,
+        req: FunSubmitRecoveryAnswersReq,
+    ) -> BoxFuture<'_, Result<FunSubmitRecoveryAnswersResp>> {
+        Box::pin(DbTransaction::fun_submit_recovery_answers(self, req))
+    }
+    fn fun_auth_reset_password(
+        &self,
+        req: FunAuthResetPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthResetPasswordResp>> {
+        Box::pin(DbTransaction::fun_auth_reset_password(self, req))
+    }
+    fn fun_auth_enroll_totp(
+        &self,
+        req: FunAuthEnrollTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthEnrollTotpResp>> {
+        Box::pin(DbTransaction::fun_auth_enroll_totp(self, req))
+    }
+    fn fun_auth_confirm_totp(
+        &self,
+        req: FunAuthConfirmTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthConfirmTotpResp>> {
+        Box::pin(DbTransaction::fun_auth_confirm_totp(self, req))
+    }
+    fn fun_auth_disable_totp(
+        &self,
+        req: FunAuthDisableTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthDisableTotpResp>> {
+        Box::pin(DbTransaction::fun_auth_disable_totp(self, req))
+    }
+    fn fun_auth_use_two_factor_code(
+        &self,
+        req: FunAuthUseTwoFactorCodeReq,
+    ) -> BoxFuture<'_, Result<FunAuthUseTwoFactorCodeResp>> {
+        Box::pin(DbTransaction::fun_auth_use_two_factor_code(self, req))
+    }
+    fn fun_auth_create_login_challenge(
+        &self,
+        req: FunAuthCreateLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateLoginChallengeResp>> {
+        Box::pin(DbTransaction::fun_auth_create_login_challenge(self, req))
+    }
+    fn fun_auth_get_login_challenge(
+        &self,
+        req: FunAuthGetLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetLoginChallengeResp>> {
+        Box::pin(DbTransaction::fun_auth_get_login_challenge(self, req))
+    }
+    fn fun_auth_complete_login_challenge(
+        &self,
+        req: FunAuthCompleteLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCompleteLoginChallengeResp>> {
+        Box::pin(DbTransaction::fun_auth_complete_login_challenge(self, req))
+    }
+    fn fun_admin_list_users(
+        &self,
+        req: FunAdminListUsersReq,
+    ) -> BoxFuture<'_, Result<FunAdminListUsersResp>> {
+        Box::pin(DbTransaction::fun_admin_list_users(self, req))
+    }
+    fn fun_admin_assign_role(
+        &self,
+        req: FunAdminAssignRoleReq,
+    ) -> BoxFuture<'_, Result<FunAdminAssignRoleResp>> {
+        Box::pin(DbTransaction::fun_admin_assign_role(self, req))
+    }
+    fn fun_admin_revoke_sessions(
+        &self,
+        req: FunAdminRevokeSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAdminRevokeSessionsResp>> {
+        Box::pin(DbTransaction::fun_admin_revoke_sessions(self, req))
+    }
+    fn fun_admin_list_login_failures(
+        &self,
+        req: FunAdminListLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminListLoginFailuresResp>> {
+        Box::pin(DbTransaction::fun_admin_list_login_failures(self, req))
+    }
+    fn fun_admin_clear_login_failures(
+        &self,
+        req: FunAdminClearLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminClearLoginFailuresResp>> {
+        Box::pin(DbTransaction::fun_admin_clear_login_failures(self, req))
+    }
+    fn fun_admin_set_two_factor_required(
+        &self,
+        req: FunAdminSetTwoFactorRequiredReq,
+    ) -> BoxFuture<'_, Result<FunAdminSetTwoFactorRequiredResp>> {
+        Box::pin(DbTransaction::fun_admin_set_two_factor_required(self, req))
+    }
+    fn fun_organization_get(
+        &self,
+        req: FunOrganizationGetReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationGetResp>> {
+        Box::pin(DbTransaction::fun_organization_get(self, req))
+    }
+    fn fun_organization_list(
+        &self,
+        req: FunOrganizationListReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationListResp>> {
+        Box::pin(DbTransaction::fun_organization_list(self, req))
+    }
+    fn fun_organization_insert(
+        &self,
+        req: FunOrganizationInsertReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationInsertResp>> {
+        Box::pin(DbTransaction::fun_organization_insert(self, req))
+    }
+    fn fun_organization_update(
+        &self,
+        req: FunOrganizationUpdateReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationUpdateResp>> {
+        Box::pin(DbTransaction::fun_organization_update(self, req))
+    }
+    fn fun_organization_delete(
+        &self,
+        req: FunOrganizationDeleteReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationDeleteResp>> {
+        Box::pin(DbTransaction::fun_organization_delete(self, req))
+    }
+    fn fun_asset_get(&self, req: FunAssetGetReq) -> BoxFuture<'_, Result<FunAssetGetResp>> {
+        Box::pin(DbTransaction::fun_asset_get(self, req))
+    }
+    fn fun_asset_list(&self, req: FunAssetListReq) -> BoxFuture<'_, Result<FunAssetListResp>> {
+        Box::pin(DbTransaction::fun_asset_list(self, req))
+    }
+    fn fun_asset_insert(
+        &self,
+        req: FunAssetInsertReq,
+    ) -> BoxFuture<'_, Result<FunAssetInsertResp>> {
+        Box::pin(DbTransaction::fun_asset_insert(self, req))
+    }
+    fn fun_asset_update(
+        &self,
+        req: FunAssetUpdateReq,
+    ) -> BoxFuture<'_, Result<FunAssetUpdateResp>> {
+        Box::pin(DbTransaction::fun_asset_update(self, req))
+    }
+    fn fun_asset_delete(
+        &self,
+        req: FunAssetDeleteReq,
+    ) -> BoxFuture<'_, Result<FunAssetDeleteResp>> {
+        Box::pin(DbTransaction::fun_asset_delete(self, req))
+    }
+    fn fun_support_ticket_get(
+        &self,
+        req: FunSupportTicketGetReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketGetResp>> {
+        Box::pin(DbTransaction::fun_support_ticket_get(self, req))
+    }
+    fn fun_support_ticket_list(
+        &self,
+        req: FunSupportTicketListReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketListResp>> {
+        Box::pin(DbTransaction::fun_support_ticket_list(self, req))
+    }
+    fn fun_support_ticket_insert(
+        &self,
+        req: FunSupportTicketInsertReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketInsertResp>> {
+        Box::pin(DbTransaction::fun_support_ticket_insert(self, req))
+    }
+    fn fun_support_ticket_update(
+        &self,
+        req: FunSupportTicketUpdateReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketUpdateResp>> {
+        Box::pin(DbTransaction::fun_support_ticket_update(self, req))
+    }
+    fn fun_support_ticket_delete(
+        &self,
+        req: FunSupportTicketDeleteReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketDeleteResp>> {
+        Box::pin(DbTransaction::fun_support_ticket_delete(self, req))
+    }
+    fn fun_favorite_wallet_get(
+        &self,
+        req: FunFavoriteWalletGetReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletGetResp>> {
+        Box::pin(DbTransaction::fun_favorite_wallet_get(self, req))
+    }
+    fn fun_favorite_wallet_list(
+        &self,
+        req: FunFavoriteWalletListReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletListResp>> {
+        Box::pin(DbTransaction::fun_favorite_wallet_list(self, req))
+    }
+    fn fun_favorite_wallet_insert(
+        &self,
+        req: FunFavoriteWalletInsertReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletInsertResp>> {
+        Box::pin(DbTransaction::fun_favorite_wallet_insert(self, req))
+    }
+    fn fun_favorite_wallet_update(
+        &self,
+        req: FunFavoriteWalletUpdateReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletUpdateResp>> {
+        Box::pin(DbTransaction::fun_favorite_wallet_update(self, req))
+    }
+    fn fun_favorite_wallet_delete(
+        &self,
+        req: FunFavoriteWalletDeleteReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletDeleteResp>> {
+        Box::pin(DbTransaction::fun_favorite_wallet_delete(self, req))
+    }
+}
+
+type MockFn<Req, Resp> = Box<dyn Fn(Req) -> Result<Resp> + Send + Sync>;
+
+/// A `DbApi` answering from a closure set per function with `on_fun_*`. Calling a function
+/// without one fails
+#[derive(Default)]
+pub struct MockDbClient {
+    fun_auth_signup: Option<MockFn<FunAuthSignupReq, FunAuthSignupResp>>,
+    fun_auth_authenticate: Option<MockFn<FunAuthAuthenticateReq, FunAuthAuthenticateResp>>,
+    fun_auth_get_password_hash:
+        Option<MockFn<FunAuthGetPasswordHashReq, FunAuthGetPasswordHashResp>>,
+    fun_auth_begin_login_attempt:
+        Option<MockFn<FunAuthBeginLoginAttemptReq, FunAuthBeginLoginAttemptResp>>,
+    fun_auth_finish_login_attempt:
+        Option<MockFn<FunAuthFinishLoginAttemptReq, FunAuthFinishLoginAttemptResp>>,
+    fun_auth_rehash_password: Option<MockFn<FunAuthRehashPasswordReq, FunAuthRehashPasswordResp>>,
+    fun_auth_create_session: Option<MockFn<FunAuthCreateSessionReq, FunAuthCreateSessionResp>>,
+    fun_auth_authorize: Option<MockFn<FunAuthAuthorizeReq, FunAuthAuthorizeResp>>,
+    fun_auth_refresh: Option<MockFn<FunAuthRefreshReq, FunAuthRefreshResp>>,
+    fun_auth_logout: Option<MockFn<FunAuthLogoutReq, FunAuthLogoutResp>>,
+    fun_auth_logout_all: Option<MockFn<FunAuthLogoutAllReq, FunAuthLogoutAllResp>>,
+    fun_auth_filter_sessions: Option<MockFn<FunAuthFilterSessionsReq, FunAuthFilterSessionsResp>>,
+    fun_auth_change_password: Option<MockFn<FunAuthChangePasswordReq, FunAuthChangePasswordResp>>,
+    fun_get_recovery_question_data:
+        Option<MockFn<FunGetRecoveryQuestionDataReq, FunGetRecoveryQuestionDataResp>>,
+    fun_auth_set_recovery_questions:
+        Option<MockFn<FunAuthSetRecoveryQuestionsReq, FunAuthSetRecoveryQuestionsResp>>,
+    fun_auth_basic_authenticate:
+        Option<MockFn<FunAuthBasicAuthenticateReq, FunAuthBasicAuthenticateResp>>,
+    fun_auth_get_recovery_questions:
+        Option<MockFn<FunAuthGetRecoveryQuestionsReq, FunAuthGetRecoveryQuestionsResp>>,
+    fun_auth_begin_recovery_attempt:
+        Option<MockFn<FunAuthBeginRecoveryAttemptReq, FunAuthBeginRecoveryAttemptResp>>,
+    fun_submit_recovery_answers:
+        Option<MockFn<FunSubmitRecoveryAnswersReq, FunSubmitRecoveryAnswersResp>>,
+    fun_auth_reset_password: Option<MockFn<FunAuthResetPasswordReq, FunAuthResetPasswordResp>>,
+    fun_auth_enroll_totp: Option<MockFn<FunAuthEnrollTotpReq, FunAuthEnrollTotpResp>>,
+    fun_auth_confirm_totp: Option<MockFn<FunAuthConfirmTotpReq, FunAuthConfirmTotpResp>>,
+    fun_auth_disable_totp: Option<MockFn<FunAuthDisableTotpReq, FunAuthDisableTotpResp>>,
+    fun_auth_use_two_factor_code:
+        Option<MockFn<FunAuthUseTwoFactorCodeReq, FunAuthUseTwoFactorCodeResp>>,
+    fun_auth_create_login_challenge:
+        Option<MockFn<FunAuthCreateLoginChallengeReq, FunAuthCreateLoginChallengeResp>>,
+    fun_auth_get_login_challenge:
+        Option<MockFn<FunAuthGetLoginChallengeReq, FunAuthGetLoginChallengeResp>>,
+    fun_auth_complete_login_challenge:
+        Option<MockFn<FunAuthCompleteLoginChallengeReq, FunAuthCompleteLoginChallengeResp>>,
+    fun_admin_list_users: Option<MockFn<FunAdminListUsersReq, FunAdminListUsersResp>>,
+    fun_admin_assign_role: Option<MockFn<FunAdminAssignRoleReq, FunAdminAssignRoleResp>>,
+    fun_admin_revoke_sessions:
+        Option<MockFn<FunAdminRevokeSessionsReq, FunAdminRevokeSessionsResp>>,
+    fun_admin_list_login_failures:
+        Option<MockFn<FunAdminListLoginFailuresReq, FunAdminListLoginFailuresResp>>,
+    fun_admin_clear_login_failures:
+        Option<MockFn<FunAdminClearLoginFailuresReq, FunAdminClearLoginFailuresResp>>,
+    fun_admin_set_two_factor_required:
+        Option<MockFn<FunAdminSetTwoFactorRequiredReq, FunAdminSetTwoFactorRequiredResp>>,
+    fun_organization_get: Option<MockFn<FunOrganizationGetReq, FunOrganizationGetResp>>,
+    fun_organization_list: Option<MockFn<FunOrganizationListReq, FunOrganizationListResp>>,
+    fun_organization_insert: Option<MockFn<FunOrganizationInsertReq, FunOrganizationInsertResp>>,
+    fun_organization_update: Option<MockFn<FunOrganizationUpdateReq, FunOrganizationUpdateResp>>,
+    fun_organization_delete: Option<MockFn<FunOrganizationDeleteReq, FunOrganizationDeleteResp>>,
+    fun_asset_get: Option<MockFn<FunAssetGetReq, FunAssetGetResp>>,
+    fun_asset_list: Option<MockFn<FunAssetListReq, FunAssetListResp>>,
+    fun_asset_insert: Option<MockFn<FunAssetInsertReq, FunAssetInsertResp>>,
+    fun_asset_update: Option<MockFn<FunAssetUpdateReq, FunAssetUpdateResp>>,
+    fun_asset_delete: Option<MockFn<FunAssetDeleteReq, FunAssetDeleteResp>>,
+    fun_support_ticket_get: Option<MockFn<FunSupportTicketGetReq, FunSupportTicketGetResp>>,
+    fun_support_ticket_list: Option<MockFn<FunSupportTicketListReq, FunSupportTicketListResp>>,
+    fun_support_ticket_insert:
+        Option<MockFn<FunSupportTicketInsertReq, FunSupportTicketInsertResp>>,
+    fun_support_ticket_update:
+        Option<MockFn<FunSupportTicketUpdateReq, FunSupportTicketUpdateResp>>,
+    fun_support_ticket_delete:
+        Option<MockFn<FunSupportTicketDeleteReq, FunSupportTicketDeleteResp>>,
+    fun_favorite_wallet_get: Option<MockFn<FunFavoriteWalletGetReq, FunFavoriteWalletGetResp>>,
+    fun_favorite_wallet_list: Option<MockFn<FunFavoriteWalletListReq, FunFavoriteWalletListResp>>,
+    fun_favorite_wallet_insert:
+        Option<MockFn<FunFavoriteWalletInsertReq, FunFavoriteWalletInsertResp>>,
+    fun_favorite_wallet_update:
+        Option<MockFn<FunFavoriteWalletUpdateReq, FunFavoriteWalletUpdateResp>>,
+    fun_favorite_wallet_delete:
+        Option<MockFn<FunFavoriteWalletDeleteReq, FunFavoriteWalletDeleteResp>>,
+}
+impl MockDbClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn on_fun_auth_signup(
+        mut self,
+        f: impl Fn(FunAuthSignupReq) -> Result<FunAuthSignupResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_signup = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_authenticate(
+        mut self,
+        f: impl Fn(FunAuthAuthenticateReq) -> Result<FunAuthAuthenticateResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_authenticate = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_get_password_hash(
+        mut self,
+        f: impl Fn(FunAuthGetPasswordHashReq) -> Result<FunAuthGetPasswordHashResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_get_password_hash = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_begin_login_attempt(
+        mut self,
+        f: impl Fn(FunAuthBeginLoginAttemptReq) -> Result<FunAuthBeginLoginAttemptResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_begin_login_attempt = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_finish_login_attempt(
+        mut self,
+        f: impl Fn(FunAuthFinishLoginAttemptReq) -> Result<FunAuthFinishLoginAttemptResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_finish_login_attempt = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_rehash_password(
+        mut self,
+        f: impl Fn(FunAuthRehashPasswordReq) -> Result<FunAuthRehashPasswordResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_rehash_password = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_create_session(
+        mut self,
+        f: impl Fn(FunAuthCreateSessionReq) -> Result<FunAuthCreateSessionResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_create_session = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_authorize(
+        mut self,
+        f: impl Fn(FunAuthAuthorizeReq) -> Result<FunAuthAuthorizeResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_authorize = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_refresh(
+        mut self,
+        f: impl Fn(FunAuthRefreshReq) -> Result<FunAuthRefreshResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_refresh = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_logout(
+        mut self,
+        f: impl Fn(FunAuthLogoutReq) -> Result<FunAuthLogoutResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_logout = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_logout_all(
+        mut self,
+        f: impl Fn(FunAuthLogoutAllReq) -> Result<FunAuthLogoutAllResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_logout_all = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_filter_sessions(
+        mut self,
+        f: impl Fn(FunAuthFilterSessionsReq) -> Result<FunAuthFilterSessionsResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_filter_sessions = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_change_password(
+        mut self,
+        f: impl Fn(FunAuthChangePasswordReq) -> Result<FunAuthChangePasswordResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_change_password = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_get_recovery_question_data(
+        mut self,
+        f: impl Fn(FunGetRecoveryQuestionDataReq) -> Result<FunGetRecoveryQuestionDataResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_get_recovery_question_data = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_set_recovery_questions(
+        mut self,
+        f: impl Fn(FunAuthSetRecoveryQuestionsReq) -> Result<FunAuthSetRecoveryQuestionsResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_set_recovery_questions = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_basic_authenticate(
+        mut self,
+        f: impl Fn(FunAuthBasicAuthenticateReq) -> Result<FunAuthBasicAuthenticateResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_basic_authenticate = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_get_recovery_questions(
+        mut self,
+        f: impl Fn(FunAuthGetRecoveryQuestionsReq) -> Result<FunAuthGetRecoveryQuestionsResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_get_recovery_questions = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_begin_recovery_attempt(
+        mut self,
+        f: impl Fn(FunAuthBeginRecoveryAttemptReq) -> Result<FunAuthBeginRecoveryAttemptResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_begin_recovery_attempt = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_submit_recovery_answers(
+        mut self,
+        f: impl Fn(FunSubmitRecoveryAnswersReq) -> Result<FunSubmitRecoveryAnswersResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_submit_recovery_answers = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_reset_password(
+        mut self,
+        f: impl Fn(FunAuthResetPasswordReq) -> Result<FunAuthResetPasswordResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_reset_password = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_enroll_totp(
+        mut self,
+        f: impl Fn(FunAuthEnrollTotpReq) -> Result<FunAuthEnrollTotpResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_enroll_totp = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_confirm_totp(
+        mut self,
+        f: impl Fn(FunAuthConfirmTotpReq) -> Result<FunAuthConfirmTotpResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_confirm_totp = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_disable_totp(
+        mut self,
+        f: impl Fn(FunAuthDisableTotpReq) -> Result<FunAuthDisableTotpResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_auth_disable_totp = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_use_two_factor_code(
+        mut self,
+        f: impl Fn(FunAuthUseTwoFactorCodeReq) -> Result<FunAuthUseTwoFactorCodeResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_use_two_factor_code = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_create_login_challenge(
+        mut self,
+        f: impl Fn(FunAuthCreateLoginChallengeReq) -> Result<FunAuthCreateLoginChallengeResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_create_login_challenge = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_get_login_challenge(
+        mut self,
+        f: impl Fn(FunAuthGetLoginChallengeReq) -> Result<FunAuthGetLoginChallengeResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_get_login_challenge = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_auth_complete_login_challenge(
+        mut self,
+        f: impl Fn(FunAuthCompleteLoginChallengeReq) -> Result<FunAuthCompleteLoginChallengeResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_auth_complete_login_challenge = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_admin_list_users(
+        mut self,
+        f: impl Fn(FunAdminListUsersReq) -> Result<FunAdminListUsersResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_admin_list_users = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_admin_assign_role(
+        mut self,
+        f: impl Fn(FunAdminAssignRoleReq) -> Result<FunAdminAssignRoleResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_admin_assign_role = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_admin_revoke_sessions(
+        mut self,
+        f: impl Fn(FunAdminRevokeSessionsReq) -> Result<FunAdminRevokeSessionsResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_admin_revoke_sessions = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_admin_list_login_failures(
+        mut self,
+        f: impl Fn(FunAdminListLoginFailuresReq) -> Result<FunAdminListLoginFailuresResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_admin_list_login_failures = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_admin_clear_login_failures(
+        mut self,
+        f: impl Fn(FunAdminClearLoginFailuresReq) -> Result<FunAdminClearLoginFailuresResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_admin_clear_login_failures = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_admin_set_two_factor_required(
+        mut self,
+        f: impl Fn(FunAdminSetTwoFactorRequiredReq) -> Result<FunAdminSetTwoFactorRequiredResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_admin_set_two_factor_required = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_organization_get(
+        mut self,
+        f: impl Fn(FunOrganizationGetReq) -> Result<FunOrganizationGetResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_organization_get = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_organization_list(
+        mut self,
+        f: impl Fn(FunOrganizationListReq) -> Result<FunOrganizationListResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_organization_list = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_organization_insert(
+        mut self,
+        f: impl Fn(FunOrganizationInsertReq) -> Result<FunOrganizationInsertResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_organization_insert = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_organization_update(
+        mut self,
+        f: impl Fn(FunOrganizationUpdateReq) -> Result<FunOrganizationUpdateResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_organization_update = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_organization_delete(
+        mut self,
+        f: impl Fn(FunOrganizationDeleteReq) -> Result<FunOrganizationDeleteResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_organization_delete = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_asset_get(
+        mut self,
+        f: impl Fn(FunAssetGetReq) -> Result<FunAssetGetResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_asset_get = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_asset_list(
+        mut self,
+        f: impl Fn(FunAssetListReq) -> Result<FunAssetListResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_asset_list = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_asset_insert(
+        mut self,
+        f: impl Fn(FunAssetInsertReq) -> Result<FunAssetInsertResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_asset_insert = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_asset_update(
+        mut self,
+        f: impl Fn(FunAssetUpdateReq) -> Result<FunAssetUpdateResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_asset_update = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_asset_delete(
+        mut self,
+        f: impl Fn(FunAssetDeleteReq) -> Result<FunAssetDeleteResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_asset_delete = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_support_ticket_get(
+        mut self,
+        f: impl Fn(FunSupportTicketGetReq) -> Result<FunSupportTicketGetResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_support_ticket_get = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_support_ticket_list(
+        mut self,
+        f: impl Fn(FunSupportTicketListReq) -> Result<FunSupportTicketListResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_support_ticket_list = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_support_ticket_insert(
+        mut self,
+        f: impl Fn(FunSupportTicketInsertReq) -> Result<FunSupportTicketInsertResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_support_ticket_insert = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_support_ticket_update(
+        mut self,
+        f: impl Fn(FunSupportTicketUpdateReq) -> Result<FunSupportTicketUpdateResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_support_ticket_update = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_support_ticket_delete(
+        mut self,
+        f: impl Fn(FunSupportTicketDeleteReq) -> Result<FunSupportTicketDeleteResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_support_ticket_delete = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_favorite_wallet_get(
+        mut self,
+        f: impl Fn(FunFavoriteWalletGetReq) -> Result<FunFavoriteWalletGetResp> + Send + Sync + 'static,
+    ) -> Self {
+        self.fun_favorite_wallet_get = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_favorite_wallet_list(
+        mut self,
+        f: impl Fn(FunFavoriteWalletListReq) -> Result<FunFavoriteWalletListResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_favorite_wallet_list = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_favorite_wallet_insert(
+        mut self,
+        f: impl Fn(FunFavoriteWalletInsertReq) -> Result<FunFavoriteWalletInsertResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_favorite_wallet_insert = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_favorite_wallet_update(
+        mut self,
+        f: impl Fn(FunFavoriteWalletUpdateReq) -> Result<FunFavoriteWalletUpdateResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_favorite_wallet_update = Some(Box::new(f));
+        self
+    }
+    pub fn on_fun_favorite_wallet_delete(
+        mut self,
+        f: impl Fn(FunFavoriteWalletDeleteReq) -> Result<FunFavoriteWalletDeleteResp>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.fun_favorite_wallet_delete = Some(Box::new(f));
+        self
+    }
+}
+impl DbApi for MockDbClient {
+    fn fun_auth_signup(&self, req: FunAuthSignupReq) -> BoxFuture<'_, Result<FunAuthSignupResp>> {
+        let result = match &self.fun_auth_signup {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_auth_signup")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_authenticate(
+        &self,
+        req: FunAuthAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthenticateResp>> {
+        let result = match &self.fun_auth_authenticate {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_authenticate"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_get_password_hash(
+        &self,
+        req: FunAuthGetPasswordHashReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetPasswordHashResp>> {
+        let result = match &self.fun_auth_get_password_hash {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_get_password_hash"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_begin_login_attempt(
+        &self,
+        req: FunAuthBeginLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginLoginAttemptResp>> {
+        let result = match &self.fun_auth_begin_login_attempt {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_begin_login_attempt"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_finish_login_attempt(
+        &self,
+        req: FunAuthFinishLoginAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthFinishLoginAttemptResp>> {
+        let result = match &self.fun_auth_finish_login_attempt {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_finish_login_attempt"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_rehash_password(
+        &self,
+        req: FunAuthRehashPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthRehashPasswordResp>> {
+        let result = match &self.fun_auth_rehash_password {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_rehash_password"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_create_session(
+        &self,
+        req: FunAuthCreateSessionReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateSessionResp>> {
+        let result = match &self.fun_auth_create_session {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_create_session"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_authorize(
+        &self,
+        req: FunAuthAuthorizeReq,
+    ) -> BoxFuture<'_, Result<FunAuthAuthorizeResp>> {
+        let result = match &self.fun_auth_authorize {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_auth_authorize")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_refresh(
+        &self,
+        req: FunAuthRefreshReq,
+    ) -> BoxFuture<'_, Result<FunAuthRefreshResp>> {
+        let result = match &self.fun_auth_refresh {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_auth_refresh")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_logout(&self, req: FunAuthLogoutReq) -> BoxFuture<'_, Result<FunAuthLogoutResp>> {
+        let result = match &self.fun_auth_logout {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_auth_logout")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_logout_all(
+        &self,
+        req: FunAuthLogoutAllReq,
+    ) -> BoxFuture<'_, Result<FunAuthLogoutAllResp>> {
+        let result = match &self.fun_auth_logout_all {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_logout_all"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_filter_sessions(
+        &self,
+        req: FunAuthFilterSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthFilterSessionsResp>> {
+        let result = match &self.fun_auth_filter_sessions {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_filter_sessions"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_change_password(
+        &self,
+        req: FunAuthChangePasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthChangePasswordResp>> {
+        let result = match &self.fun_auth_change_password {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_change_password"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_get_recovery_question_data(
+        &self,
+        req: FunGetRecoveryQuestionDataReq,
+    ) -> BoxFuture<'_, Result<FunGetRecoveryQuestionDataResp>> {
+        let result = match &self.fun_get_recovery_question_data {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_get_recovery_question_data"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_set_recovery_questions(
+        &self,
+        req: FunAuthSetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthSetRecoveryQuestionsResp>> {
+        let result = match &self.fun_auth_set_recovery_questions {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_set_recovery_questions"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_basic_authenticate(
+        &self,
+        req: FunAuthBasicAuthenticateReq,
+    ) -> BoxFuture<'_, Result<FunAuthBasicAuthenticateResp>> {
+        let result = match &self.fun_auth_basic_authenticate {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_basic_authenticate"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_get_recovery_questions(
+        &self,
+        req: FunAuthGetRecoveryQuestionsReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetRecoveryQuestionsResp>> {
+        let result = match &self.fun_auth_get_recovery_questions {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_get_recovery_questions"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_begin_recovery_attempt(
+        &self,
+        req: FunAuthBeginRecoveryAttemptReq,
+    ) -> BoxFuture<'_, Result<FunAuthBeginRecoveryAttemptResp>> {
+        let result = match &self.fun_auth_begin_recovery_attempt {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_begin_recovery_attempt"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_submit_recovery_answers(
+        &self,
+        req: FunSubmitRecoveryAnswersReq,
+    ) -> BoxFuture<'_, Result<FunSubmitRecoveryAnswersResp>> {
+        let result = match &self.fun_submit_recovery_answers {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_submit_recovery_answers"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_reset_password(
+        &self,
+        req: FunAuthResetPasswordReq,
+    ) -> BoxFuture<'_, Result<FunAuthResetPasswordResp>> {
+        let result = match &self.fun_auth_reset_password {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_reset_password"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_enroll_totp(
+        &self,
+        req: FunAuthEnrollTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthEnrollTotpResp>> {
+        let result = match &self.fun_auth_enroll_totp {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_enroll_totp"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_confirm_totp(
+        &self,
+        req: FunAuthConfirmTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthConfirmTotpResp>> {
+        let result = match &self.fun_auth_confirm_totp {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_confirm_totp"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_disable_totp(
+        &self,
+        req: FunAuthDisableTotpReq,
+    ) -> BoxFuture<'_, Result<FunAuthDisableTotpResp>> {
+        let result = match &self.fun_auth_disable_totp {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_disable_totp"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_use_two_factor_code(
+        &self,
+        req: FunAuthUseTwoFactorCodeReq,
+    ) -> BoxFuture<'_, Result<FunAuthUseTwoFactorCodeResp>> {
+        let result = match &self.fun_auth_use_two_factor_code {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_use_two_factor_code"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_create_login_challenge(
+        &self,
+        req: FunAuthCreateLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCreateLoginChallengeResp>> {
+        let result = match &self.fun_auth_create_login_challenge {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_create_login_challenge"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_get_login_challenge(
+        &self,
+        req: FunAuthGetLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthGetLoginChallengeResp>> {
+        let result = match &self.fun_auth_get_login_challenge {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_get_login_challenge"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_auth_complete_login_challenge(
+        &self,
+        req: FunAuthCompleteLoginChallengeReq,
+    ) -> BoxFuture<'_, Result<FunAuthCompleteLoginChallengeResp>> {
+        let result = match &self.fun_auth_complete_login_challenge {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_auth_complete_login_challenge"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_admin_list_users(
+        &self,
+        req: FunAdminListUsersReq,
+    ) -> BoxFuture<'_, Result<FunAdminListUsersResp>> {
+        let result = match &self.fun_admin_list_users {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_admin_list_users"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_admin_assign_role(
+        &self,
+        req: FunAdminAssignRoleReq,
+    ) -> BoxFuture<'_, Result<FunAdminAssignRoleResp>> {
+        let result = match &self.fun_admin_assign_role {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_admin_assign_role"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_admin_revoke_sessions(
+        &self,
+        req: FunAdminRevokeSessionsReq,
+    ) -> BoxFuture<'_, Result<FunAdminRevokeSessionsResp>> {
+        let result = match &self.fun_admin_revoke_sessions {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_admin_revoke_sessions"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_admin_list_login_failures(
+        &self,
+        req: FunAdminListLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminListLoginFailuresResp>> {
+        let result = match &self.fun_admin_list_login_failures {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_admin_list_login_failures"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_admin_clear_login_failures(
+        &self,
+        req: FunAdminClearLoginFailuresReq,
+    ) -> BoxFuture<'_, Result<FunAdminClearLoginFailuresResp>> {
+        let result = match &self.fun_admin_clear_login_failures {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_admin_clear_login_failures"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_admin_set_two_factor_required(
+        &self,
+        req: FunAdminSetTwoFactorRequiredReq,
+    ) -> BoxFuture<'_, Result<FunAdminSetTwoFactorRequiredResp>> {
+        let result = match &self.fun_admin_set_two_factor_required {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_admin_set_two_factor_required"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_organization_get(
+        &self,
+        req: FunOrganizationGetReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationGetResp>> {
+        let result = match &self.fun_organization_get {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_organization_get"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_organization_list(
+        &self,
+        req: FunOrganizationListReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationListResp>> {
+        let result = match &self.fun_organization_list {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_organization_list"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_organization_insert(
+        &self,
+        req: FunOrganizationInsertReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationInsertResp>> {
+        let result = match &self.fun_organization_insert {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_organization_insert"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_organization_update(
+        &self,
+        req: FunOrganizationUpdateReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationUpdateResp>> {
+        let result = match &self.fun_organization_update {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_organization_update"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_organization_delete(
+        &self,
+        req: FunOrganizationDeleteReq,
+    ) -> BoxFuture<'_, Result<FunOrganizationDeleteResp>> {
+        let result = match &self.fun_organization_delete {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_organization_delete"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_asset_get(&self, req: FunAssetGetReq) -> BoxFuture<'_, Result<FunAssetGetResp>> {
+        let result = match &self.fun_asset_get {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_asset_get")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_asset_list(&self, req: FunAssetListReq) -> BoxFuture<'_, Result<FunAssetListResp>> {
+        let result = match &self.fun_asset_list {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_asset_list")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_asset_insert(
+        &self,
+        req: FunAssetInsertReq,
+    ) -> BoxFuture<'_, Result<FunAssetInsertResp>> {
+        let result = match &self.fun_asset_insert {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_asset_insert")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_asset_update(
+        &self,
+        req: FunAssetUpdateReq,
+    ) -> BoxFuture<'_, Result<FunAssetUpdateResp>> {
+        let result = match &self.fun_asset_update {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_asset_update")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_asset_delete(
+        &self,
+        req: FunAssetDeleteReq,
+    ) -> BoxFuture<'_, Result<FunAssetDeleteResp>> {
+        let result = match &self.fun_asset_delete {
+            Some(f) => f(req),
+            None => Err(eyre!("MockDbClient has no response for fun_asset_delete")),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_support_ticket_get(
+        &self,
+        req: FunSupportTicketGetReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketGetResp>> {
+        let result = match &self.fun_support_ticket_get {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_support_ticket_get"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_support_ticket_list(
+        &self,
+        req: FunSupportTicketListReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketListResp>> {
+        let result = match &self.fun_support_ticket_list {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_support_ticket_list"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_support_ticket_insert(
+        &self,
+        req: FunSupportTicketInsertReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketInsertResp>> {
+        let result = match &self.fun_support_ticket_insert {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_support_ticket_insert"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_support_ticket_update(
+        &self,
+        req: FunSupportTicketUpdateReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketUpdateResp>> {
+        let result = match &self.fun_support_ticket_update {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_support_ticket_update"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_support_ticket_delete(
+        &self,
+        req: FunSupportTicketDeleteReq,
+    ) -> BoxFuture<'_, Result<FunSupportTicketDeleteResp>> {
+        let result = match &self.fun_support_ticket_delete {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_support_ticket_delete"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_favorite_wallet_get(
+        &self,
+        req: FunFavoriteWalletGetReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletGetResp>> {
+        let result = match &self.fun_favorite_wallet_get {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_favorite_wallet_get"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_favorite_wallet_list(
+        &self,
+        req: FunFavoriteWalletListReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletListResp>> {
+        let result = match &self.fun_favorite_wallet_list {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_favorite_wallet_list"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_favorite_wallet_insert(
+        &self,
+        req: FunFavoriteWalletInsertReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletInsertResp>> {
+        let result = match &self.fun_favorite_wallet_insert {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_favorite_wallet_insert"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_favorite_wallet_update(
+        &self,
+        req: FunFavoriteWalletUpdateReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletUpdateResp>> {
+        let result = match &self.fun_favorite_wallet_update {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_favorite_wallet_update"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+    fn fun_favorite_wallet_delete(
+        &self,
+        req: FunFavoriteWalletDeleteReq,
+    ) -> BoxFuture<'_, Result<FunFavoriteWalletDeleteResp>> {
+        let result = match &self.fun_favorite_wallet_delete {
+            Some(f) => f(req),
+            None => Err(eyre!(
+                "MockDbClient has no response for fun_favorite_wallet_delete"
+            )),
+        };
+        Box::pin(async move { result })
+    }
+}
@@ -0,0 +1,118 @@
+use lib::error_code::ErrorCode;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use serde::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, FromPrimitive)]
+#[repr(u32)]
+pub enum EnumErrorCode {
+    /// Value is not a valid enum variant (22P02, HTTP 400)
+    InvalidEnumLevel = 3484946,
+    /// Unspecified error (R0000, HTTP 500)
+    Error = 45349632,
+    /// An argument is invalid (R0001, HTTP 400)
+    InvalidArgument = 45349633,
+    /// Operation not allowed in the current state (R0002, HTTP 409)
+    InvalidState = 45349634,
+    /// Request sequence number is invalid (R0003, HTTP 400)
+    InvalidSeq = 45349635,
+    /// Unknown method code (R0004, HTTP 400)
+    InvalidMethod = 45349636,
+    /// Message violates the protocol (R0005, HTTP 400)
+    ProtocolViolation = 45349637,
+    /// Request could not be parsed (R0006, HTTP 400)
+    MalformedRequest = 45349638,
+    /// User does not exist (R0007, HTTP 404)
+    UnknownUser = 45349639,
+    /// User is blocked (R0008, HTTP 403)
+    BlockedUser = 45349640,
+    /// Password is incorrect (R0009, HTTP 401)
+    InvalidPassword = 45349641,
+    /// Token is invalid or expired (R000A, HTTP 401)
+    InvalidToken = 45349642,
+    /// Try again later (R000B, HTTP 503)
+    TemporarilyUnavailable = 45349643,
+    /// Unexpected exception (R000C, HTTP 500)
+    UnexpectedException = 45349644,
+    /// Server is overloaded (R000D, HTTP 503)
+    BackPressureIncreased = 45349645,
+    /// Public id is invalid (R000E, HTTP 400)
+    InvalidPublicId = 45349646,
+    /// Value is out of range (R000F, HTTP 400)
+    InvalidRange = 45349647,
+    /// Bank account already exists (R000G, HTTP 409)
+    BankAccountAlreadyExists = 45349648,
+    /// Balance is too low (R000H, HTTP 400)
+    InsufficientFunds = 45349649,
+    /// Internal logic error (R000M, HTTP 500)
+    LogicalError = 45349654,
+    /// User lacks the privileges (R000N, HTTP 403)
+    RestrictedUserPrivileges = 45349655,
+    /// New value equals the old one (R000O, HTTP 400)
+    IdenticalReplacement = 45349656,
+    /// Recovery questions are invalid (R000R, HTTP 400)
+    InvalidRecoveryQuestions = 45349659,
+    /// Role not allowed for this service (R000S, HTTP 403)
+    InvalidRole = 45349660,
+    /// Recovery answers do not match (R000T, HTTP 401)
+    WrongRecoveryAnswers = 45349661,
+    /// Message could not be delivered (R000U, HTTP 502)
+    MessageNotDelivered = 45349662,
+    /// No reply received (R000V, HTTP 504)
+    NoReply = 45349663,
+    /// A required attribute is null (R000W, HTTP 400)
+    NullAttribute = 45349664,
+    /// Terms of service or privacy policy not agreed (R000X, HTTP 400)
+    ConsentMissing = 45349665,
+    /// Requires an active subscription (R000Y, HTTP 402)
+    ActiveSubscriptionRequired = 45349666,
+    /// Username is taken (R000Z, HTTP 409)
+    UsernameAlreadyRegistered = 45349667,
+    /// User has no recovery questions (R0010, HTTP 400)
+    RecoveryQuestionsNotSet = 45349668,
+    /// Every recovery question must be answered (R0011, HTTP 400)
+    MustSubmitAllRecoveryQuestions = 45349669,
+    /// Recovery token is invalid or expired (R0012, HTTP 401)
+    InvalidRecoveryToken = 45349670,
+    /// Access token expired, the session can be refreshed (R0013, HTTP 401)
+    TokenExpired = 45349671,
+    /// Too many failed logins, a CAPTCHA has to be solved (R0014, HTTP 429)
+    CaptchaRequired = 45349672,
+    /// Two-factor code or recovery code is wrong or already used (R0015, HTTP 401)
+    InvalidTwoFactorCode = 45349673,
+    /// The service requires two-factor authentication, which the user has not enabled (R0016, HTTP 403)
+    TwoFactorRequired = 45349674,
+    /// Login challenge is invalid, expired or already completed (R0017, HTTP 401)
+    InvalidLoginChallenge = 45349675,
+    /// Message could not be routed (R0018, HTTP 502)
+    RoutingError = 45349676,
+    /// Not allowed to send this message (R0019, HTTP 403)
+    UnauthorizedMessage = 45349677,
+    /// Authentication failed (R001B, HTTP 401)
+    AuthError = 45349679,
+    /// Two-factor authentication is already enabled (R001C, HTTP 409)
+    TwoFactorAlreadyEnabled = 45349680,
+    /// Two-factor authentication is not enabled, or has no pending enrollment to confirm (R001D, HTTP 409)
+    TwoFactorNotEnabled = 45349681,
+    /// Internal server error (R001G, HTTP 500)
+    InternalError = 45349684,
+    /// Record does not exist (R001H, HTTP 404)
+    NotFound = 45349685,
+}
+impl EnumErrorCode {
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+}
+impl TryFrom<u32> for EnumErrorCode {
+    type Error = u32;
+
+    fn try_from(code: u32) -> Result<Self, u32> {
+        Self::from_u32(code).ok_or(code)
+    }
+}
+impl From<EnumErrorCode> for ErrorCode {
+    fn from(code: EnumErrorCode) -> Self {
+        ErrorCode::new(code as u32)
+    }
+}
@@ -1,2 +1,4 @@
+pub mod crud;
 pub mod database;
+pub mod error_code;
 pub mod model;
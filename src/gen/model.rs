@@ -61,14 +61,65 @@ pub struct LoginRequest {
     pub service_code: EnumService,
     pub device_id: String,
     pub device_os: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captcha_token: Option<String>,
+}
+impl lib::validation::Validate for LoginRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "password");
+            let v = &self.password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceId");
+            let v = &self.device_id;
+            lib::validation::check_length(&p, v, None, Some(256), errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceOs");
+            let v = &self.device_os;
+            lib::validation::check_length(&p, v, None, Some(64), errors);
+        }
+    }
 }
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct LoginResponse {
     pub username: String,
     pub user_public_id: i64,
-    pub user_token: String,
-    pub admin_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "lib::time::epoch_millis::option"
+    )]
+    pub access_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "lib::time::epoch_millis::option"
+    )]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_token: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "lib::time::epoch_millis::option"
+    )]
+    pub challenge_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+impl lib::validation::Validate for LoginResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
 }
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -80,12 +131,41 @@ pub struct SignupRequest {
     pub agreed_tos: bool,
     pub agreed_privacy: bool,
 }
+impl lib::validation::Validate for SignupRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), Some(20), errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "password");
+            let v = &self.password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "email");
+            let v = &self.email;
+            lib::validation::check_length(&p, v, None, Some(320), errors);
+            lib::validation::check_format(&p, v, "email", errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "phone");
+            let v = &self.phone;
+            lib::validation::check_length(&p, v, None, Some(15), errors);
+            lib::validation::check_format(&p, v, "phone", errors);
+        }
+    }
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SignupResponse {
     pub username: String,
     pub user_public_id: i64,
 }
+impl lib::validation::Validate for SignupResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthorizeRequest {
@@ -95,38 +175,556 @@ pub struct AuthorizeRequest {
     pub device_id: String,
     pub device_os: String,
 }
+impl lib::validation::Validate for AuthorizeRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "token");
+            let v = &self.token;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceId");
+            let v = &self.device_id;
+            lib::validation::check_length(&p, v, None, Some(256), errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceOs");
+            let v = &self.device_os;
+            lib::validation::check_length(&p, v, None, Some(64), errors);
+        }
+    }
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthorizeResponse {
     pub success: bool,
 }
+impl lib::validation::Validate for AuthorizeResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+    pub device_id: String,
+    pub device_os: String,
+}
+impl lib::validation::Validate for RefreshRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "refreshToken");
+            let v = &self.refresh_token;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceId");
+            let v = &self.device_id;
+            lib::validation::check_length(&p, v, None, Some(256), errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceOs");
+            let v = &self.device_os;
+            lib::validation::check_length(&p, v, None, Some(64), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshResponse {
+    pub username: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub access_expires_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for RefreshResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutRequest {
+    pub username: String,
+    pub token: String,
+}
+impl lib::validation::Validate for LogoutRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "token");
+            let v = &self.token;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutResponse {
+    pub success: bool,
+}
+impl lib::validation::Validate for LogoutResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutAllRequest {
+    pub username: String,
+    pub token: String,
+}
+impl lib::validation::Validate for LogoutAllRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "token");
+            let v = &self.token;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutAllResponse {
+    pub revoked_sessions: i32,
+}
+impl lib::validation::Validate for LogoutAllResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordRequest {
+    pub username: String,
+    pub old_password: String,
+    pub new_password: String,
+    pub device_id: String,
+    pub device_os: String,
+}
+impl lib::validation::Validate for ChangePasswordRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "oldPassword");
+            let v = &self.old_password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "newPassword");
+            let v = &self.new_password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceId");
+            let v = &self.device_id;
+            lib::validation::check_length(&p, v, None, Some(256), errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "deviceOs");
+            let v = &self.device_os;
+            lib::validation::check_length(&p, v, None, Some(64), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordResponse {
+    pub success: bool,
+}
+impl lib::validation::Validate for ChangePasswordResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRecoveryQuestionCatalogRequest {}
+impl lib::validation::Validate for ListRecoveryQuestionCatalogRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRecoveryQuestionCatalogResponse {
+    pub questions: Vec<RecoveryQuestionCatalogRow>,
+}
+impl lib::validation::Validate for ListRecoveryQuestionCatalogResponse {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "questions");
+            let v = &self.questions;
+            for (i, x) in v.iter().enumerate() {
+                lib::validation::Validate::validate_at(x, &format!("{}[{}]", p, i), errors);
+            }
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryQuestionCatalogRow {
+    pub question_id: i32,
+    pub content: String,
+    pub category: EnumRecoveryQuestionCategory,
+}
+impl lib::validation::Validate for RecoveryQuestionCatalogRow {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRecoveryQuestionsRequest {
+    pub username: String,
+    pub password: String,
+    pub question_ids: Vec<i32>,
+    pub answers: Vec<String>,
+}
+impl lib::validation::Validate for SetRecoveryQuestionsRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "password");
+            let v = &self.password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRecoveryQuestionsResponse {
+    pub success: bool,
+}
+impl lib::validation::Validate for SetRecoveryQuestionsResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMyRecoveryQuestionsRequest {
+    pub username: String,
+}
+impl lib::validation::Validate for GetMyRecoveryQuestionsRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMyRecoveryQuestionsResponse {
+    pub questions: Vec<RecoveryQuestionRow>,
+}
+impl lib::validation::Validate for GetMyRecoveryQuestionsResponse {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "questions");
+            let v = &self.questions;
+            for (i, x) in v.iter().enumerate() {
+                lib::validation::Validate::validate_at(x, &format!("{}[{}]", p, i), errors);
+            }
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryQuestionRow {
+    pub question_id: i32,
+    pub question: String,
+}
+impl lib::validation::Validate for RecoveryQuestionRow {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitRecoveryAnswersRequest {
+    pub username: String,
+    pub question_ids: Vec<i32>,
+    pub answers: Vec<String>,
+}
+impl lib::validation::Validate for SubmitRecoveryAnswersRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitRecoveryAnswersResponse {
+    pub reset_token: String,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub reset_token_expires_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for SubmitRecoveryAnswersResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPasswordRequest {
+    pub username: String,
+    pub reset_token: String,
+    pub new_password: String,
+}
+impl lib::validation::Validate for ResetPasswordRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "resetToken");
+            let v = &self.reset_token;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "newPassword");
+            let v = &self.new_password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPasswordResponse {
+    pub success: bool,
+}
+impl lib::validation::Validate for ResetPasswordResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginVerify2FARequest {
+    pub username: String,
+    pub challenge_token: String,
+    pub code: String,
+}
+impl lib::validation::Validate for LoginVerify2FARequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "challengeToken");
+            let v = &self.challenge_token;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "code");
+            let v = &self.code;
+            lib::validation::check_length(&p, v, Some(1), Some(64), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginVerify2FAResponse {
+    pub username: String,
+    pub user_public_id: i64,
+    pub access_token: String,
+    pub refresh_token: String,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub access_expires_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for LoginVerify2FAResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrollTotpRequest {
+    pub username: String,
+    pub password: String,
+}
+impl lib::validation::Validate for EnrollTotpRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "password");
+            let v = &self.password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrollTotpResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+impl lib::validation::Validate for EnrollTotpResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmTotpRequest {
+    pub username: String,
+    pub password: String,
+    pub code: String,
+}
+impl lib::validation::Validate for ConfirmTotpRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "password");
+            let v = &self.password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "code");
+            let v = &self.code;
+            lib::validation::check_length(&p, v, Some(1), Some(64), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmTotpResponse {
+    pub recovery_codes: Vec<String>,
+}
+impl lib::validation::Validate for ConfirmTotpResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DisableTotpRequest {
+    pub username: String,
+    pub password: String,
+    pub code: String,
+}
+impl lib::validation::Validate for DisableTotpRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "username");
+            let v = &self.username;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "password");
+            let v = &self.password;
+            lib::validation::check_length(&p, v, Some(1), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "code");
+            let v = &self.code;
+            lib::validation::check_length(&p, v, Some(1), Some(64), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DisableTotpResponse {
+    pub success: bool,
+}
+impl lib::validation::Validate for DisableTotpResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct FooRequest {}
+impl lib::validation::Validate for FooRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct FooResponse {
     pub foo: bool,
 }
+impl lib::validation::Validate for FooResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ListUsersRequest {
     pub offset: i32,
     pub limit: i32,
 }
+impl lib::validation::Validate for ListUsersRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "offset");
+            let v = &self.offset;
+            lib::validation::check_range(&p, *v as f64, Some(0.0), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "limit");
+            let v = &self.limit;
+            lib::validation::check_range(&p, *v as f64, Some(1.0), Some(1000.0), errors);
+        }
+    }
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ListUsersResponse {
     pub users: Vec<ListUsersResponseRow>,
 }
+impl lib::validation::Validate for ListUsersResponse {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "users");
+            let v = &self.users;
+            for (i, x) in v.iter().enumerate() {
+                lib::validation::Validate::validate_at(x, &format!("{}[{}]", p, i), errors);
+            }
+        }
+    }
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[derive(ToSql, FromSql)]
+#[postgres(name = "struct_list_users_response_row")]
 pub struct ListUsersResponseRow {
     pub user_public_id: i64,
     pub username: String,
-    pub email: String,
-    pub created_at: i32,
-    pub updated_at: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    pub role: EnumRole,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "lib::time::epoch_millis::option"
+    )]
+    pub last_login: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for ListUsersResponseRow {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
 }
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -134,8 +732,590 @@ pub struct AssignRoleRequest {
     pub user_public_id: i64,
     pub new_role: String,
 }
+impl lib::validation::Validate for AssignRoleRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AssignRoleResponse {
     pub success: bool,
 }
+impl lib::validation::Validate for AssignRoleResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeSessionsRequest {
+    pub user_public_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<EnumService>,
+}
+impl lib::validation::Validate for RevokeSessionsRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeSessionsResponse {
+    pub revoked_sessions: i32,
+}
+impl lib::validation::Validate for RevokeSessionsResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListLoginLockoutsRequest {}
+impl lib::validation::Validate for ListLoginLockoutsRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListLoginLockoutsResponse {
+    pub lockouts: Vec<LoginLockoutRow>,
+}
+impl lib::validation::Validate for ListLoginLockoutsResponse {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "lockouts");
+            let v = &self.lockouts;
+            for (i, x) in v.iter().enumerate() {
+                lib::validation::Validate::validate_at(x, &format!("{}[{}]", p, i), errors);
+            }
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginLockoutRow {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<std::net::IpAddr>,
+    pub failures: i32,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub last_failure: chrono::DateTime<chrono::Utc>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "lib::time::epoch_millis::option"
+    )]
+    pub blocked_until: Option<chrono::DateTime<chrono::Utc>>,
+    pub captcha_required: bool,
+}
+impl lib::validation::Validate for LoginLockoutRow {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearLoginLockoutRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<std::net::IpAddr>,
+}
+impl lib::validation::Validate for ClearLoginLockoutRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearLoginLockoutResponse {
+    pub cleared: i32,
+}
+impl lib::validation::Validate for ClearLoginLockoutResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTwoFactorRequiredRequest {
+    pub service: EnumService,
+    pub required: bool,
+}
+impl lib::validation::Validate for SetTwoFactorRequiredRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTwoFactorRequiredResponse {
+    pub revoked_sessions: i32,
+}
+impl lib::validation::Validate for SetTwoFactorRequiredResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOrganizationRequest {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for GetOrganizationRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOrganizationResponse {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+impl lib::validation::Validate for GetOrganizationResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListOrganizationsRequest {
+    pub offset: i32,
+    pub limit: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
+impl lib::validation::Validate for ListOrganizationsRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "offset");
+            let v = &self.offset;
+            lib::validation::check_range(&p, *v as f64, Some(0.0), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "limit");
+            let v = &self.limit;
+            lib::validation::check_range(&p, *v as f64, Some(1.0), Some(1000.0), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListOrganizationsResponse {
+    pub rows: Vec<OrganizationRow>,
+}
+impl lib::validation::Validate for ListOrganizationsResponse {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "rows");
+            let v = &self.rows;
+            for (i, x) in v.iter().enumerate() {
+                lib::validation::Validate::validate_at(x, &format!("{}[{}]", p, i), errors);
+            }
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationRow {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+impl lib::validation::Validate for OrganizationRow {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+impl lib::validation::Validate for CreateOrganizationRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrganizationResponse {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+impl lib::validation::Validate for CreateOrganizationResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateOrganizationRequest {
+    pub pkey_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+impl lib::validation::Validate for UpdateOrganizationRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateOrganizationResponse {
+    pub pkey_id: i64,
+    pub name: String,
+    pub country: String,
+    pub tax_id: String,
+    pub address: String,
+    pub note: String,
+}
+impl lib::validation::Validate for UpdateOrganizationResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteOrganizationRequest {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for DeleteOrganizationRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteOrganizationResponse {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for DeleteOrganizationResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAssetRequest {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for GetAssetRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAssetResponse {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_prototype: Option<i64>,
+}
+impl lib::validation::Validate for GetAssetResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAssetsRequest {
+    pub offset: i32,
+    pub limit: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_prototype: Option<i64>,
+}
+impl lib::validation::Validate for ListAssetsRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "offset");
+            let v = &self.offset;
+            lib::validation::check_range(&p, *v as f64, Some(0.0), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "limit");
+            let v = &self.limit;
+            lib::validation::check_range(&p, *v as f64, Some(1.0), Some(1000.0), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAssetsResponse {
+    pub rows: Vec<AssetRow>,
+}
+impl lib::validation::Validate for ListAssetsResponse {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "rows");
+            let v = &self.rows;
+            for (i, x) in v.iter().enumerate() {
+                lib::validation::Validate::validate_at(x, &format!("{}[{}]", p, i), errors);
+            }
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetRow {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_prototype: Option<i64>,
+}
+impl lib::validation::Validate for AssetRow {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAssetRequest {
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_prototype: Option<i64>,
+}
+impl lib::validation::Validate for CreateAssetRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAssetResponse {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_prototype: Option<i64>,
+}
+impl lib::validation::Validate for CreateAssetResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAssetRequest {
+    pub pkey_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_prototype: Option<i64>,
+}
+impl lib::validation::Validate for UpdateAssetRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAssetResponse {
+    pub pkey_id: i64,
+    pub short_name: String,
+    pub long_name: String,
+    pub description: String,
+    pub network_type: String,
+    pub contract_address: String,
+    pub precision: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_prototype: Option<i64>,
+}
+impl lib::validation::Validate for UpdateAssetResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAssetRequest {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for DeleteAssetRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAssetResponse {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for DeleteAssetResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSupportTicketRequest {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for GetSupportTicketRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSupportTicketResponse {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for GetSupportTicketResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSupportTicketsRequest {
+    pub offset: i32,
+    pub limit: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_user: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_handler_user: Option<i64>,
+}
+impl lib::validation::Validate for ListSupportTicketsRequest {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "offset");
+            let v = &self.offset;
+            lib::validation::check_range(&p, *v as f64, Some(0.0), None, errors);
+        }
+        {
+            let p = lib::validation::join_path(path, "limit");
+            let v = &self.limit;
+            lib::validation::check_range(&p, *v as f64, Some(1.0), Some(1000.0), errors);
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSupportTicketsResponse {
+    pub rows: Vec<SupportTicketRow>,
+}
+impl lib::validation::Validate for ListSupportTicketsResponse {
+    fn validate_at(&self, path: &str, errors: &mut Vec<lib::json_schema::SchemaError>) {
+        {
+            let p = lib::validation::join_path(path, "rows");
+            let v = &self.rows;
+            for (i, x) in v.iter().enumerate() {
+                lib::validation::Validate::validate_at(x, &format!("{}[{}]", p, i), errors);
+            }
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportTicketRow {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for SupportTicketRow {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSupportTicketRequest {
+    pub fkey_user: i64,
+    pub content: String,
+    pub response: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_handler_user: Option<i64>,
+}
+impl lib::validation::Validate for CreateSupportTicketRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSupportTicketResponse {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for CreateSupportTicketResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSupportTicketRequest {
+    pub pkey_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_user: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_handler_user: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "lib::time::epoch_millis::option"
+    )]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+impl lib::validation::Validate for UpdateSupportTicketRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSupportTicketResponse {
+    pub pkey_id: i64,
+    pub fkey_user: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fkey_handler_user: Option<i64>,
+    pub content: String,
+    pub response: String,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "lib::time::epoch_millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+impl lib::validation::Validate for UpdateSupportTicketResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSupportTicketRequest {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for DeleteSupportTicketRequest {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSupportTicketResponse {
+    pub pkey_id: i64,
+}
+impl lib::validation::Validate for DeleteSupportTicketResponse {
+    fn validate_at(&self, _path: &str, _errors: &mut Vec<lib::json_schema::SchemaError>) {}
+}
@@ -1,6 +1,9 @@
 use crate::database::DatabaseConfig;
+use crate::lockout::LockoutConfig;
 use crate::log::LogLevel;
-use clap::Parser;
+use crate::password::PasswordConfig;
+use crate::totp::TotpConfig;
+use clap::{Parser, Subcommand};
 use eyre::*;
 use serde::*;
 use std::env::current_dir;
@@ -30,12 +33,49 @@ struct CliArgument {
     pub_cert: String,
     #[clap(long, default_value = "", env = "PRIV_CERT")]
     priv_cert: String,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Apply pending database migrations and exit
+    Migrate(MigrateArgs),
+}
+#[derive(Debug, Clone, Parser)]
+pub struct MigrateArgs {
+    /// The directory containing model.sql, api.sql and migrations/
+    #[clap(long, default_value = "db", env = "MIGRATIONS_DIR")]
+    pub dir: PathBuf,
+    /// Only report which migrations would be applied
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Record the first versioned migration as applied without running it, for databases
+    /// created by the old setup_database.sh, then apply the rest
+    #[clap(long)]
+    pub baseline: bool,
+}
+impl Default for MigrateArgs {
+    fn default() -> Self {
+        Self::parse_from(["migrate"])
+    }
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub db: DatabaseConfig,
+    #[serde(default)]
+    pub password: PasswordConfig,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub recovery: RecoveryConfig,
+    #[serde(default)]
+    pub lockout: LockoutConfig,
+    #[serde(default)]
+    pub totp: TotpConfig,
     #[serde(skip)]
     pub app: AppConfig,
+    #[serde(skip)]
+    pub command: Option<Command>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
@@ -46,6 +86,45 @@ pub struct AppConfig {
     pub pub_cert: String,
     pub priv_cert: String,
 }
+/// Lifetime of login sessions, the `session` section of the config, in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// Until the access token has to be refreshed
+    pub access_token_lifetime: u32,
+    /// A session unused for this long ends
+    pub idle_timeout: u32,
+    /// A session ends this long after login, however often it is refreshed
+    pub absolute_timeout: u32,
+}
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            access_token_lifetime: 15 * 60,
+            idle_timeout: 7 * 24 * 3600,
+            absolute_timeout: 30 * 24 * 3600,
+        }
+    }
+}
+/// Limits of account recovery, the `recovery` section of the config, times in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecoveryConfig {
+    /// Until the token from `SubmitRecoveryAnswers` can no longer reset the password
+    pub reset_token_lifetime: u32,
+    /// Wrong answers a user may submit within `attempt_window` before being turned away
+    pub max_failed_attempts: u32,
+    pub attempt_window: u32,
+}
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            reset_token_lifetime: 15 * 60,
+            max_failed_attempts: 5,
+            attempt_window: 3600,
+        }
+    }
+}
 pub fn load_config(service_name: String) -> Result<Config> {
     let args: CliArgument = CliArgument::parse();
 
@@ -59,6 +138,7 @@ pub fn load_config(service_name: String) -> Result<Config> {
     config.app.name = service_name;
     config.app.pub_cert = args.pub_cert;
     config.app.priv_cert = args.priv_cert;
+    config.command = args.command;
     println!("App config {:#?}", config.app);
     Ok(config)
 }
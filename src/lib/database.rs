@@ -1,26 +1,263 @@
 use deadpool_postgres::Runtime;
 use deadpool_postgres::*;
 use eyre::*;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use tokio::sync::mpsc;
 use tokio_postgres::types::ToSql;
-use tokio_postgres::{NoTls, Row, ToStatement};
+use tokio_postgres::{AsyncMessage, NoTls, Row};
 use tracing::*;
 
+pub use tokio_postgres::IsolationLevel;
 pub type DatabaseConfig = deadpool_postgres::Config;
 #[derive(Clone)]
 pub struct SimpleDbClient {
     pool: Pool,
+    pg_config: tokio_postgres::Config,
 }
 impl SimpleDbClient {
-    pub async fn query<T>(
+    /// Runs a statement prepared once per connection
+    pub async fn query(
         &self,
-        statement: &T,
+        statement: &str,
         params: &[&(dyn ToSql + Sync)],
-    ) -> Result<Vec<Row>, Error>
+    ) -> Result<Vec<Row>, Error> {
+        query(&self.pool.get().await?, statement, params).await
+    }
+    /// Like `query`, but yields rows as they arrive. The connection goes back to the pool once
+    /// the stream is dropped
+    pub async fn query_stream(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<BoxStream<'static, Result<Row>>> {
+        let client = self.pool.get().await?;
+        let rows = query_stream(&client, statement, params).await?;
+        Ok(rows
+            .map(move |row| {
+                let _client = &client;
+                row
+            })
+            .boxed())
+    }
+    /// Subscribes to `NOTIFY <channel>` on a connection of its own, as pooled connections do not
+    /// deliver notifications
+    pub async fn listen(&self, channel: &str) -> Result<Notifications> {
+        let (client, mut connection) = self.pg_config.connect(NoTls).await?;
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut messages = futures::stream::poll_fn(move |cx| connection.poll_message(cx));
+            while let Some(message) = messages.next().await {
+                match message {
+                    Ok(AsyncMessage::Notification(x)) => {
+                        if sender.send(x.payload().to_owned()).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        warn!("Lost the connection listening to notifications: {:?}", err);
+                        break;
+                    }
+                }
+            }
+        });
+        client
+            .batch_execute(&format!("LISTEN \"{}\"", channel))
+            .await?;
+        Ok(Notifications {
+            _client: client,
+            receiver,
+        })
+    }
+    /// A dedicated connection, for work that spans several statements
+    pub(crate) async fn get_client(&self) -> Result<Object> {
+        Ok(self.pool.get().await?)
+    }
+    /// Starts a transaction on a dedicated connection
+    pub async fn begin(&self, isolation: IsolationLevel) -> Result<SimpleDbTransaction> {
+        let client = self.get_client().await?;
+        let isolation = match isolation {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+            _ => bail!("unsupported isolation level {:?}", isolation),
+        };
+        client
+            .batch_execute(&format!("BEGIN ISOLATION LEVEL {}", isolation))
+            .await?;
+        Ok(SimpleDbTransaction {
+            client: Some(client),
+        })
+    }
+}
+
+/// Payloads of the notifications of a `SimpleDbClient::listen`, unsubscribed when dropped
+pub struct Notifications {
+    _client: tokio_postgres::Client,
+    receiver: mpsc::UnboundedReceiver<String>,
+}
+impl Notifications {
+    /// The next payload, `None` once the connection is lost
+    pub async fn recv(&mut self) -> Option<String> {
+        self.receiver.recv().await
+    }
+}
+
+/// A transaction started by `SimpleDbClient::begin`. Rolled back if dropped without `commit`
+pub struct SimpleDbTransaction {
+    client: Option<Object>,
+}
+impl SimpleDbTransaction {
+    fn client(&self) -> Result<&Object> {
+        self.client.as_ref().context("transaction already finished")
+    }
+    pub async fn query(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<Row>, Error> {
+        query(self.client()?, statement, params).await
+    }
+    pub async fn query_stream(
+        &self,
+        statement: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<BoxStream<'_, Result<Row>>> {
+        query_stream(self.client()?, statement, params).await
+    }
+    async fn finish(&mut self, statement: &str) -> Result<()> {
+        let client = self.client.take().context("transaction already finished")?;
+        if let Err(err) = client.batch_execute(statement).await {
+            // the connection is in an unknown state, do not give it back to the pool
+            let _ = Object::take(client);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+    pub async fn commit(mut self) -> Result<()> {
+        self.finish("COMMIT").await
+    }
+    pub async fn rollback(mut self) -> Result<()> {
+        self.finish("ROLLBACK").await
+    }
+}
+impl Drop for SimpleDbTransaction {
+    fn drop(&mut self) {
+        let Some(client) = self.client.take() else {
+            return;
+        };
+        // rolling back needs the runtime; without one, closing the connection rolls back as well
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(err) = client.batch_execute("ROLLBACK").await {
+                        warn!("Failed to roll back dropped transaction: {:?}", err);
+                        let _ = Object::take(client);
+                    }
+                });
+            }
+            Err(_) => {
+                let _ = Object::take(client);
+            }
+        }
+    }
+}
+
+/// What the generated `fun_*` methods need from a connection, so that they are written once for
+/// `SimpleDbClient` and `SimpleDbTransaction`
+pub trait DbQuery: Send + Sync {
+    fn query<'a>(
+        &'a self,
+        statement: &'a str,
+        params: &'a [&'a (dyn ToSql + Sync)],
+    ) -> BoxFuture<'a, Result<Vec<Row>>>;
+    fn query_stream<'a, 'b>(
+        &'a self,
+        statement: &'b str,
+        params: &'b [&'b (dyn ToSql + Sync)],
+    ) -> BoxFuture<'b, Result<BoxStream<'a, Result<Row>>>>
+    where
+        'a: 'b;
+}
+impl DbQuery for SimpleDbClient {
+    fn query<'a>(
+        &'a self,
+        statement: &'a str,
+        params: &'a [&'a (dyn ToSql + Sync)],
+    ) -> BoxFuture<'a, Result<Vec<Row>>> {
+        Box::pin(SimpleDbClient::query(self, statement, params))
+    }
+    fn query_stream<'a, 'b>(
+        &'a self,
+        statement: &'b str,
+        params: &'b [&'b (dyn ToSql + Sync)],
+    ) -> BoxFuture<'b, Result<BoxStream<'a, Result<Row>>>>
+    where
+        'a: 'b,
+    {
+        Box::pin(async move {
+            let rows: BoxStream<'a, Result<Row>> =
+                SimpleDbClient::query_stream(self, statement, params).await?;
+            Ok(rows)
+        })
+    }
+}
+impl DbQuery for SimpleDbTransaction {
+    fn query<'a>(
+        &'a self,
+        statement: &'a str,
+        params: &'a [&'a (dyn ToSql + Sync)],
+    ) -> BoxFuture<'a, Result<Vec<Row>>> {
+        Box::pin(SimpleDbTransaction::query(self, statement, params))
+    }
+    fn query_stream<'a, 'b>(
+        &'a self,
+        statement: &'b str,
+        params: &'b [&'b (dyn ToSql + Sync)],
+    ) -> BoxFuture<'b, Result<BoxStream<'a, Result<Row>>>>
     where
-        T: ?Sized + ToStatement,
+        'a: 'b,
     {
-        Ok(self.pool.get().await?.query(statement, params).await?)
+        Box::pin(SimpleDbTransaction::query_stream(self, statement, params))
+    }
+}
+
+async fn query(
+    client: &Object,
+    statement: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Vec<Row>> {
+    let statement = client.prepare_cached(statement).await?;
+    Ok(client.query(&statement, params).await?)
+}
+async fn query_stream(
+    client: &Object,
+    statement: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<BoxStream<'static, Result<Row>>> {
+    let statement = client.prepare_cached(statement).await?;
+    let rows = client.query_raw(&statement, params.iter().copied()).await?;
+    Ok(rows.map_err(Error::from).boxed())
+}
+
+/// The row of a function declared with `Cardinality::One`
+pub fn expect_one<T>(name: &str, rows: Vec<T>) -> Result<T> {
+    let count = rows.len();
+    let mut rows = rows.into_iter();
+    match (rows.next(), count) {
+        (Some(row), 1) => Ok(row),
+        _ => bail!("{} returned {} rows, expected one", name, count),
+    }
+}
+/// The row, if any, of a function declared with `Cardinality::Optional`
+pub fn expect_optional<T>(name: &str, rows: Vec<T>) -> Result<Option<T>> {
+    if rows.len() > 1 {
+        bail!("{} returned {} rows, expected at most one", name, rows.len());
     }
+    Ok(rows.into_iter().next())
 }
 
 pub async fn connect_to_database(config: DatabaseConfig) -> Result<SimpleDbClient> {
@@ -30,5 +267,6 @@ pub async fn connect_to_database(config: DatabaseConfig) -> Result<SimpleDbClien
         config.port.as_ref().unwrap()
     );
     let pool = config.create_pool(Some(Runtime::Tokio1), NoTls)?;
-    Ok(SimpleDbClient { pool })
+    let pg_config = config.get_pg_config()?;
+    Ok(SimpleDbClient { pool, pg_config })
 }
@@ -1,3 +1,7 @@
+use crate::database::SimpleDbClient;
+use crate::locale::Locale;
+use eyre::*;
+use model::error_code::*;
 use reqwest::StatusCode;
 use serde::*;
 
@@ -17,59 +21,55 @@ impl ErrorCode {
     pub fn to_u32(self) -> u32 {
         self.code
     }
+    /// Registry entry of an application code, `None` for HTTP status codes
+    pub fn info(self) -> Option<&'static ErrorCodeInfo> {
+        get_error_code(self.code)
+    }
+    /// HTTP status the code maps to, either itself or the one declared in the registry
+    pub fn http_status(self) -> Option<StatusCode> {
+        self.to_status_code()
+            .or_else(|| StatusCode::from_u16(self.info()?.http_status).ok())
+    }
+    /// Message shown to users, falling back to the HTTP reason phrase
+    pub fn message(self, locale: Locale) -> &'static str {
+        get_error_message(self.code, locale.as_str())
+            .or_else(|| self.canonical_reason())
+            .unwrap_or("")
+    }
     pub fn canonical_reason(self) -> Option<&'static str> {
         if let Some(x) = self.to_status_code() {
             x.canonical_reason()
         } else {
-            match self.code {
-                3484946 => Some("InvalidEnumLevel"), // 22P02
-
-                45349632 => Some("Error"),                    // R0000
-                45349633 => Some("InvalidArgument"),          // R0001
-                45349634 => Some("InvalidState"),             // R0002
-                45349635 => Some("InvalidSeq"),               // R0003
-                45349636 => Some("InvalidMethod"),            // R0004
-                45349637 => Some("ProtocolViolation"),        // R0005
-                45349638 => Some("MalformedRequest"),         // R0006
-                45349639 => Some("UnknownUser"),              // R0007
-                45349640 => Some("BlockedUser"),              // R0008
-                45349641 => Some("InvalidPassword"),          // R0009
-                45349642 => Some("InvalidToken"),             // R000A
-                45349643 => Some("TemporarilyUnavailable"),   // R000B
-                45349644 => Some("UnexpectedException"),      // R000C
-                45349645 => Some("BackPressureIncreased"),    // R000D
-                45349646 => Some("InvalidPublicId"),          // R000E
-                45349647 => Some("InvalidRange"),             // R000F
-                45349648 => Some("BankAccountAlreadyExists"), // R000G
-                45349649 => Some("InsufficientFunds"),        // R000H
-
-                45349654 => Some("LogicalError"), // R000M
-                45349655 => Some("RestrictedUserPrivileges"), // R000N
-                45349656 => Some("IdenticalReplacement"), // R000O
-
-                45349659 => Some("InvalidRecoveryQuestions"), // R000R
-                45349660 => Some("InvalidRole"),              // R000S
-                45349661 => Some("WrongRecoveryAnswers"),     // R000T
-                45349662 => Some("MessageNotDelivered"),      // R000U
-                45349663 => Some("NoReply"),                  // R000V
-                45349664 => Some("NullAttribute"),            // R000W
-                45349665 => Some("ConsentMissing"),           // R000X
-                45349666 => Some("ActiveSubscriptionRequired"), // R000Y
-                45349667 => Some("UsernameAlreadyRegistered"), // R000Z
-                45349668 => Some("RecoveryQuestionsNotSet"),  // R0010
-                45349669 => Some("MustSubmitAllRecoveryQuestions"), // R0011
-                45349670 => Some("InvalidRecoveryToken"),     // R0012
-
-                45349676 => Some("RoutingError"),        // R0018
-                45349677 => Some("UnauthorizedMessage"), // R0019
-
-                45349679 => Some("AuthError"), // R001B
+            self.info().map(|x| x.name)
+        }
+    }
+}
 
-                45349684 => Some("InternalError"), // R001G
-                _ => None,
-            }
+/// Fails if a function in the `api` schema raises a SQLSTATE missing from `ERROR_CODES`, as when
+/// the database was migrated by a newer build than the one starting
+pub async fn check_raised_error_codes(db: &SimpleDbClient) -> Result<()> {
+    let rows = db
+        .query(
+            "SELECT p.proname::text, p.prosrc
+             FROM pg_proc p
+                      JOIN pg_namespace n ON n.oid = p.pronamespace
+             WHERE n.nspname = 'api'",
+            &[],
+        )
+        .await?;
+    for row in rows {
+        let name: String = row.get(0);
+        let body: String = row.get(1);
+        for state in get_raised_sqlstates(&body) {
+            ensure!(
+                get_error_code_by_sqlstate(&state).is_some(),
+                "api.{} raises SQLSTATE {}, which is not a known error code",
+                name,
+                state
+            );
         }
     }
+    Ok(())
 }
 
 impl From<StatusCode> for ErrorCode {
@@ -1,6 +1,8 @@
+use crate::json_schema::validate_json;
 use crate::toolbox::{RequestContext, Toolbox};
 use crate::ws::*;
 use core::marker::{Send, Sync};
+use model::endpoint::EndpointSchema;
 use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
@@ -20,11 +22,32 @@ pub trait RequestHandler: Send + Sync {
 }
 
 pub trait RequestHandlerErased: Send + Sync {
-    fn handle(&self, toolbox: &Toolbox, ctx: RequestContext, conn: Arc<Connection>, req: Value);
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        schema: &EndpointSchema,
+        req: Value,
+    );
 }
 
 impl<T: RequestHandler> RequestHandlerErased for T {
-    fn handle(&self, toolbox: &Toolbox, ctx: RequestContext, conn: Arc<Connection>, req: Value) {
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        schema: &EndpointSchema,
+        req: Value,
+    ) {
+        if let Err(err) = validate_json(&schema.json_schema["parameters"], &req) {
+            toolbox.send(
+                &ctx,
+                validation_error_to_resp(&ctx, StatusCode::BAD_REQUEST.into(), err),
+            );
+            return;
+        }
         let data: T::Request = match serde_json::from_value(req) {
             Ok(data) => data,
             Err(err) => {
@@ -0,0 +1,181 @@
+use crate::validation::*;
+use serde::*;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaError {
+    pub path: String,
+    pub message: String,
+}
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaErrors(pub Vec<SchemaError>);
+impl Display for SchemaErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("invalid request: ")?;
+        for (i, err) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", err)?;
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for SchemaErrors {}
+
+fn get_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(x) if x.is_i64() || x.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+fn is_type(value: &Value, ty: &str) -> bool {
+    match (ty, get_type_name(value)) {
+        ("number", "integer") => true,
+        (expected, actual) => expected == actual,
+    }
+}
+/// Validates `value` against the subset of JSON Schema produced by `model::json_schema`,
+/// collecting every violation instead of stopping at the first one
+pub fn validate_json(schema: &Value, value: &Value) -> Result<(), SchemaErrors> {
+    let mut errors = vec![];
+    validate_at(schema, value, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaErrors(errors))
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<SchemaError>) {
+    if let Some(options) = schema.get("oneOf").and_then(|x| x.as_array()) {
+        let matched = options.iter().any(|x| {
+            let mut errs = vec![];
+            validate_at(x, value, path, &mut errs);
+            errs.is_empty()
+        });
+        if !matched {
+            match options
+                .iter()
+                .find(|x| x.get("type").and_then(|x| x.as_str()) != Some("null"))
+            {
+                Some(inner) if !value.is_null() => validate_at(inner, value, path, errors),
+                _ => push_error(errors, path, "does not match any allowed schema".to_owned()),
+            }
+        }
+        return;
+    }
+    if let Some(ty) = schema.get("type").and_then(|x| x.as_str()) {
+        if !is_type(value, ty) {
+            push_error(
+                errors,
+                path,
+                format!("expected {}, found {}", ty, get_type_name(value)),
+            );
+            return;
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            push_error(errors, path, format!("expected {}", expected));
+        }
+    }
+    if let Some(variants) = schema.get("enum").and_then(|x| x.as_array()) {
+        if !variants.contains(value) {
+            push_error(
+                errors,
+                path,
+                format!(
+                    "expected one of {}",
+                    variants
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        }
+    }
+    let get_usize = |key: &str| schema.get(key).and_then(|x| x.as_u64()).map(|x| x as usize);
+    if let Some(x) = value.as_f64() {
+        check_range(
+            path,
+            x,
+            schema.get("minimum").and_then(|x| x.as_f64()),
+            schema.get("maximum").and_then(|x| x.as_f64()),
+            errors,
+        );
+    }
+    if let Some(x) = value.as_str() {
+        check_length(
+            path,
+            x,
+            get_usize("minLength"),
+            get_usize("maxLength"),
+            errors,
+        );
+        if let Some(pattern) = schema.get("pattern").and_then(|x| x.as_str()) {
+            check_pattern(path, x, pattern, errors);
+        }
+        if let Some(format) = schema.get("format").and_then(|x| x.as_str()) {
+            check_format(path, x, format, errors);
+            if format == "decimal" {
+                if let Ok(x) = x.parse() {
+                    check_decimal_range(
+                        path,
+                        &x,
+                        schema.get("minimum").and_then(|x| x.as_f64()),
+                        schema.get("maximum").and_then(|x| x.as_f64()),
+                        errors,
+                    );
+                }
+            }
+        }
+    }
+    if let Some(values) = value.as_array() {
+        check_items(
+            path,
+            values.len(),
+            get_usize("minItems"),
+            get_usize("maxItems"),
+            errors,
+        );
+        if let Some(items) = schema.get("items") {
+            for (i, x) in values.iter().enumerate() {
+                validate_at(items, x, &format!("{}[{}]", path, i), errors);
+            }
+        }
+    }
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(|x| x.as_array()) {
+            for key in required.iter().filter_map(|x| x.as_str()) {
+                if !object.contains_key(key) {
+                    push_error(
+                        errors,
+                        &join_path(path, key),
+                        "missing required field".to_owned(),
+                    );
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(|x| x.as_object()) {
+            for (key, sub_schema) in properties {
+                if let Some(x) = object.get(key) {
+                    validate_at(sub_schema, x, &join_path(path, key), errors);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use model::error_code::LOCALES;
+use std::fmt::{Display, Formatter};
+
+/// One of the locales in `model::error_code::LOCALES`, defaults to the first one
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Locale(u8);
+impl Locale {
+    pub fn as_str(self) -> &'static str {
+        LOCALES[self.0 as usize]
+    }
+    pub fn to_u8(self) -> u8 {
+        self.0
+    }
+    pub fn from_u8(index: u8) -> Option<Self> {
+        ((index as usize) < LOCALES.len()).then_some(Self(index))
+    }
+    /// Matches a language tag like `zh`, `zh-CN` or `zh_CN` by its primary language
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_']).next()?;
+        LOCALES
+            .iter()
+            .position(|x| x.eq_ignore_ascii_case(language))
+            .map(|x| Self(x as u8))
+    }
+    /// Picks the supported locale with the highest quality from an `Accept-Language` header
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        let mut best: Option<(f32, Self)> = None;
+        for item in header.split(',') {
+            let mut parts = item.split(';');
+            let Some(locale) = parts.next().and_then(Self::from_tag) else {
+                continue;
+            };
+            let quality = parts
+                .find_map(|x| x.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |x| x.trim().parse::<f32>().ok());
+            match quality {
+                Some(q) if q > 0.0 && best.map_or(true, |(b, _)| q > b) => best = Some((q, locale)),
+                _ => {}
+            }
+        }
+        best.map(|(_, locale)| locale)
+    }
+}
+impl Display for Locale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
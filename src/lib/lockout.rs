@@ -0,0 +1,134 @@
+//! Brute-force protection from the history of failed password checks in `tbl.login_attempt`
+//!
+//! Failures are counted per username, since its last successful login, and per IP address, each
+//! within the window of its policy. Once a count reaches `max_failures` the policy either blocks
+//! further attempts for a delay that doubles with every failure after that, or asks for a CAPTCHA
+//! solved with the provider configured in `captcha`
+use chrono::{DateTime, Duration, Utc};
+use eyre::*;
+use serde::*;
+use std::net::IpAddr;
+
+/// What a policy does once it is triggered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockoutAction {
+    Block,
+    Captcha,
+}
+
+/// Times in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LockoutPolicy {
+    /// Failures within `window` that trigger the policy, 0 to disable it
+    pub max_failures: u32,
+    pub window: u32,
+    pub action: LockoutAction,
+    /// The first block, doubled with each further failure up to `max_delay`
+    pub base_delay: u32,
+    pub max_delay: u32,
+}
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            max_failures: 5,
+            window: 15 * 60,
+            action: LockoutAction::Block,
+            base_delay: 30,
+            max_delay: 15 * 60,
+        }
+    }
+}
+
+/// State of a username or address under a policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lockout {
+    None,
+    Blocked { until: DateTime<Utc> },
+    CaptchaRequired,
+}
+
+impl LockoutPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.max_failures > 0
+    }
+    /// How long the block lasts after the last of `failures`
+    pub fn delay(&self, failures: u32) -> Duration {
+        let doublings = failures.saturating_sub(self.max_failures).min(31);
+        let delay = (self.base_delay as u64) << doublings;
+        Duration::seconds(delay.min(self.max_delay as u64) as i64)
+    }
+    pub fn check(
+        &self,
+        failures: u32,
+        last_failure: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Lockout {
+        if !self.is_enabled() || failures < self.max_failures {
+            return Lockout::None;
+        }
+        match (self.action, last_failure) {
+            (LockoutAction::Captcha, _) => Lockout::CaptchaRequired,
+            (LockoutAction::Block, Some(last)) if last + self.delay(failures) > now => {
+                Lockout::Blocked {
+                    until: last + self.delay(failures),
+                }
+            }
+            (LockoutAction::Block, _) => Lockout::None,
+        }
+    }
+}
+
+/// A siteverify endpoint as offered by hCaptcha, reCAPTCHA and Turnstile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptchaConfig {
+    pub verify_url: String,
+    pub secret: String,
+}
+impl CaptchaConfig {
+    pub async fn verify(&self, token: &str, address: IpAddr) -> Result<bool> {
+        #[derive(Deserialize)]
+        struct Verification {
+            success: bool,
+        }
+        let address = address.to_string();
+        let resp: Verification = reqwest::Client::new()
+            .post(&self.verify_url)
+            .form(&[
+                ("secret", self.secret.as_str()),
+                ("response", token),
+                ("remoteip", address.as_str()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid response from the CAPTCHA provider")?;
+        Ok(resp.success)
+    }
+}
+
+/// The `lockout` section of the config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LockoutConfig {
+    pub username: LockoutPolicy,
+    pub ip: LockoutPolicy,
+    /// Without it a CAPTCHA can never be solved, and a `captcha` policy blocks until its window
+    /// passes
+    pub captcha: Option<CaptchaConfig>,
+}
+impl Default for LockoutConfig {
+    fn default() -> Self {
+        Self {
+            username: LockoutPolicy::default(),
+            ip: LockoutPolicy {
+                max_failures: 20,
+                ..LockoutPolicy::default()
+            },
+            captcha: None,
+        }
+    }
+}
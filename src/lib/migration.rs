@@ -0,0 +1,268 @@
+use crate::config::MigrateArgs;
+use crate::database::SimpleDbClient;
+use eyre::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::*;
+
+/// Arbitrary key for `pg_advisory_lock`, so that services starting together migrate one by one
+const MIGRATION_LOCK_KEY: i64 = 0x696c_725f_6d69_67;
+
+const CREATE_SCHEMA_MIGRATIONS: &str = "CREATE TABLE IF NOT EXISTS public.schema_migrations (
+    name       varchar     NOT NULL PRIMARY KEY,
+    version    int         NULL,
+    checksum   varchar     NOT NULL,
+    applied_at timestamptz NOT NULL DEFAULT NOW()
+);";
+const RECORD_MIGRATION: &str = "INSERT INTO public.schema_migrations (name, version, checksum)
+VALUES ($1, $2, $3)
+ON CONFLICT (name) DO UPDATE SET checksum = excluded.checksum, applied_at = NOW()";
+
+/// A SQL file applied by the runner. Versioned migrations are applied once, in order.
+/// Repeatable ones (the generated `model.sql`, `tables.sql` and `api.sql`) are applied again
+/// whenever they change
+#[derive(Clone, Debug)]
+pub struct Migration {
+    pub name: String,
+    pub version: Option<u32>,
+    pub sql: String,
+    pub checksum: String,
+}
+impl Migration {
+    pub fn new(name: impl Into<String>, version: Option<u32>, sql: impl Into<String>) -> Self {
+        let sql = sql.into();
+        Self {
+            name: name.into(),
+            version,
+            checksum: hex::encode(Sha256::digest(sql.as_bytes())),
+            sql,
+        }
+    }
+    pub fn is_repeatable(&self) -> bool {
+        self.version.is_none()
+    }
+}
+
+/// Loads `model.sql`, `migrations/V<version>__<description>.sql`, `tables.sql` and `api.sql` from
+/// `dir`, in the order they are applied: types first, then tables, then functions
+pub fn load_migrations(dir: &Path) -> Result<Vec<Migration>> {
+    let read = |name: &str| {
+        let path = dir.join(name);
+        std::fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))
+    };
+    let mut versioned = vec![];
+    for entry in std::fs::read_dir(dir.join("migrations"))? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        let Some(stem) = name.strip_suffix(".sql") else {
+            continue;
+        };
+        let version = stem
+            .strip_prefix('V')
+            .and_then(|x| x.split_once("__"))
+            .and_then(|(version, _)| version.parse::<u32>().ok())
+            .with_context(|| {
+                format!(
+                    "migration {} is not named V<version>__<description>.sql",
+                    name
+                )
+            })?;
+        versioned.push(Migration::new(
+            &name,
+            Some(version),
+            read(&format!("migrations/{}", name))?,
+        ));
+    }
+    versioned.sort_by_key(|x| x.version);
+    for (a, b) in versioned.iter().zip(versioned.iter().skip(1)) {
+        if a.version == b.version {
+            bail!("migrations {} and {} share a version", a.name, b.name);
+        }
+    }
+
+    let mut migrations = vec![Migration::new("model.sql", None, read("model.sql")?)];
+    migrations.extend(versioned);
+    migrations.push(Migration::new("tables.sql", None, read("tables.sql")?));
+    migrations.push(Migration::new("api.sql", None, read("api.sql")?));
+    Ok(migrations)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrationAction {
+    Apply,
+    Reapply,
+    Skip,
+}
+
+/// Decides what to do with each migration given the checksums already applied. A repeatable
+/// migration is applied again if it changed or if one before it was, since it may depend on the
+/// types or tables those create
+pub fn plan_migrations(
+    migrations: &[Migration],
+    applied: &HashMap<String, String>,
+) -> Result<Vec<MigrationAction>> {
+    let latest = migrations
+        .iter()
+        .filter(|x| applied.contains_key(&x.name))
+        .filter_map(|x| x.version)
+        .max();
+    let mut reapply = false;
+    let mut plan = vec![];
+    for migration in migrations {
+        let action = match (applied.get(&migration.name), migration.version) {
+            (Some(checksum), Some(_)) if *checksum != migration.checksum => bail!(
+                "migration {} was modified after being applied, add a new migration instead",
+                migration.name
+            ),
+            (Some(_), Some(_)) => MigrationAction::Skip,
+            (None, Some(version)) if latest.map_or(false, |x| version < x) => bail!(
+                "migration {} is older than the latest applied one, V{}",
+                migration.name,
+                latest.unwrap()
+            ),
+            (None, _) => MigrationAction::Apply,
+            (Some(checksum), None) if reapply || *checksum != migration.checksum => {
+                MigrationAction::Reapply
+            }
+            (Some(_), None) => MigrationAction::Skip,
+        };
+        if migration.is_repeatable() && action != MigrationAction::Skip {
+            reapply = true;
+        }
+        plan.push(action);
+    }
+    for name in applied.keys() {
+        if !migrations.iter().any(|x| &x.name == name) {
+            warn!("Migration {} is applied but no longer exists", name);
+        }
+    }
+    Ok(plan)
+}
+
+/// Applies pending migrations, each in its own transaction, and records them in
+/// `schema_migrations`. With `dry_run` only reports what would be applied
+pub async fn run_migrations(
+    db: &SimpleDbClient,
+    migrations: &[Migration],
+    dry_run: bool,
+) -> Result<Vec<MigrationAction>> {
+    let mut client = db.get_client().await?;
+    if !dry_run {
+        client.batch_execute(CREATE_SCHEMA_MIGRATIONS).await?;
+        client
+            .execute("SELECT pg_advisory_lock($1)", &[&MIGRATION_LOCK_KEY])
+            .await?;
+    }
+    let result = async {
+        let exists: bool = client
+            .query_one(
+                "SELECT to_regclass('public.schema_migrations') IS NOT NULL",
+                &[],
+            )
+            .await?
+            .get(0);
+        let applied: HashMap<String, String> = if exists {
+            client
+                .query("SELECT name, checksum FROM public.schema_migrations", &[])
+                .await?
+                .into_iter()
+                .map(|x| (x.get(0), x.get(1)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let plan = plan_migrations(migrations, &applied)?;
+        for (migration, action) in migrations.iter().zip(&plan) {
+            match (action, dry_run) {
+                (MigrationAction::Skip, _) => {
+                    info!("Migration {} is up to date", migration.name)
+                }
+                (action, true) => info!("Migration {} is pending: {:?}", migration.name, action),
+                (action, false) => {
+                    info!("Applying migration {}: {:?}", migration.name, action);
+                    let tx = client.transaction().await?;
+                    tx.batch_execute(&migration.sql)
+                        .await
+                        .with_context(|| format!("migration {} failed", migration.name))?;
+                    tx.execute(
+                        RECORD_MIGRATION,
+                        &[
+                            &migration.name,
+                            &migration.version.map(|x| x as i32),
+                            &migration.checksum,
+                        ],
+                    )
+                    .await?;
+                    tx.commit().await?;
+                }
+            }
+        }
+        Ok(plan)
+    }
+    .await;
+    if !dry_run {
+        client
+            .execute("SELECT pg_advisory_unlock($1)", &[&MIGRATION_LOCK_KEY])
+            .await?;
+    }
+    result
+}
+
+/// Records the first versioned migration as applied without running it, for databases created
+/// before migrations were tracked, e.g. by the old `setup_database.sh`. Fails if any versioned
+/// migration is recorded already
+pub async fn baseline_migrations(db: &SimpleDbClient, migrations: &[Migration]) -> Result<()> {
+    let baseline = migrations
+        .iter()
+        .find(|x| !x.is_repeatable())
+        .context("there is no versioned migration to baseline")?;
+    let mut client = db.get_client().await?;
+    client.batch_execute(CREATE_SCHEMA_MIGRATIONS).await?;
+    let tx = client.transaction().await?;
+    tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY])
+        .await?;
+    let applied: Option<String> = tx
+        .query_opt(
+            "SELECT name FROM public.schema_migrations WHERE version IS NOT NULL LIMIT 1",
+            &[],
+        )
+        .await?
+        .map(|x| x.get(0));
+    if let Some(name) = applied {
+        bail!(
+            "migration {} is applied already, baseline is only for untracked databases",
+            name
+        );
+    }
+    tx.execute(
+        RECORD_MIGRATION,
+        &[
+            &baseline.name,
+            &baseline.version.map(|x| x as i32),
+            &baseline.checksum,
+        ],
+    )
+    .await?;
+    tx.commit().await?;
+    info!("Recorded migration {} as applied", baseline.name);
+    Ok(())
+}
+
+/// Entry point of the `migrate` subcommand
+pub async fn migrate(db: &SimpleDbClient, args: &MigrateArgs) -> Result<()> {
+    let migrations = load_migrations(&args.dir)?;
+    if args.baseline {
+        if args.dry_run {
+            bail!("--baseline cannot be combined with --dry-run");
+        }
+        baseline_migrations(db, &migrations).await?;
+    }
+    let plan = run_migrations(db, &migrations, args.dry_run).await?;
+    let pending = plan.iter().filter(|x| **x != MigrationAction::Skip).count();
+    if args.dry_run {
+        info!("{} of {} migrations pending", pending, migrations.len());
+    } else {
+        info!("Applied {} of {} migrations", pending, migrations.len());
+    }
+    Ok(())
+}
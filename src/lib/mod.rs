@@ -3,7 +3,15 @@ pub mod database;
 pub mod dataframe_hack;
 pub mod error_code;
 pub mod handler;
+pub mod json_schema;
+pub mod locale;
+pub mod lockout;
 pub mod log;
+pub mod migration;
+pub mod password;
 pub mod toolbox;
+pub mod time;
+pub mod totp;
 pub mod utils;
+pub mod validation;
 pub mod ws;
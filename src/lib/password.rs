@@ -0,0 +1,121 @@
+//! Password hashing with argon2id, stored as PHC strings like `$argon2id$v=19$m=19456,t=2,p=1$...`
+//!
+//! Users who signed up before argon2id have a hex encoded SHA-256 of the password followed by a
+//! salt stored separately. Those still verify, and are reported as outdated so that the caller
+//! stores a new hash
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use eyre::*;
+use serde::*;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+/// Cost of new hashes, the `password` section of the config. Defaults to the OWASP minimum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PasswordConfig {
+    /// Memory in KiB
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// Outcome of `PasswordConfig::verify`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordCheck {
+    Wrong,
+    Ok,
+    /// Correct, but hashed with SHA-256 or other argon2 parameters than configured
+    Outdated,
+}
+impl PasswordCheck {
+    pub fn is_ok(self) -> bool {
+        self != Self::Wrong
+    }
+}
+
+impl PasswordConfig {
+    fn argon2(&self) -> Result<Argon2<'static>> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+            .map_err(|err| eyre!("Invalid password hashing parameters: {}", err))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+    /// Hashes with a random salt, returning the PHC string
+    pub fn hash(&self, password: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = self
+            .argon2()?
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|err| eyre!("Failed to hash password: {}", err))?;
+        Ok(hash.to_string())
+    }
+    /// Checks `password` against a stored hash, `legacy_salt` being the salt of a SHA-256 one
+    pub fn verify(
+        &self,
+        password: &str,
+        stored: &str,
+        legacy_salt: Option<&[u8]>,
+    ) -> Result<PasswordCheck> {
+        if let Some(salt) = legacy_salt {
+            let stored = hex::decode(stored).context("Invalid legacy password hash")?;
+            let mut hasher = Sha256::new();
+            hasher.update(password.as_bytes());
+            hasher.update(salt);
+            let ok: bool = hasher.finalize().as_slice().ct_eq(&stored).into();
+            return Ok(if ok {
+                PasswordCheck::Outdated
+            } else {
+                PasswordCheck::Wrong
+            });
+        }
+        let hash =
+            PasswordHash::new(stored).map_err(|err| eyre!("Invalid password hash: {}", err))?;
+        // compares the outputs in constant time
+        match Argon2::default().verify_password(password.as_bytes(), &hash) {
+            Ok(()) => {}
+            Err(argon2::password_hash::Error::Password) => return Ok(PasswordCheck::Wrong),
+            Err(err) => bail!("Failed to verify password: {}", err),
+        }
+        let current = Params::try_from(&hash).ok();
+        let outdated = hash.algorithm != Algorithm::Argon2id.ident()
+            || current.map_or(true, |x| {
+                (x.m_cost(), x.t_cost(), x.p_cost())
+                    != (self.memory_kib, self.iterations, self.parallelism)
+            });
+        Ok(if outdated {
+            PasswordCheck::Outdated
+        } else {
+            PasswordCheck::Ok
+        })
+    }
+    /// `hash` on the blocking pool, hashing is too slow to run on the runtime
+    pub async fn hash_blocking(self, password: String) -> Result<String> {
+        tokio::task::spawn_blocking(move || self.hash(&password)).await?
+    }
+    /// Takes as long as `verify` of a wrong password, for users without a stored hash, so that
+    /// response times do not tell unknown users apart
+    pub async fn verify_dummy_blocking(self, password: String) -> Result<PasswordCheck> {
+        self.hash_blocking(password).await?;
+        Ok(PasswordCheck::Wrong)
+    }
+    /// `verify` on the blocking pool
+    pub async fn verify_blocking(
+        self,
+        password: String,
+        stored: String,
+        legacy_salt: Option<Vec<u8>>,
+    ) -> Result<PasswordCheck> {
+        tokio::task::spawn_blocking(move || self.verify(&password, &stored, legacy_salt.as_deref()))
+            .await?
+    }
+}
@@ -0,0 +1,243 @@
+use bytes::{Buf, BufMut, BytesMut};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+use tokio_postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+const MICROS_PER_MILLI: i64 = 1_000;
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// A postgres `interval`. Months and days are kept apart from the time part, like postgres
+/// does, because their length depends on the date they are added to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+impl Interval {
+    pub fn from_millis(millis: i64) -> Self {
+        Self {
+            months: 0,
+            days: 0,
+            microseconds: millis * MICROS_PER_MILLI,
+        }
+    }
+    pub fn from_secs(secs: i64) -> Self {
+        Self::from_millis(secs * 1000)
+    }
+    /// Length in milliseconds, counting a month as 30 days like postgres' `EXTRACT(EPOCH ...)`
+    pub fn to_millis(&self) -> i64 {
+        (self.months as i64 * 30 * MICROS_PER_DAY
+            + self.days as i64 * MICROS_PER_DAY
+            + self.microseconds)
+            / MICROS_PER_MILLI
+    }
+}
+impl Display for Interval {
+    /// Formats as an ISO-8601 duration, e.g. `P1Y2M3DT4H5M6.5S`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut s = "P".to_owned();
+        let (years, months) = (self.months / 12, self.months % 12);
+        for (value, unit) in [(years, 'Y'), (months, 'M'), (self.days, 'D')] {
+            if value != 0 {
+                write!(s, "{}{}", value, unit)?;
+            }
+        }
+        let micros = self.microseconds;
+        let hours = micros / MICROS_PER_HOUR;
+        let minutes = micros % MICROS_PER_HOUR / MICROS_PER_MINUTE;
+        let seconds = micros % MICROS_PER_MINUTE;
+        if micros != 0 || s.len() == 1 {
+            s.push('T');
+            for (value, unit) in [(hours, 'H'), (minutes, 'M')] {
+                if value != 0 {
+                    write!(s, "{}{}", value, unit)?;
+                }
+            }
+            if seconds != 0 || s.ends_with('T') {
+                if seconds < 0 {
+                    s.push('-');
+                }
+                let seconds = seconds.abs();
+                write!(s, "{}", seconds / MICROS_PER_SECOND)?;
+                let fraction = seconds % MICROS_PER_SECOND;
+                if fraction != 0 {
+                    write!(s, ".{}", format!("{:06}", fraction).trim_end_matches('0'))?;
+                }
+                s.push('S');
+            }
+        }
+        f.write_str(&s)
+    }
+}
+impl FromStr for Interval {
+    type Err = eyre::Error;
+
+    /// Parses an ISO-8601 duration. Each component may carry its own sign, only seconds may
+    /// have a fraction
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || eyre::eyre!("invalid ISO-8601 duration: {}", s);
+        let rest = s.strip_prefix('P').ok_or_else(err)?;
+        let mut interval = Interval::default();
+        let mut in_time = false;
+        let mut number = String::new();
+        for c in rest.chars() {
+            match c {
+                'T' if !in_time && number.is_empty() => in_time = true,
+                '0'..='9' | '-' | '+' | '.' => number.push(c),
+                unit => {
+                    if number.is_empty() {
+                        return Err(err());
+                    }
+                    if unit == 'S' && in_time {
+                        let seconds: f64 = number.parse().map_err(|_| err())?;
+                        interval.microseconds +=
+                            (seconds * MICROS_PER_SECOND as f64).round() as i64;
+                    } else {
+                        let value: i64 = number.parse().map_err(|_| err())?;
+                        match (in_time, unit) {
+                            (false, 'Y') => interval.months += (value * 12) as i32,
+                            (false, 'M') => interval.months += value as i32,
+                            (false, 'W') => interval.days += (value * 7) as i32,
+                            (false, 'D') => interval.days += value as i32,
+                            (true, 'H') => interval.microseconds += value * MICROS_PER_HOUR,
+                            (true, 'M') => interval.microseconds += value * MICROS_PER_MINUTE,
+                            _ => return Err(err()),
+                        }
+                    }
+                    number.clear();
+                }
+            }
+        }
+        if !number.is_empty() || rest.is_empty() || rest.ends_with('T') {
+            return Err(err());
+        }
+        Ok(interval)
+    }
+}
+impl Serialize for Interval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for Interval {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+impl ToSql for Interval {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.put_i64(self.microseconds);
+        out.put_i32(self.days);
+        out.put_i32(self.months);
+        Ok(IsNull::No)
+    }
+    accepts!(INTERVAL);
+    to_sql_checked!();
+}
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_ty: &Type, mut raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err("invalid message length for interval".into());
+        }
+        Ok(Self {
+            microseconds: raw.get_i64(),
+            days: raw.get_i32(),
+            months: raw.get_i32(),
+        })
+    }
+    accepts!(INTERVAL);
+}
+
+/// Date/time types that can be represented as milliseconds since the unix epoch
+pub trait EpochMillis: Sized {
+    fn to_epoch_millis(&self) -> i64;
+    fn from_epoch_millis(millis: i64) -> Option<Self>;
+}
+impl EpochMillis for DateTime<Utc> {
+    fn to_epoch_millis(&self) -> i64 {
+        self.timestamp_millis()
+    }
+    fn from_epoch_millis(millis: i64) -> Option<Self> {
+        DateTime::from_timestamp_millis(millis)
+    }
+}
+impl EpochMillis for NaiveDateTime {
+    fn to_epoch_millis(&self) -> i64 {
+        self.and_utc().timestamp_millis()
+    }
+    fn from_epoch_millis(millis: i64) -> Option<Self> {
+        DateTime::from_timestamp_millis(millis).map(|x| x.naive_utc())
+    }
+}
+impl EpochMillis for NaiveDate {
+    fn to_epoch_millis(&self) -> i64 {
+        self.and_time(Default::default()).to_epoch_millis()
+    }
+    fn from_epoch_millis(millis: i64) -> Option<Self> {
+        DateTime::from_timestamp_millis(millis).map(|x| x.date_naive())
+    }
+}
+impl EpochMillis for Interval {
+    fn to_epoch_millis(&self) -> i64 {
+        self.to_millis()
+    }
+    fn from_epoch_millis(millis: i64) -> Option<Self> {
+        Some(Self::from_millis(millis))
+    }
+}
+
+/// `#[serde(with = "lib::time::epoch_millis")]` for fields with `TimeFormat::EpochMillis`
+pub mod epoch_millis {
+    use super::*;
+
+    pub fn serialize<T: EpochMillis, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(value.to_epoch_millis())
+    }
+    pub fn deserialize<'de, T: EpochMillis, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let millis = i64::deserialize(deserializer)?;
+        T::from_epoch_millis(millis)
+            .ok_or_else(|| D::Error::custom(format!("timestamp out of range: {}", millis)))
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<T: EpochMillis, S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value
+                .as_ref()
+                .map(|x| x.to_epoch_millis())
+                .serialize(serializer)
+        }
+        pub fn deserialize<'de, T: EpochMillis, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<T>, D::Error> {
+            match Option::<i64>::deserialize(deserializer)? {
+                Some(millis) => T::from_epoch_millis(millis)
+                    .map(Some)
+                    .ok_or_else(|| D::Error::custom(format!("timestamp out of range: {}", millis))),
+                None => Ok(None),
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::database::SimpleDbClient;
 use crate::error_code::ErrorCode;
+use crate::locale::Locale;
 use crate::log::LogLevel;
 use crate::ws::*;
 use dashmap::DashMap;
@@ -24,14 +25,22 @@ impl std::error::Error for NoResp {}
 pub struct CustomError {
     pub code: ErrorCode,
     pub reason: String,
+    /// Seconds until the request may succeed, sent along with the error
+    #[serde(default)]
+    pub retry_after: Option<u32>,
 }
 impl CustomError {
     pub fn new(code: impl Into<ErrorCode>, reason: impl Into<String>) -> Self {
         Self {
             code: code.into(),
             reason: reason.into(),
+            retry_after: None,
         }
     }
+    pub fn retry_after(mut self, seconds: u32) -> Self {
+        self.retry_after = Some(seconds);
+        self
+    }
     pub fn from_sql_error(err: &str, msg: impl Display) -> Result<Self> {
         let code = u32::from_str_radix(err, 36)?;
         let error_code = ErrorCode::new(code);
@@ -43,6 +52,7 @@ impl CustomError {
                 error_code.canonical_reason().unwrap_or(""),
                 msg
             ),
+            retry_after: None,
         };
 
         Ok(this)
@@ -61,29 +71,46 @@ pub struct RequestContext {
     pub seq: u32,
     pub method: u32,
     pub log_id: u64,
+    pub locale: Locale,
 }
 #[derive(Clone)]
 pub struct Toolbox {
     db: Option<SimpleDbClient>,
+    mock_db: Option<Arc<dyn Any + Send + Sync>>,
     values: Arc<DashMap<String, Arc<dyn Any + Send + Sync>>>,
     sender: mpsc::Sender<WsMessage>,
     tasks: Option<Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>>,
+    connections: Arc<DashMap<u32, Arc<Connection>>>,
 }
 
 impl Toolbox {
     pub fn new(sender: mpsc::Sender<WsMessage>) -> Self {
         Self {
             db: None,
+            mock_db: None,
             values: Arc::new(Default::default()),
             sender,
             tasks: None,
+            connections: Arc::new(Default::default()),
         }
     }
     pub fn set_db(&mut self, db: SimpleDbClient) {
         self.db = Some(db);
     }
-    pub fn get_db<T: From<SimpleDbClient>>(&self) -> T {
-        T::from(self.db.as_ref().expect("Db not Initialized").clone())
+    /// Makes `get_db::<T>` return `db` instead of a client of the pool, e.g. a `DbClient` around a
+    /// `MockDbClient` in tests
+    pub fn set_mock_db<T: Clone + Send + Sync + 'static>(&mut self, db: T) {
+        self.mock_db = Some(Arc::new(db));
+    }
+    /// Fails if the mock db is of another type than `T`, or if no db was set
+    pub fn get_db<T: From<SimpleDbClient> + Clone + 'static>(&self) -> Result<T> {
+        if let Some(db) = &self.mock_db {
+            return db
+                .downcast_ref::<T>()
+                .cloned()
+                .with_context(|| format!("mock db is not a {}", std::any::type_name::<T>()));
+        }
+        Ok(T::from(self.db.clone().context("db not initialized")?))
     }
     pub fn set_value(&mut self, key: &str, value: Arc<dyn Any + Send + Sync>) {
         self.values.insert(key.to_string(), value);
@@ -95,6 +122,29 @@ impl Toolbox {
                 .expect("Cannot convert type")
         })
     }
+    /// Called by the server for each connection it accepts, until `remove_connection`
+    pub fn add_connection(&self, conn: Arc<Connection>) {
+        self.connections.insert(conn.connection_id, conn);
+    }
+    pub fn remove_connection(&self, connection_id: u32) {
+        self.connections.remove(&connection_id);
+    }
+    /// The open connections of the server
+    pub fn get_connections(&self) -> Vec<Arc<Connection>> {
+        self.connections
+            .iter()
+            .map(|x| Arc::clone(x.value()))
+            .collect()
+    }
+    /// Closes the open connections matching `f`, returning how many
+    pub fn close_connections(&self, f: impl Fn(&Connection) -> bool) -> usize {
+        let mut count = 0;
+        for conn in self.connections.iter().filter(|x| f(x.value())) {
+            conn.close();
+            count += 1;
+        }
+        count
+    }
     pub fn send(&self, ctx: &RequestContext, resp: WsResponse) {
         self.sender
             .try_send(WsMessage {
@@ -143,6 +193,7 @@ impl Toolbox {
             seq,
             method,
             log_id,
+            locale,
         } = ctx;
         let t = tokio::spawn(async move {
             let resp = f.await;
@@ -173,8 +224,7 @@ impl Toolbox {
                     }
                 }
                 Err(err) if err.downcast_ref::<CustomError>().is_some() => {
-                    let err = err.downcast::<CustomError>().unwrap();
-                    request_error_to_resp(&ctx, err.code, err)
+                    custom_error_to_resp(&ctx, err.downcast::<CustomError>().unwrap())
                 }
                 Err(err) => {
                     internal_error_to_resp(&ctx, StatusCode::INTERNAL_SERVER_ERROR.into(), err)
@@ -0,0 +1,141 @@
+//! Time-based one-time passwords (RFC 6238) for two-factor authentication
+//!
+//! Codes have 6 digits from HMAC-SHA1 over 30 second steps, the parameters every authenticator
+//! app supports. A code is accepted within one step of the current one, and each step only once,
+//! which the caller enforces by storing the last step used. Recovery codes stand in for a code
+//! when the authenticator is lost, and are stored as SHA-256 hashes as they are random
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::{DateTime, Utc};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use serde::*;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+pub const DIGITS: u32 = 6;
+/// Length of a step in seconds
+pub const PERIOD: i64 = 30;
+/// Steps before and after the current one whose codes are accepted, for clock drift
+pub const SKEW: i64 = 1;
+const SECRET_LEN: usize = 20;
+const RECOVERY_CODE_LEN: usize = 10;
+
+/// The `totp` section of the config, times in seconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TotpConfig {
+    /// Shown by authenticator apps next to the username
+    pub issuer: String,
+    /// Until a challenge from `Login` can no longer be completed
+    pub challenge_lifetime: u32,
+    /// Wrong codes a user may enter within `attempt_window` before being turned away
+    pub max_failed_attempts: u32,
+    pub attempt_window: u32,
+    /// Recovery codes handed out when 2FA is enabled
+    pub recovery_codes: u32,
+}
+impl Default for TotpConfig {
+    fn default() -> Self {
+        Self {
+            issuer: "iloverust".to_owned(),
+            challenge_lifetime: 5 * 60,
+            max_failed_attempts: 5,
+            attempt_window: 15 * 60,
+            recovery_codes: 10,
+        }
+    }
+}
+
+impl TotpConfig {
+    /// The `otpauth://` URI authenticator apps enroll with, usually shown as a QR code
+    pub fn otpauth_uri(&self, username: &str, secret: &[u8]) -> String {
+        let issuer = urlencoding::encode(&self.issuer);
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            issuer,
+            urlencoding::encode(username),
+            encode_secret(secret),
+            issuer,
+            DIGITS,
+            PERIOD
+        )
+    }
+}
+
+pub fn generate_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; SECRET_LEN];
+    OsRng.fill_bytes(&mut secret);
+    secret
+}
+/// Base32 without padding, as typed into authenticator apps
+pub fn encode_secret(secret: &[u8]) -> String {
+    BASE32_NOPAD.encode(secret)
+}
+
+pub fn step_at(time: DateTime<Utc>) -> i64 {
+    time.timestamp().div_euclid(PERIOD)
+}
+
+/// The HOTP value (RFC 4226) of a step
+pub fn code_at_step(secret: &[u8], step: i64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(&step.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    let offset = (hash[hash.len() - 1] & 0xf) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset],
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]) & 0x7fff_ffff;
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+pub fn code_at(secret: &[u8], time: DateTime<Utc>) -> String {
+    code_at_step(secret, step_at(time))
+}
+
+/// Whether `code` is shaped like a TOTP code rather than a recovery code
+pub fn is_totp_code(code: &str) -> bool {
+    code.len() == DIGITS as usize && code.bytes().all(|x| x.is_ascii_digit())
+}
+
+/// The step `code` belongs to if it is valid at `now`, the latest if several match
+pub fn verify(secret: &[u8], code: &str, now: DateTime<Utc>) -> Option<i64> {
+    let current = step_at(now);
+    let mut matched = None;
+    // every step is checked, so the time taken does not tell which one matched
+    for step in current - SKEW..=current + SKEW {
+        if bool::from(code_at_step(secret, step).as_bytes().ct_eq(code.as_bytes())) {
+            matched = Some(step);
+        }
+    }
+    matched
+}
+
+/// A random code like `abcde-fghij`, from 50 bits
+pub fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; 7];
+    OsRng.fill_bytes(&mut bytes);
+    let code = BASE32_NOPAD.encode(&bytes).to_ascii_lowercase();
+    format!(
+        "{}-{}",
+        &code[..RECOVERY_CODE_LEN / 2],
+        &code[RECOVERY_CODE_LEN / 2..RECOVERY_CODE_LEN]
+    )
+}
+
+/// Hex encoded SHA-256 of the code, ignoring case, dashes and spaces
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|x| !x.is_whitespace() && *x != '-')
+        .map(|x| x.to_ascii_lowercase())
+        .collect();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
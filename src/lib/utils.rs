@@ -1,8 +1,3 @@
-use convert_case::{Case, Casing};
-use eyre::*;
-use model::endpoint::EndpointSchema;
-use serde::Serialize;
-use std::fmt::Write;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn get_log_id() -> u64 {
@@ -18,25 +13,3 @@ pub fn get_conn_id() -> u32 {
         .unwrap()
         .as_nanos() as _
 }
-
-pub fn encode_header<T: Serialize>(v: T, schema: EndpointSchema) -> Result<String> {
-    let mut s = String::new();
-    write!(s, "0{}", schema.name.to_ascii_lowercase())?;
-    let v = serde_json::to_value(&v)?;
-
-    for (i, f) in schema.parameters.iter().enumerate() {
-        let key = f.name.to_case(Case::Camel);
-        write!(
-            s,
-            ", {}{}",
-            i + 1,
-            urlencoding::encode(
-                &v.get(&key)
-                    .with_context(|| format!("key: {}", key))?
-                    .to_string()
-                    .replace("\"", "")
-            )
-        )?;
-    }
-    Ok(s)
-}
@@ -0,0 +1,155 @@
+use crate::json_schema::{SchemaError, SchemaErrors};
+use dashmap::DashMap;
+use regex::Regex;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use std::sync::OnceLock;
+
+pub const EMAIL_PATTERN: &str = r"^[^@\s]+@[^@\s]+\.[^@\s]+$";
+pub const PHONE_PATTERN: &str = r"^\+?[0-9]{4,15}$";
+
+/// Implemented by generated models to enforce the field constraints declared in the model
+///
+/// Websocket requests are checked against the endpoint's json schema instead; this is for values
+/// built in Rust or read from other sources
+pub trait Validate {
+    fn validate_at(&self, path: &str, errors: &mut Vec<SchemaError>);
+    fn validate(&self) -> Result<(), SchemaErrors> {
+        let mut errors = vec![];
+        self.validate_at("", &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaErrors(errors))
+        }
+    }
+}
+
+pub fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+pub(crate) fn push_error(errors: &mut Vec<SchemaError>, path: &str, message: String) {
+    errors.push(SchemaError {
+        path: if path.is_empty() {
+            "$".to_owned()
+        } else {
+            path.to_owned()
+        },
+        message,
+    })
+}
+
+fn get_regex(pattern: &str) -> Option<Regex> {
+    static CACHE: OnceLock<DashMap<String, Regex>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(re) = cache.get(pattern) {
+        return Some(re.clone());
+    }
+    let re = Regex::new(pattern).ok()?;
+    cache.insert(pattern.to_owned(), re.clone());
+    Some(re)
+}
+
+pub fn check_length(
+    path: &str,
+    value: &str,
+    min: Option<usize>,
+    max: Option<usize>,
+    errors: &mut Vec<SchemaError>,
+) {
+    let len = value.chars().count();
+    if let Some(min) = min {
+        if len < min {
+            push_error(errors, path, format!("must be at least {} characters", min));
+        }
+    }
+    if let Some(max) = max {
+        if len > max {
+            push_error(errors, path, format!("must be at most {} characters", max));
+        }
+    }
+}
+
+pub fn check_items(
+    path: &str,
+    len: usize,
+    min: Option<usize>,
+    max: Option<usize>,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(min) = min {
+        if len < min {
+            push_error(errors, path, format!("must have at least {} items", min));
+        }
+    }
+    if let Some(max) = max {
+        if len > max {
+            push_error(errors, path, format!("must have at most {} items", max));
+        }
+    }
+}
+
+pub fn check_range(
+    path: &str,
+    value: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(min) = min {
+        if value < min {
+            push_error(errors, path, format!("must be at least {}", min));
+        }
+    }
+    if let Some(max) = max {
+        if value > max {
+            push_error(errors, path, format!("must be at most {}", max));
+        }
+    }
+}
+
+pub fn check_decimal_range(
+    path: &str,
+    value: &Decimal,
+    min: Option<f64>,
+    max: Option<f64>,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(min) = min.and_then(Decimal::from_f64) {
+        if *value < min {
+            push_error(errors, path, format!("must be at least {}", min));
+        }
+    }
+    if let Some(max) = max.and_then(Decimal::from_f64) {
+        if *value > max {
+            push_error(errors, path, format!("must be at most {}", max));
+        }
+    }
+}
+
+pub fn check_pattern(path: &str, value: &str, pattern: &str, errors: &mut Vec<SchemaError>) {
+    match get_regex(pattern) {
+        Some(re) if re.is_match(value) => {}
+        Some(_) => push_error(errors, path, format!("must match {}", pattern)),
+        None => push_error(errors, path, format!("invalid pattern {}", pattern)),
+    }
+}
+
+pub fn check_format(path: &str, value: &str, format: &str, errors: &mut Vec<SchemaError>) {
+    let pattern = match format {
+        "email" => EMAIL_PATTERN,
+        "phone" => PHONE_PATTERN,
+        _ => return,
+    };
+    if !get_regex(pattern)
+        .map(|re| re.is_match(value))
+        .unwrap_or(false)
+    {
+        push_error(errors, path, format!("must be a valid {}", format));
+    }
+}
@@ -1,14 +1,17 @@
 use crate::error_code::ErrorCode;
 use crate::handler::RequestHandlerErased;
+use crate::json_schema::{SchemaError, SchemaErrors};
+use crate::locale::Locale;
 use crate::log::LogLevel;
-use crate::toolbox::RequestContext;
+use crate::toolbox::{CustomError, RequestContext};
 use eyre::*;
 use model::endpoint::EndpointSchema;
 use serde::*;
 use std::fmt::{Debug, Display};
 use std::net::IpAddr;
-use std::sync::atomic::{AtomicI64, AtomicU32};
+use std::sync::atomic::{AtomicI64, AtomicU32, AtomicU8, Ordering};
 use std::sync::Arc;
+use tokio::sync::Notify;
 use tracing::*;
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -24,20 +27,61 @@ pub struct WsResponseError {
     pub method: u32,
     pub code: u32,
     pub seq: u32,
+    /// Localized message for users, details are only logged under `log_id`
     pub reason: String,
+    pub log_id: u64,
+    /// Seconds to wait before trying again, for errors like `TemporarilyUnavailable`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u32>,
+    /// The fields an invalid request was rejected for, each with its path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<SchemaError>,
 }
 
 #[derive(Debug)]
 pub struct Connection {
     pub connection_id: u32,
     pub user_id: AtomicI64,
+    /// The login session the connection was authorized with, 0 if none
+    pub session_id: AtomicI64,
     pub role: AtomicU32,
     pub address: IpAddr,
     pub log_id: u64,
+    pub locale: AtomicU8,
+    closing: Notify,
 }
 impl Connection {
+    pub fn new(connection_id: u32, address: IpAddr, log_id: u64, locale: Locale) -> Self {
+        Self {
+            connection_id,
+            user_id: Default::default(),
+            session_id: Default::default(),
+            role: AtomicU32::new(0),
+            address,
+            log_id,
+            locale: AtomicU8::new(locale.to_u8()),
+            closing: Notify::new(),
+        }
+    }
     pub fn get_user_id(&self) -> i64 {
-        self.user_id.load(std::sync::atomic::Ordering::Relaxed)
+        self.user_id.load(Ordering::Relaxed)
+    }
+    pub fn get_session_id(&self) -> i64 {
+        self.session_id.load(Ordering::Relaxed)
+    }
+    /// Asks the server to close the connection, also if it is not receiving yet
+    pub fn close(&self) {
+        self.closing.notify_one();
+    }
+    /// Resolves once `close` is called
+    pub async fn closed(&self) {
+        self.closing.notified().await
+    }
+    pub fn get_locale(&self) -> Locale {
+        Locale::from_u8(self.locale.load(Ordering::Relaxed)).unwrap_or_default()
+    }
+    pub fn set_locale(&self, locale: Locale) {
+        self.locale.store(locale.to_u8(), Ordering::Relaxed);
     }
 }
 
@@ -88,17 +132,24 @@ pub struct WsEndpoint {
     pub handler: Arc<dyn RequestHandlerErased>,
 }
 
-pub fn internal_error_to_resp(ctx: &RequestContext, code: ErrorCode, err: Error) -> WsResponse {
-    let log_id = ctx.log_id;
-    error!(?log_id, "Internal error: {:?}", err);
+fn error_to_resp(ctx: &RequestContext, code: ErrorCode, retry_after: Option<u32>) -> WsResponse {
     WsResponse::Error(WsResponseError {
         method: ctx.method,
         code: code.to_u32(),
         seq: ctx.seq,
-        reason: format!("Internal error: log_id={}", log_id),
+        reason: code.message(ctx.locale).to_owned(),
+        log_id: ctx.log_id,
+        retry_after,
+        details: vec![],
     })
 }
 
+pub fn internal_error_to_resp(ctx: &RequestContext, code: ErrorCode, err: Error) -> WsResponse {
+    let log_id = ctx.log_id;
+    error!(?log_id, "Internal error: {:?}", err);
+    error_to_resp(ctx, code, None)
+}
+
 pub fn request_error_to_resp<E: Display + Debug>(
     ctx: &RequestContext,
     code: ErrorCode,
@@ -107,10 +158,26 @@ pub fn request_error_to_resp<E: Display + Debug>(
     let log_id = ctx.log_id;
 
     warn!(?log_id, "Request error: {:?}", err);
-    WsResponse::Error(WsResponseError {
-        method: ctx.method,
-        code: code.to_u32(),
-        seq: ctx.seq,
-        reason: format!("Request error log_id={}: {}", log_id, err),
-    })
+    error_to_resp(ctx, code, None)
+}
+
+/// As `request_error_to_resp`, sending the violations along, which only name fields of the request
+pub fn validation_error_to_resp(
+    ctx: &RequestContext,
+    code: ErrorCode,
+    err: SchemaErrors,
+) -> WsResponse {
+    let mut resp = request_error_to_resp(ctx, code, &err);
+    if let WsResponse::Error(resp) = &mut resp {
+        resp.details = err.0;
+    }
+    resp
+}
+
+/// As `request_error_to_resp`, keeping the `retry_after` of the error
+pub fn custom_error_to_resp(ctx: &RequestContext, err: CustomError) -> WsResponse {
+    let log_id = ctx.log_id;
+
+    warn!(?log_id, "Request error: {:?}", err);
+    error_to_resp(ctx, err.code, err.retry_after)
 }
@@ -90,17 +90,26 @@ impl WsClient {
                             LogLevel::Off => {}
                         },
                         WsResponseGeneric::Error(err) => bail!(
-                            "Error: {} {:?} {:?}",
+                            "Error: {} {:?} {:?} log_id={}{}{}",
                             err.code,
                             ErrorCode::new(err.code)
                                 .canonical_reason()
                                 .unwrap_or("UNKNOWN"),
-                            err.reason
+                            err.reason,
+                            err.log_id,
+                            err.details
+                                .iter()
+                                .map(|x| format!(" {}", x))
+                                .collect::<String>(),
+                            err.retry_after
+                                .map(|x| format!(" retry_after={}", x))
+                                .unwrap_or_default()
                         ),
                     }
                 }
                 Message::Close(_) => {
-                    self.stream.close(None).await?;
+                    // the close reply is already queued when the server closed first
+                    self.stream.close(None).await.ok();
                     bail!("Connection closed")
                 }
                 _ => {}
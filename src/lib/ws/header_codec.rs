@@ -0,0 +1,179 @@
+//! The request sent to auth endpoints in the `Sec-WebSocket-Protocol` header of the handshake
+//!
+//! ```text
+//! header = field *( "," *SP field )
+//! field  = index value
+//! index  = DIGIT            ; 0 for the method, 1..9 for the parameters of the endpoint in order
+//! value  = *( unreserved / pct-encoded )
+//! ```
+//!
+//! The method is the endpoint name in lower case, e.g. `0login, 1pepe, 2secret`, which limits auth
+//! endpoints to 9 parameters. A value is the parameter percent-encoded: strings as they are,
+//! numbers and booleans as in JSON, dates, times and durations as ISO-8601 text or, for fields with
+//! `TimeFormat::EpochMillis`, integer milliseconds, enums by variant name or value, and bytea,
+//! vectors and objects as JSON text. Fields may come in any order, empty ones are skipped.
+//!
+//! A missing optional parameter is null. An empty one is the empty string for strings, and missing
+//! as well for the types where empty is not a valid value.
+//!
+//! `decode_header` of what `encode_header` produces gives back exactly the parameters as the
+//! generated request types serialize them, with the null ones left out
+use convert_case::{Case, Casing};
+use eyre::*;
+use model::endpoint::EndpointSchema;
+use model::types::{TimeFormat, Type};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// The highest parameter index a single digit can hold
+pub const MAX_HEADER_PARAMETERS: usize = 9;
+
+/// A header split into fields, values still encoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawHeader<'a> {
+    pub method: &'a str,
+    fields: [Option<&'a str>; MAX_HEADER_PARAMETERS],
+}
+impl<'a> RawHeader<'a> {
+    pub fn parse(header: &'a str) -> Result<Self> {
+        let mut method = None;
+        let mut fields = [None; MAX_HEADER_PARAMETERS];
+        for field in header.split(',').map(|x| x.trim_matches(' ')) {
+            let mut chars = field.chars();
+            let index = match chars.next() {
+                None => continue,
+                Some(x) => x.to_digit(10).with_context(|| {
+                    format!("Header field does not start with a digit: {}", field)
+                })? as usize,
+            };
+            let value = chars.as_str();
+            let slot = match index {
+                0 => &mut method,
+                _ => &mut fields[index - 1],
+            };
+            ensure!(slot.is_none(), "Header field {} is repeated", index);
+            *slot = Some(value);
+        }
+        Ok(Self {
+            method: method.context("Could not find method")?,
+            fields,
+        })
+    }
+    /// The parameters of `schema` as a request object with camelCase keys, which should be the
+    /// endpoint of `method` with its enums resolved
+    pub fn decode_params(&self, schema: &EndpointSchema) -> Result<serde_json::Map<String, Value>> {
+        if let Some(index) =
+            (schema.parameters.len()..MAX_HEADER_PARAMETERS).find(|x| self.fields[*x].is_some())
+        {
+            bail!("Unexpected param {} for method {}", index + 1, self.method);
+        }
+        let mut params = serde_json::Map::new();
+        for (index, param) in schema.parameters.iter().enumerate() {
+            match (self.fields.get(index).copied().flatten(), &param.ty) {
+                (Some(""), Type::Optional(ty)) if !matches!(**ty, Type::String) => {}
+                (Some(value), ty) => {
+                    let value = urlencoding::decode(value)
+                        .with_context(|| format!("Failed to decode param {}", param.name))?;
+                    params.insert(
+                        param.name.to_case(Case::Camel),
+                        decode_header_value(ty, param.time_format, &value)?,
+                    );
+                }
+                (None, Type::Optional(_)) => {}
+                (None, _) => {
+                    bail!("Could not find param {} {}", param.name, index + 1);
+                }
+            }
+        }
+        Ok(params)
+    }
+}
+
+/// Decodes a header for `schema`, see the module documentation for the format
+pub fn decode_header(
+    header: &str,
+    schema: &EndpointSchema,
+) -> Result<serde_json::Map<String, Value>> {
+    let header = RawHeader::parse(header)?;
+    let name = schema.name.to_ascii_lowercase();
+    ensure!(
+        header.method == name,
+        "Method {} does not match endpoint {}",
+        header.method,
+        name
+    );
+    header.decode_params(schema)
+}
+
+fn decode_header_value(ty: &Type, time_format: TimeFormat, value: &str) -> Result<Value> {
+    let parse_int = || {
+        value
+            .parse::<i64>()
+            .map(|x| Value::Number(x.into()))
+            .with_context(|| format!("Failed to parse integer: {}", value))
+    };
+    Ok(match ty {
+        Type::Optional(ty) => decode_header_value(ty, time_format, value)?,
+        Type::Int | Type::BigInt | Type::Second | Type::MilliSecond => parse_int()?,
+        Type::Boolean => Value::Bool(
+            value
+                .parse::<bool>()
+                .with_context(|| format!("Failed to parse boolean: {}", value))?,
+        ),
+        Type::String | Type::UUID | Type::Inet | Type::Numeric { .. } => {
+            Value::String(value.to_owned())
+        }
+        Type::Date | Type::Timestamp | Type::TimestampTz | Type::Duration => match time_format {
+            TimeFormat::Iso8601 => Value::String(value.to_owned()),
+            TimeFormat::EpochMillis => parse_int()?,
+        },
+        Type::Enum { name, variants } if variants.is_empty() => {
+            bail!("Enum {} is not resolved, cannot decode {}", name, value)
+        }
+        Type::Enum { name, variants } => {
+            let variant = match value.parse::<i64>() {
+                Ok(x) => variants.iter().find(|v| v.value == x),
+                Err(_) => variants.iter().find(|v| {
+                    v.name.eq_ignore_ascii_case(value) || v.name.to_case(Case::Pascal) == value
+                }),
+            }
+            .with_context(|| format!("Invalid value for enum {}: {}", name, value))?;
+            Value::String(variant.name.to_case(Case::Pascal))
+        }
+        Type::Unit => Value::Null,
+        Type::Bytea | Type::Vec(_) | Type::Object { .. } | Type::DataTable { .. } => {
+            serde_json::from_str(value)
+                .with_context(|| format!("Failed to parse json: {}", value))?
+        }
+    })
+}
+
+/// Encodes the parameters of `schema` taken from `v`, which should serialize to an object with
+/// camelCase keys like the generated request types
+pub fn encode_header<T: Serialize>(v: T, schema: EndpointSchema) -> Result<String> {
+    ensure!(
+        schema.parameters.len() <= MAX_HEADER_PARAMETERS,
+        "Endpoint {} has more than {} params, too many for a header",
+        schema.name,
+        MAX_HEADER_PARAMETERS
+    );
+    let mut s = String::new();
+    write!(s, "0{}", schema.name.to_ascii_lowercase())?;
+    let v = serde_json::to_value(&v)?;
+
+    for (i, f) in schema.parameters.iter().enumerate() {
+        let key = f.name.to_case(Case::Camel);
+        let value = match (v.get(&key), &f.ty) {
+            (None | Some(Value::Null), Type::Optional(_)) => continue,
+            (None | Some(Value::Null), _) => bail!("key: {} is missing", key),
+            (Some(value), _) => value,
+        };
+        let value = match value {
+            Value::String(value) => urlencoding::encode(value),
+            value => urlencoding::encode(&value.to_string()).into_owned().into(),
+        };
+        write!(s, ", {}{}", i + 1, value)?;
+    }
+    Ok(s)
+}
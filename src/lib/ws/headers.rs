@@ -2,23 +2,25 @@ use eyre::*;
 
 use crate::handler::RequestHandlerErased;
 use crate::toolbox::{RequestContext, Toolbox};
-use crate::ws::{Connection, WsEndpoint};
-use convert_case::Case;
-use convert_case::Casing;
+use crate::ws::{Connection, RawHeader, WsEndpoint};
 use dashmap::DashMap;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use model::endpoint::*;
 use model::types::Type;
-use std::collections::HashMap;
 use std::sync::Arc;
 use tokio_tungstenite::tungstenite::handshake::server::{
     Callback, ErrorResponse, Request, Response,
 };
 use tracing::*;
 
+/// The parts of the websocket handshake request the server uses
+pub struct HandshakeHeaders {
+    pub protocol: String,
+    pub accept_language: Option<String>,
+}
 pub struct VerifyProtocol {
-    pub tx: tokio::sync::mpsc::Sender<String>,
+    pub tx: tokio::sync::mpsc::Sender<HandshakeHeaders>,
 }
 
 impl Callback for VerifyProtocol {
@@ -28,18 +30,26 @@ impl Callback for VerifyProtocol {
             .headers()
             .get("Sec-WebSocket-Protocol")
             .or_else(|| request.headers().get("sec-websocket-protocol"));
+        let accept_language = request
+            .headers()
+            .get("Accept-Language")
+            .and_then(|x| x.to_str().ok())
+            .map(|x| x.to_owned());
 
         self.tx
-            .try_send(match protocol {
-                Some(protocol) => protocol
-                    .to_str()
-                    .map_err(|_| {
-                        ErrorResponse::new(Some(
-                            "Sec-WebSocket-Protocol is not valid utf-8".to_owned(),
-                        ))
-                    })?
-                    .to_string(),
-                None => "".to_string(),
+            .try_send(HandshakeHeaders {
+                protocol: match protocol {
+                    Some(protocol) => protocol
+                        .to_str()
+                        .map_err(|_| {
+                            ErrorResponse::new(Some(
+                                "Sec-WebSocket-Protocol is not valid utf-8".to_owned(),
+                            ))
+                        })?
+                        .to_string(),
+                    None => "".to_string(),
+                },
+                accept_language,
             })
             .unwrap();
         Ok(response)
@@ -58,19 +68,31 @@ impl AuthController for SimpleAuthContoller {
 pub struct EndpointAuthController {
     pub auth_endpoints: Arc<DashMap<String, WsEndpoint>>,
     pub toolbox: Toolbox,
+    pub enums: Vec<Type>,
 }
 impl EndpointAuthController {
     pub fn new(toolbox: Toolbox) -> Self {
         Self {
             auth_endpoints: Default::default(),
             toolbox,
+            enums: vec![],
+        }
+    }
+    /// Enum definitions used to decode enum parameters by name or value, for the endpoints added
+    /// before as well as after
+    pub fn with_enums(mut self, enums: Vec<Type>) -> Self {
+        for mut endpoint in self.auth_endpoints.iter_mut() {
+            endpoint.schema.resolve_enums(&enums);
         }
+        self.enums = enums;
+        self
     }
     pub fn add_auth_endpoint(
         &self,
-        schema: EndpointSchema,
+        mut schema: EndpointSchema,
         handler: impl RequestHandlerErased + 'static,
     ) {
+        schema.resolve_enums(&self.enums);
         self.auth_endpoints.insert(
             schema.name.to_ascii_lowercase(),
             WsEndpoint {
@@ -87,60 +109,11 @@ impl AuthController for EndpointAuthController {
 
         let endpoints = self.auth_endpoints.clone();
         async move {
-            let splits = header
-                .split(",")
-                .map(|x| x.trim())
-                .filter(|x| !x.is_empty())
-                .map(|x| (&x[..1], &x[1..]))
-                .collect::<HashMap<&str, &str>>();
-
-            let method = splits.get("0").context("Could not find method")?;
+            let header = RawHeader::parse(&header)?;
             let endpoint = endpoints
-                .get(*method)
-                .with_context(|| format!("Could not find endpoint for method {}", method))?;
-            let mut params = serde_json::Map::new();
-            for (index, param) in endpoint.schema.parameters.iter().enumerate() {
-                let index = index + 1;
-                match splits.get(&index.to_string().as_str()) {
-                    Some(value) => {
-                        params.insert(
-                            param.name.to_case(Case::Camel),
-                            match &param.ty {
-                                Type::String => {
-                                    let decoded = urlencoding::decode(value)?;
-                                    serde_json::Value::String(decoded.to_string())
-                                }
-                                Type::Int => serde_json::Value::Number(
-                                    value
-                                        .parse::<i64>()
-                                        .with_context(|| {
-                                            format!("Failed to parse integer: {}", value)
-                                        })?
-                                        .into(),
-                                ),
-                                Type::Boolean => serde_json::Value::Bool(
-                                    value
-                                        .parse::<bool>()
-                                        .with_context(|| {
-                                            format!("Failed to parse boolean: {}", value)
-                                        })?
-                                        .into(),
-                                ),
-                                Type::Enum { name, .. } if name == "service" => match *value {
-                                    "2" => serde_json::Value::String("User".to_string()),
-                                    "3" => serde_json::Value::String("Admin".to_string()),
-                                    x => serde_json::Value::String(x.to_string()),
-                                },
-                                Type::Enum { .. } => serde_json::Value::String(value.to_string()),
-                                _ => todo!("Implement other types"),
-                            },
-                        );
-                    }
-                    None => {
-                        bail!("Could not find param {} {}", param.name, index);
-                    }
-                }
-            }
+                .get(header.method)
+                .with_context(|| format!("Could not find endpoint for method {}", header.method))?;
+            let params = header.decode_params(&endpoint.schema)?;
 
             let tasks = toolbox.collect_tasks(|toolbox| {
                 endpoint.handler.handle(
@@ -151,8 +124,10 @@ impl AuthController for EndpointAuthController {
                         seq: 0,
                         method: endpoint.schema.code,
                         log_id: conn.log_id,
+                        locale: conn.get_locale(),
                     },
                     conn,
+                    &endpoint.schema,
                     serde_json::Value::Object(params),
                 )
             });
@@ -1,9 +1,11 @@
 mod basics;
 mod client;
+mod header_codec;
 mod headers;
 mod server;
 
 pub use basics::*;
 pub use client::*;
+pub use header_codec::*;
 pub use headers::*;
 pub use server::*;
@@ -1,13 +1,14 @@
 use crate::database::SimpleDbClient;
 use dashmap::DashMap;
 use eyre::*;
+use futures::future::BoxFuture;
 use futures::stream::{SplitSink, SplitStream};
 use futures::SinkExt;
 use futures::StreamExt;
 use std::collections::HashMap;
+use std::future::Future;
 use std::net::SocketAddr;
-use std::sync::atomic::AtomicU32;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::mpsc;
 use tokio_rustls::TlsAcceptor;
@@ -20,6 +21,7 @@ use tracing::*;
 
 use crate::config::AppConfig;
 use crate::handler::*;
+use crate::locale::Locale;
 use crate::toolbox::{RequestContext, Toolbox};
 use crate::utils::{get_conn_id, get_log_id};
 use crate::ws::basics::{Connection, WsRequest};
@@ -44,6 +46,8 @@ pub struct WebsocketServer {
     pub message_receiver: Option<mpsc::Receiver<WsMessage>>,
     pub toolbox: Toolbox,
     pub config: AppConfig,
+    // behind a mutex only to keep the server Sync, futures are not
+    background_tasks: Mutex<Vec<BoxFuture<'static, Result<()>>>>,
 }
 #[derive(Default)]
 pub struct WebsocketStates<S> {
@@ -66,6 +70,7 @@ impl Default for WebsocketServer {
             message_receiver: Some(msg_rx),
             toolbox: Toolbox::new(msg_tx),
             config: Default::default(),
+            background_tasks: Mutex::new(vec![]),
         }
     }
 }
@@ -73,6 +78,12 @@ fn wrap_ws_error<T>(err: Result<T, WsError>) -> Result<T> {
     err.map_err(|x| eyre!(x))
 }
 
+/// Runs until one of the tasks fails, or forever
+async fn run_background_tasks(tasks: Vec<BoxFuture<'static, Result<()>>>) -> Result<()> {
+    futures::future::try_join_all(tasks).await?;
+    std::future::pending().await
+}
+
 fn check_name(cat: &str, be_name: &str, should_name: &str) -> Result<()> {
     if !be_name.contains(&should_name) {
         bail!("{} name should be {} but got {}", cat, should_name, be_name);
@@ -96,6 +107,13 @@ impl WebsocketServer {
     pub fn get_toolbox(&self) -> Toolbox {
         self.toolbox.clone()
     }
+    /// Runs `task` along with the server once it listens. The server stops if the task fails
+    pub fn add_background_task(&mut self, task: impl Future<Output = Result<()>> + Send + 'static) {
+        self.background_tasks
+            .get_mut()
+            .unwrap()
+            .push(Box::pin(task));
+    }
     pub fn add_handler<T: RequestHandler + 'static>(&mut self, schema: EndpointSchema, handler: T) {
         let handler_name = std::any::type_name::<T>();
         let should_handler_name = format!("{}Handler", schema.name);
@@ -133,19 +151,36 @@ impl WebsocketServer {
         let result: Result<()> = async move {
             let (tx, mut rx) = mpsc::channel(1);
             let hs = tokio_tungstenite::accept_hdr_async(stream, VerifyProtocol { tx }).await;
-            let mut stream = wrap_ws_error(hs)?;
-            let conn = Arc::new(Connection {
-                connection_id: get_conn_id(),
-                user_id: Default::default(),
-                role: AtomicU32::new(0),
-                address: addr.ip(),
-                log_id: get_log_id(),
-            });
+            let stream = wrap_ws_error(hs)?;
             let headers = rx
                 .recv()
                 .await
                 .ok_or_else(|| eyre!("Failed to receive ws headers"))?;
-            let auth_result = self.auth_controller.auth(headers, Arc::clone(&conn)).await;
+            let locale = headers
+                .accept_language
+                .as_deref()
+                .and_then(Locale::from_accept_language)
+                .unwrap_or_default();
+            let conn = Arc::new(Connection::new(
+                get_conn_id(),
+                addr.ip(),
+                get_log_id(),
+                locale,
+            ));
+            // registered before auth, so that the response of an auth endpoint finds it
+            let (ws_sink, ws_stream) = stream.split();
+            states.connection.insert(
+                conn.connection_id,
+                WsStream {
+                    ws_sink,
+                    conn: Arc::clone(&conn),
+                },
+            );
+            self.toolbox.add_connection(Arc::clone(&conn));
+            let auth_result = self
+                .auth_controller
+                .auth(headers.protocol, Arc::clone(&conn))
+                .await;
             if let Err(err) = auth_result {
                 let resp = request_error_to_resp(
                     &RequestContext {
@@ -154,23 +189,20 @@ impl WebsocketServer {
                         seq: 0,
                         method: 0,
                         log_id: conn.log_id,
+                        locale: conn.get_locale(),
                     },
                     StatusCode::BAD_REQUEST.into(),
                     err,
                 );
-                let _ = stream
-                    .send(Message::Text(serde_json::to_string(&resp)?))
-                    .await;
+                self.toolbox.remove_connection(conn.connection_id);
+                if let Some((_, mut stream)) = states.connection.remove(&conn.connection_id) {
+                    let _ = stream
+                        .ws_sink
+                        .send(Message::Text(serde_json::to_string(&resp)?))
+                        .await;
+                }
                 return Ok(());
             }
-            let (ws_sink, ws_stream) = stream.split();
-
-            let stream = WsStream {
-                ws_sink: ws_sink,
-                conn,
-            };
-            let conn = Arc::clone(&stream.conn);
-            states.connection.insert(conn.connection_id, stream);
             tokio::spawn(Arc::clone(&self).recv_msg(conn, states, ws_stream));
             Ok(())
         }
@@ -193,8 +225,22 @@ impl WebsocketServer {
             seq: 0,
             method: 0,
             log_id: conn.log_id,
+            locale: conn.get_locale(),
         };
-        while let Some(msg) = reader.next().await {
+        loop {
+            let msg = tokio::select! {
+                msg = reader.next() => msg,
+                _ = conn.closed() => {
+                    info!(?addr, "Closed by the server");
+                    if let Some((_, mut stream)) = states.connection.remove(&conn.connection_id) {
+                        let _ = stream.ws_sink.send(Message::Close(None)).await;
+                    }
+                    break;
+                }
+            };
+            let Some(msg) = msg else {
+                break;
+            };
             match msg {
                 Ok(req) => {
                     let obj: Result<WsRequest, _> = match req {
@@ -256,9 +302,13 @@ impl WebsocketServer {
                             continue;
                         }
                     };
-                    handler
-                        .handler
-                        .handle(&self.toolbox, context, Arc::clone(&conn), req.params);
+                    handler.handler.handle(
+                        &self.toolbox,
+                        context,
+                        Arc::clone(&conn),
+                        &handler.schema,
+                        req.params,
+                    );
                 }
                 Err(WsError::Protocol(ProtocolError::ResetWithoutClosingHandshake)) => {
                     info!(?addr, "Receive side terminated");
@@ -271,6 +321,7 @@ impl WebsocketServer {
             }
         }
         states.connection.remove(&context.connection_id);
+        self.toolbox.remove_connection(context.connection_id);
         info!(?addr, "Connection closed");
     }
     pub async fn send_msg<S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(
@@ -305,20 +356,36 @@ impl WebsocketServer {
             bail!("pub_cert and priv_cert should be both set or unset")
         }
     }
-    async fn listen_tcp(mut self) -> Result<()> {
+    async fn listen_tcp(self) -> Result<()> {
         let addr = format!("{}:{}", self.config.host, self.config.port);
-        info!("{} listening on {}(tcp)", self.config.name, addr);
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        self.listen_on(listener).await
+    }
+    /// Serves plain websocket connections from a listener bound by the caller, e.g. to an
+    /// ephemeral port in tests
+    pub async fn listen_on(mut self, listener: tokio::net::TcpListener) -> Result<()> {
+        info!(
+            "{} listening on {}(tcp)",
+            self.config.name,
+            listener.local_addr()?
+        );
 
         let message_receiver = self.message_receiver.take().unwrap();
+        let background_tasks = std::mem::take(self.background_tasks.get_mut().unwrap());
         let this = Arc::new(self);
         let states = Arc::new(WebsocketStates::new());
         tokio::spawn(Arc::clone(&this).send_msg(Arc::clone(&states), message_receiver));
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        loop {
-            let (stream, addr) = listener.accept().await?;
+        let accept = async {
+            loop {
+                let (stream, addr) = listener.accept().await?;
 
-            info!("Accepted stream from {}", addr);
-            tokio::spawn(Arc::clone(&this).handle_request(addr, Arc::clone(&states), stream));
+                info!("Accepted stream from {}", addr);
+                tokio::spawn(Arc::clone(&this).handle_request(addr, Arc::clone(&states), stream));
+            }
+        };
+        tokio::select! {
+            result = accept => result,
+            result = run_background_tasks(background_tasks) => result,
         }
     }
     async fn listen_tls(mut self) -> Result<()> {
@@ -340,17 +407,24 @@ impl WebsocketServer {
             Arc::new(cfg)
         };
         let message_receiver = self.message_receiver.take().unwrap();
+        let background_tasks = std::mem::take(self.background_tasks.get_mut().unwrap());
         let this = Arc::new(self);
         let states = Arc::new(WebsocketStates::new());
         tokio::spawn(Arc::clone(&this).send_msg(Arc::clone(&states), message_receiver));
         let listener = tokio::net::TcpListener::bind(addr).await?;
         let acceptor = TlsAcceptor::from(tls_cfg);
-        loop {
-            let (stream, addr) = listener.accept().await?;
-            let stream = acceptor.accept(stream).await?;
+        let accept = async {
+            loop {
+                let (stream, addr) = listener.accept().await?;
+                let stream = acceptor.accept(stream).await?;
 
-            info!("Accepted stream from {}", addr);
-            tokio::spawn(Arc::clone(&this).handle_request(addr, Arc::clone(&states), stream));
+                info!("Accepted stream from {}", addr);
+                tokio::spawn(Arc::clone(&this).handle_request(addr, Arc::clone(&states), stream));
+            }
+        };
+        tokio::select! {
+            result = accept => result,
+            result = run_background_tasks(background_tasks) => result,
         }
     }
 }
@@ -0,0 +1,279 @@
+use crate::endpoint::EndpointSchema;
+use crate::types::*;
+use convert_case::{Case, Casing};
+
+/// The standard operations generated for a `Table`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrudOperation {
+    Get,
+    List,
+    Insert,
+    Update,
+    Delete,
+}
+impl CrudOperation {
+    pub const ALL: [Self; 5] = [
+        Self::Get,
+        Self::List,
+        Self::Insert,
+        Self::Update,
+        Self::Delete,
+    ];
+    pub fn function_name(self, table: &Table) -> String {
+        let op = match self {
+            Self::Get => "get",
+            Self::List => "list",
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        };
+        format!("fun_{}_{}", table.name, op)
+    }
+    pub fn endpoint_name(self, table: &Table) -> String {
+        let name = table.name.to_case(Case::Pascal);
+        match self {
+            Self::Get => format!("Get{}", name),
+            Self::List => format!("List{}s", name),
+            Self::Insert => format!("Create{}", name),
+            Self::Update => format!("Update{}", name),
+            Self::Delete => format!("Delete{}", name),
+        }
+    }
+    pub fn parameters(self, table: &Table) -> Vec<Field> {
+        let key = table.get_primary_key().to_field();
+        match self {
+            Self::Get | Self::Delete => vec![key],
+            Self::List => {
+                let mut params = vec![
+                    Field::new("offset", Type::Int),
+                    Field::new("limit", Type::Int),
+                ];
+                params.extend(
+                    table
+                        .columns
+                        .iter()
+                        .filter(|x| x.filter)
+                        .map(|x| optional_field(x.to_field())),
+                );
+                params
+            }
+            Self::Insert => {
+                // required parameters first, postgres only allows defaults on trailing ones
+                let (optional, required): (Vec<_>, Vec<_>) =
+                    get_inserted_columns(table).partition(|x| matches!(x.ty, Type::Optional(_)));
+                required
+                    .into_iter()
+                    .map(|x| x.to_field())
+                    .chain(optional.into_iter().map(|x| optional_field(x.to_field())))
+                    .collect()
+            }
+            Self::Update => {
+                let mut params = vec![key];
+                params.extend(get_updated_columns(table).map(|x| optional_field(x.to_field())));
+                params
+            }
+        }
+    }
+    pub fn cardinality(self) -> Cardinality {
+        match self {
+            Self::List => Cardinality::Many,
+            _ => Cardinality::One,
+        }
+    }
+    pub fn returns(self, table: &Table) -> Vec<Field> {
+        match self {
+            Self::Delete => vec![table.get_primary_key().to_field()],
+            _ => table.columns.iter().map(|x| x.to_field()).collect(),
+        }
+    }
+    pub fn body(self, table: &Table) -> String {
+        let key = quote(&table.primary_key);
+        let columns = table
+            .columns
+            .iter()
+            .map(|x| format!("t.{}", quote(&x.name)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let from = format!("tbl.{} AS t", quote(&table.name));
+        let not_found = "
+    IF NOT FOUND THEN
+        RAISE SQLSTATE 'R001H'; -- NotFound
+    END IF;";
+        match self {
+            Self::Get => format!(
+                "
+BEGIN
+    RETURN QUERY SELECT {columns}
+                 FROM {from}
+                 WHERE t.{key} = ${pkey};{not_found}
+END
+",
+                pkey = table.primary_key,
+            ),
+            Self::List => {
+                let filters = table
+                    .columns
+                    .iter()
+                    .filter(|x| x.filter)
+                    .map(|x| format!("(${} ISNULL OR t.{} = ${})", x.name, quote(&x.name), x.name))
+                    .collect::<Vec<_>>();
+                let filters = if filters.is_empty() {
+                    "TRUE".to_owned()
+                } else {
+                    filters.join("\n                   AND ")
+                };
+                format!(
+                    "
+BEGIN
+    RETURN QUERY SELECT {columns}
+                 FROM {from}
+                 WHERE {filters}
+                 ORDER BY t.{key}
+                 OFFSET $offset LIMIT $limit;
+END
+"
+                )
+            }
+            Self::Insert => {
+                let names = get_inserted_columns(table)
+                    .map(|x| quote(&x.name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let values = get_inserted_columns(table)
+                    .map(|x| format!("${}", x.name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "
+BEGIN
+    RETURN QUERY INSERT INTO {from} ({names})
+                 VALUES ({values})
+                 RETURNING {columns};
+END
+"
+                )
+            }
+            Self::Update => {
+                let sets = table
+                    .columns
+                    .iter()
+                    .filter(|x| x.name != table.primary_key)
+                    .map(|x| match &x.on_update {
+                        Some(expr) => format!("{} = {}", quote(&x.name), expr),
+                        None => format!(
+                            "{} = COALESCE(${}, t.{})",
+                            quote(&x.name),
+                            x.name,
+                            quote(&x.name)
+                        ),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",\n                     ");
+                format!(
+                    "
+BEGIN
+    RETURN QUERY UPDATE {from}
+                 SET {sets}
+                 WHERE t.{key} = ${pkey}
+                 RETURNING {columns};{not_found}
+END
+",
+                    pkey = table.primary_key,
+                )
+            }
+            Self::Delete => format!(
+                "
+BEGIN
+    RETURN QUERY DELETE
+                 FROM {from}
+                 WHERE t.{key} = ${pkey}
+                 RETURNING t.{key};{not_found}
+END
+",
+                pkey = table.primary_key,
+            ),
+        }
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name)
+}
+
+fn optional_field(mut field: Field) -> Field {
+    if !matches!(field.ty, Type::Optional(_)) {
+        field.ty = Type::optional(field.ty);
+    }
+    field
+}
+
+/// Columns an insert sets, the ones with a sequence or default are left to the database
+fn get_inserted_columns(table: &Table) -> impl Iterator<Item = &Column> {
+    table.columns.iter().filter(|x| x.get_default().is_none())
+}
+
+/// Columns an update can set, everything but the key and the ones set by `on_update`
+fn get_updated_columns(table: &Table) -> impl Iterator<Item = &Column> {
+    table
+        .columns
+        .iter()
+        .filter(move |x| x.name != table.primary_key && x.on_update.is_none())
+}
+
+/// Name of the row type returned by the list endpoint
+pub fn get_row_type_name(table: &Table) -> String {
+    format!("{}Row", table.name.to_case(Case::Pascal))
+}
+
+/// `fun_<table>_{get,list,insert,update,delete}`. Columns are updated only where a value is
+/// given, so update cannot set a column to NULL
+pub fn get_crud_functions(table: &Table) -> Vec<ProceduralFunction> {
+    CrudOperation::ALL
+        .iter()
+        .map(|op| {
+            ProceduralFunction::new(
+                op.function_name(table),
+                op.parameters(table),
+                op.returns(table),
+                op.body(table),
+            )
+            .cardinality(op.cardinality())
+        })
+        .collect()
+}
+
+/// Endpoints calling the functions of `get_crud_functions`, numbered from the table's
+/// `endpoint_code`. Empty if the table has none
+pub fn get_crud_endpoints(table: &Table) -> Vec<EndpointSchema> {
+    let Some(code) = table.endpoint_code else {
+        return vec![];
+    };
+    CrudOperation::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, op)| {
+            let parameters = op
+                .parameters(table)
+                .into_iter()
+                .map(|x| match x.name.as_str() {
+                    "offset" if *op == CrudOperation::List => x.minimum(0.0),
+                    "limit" if *op == CrudOperation::List => x.range(1.0, 1000.0),
+                    _ => x,
+                })
+                .collect();
+            let returns = match op {
+                CrudOperation::List => vec![Field::new(
+                    "rows",
+                    Type::data_table(get_row_type_name(table), op.returns(table)),
+                )],
+                _ => op.returns(table),
+            };
+            EndpointSchema::new(
+                op.endpoint_name(table),
+                code + i as u32,
+                parameters,
+                returns,
+            )
+        })
+        .collect()
+}
@@ -1,3 +1,4 @@
+use crate::json_schema::{resolve_enums, ToJsonSchema};
 use crate::types::*;
 use serde::*;
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,12 +17,32 @@ impl EndpointSchema {
         parameters: Vec<Field>,
         returns: Vec<Field>,
     ) -> Self {
+        let json_schema = get_json_schema(&parameters, &returns);
         Self {
             name: name.into(),
             code,
             parameters,
             returns,
-            json_schema: Default::default(),
+            json_schema,
         }
     }
+    /// Expands enum references in the parameters and returns to their variants, and rebuilds
+    /// `json_schema` accordingly
+    pub fn resolve_enums(&mut self, enums: &[Type]) {
+        let resolve =
+            |fields: &Vec<Field>| match resolve_enums(&Type::object("", fields.clone()), enums) {
+                Type::Object { fields, .. } => fields,
+                _ => unreachable!(),
+            };
+        self.parameters = resolve(&self.parameters);
+        self.returns = resolve(&self.returns);
+        self.json_schema = get_json_schema(&self.parameters, &self.returns);
+    }
+}
+
+fn get_json_schema(parameters: &Vec<Field>, returns: &Vec<Field>) -> serde_json::Value {
+    serde_json::json!({
+        "parameters": parameters.to_json_schema(),
+        "returns": returns.to_json_schema(),
+    })
 }
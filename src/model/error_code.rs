@@ -0,0 +1,481 @@
+use serde::*;
+
+/// Locales user-facing error messages are available in, the first one is the fallback
+pub const LOCALES: &[&str] = &["en", "zh"];
+
+/// User-facing message per locale, as `(locale, message)`
+pub type LocalizedMessages = &'static [(&'static str, &'static str)];
+
+/// An application error code. pg functions raise it as `RAISE SQLSTATE '<sqlstate>'`, clients
+/// receive the SQLSTATE read as a base-36 number
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct ErrorCodeInfo {
+    pub name: &'static str,
+    pub sqlstate: &'static str,
+    pub http_status: u16,
+    /// Meant for developers, see `messages` for what users get to see
+    pub description: &'static str,
+    pub messages: LocalizedMessages,
+}
+impl ErrorCodeInfo {
+    pub const fn new(
+        name: &'static str,
+        sqlstate: &'static str,
+        http_status: u16,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            sqlstate,
+            http_status,
+            description,
+            messages: &[],
+        }
+    }
+    pub const fn messages(self, messages: LocalizedMessages) -> Self {
+        Self { messages, ..self }
+    }
+    pub fn code(&self) -> u32 {
+        u32::from_str_radix(self.sqlstate, 36).expect("SQLSTATE must be base-36")
+    }
+    pub fn message(&self, locale: &str) -> Option<&'static str> {
+        get_message(self.messages, locale)
+    }
+}
+
+fn get_message(messages: LocalizedMessages, locale: &str) -> Option<&'static str> {
+    messages
+        .iter()
+        .find(|(l, _)| *l == locale)
+        .or_else(|| messages.iter().find(|(l, _)| *l == LOCALES[0]))
+        .map(|(_, message)| *message)
+}
+
+pub const ERROR_CODES: &[ErrorCodeInfo] = &[
+    ErrorCodeInfo::new(
+        "InvalidEnumLevel",
+        "22P02",
+        400,
+        "Value is not a valid enum variant",
+    )
+    .messages(&[("en", "The value is not allowed."), ("zh", "该值无效。")]),
+    ErrorCodeInfo::new("Error", "R0000", 500, "Unspecified error")
+        .messages(&[("en", "Something went wrong."), ("zh", "出现错误。")]),
+    ErrorCodeInfo::new("InvalidArgument", "R0001", 400, "An argument is invalid").messages(&[
+        ("en", "Some of the provided information is invalid."),
+        ("zh", "提供的信息有误。"),
+    ]),
+    ErrorCodeInfo::new(
+        "InvalidState",
+        "R0002",
+        409,
+        "Operation not allowed in the current state",
+    )
+    .messages(&[
+        ("en", "This action is not possible right now."),
+        ("zh", "当前无法执行此操作。"),
+    ]),
+    ErrorCodeInfo::new(
+        "InvalidSeq",
+        "R0003",
+        400,
+        "Request sequence number is invalid",
+    )
+    .messages(&[
+        ("en", "The request is out of order."),
+        ("zh", "请求顺序错误。"),
+    ]),
+    ErrorCodeInfo::new("InvalidMethod", "R0004", 400, "Unknown method code").messages(&[
+        ("en", "This action is not supported."),
+        ("zh", "不支持此操作。"),
+    ]),
+    ErrorCodeInfo::new(
+        "ProtocolViolation",
+        "R0005",
+        400,
+        "Message violates the protocol",
+    )
+    .messages(&[("en", "The request is not valid."), ("zh", "请求无效。")]),
+    ErrorCodeInfo::new(
+        "MalformedRequest",
+        "R0006",
+        400,
+        "Request could not be parsed",
+    )
+    .messages(&[
+        ("en", "The request could not be read."),
+        ("zh", "无法解析请求。"),
+    ]),
+    ErrorCodeInfo::new("UnknownUser", "R0007", 404, "User does not exist").messages(&[
+        ("en", "The username or password is incorrect."),
+        ("zh", "用户名或密码错误。"),
+    ]),
+    ErrorCodeInfo::new("BlockedUser", "R0008", 403, "User is blocked").messages(&[
+        ("en", "This account has been blocked."),
+        ("zh", "该账户已被封禁。"),
+    ]),
+    ErrorCodeInfo::new("InvalidPassword", "R0009", 401, "Password is incorrect").messages(&[
+        ("en", "The username or password is incorrect."),
+        ("zh", "用户名或密码错误。"),
+    ]),
+    ErrorCodeInfo::new("InvalidToken", "R000A", 401, "Token is invalid or expired").messages(&[
+        ("en", "Your session has expired, please log in again."),
+        ("zh", "会话已过期，请重新登录。"),
+    ]),
+    ErrorCodeInfo::new("TemporarilyUnavailable", "R000B", 503, "Try again later").messages(&[
+        (
+            "en",
+            "The service is temporarily unavailable, please try again later.",
+        ),
+        ("zh", "服务暂时不可用，请稍后再试。"),
+    ]),
+    ErrorCodeInfo::new("UnexpectedException", "R000C", 500, "Unexpected exception")
+        .messages(&[("en", "Something went wrong."), ("zh", "出现错误。")]),
+    ErrorCodeInfo::new(
+        "BackPressureIncreased",
+        "R000D",
+        503,
+        "Server is overloaded",
+    )
+    .messages(&[
+        ("en", "The service is busy, please try again later."),
+        ("zh", "服务繁忙，请稍后再试。"),
+    ]),
+    ErrorCodeInfo::new("InvalidPublicId", "R000E", 400, "Public id is invalid")
+        .messages(&[("en", "The identifier is invalid."), ("zh", "标识符无效。")]),
+    ErrorCodeInfo::new("InvalidRange", "R000F", 400, "Value is out of range").messages(&[
+        ("en", "The value is out of range."),
+        ("zh", "数值超出范围。"),
+    ]),
+    ErrorCodeInfo::new(
+        "BankAccountAlreadyExists",
+        "R000G",
+        409,
+        "Bank account already exists",
+    )
+    .messages(&[
+        ("en", "This bank account has already been added."),
+        ("zh", "该银行账户已存在。"),
+    ]),
+    ErrorCodeInfo::new("InsufficientFunds", "R000H", 400, "Balance is too low")
+        .messages(&[("en", "Your balance is too low."), ("zh", "余额不足。")]),
+    ErrorCodeInfo::new("LogicalError", "R000M", 500, "Internal logic error")
+        .messages(&[("en", "Something went wrong."), ("zh", "出现错误。")]),
+    ErrorCodeInfo::new(
+        "RestrictedUserPrivileges",
+        "R000N",
+        403,
+        "User lacks the privileges",
+    )
+    .messages(&[
+        ("en", "You are not allowed to do this."),
+        ("zh", "您没有执行此操作的权限。"),
+    ]),
+    ErrorCodeInfo::new(
+        "IdenticalReplacement",
+        "R000O",
+        400,
+        "New value equals the old one",
+    )
+    .messages(&[
+        ("en", "The new value must differ from the current one."),
+        ("zh", "新值不能与当前值相同。"),
+    ]),
+    ErrorCodeInfo::new(
+        "InvalidRecoveryQuestions",
+        "R000R",
+        400,
+        "Recovery questions are invalid",
+    )
+    .messages(&[
+        ("en", "The recovery questions are invalid."),
+        ("zh", "安全问题无效。"),
+    ]),
+    ErrorCodeInfo::new(
+        "InvalidRole",
+        "R000S",
+        403,
+        "Role not allowed for this service",
+    )
+    .messages(&[
+        ("en", "Your account cannot use this service."),
+        ("zh", "您的账户无法使用此服务。"),
+    ]),
+    ErrorCodeInfo::new(
+        "WrongRecoveryAnswers",
+        "R000T",
+        401,
+        "Recovery answers do not match",
+    )
+    .messages(&[("en", "The answers do not match."), ("zh", "答案不正确。")]),
+    ErrorCodeInfo::new(
+        "MessageNotDelivered",
+        "R000U",
+        502,
+        "Message could not be delivered",
+    )
+    .messages(&[
+        ("en", "The message could not be delivered."),
+        ("zh", "消息发送失败。"),
+    ]),
+    ErrorCodeInfo::new("NoReply", "R000V", 504, "No reply received").messages(&[
+        ("en", "No reply was received, please try again."),
+        ("zh", "未收到回复，请重试。"),
+    ]),
+    ErrorCodeInfo::new(
+        "NullAttribute",
+        "R000W",
+        400,
+        "A required attribute is null",
+    )
+    .messages(&[
+        ("en", "A required field is missing."),
+        ("zh", "缺少必填项。"),
+    ]),
+    ErrorCodeInfo::new(
+        "ConsentMissing",
+        "R000X",
+        400,
+        "Terms of service or privacy policy not agreed",
+    )
+    .messages(&[
+        (
+            "en",
+            "You must agree to the terms of service and the privacy policy.",
+        ),
+        ("zh", "您必须同意服务条款和隐私政策。"),
+    ]),
+    ErrorCodeInfo::new(
+        "ActiveSubscriptionRequired",
+        "R000Y",
+        402,
+        "Requires an active subscription",
+    )
+    .messages(&[
+        ("en", "This requires an active subscription."),
+        ("zh", "此功能需要有效订阅。"),
+    ]),
+    ErrorCodeInfo::new(
+        "UsernameAlreadyRegistered",
+        "R000Z",
+        409,
+        "Username is taken",
+    )
+    .messages(&[
+        ("en", "This username is already taken."),
+        ("zh", "该用户名已被占用。"),
+    ]),
+    ErrorCodeInfo::new(
+        "RecoveryQuestionsNotSet",
+        "R0010",
+        400,
+        "User has no recovery questions",
+    )
+    .messages(&[
+        (
+            "en",
+            "No recovery questions have been set for this account.",
+        ),
+        ("zh", "该账户未设置安全问题。"),
+    ]),
+    ErrorCodeInfo::new(
+        "MustSubmitAllRecoveryQuestions",
+        "R0011",
+        400,
+        "Every recovery question must be answered",
+    )
+    .messages(&[
+        ("en", "Please answer every recovery question."),
+        ("zh", "请回答所有安全问题。"),
+    ]),
+    ErrorCodeInfo::new(
+        "InvalidRecoveryToken",
+        "R0012",
+        401,
+        "Recovery token is invalid or expired",
+    )
+    .messages(&[
+        ("en", "The recovery link is invalid or has expired."),
+        ("zh", "恢复链接无效或已过期。"),
+    ]),
+    ErrorCodeInfo::new(
+        "TokenExpired",
+        "R0013",
+        401,
+        "Access token expired, the session can be refreshed",
+    )
+    .messages(&[
+        ("en", "Your session needs to be renewed."),
+        ("zh", "您的会话需要续期。"),
+    ]),
+    ErrorCodeInfo::new(
+        "CaptchaRequired",
+        "R0014",
+        429,
+        "Too many failed logins, a CAPTCHA has to be solved",
+    )
+    .messages(&[
+        ("en", "Please complete the CAPTCHA to continue."),
+        ("zh", "请完成人机验证后继续。"),
+    ]),
+    ErrorCodeInfo::new(
+        "InvalidTwoFactorCode",
+        "R0015",
+        401,
+        "Two-factor code or recovery code is wrong or already used",
+    )
+    .messages(&[
+        ("en", "The verification code is incorrect."),
+        ("zh", "验证码不正确。"),
+    ]),
+    ErrorCodeInfo::new(
+        "TwoFactorRequired",
+        "R0016",
+        403,
+        "The service requires two-factor authentication, which the user has not enabled",
+    )
+    .messages(&[
+        (
+            "en",
+            "Please enable two-factor authentication to use this service.",
+        ),
+        ("zh", "请先启用双重验证再使用此服务。"),
+    ]),
+    ErrorCodeInfo::new(
+        "InvalidLoginChallenge",
+        "R0017",
+        401,
+        "Login challenge is invalid, expired or already completed",
+    )
+    .messages(&[
+        ("en", "Your login has expired, please log in again."),
+        ("zh", "登录已过期，请重新登录。"),
+    ]),
+    ErrorCodeInfo::new("RoutingError", "R0018", 502, "Message could not be routed").messages(&[
+        ("en", "The message could not be delivered."),
+        ("zh", "消息发送失败。"),
+    ]),
+    ErrorCodeInfo::new(
+        "UnauthorizedMessage",
+        "R0019",
+        403,
+        "Not allowed to send this message",
+    )
+    .messages(&[
+        ("en", "You are not allowed to send this message."),
+        ("zh", "您无权发送此消息。"),
+    ]),
+    ErrorCodeInfo::new("AuthError", "R001B", 401, "Authentication failed").messages(&[
+        ("en", "Authentication failed, please log in again."),
+        ("zh", "认证失败，请重新登录。"),
+    ]),
+    ErrorCodeInfo::new(
+        "TwoFactorAlreadyEnabled",
+        "R001C",
+        409,
+        "Two-factor authentication is already enabled",
+    )
+    .messages(&[
+        ("en", "Two-factor authentication is already enabled."),
+        ("zh", "双重验证已启用。"),
+    ]),
+    ErrorCodeInfo::new(
+        "TwoFactorNotEnabled",
+        "R001D",
+        409,
+        "Two-factor authentication is not enabled, or has no pending enrollment to confirm",
+    )
+    .messages(&[
+        ("en", "Two-factor authentication is not enabled."),
+        ("zh", "双重验证未启用。"),
+    ]),
+    ErrorCodeInfo::new("InternalError", "R001G", 500, "Internal server error")
+        .messages(&[("en", "Something went wrong."), ("zh", "出现错误。")]),
+    ErrorCodeInfo::new("NotFound", "R001H", 404, "Record does not exist").messages(&[
+        ("en", "The requested record does not exist."),
+        ("zh", "请求的记录不存在。"),
+    ]),
+];
+
+pub fn get_error_code(code: u32) -> Option<&'static ErrorCodeInfo> {
+    ERROR_CODES.iter().find(|x| x.code() == code)
+}
+pub fn get_error_code_by_sqlstate(sqlstate: &str) -> Option<&'static ErrorCodeInfo> {
+    ERROR_CODES.iter().find(|x| x.sqlstate == sqlstate)
+}
+
+/// Every SQLSTATE a pg function raises, from `RAISE SQLSTATE '...'` and `ERRCODE = '...'`
+pub fn get_raised_sqlstates(body: &str) -> Vec<String> {
+    let mut result = vec![];
+    for pattern in ["SQLSTATE '", "ERRCODE = '"] {
+        for (i, _) in body.match_indices(pattern) {
+            let start = i + pattern.len();
+            if let Some(state) = body.get(start..start + 5) {
+                result.push(state.to_owned());
+            }
+        }
+    }
+    result
+}
+
+/// User-facing messages of the HTTP status codes the servers send
+pub const HTTP_ERROR_MESSAGES: &[(u16, LocalizedMessages)] = &[
+    (
+        400,
+        &[("en", "The request is invalid."), ("zh", "请求无效。")],
+    ),
+    (401, &[("en", "Please log in first."), ("zh", "请先登录。")]),
+    (
+        403,
+        &[
+            ("en", "You are not allowed to do this."),
+            ("zh", "您没有执行此操作的权限。"),
+        ],
+    ),
+    (
+        404,
+        &[
+            ("en", "The requested resource does not exist."),
+            ("zh", "请求的资源不存在。"),
+        ],
+    ),
+    (
+        409,
+        &[
+            ("en", "The request conflicts with the current state."),
+            ("zh", "请求与当前状态冲突。"),
+        ],
+    ),
+    (
+        429,
+        &[
+            ("en", "Too many requests, please try again later."),
+            ("zh", "请求过于频繁，请稍后再试。"),
+        ],
+    ),
+    (
+        500,
+        &[("en", "Something went wrong."), ("zh", "出现错误。")],
+    ),
+    (
+        503,
+        &[
+            (
+                "en",
+                "The service is temporarily unavailable, please try again later.",
+            ),
+            ("zh", "服务暂时不可用，请稍后再试。"),
+        ],
+    ),
+];
+
+/// User-facing message of an HTTP status or application error code, in `locale` if available
+pub fn get_error_message(code: u32, locale: &str) -> Option<&'static str> {
+    if let Some(info) = get_error_code(code) {
+        return info.message(locale);
+    }
+    HTTP_ERROR_MESSAGES
+        .iter()
+        .find(|(status, _)| *status as u32 == code)
+        .and_then(|(_, messages)| get_message(messages, locale))
+}
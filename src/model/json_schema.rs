@@ -0,0 +1,168 @@
+use crate::types::*;
+use convert_case::{Case, Casing};
+use serde_json::{json, Map, Value};
+
+pub trait ToJsonSchema {
+    fn to_json_schema(&self) -> Value;
+}
+
+impl ToJsonSchema for Type {
+    fn to_json_schema(&self) -> Value {
+        match self {
+            Type::Second => json!({"type": "integer", "minimum": 0}),
+            Type::MilliSecond => json!({"type": "integer", "minimum": 0}),
+            Type::Date => json!({"type": "string", "format": "date"}),
+            Type::Timestamp => json!({"type": "string", "format": "date-time"}),
+            Type::TimestampTz => json!({"type": "string", "format": "date-time"}),
+            Type::Duration => json!({"type": "string", "format": "duration"}),
+            Type::Int => json!({"type": "integer", "format": "int32"}),
+            Type::BigInt => json!({"type": "integer", "format": "int64"}),
+            Type::Numeric { precision, scale } => {
+                let integer_digits = precision.saturating_sub(*scale).max(1);
+                let pattern = if *scale == 0 {
+                    format!("^-?[0-9]{{1,{}}}$", integer_digits)
+                } else {
+                    format!(
+                        "^-?[0-9]{{1,{}}}(\\.[0-9]{{1,{}}})?$",
+                        integer_digits, scale
+                    )
+                };
+                json!({
+                    "type": "string",
+                    "format": "decimal",
+                    "pattern": pattern,
+                    "x-precision": precision,
+                    "x-scale": scale
+                })
+            }
+            Type::Boolean => json!({"type": "boolean"}),
+            Type::String => json!({"type": "string"}),
+            Type::Bytea => json!({
+                "type": "array",
+                "items": {"type": "integer", "minimum": 0, "maximum": 255}
+            }),
+            Type::UUID => json!({"type": "string", "format": "uuid"}),
+            Type::Inet => json!({"type": "string", "format": "ip"}),
+            Type::Object { name, fields } => {
+                let mut schema = fields.to_json_schema();
+                schema["title"] = Value::String(name.clone());
+                schema
+            }
+            Type::DataTable { name, fields } => json!({
+                "type": "array",
+                "items": Type::object(name.clone(), fields.clone()).to_json_schema()
+            }),
+            Type::Vec(ele) => json!({"type": "array", "items": ele.to_json_schema()}),
+            Type::Unit => json!({"type": "null"}),
+            Type::Optional(t) => json!({"oneOf": [t.to_json_schema(), {"type": "null"}]}),
+            Type::Enum { name, variants } if variants.is_empty() => json!({
+                "title": format!("Enum{}", name.to_case(Case::Pascal)),
+                "type": "string"
+            }),
+            Type::Enum { name, variants } => json!({
+                "title": format!("Enum{}", name.to_case(Case::Pascal)),
+                "type": "string",
+                "enum": variants
+                    .iter()
+                    .map(|x| x.name.to_case(Case::Pascal))
+                    .collect::<Vec<_>>(),
+                "x-enum-values": variants.iter().map(|x| x.value).collect::<Vec<_>>()
+            }),
+        }
+    }
+}
+
+impl ToJsonSchema for Vec<Field> {
+    fn to_json_schema(&self) -> Value {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for field in self {
+            let key = field.name.to_case(Case::Camel);
+            if !matches!(field.ty, Type::Optional(_)) {
+                required.push(Value::String(key.clone()));
+            }
+            let mut schema = match field.time_format {
+                TimeFormat::EpochMillis => epoch_millis_schema(&field.ty),
+                TimeFormat::Iso8601 => field.ty.to_json_schema(),
+            };
+            apply_constraints(&mut schema, &field.constraints);
+            properties.insert(key, schema);
+        }
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required
+        })
+    }
+}
+
+fn epoch_millis_schema(ty: &Type) -> Value {
+    match ty {
+        Type::Optional(t) => json!({"oneOf": [epoch_millis_schema(t), {"type": "null"}]}),
+        Type::Date | Type::Timestamp | Type::TimestampTz | Type::Duration => {
+            json!({"type": "integer", "format": "int64"})
+        }
+        t => t.to_json_schema(),
+    }
+}
+
+fn apply_constraints(schema: &mut Value, constraints: &FieldConstraints) {
+    if constraints.is_empty() {
+        return;
+    }
+    if let Some(options) = schema.get_mut("oneOf").and_then(|x| x.as_array_mut()) {
+        if let Some(inner) = options.iter_mut().find(|x| x["type"] != "null") {
+            apply_constraints(inner, constraints);
+        }
+        return;
+    }
+    let is_array = schema["type"] == "array";
+    let (min_key, max_key) = if is_array {
+        ("minItems", "maxItems")
+    } else {
+        ("minLength", "maxLength")
+    };
+    if let Some(x) = constraints.get_min_length() {
+        schema[min_key] = x.into();
+    }
+    if let Some(x) = constraints.max_length {
+        schema[max_key] = x.into();
+    }
+    if let Some(x) = &constraints.pattern {
+        schema["pattern"] = x.clone().into();
+    }
+    if let Some(x) = constraints.minimum {
+        schema["minimum"] = x.into();
+    }
+    if let Some(x) = constraints.maximum {
+        schema["maximum"] = x.into();
+    }
+    if let Some(x) = constraints.format {
+        schema["format"] = x.as_str().into();
+    }
+}
+
+/// Replaces enum references (`Type::enum_ref`) with the matching declaration from `enums`
+pub fn resolve_enums(ty: &Type, enums: &[Type]) -> Type {
+    let resolve_fields = |fields: &Vec<Field>| {
+        fields
+            .iter()
+            .map(|x| Field {
+                ty: resolve_enums(&x.ty, enums),
+                ..x.clone()
+            })
+            .collect()
+    };
+    match ty {
+        Type::Object { name, fields } => Type::object(name.clone(), resolve_fields(fields)),
+        Type::DataTable { name, fields } => Type::data_table(name.clone(), resolve_fields(fields)),
+        Type::Vec(x) => Type::vec(resolve_enums(x, enums)),
+        Type::Optional(x) => Type::optional(resolve_enums(x, enums)),
+        Type::Enum { name, variants } if variants.is_empty() => enums
+            .iter()
+            .find(|x| matches!(x, Type::Enum { name: n, .. } if n == name))
+            .cloned()
+            .unwrap_or_else(|| ty.clone()),
+        x => x.clone(),
+    }
+}
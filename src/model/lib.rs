@@ -1,3 +1,6 @@
+pub mod crud;
 pub mod endpoint;
+pub mod error_code;
+pub mod json_schema;
 pub mod service;
 pub mod types;
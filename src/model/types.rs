@@ -3,6 +3,10 @@ use serde::*;
 pub struct Field {
     pub name: String,
     pub ty: Type,
+    #[serde(default, skip_serializing_if = "FieldConstraints::is_empty")]
+    pub constraints: FieldConstraints,
+    #[serde(default, skip_serializing_if = "TimeFormat::is_default")]
+    pub time_format: TimeFormat,
 }
 
 impl Field {
@@ -10,6 +14,105 @@ impl Field {
         Self {
             name: name.into(),
             ty,
+            constraints: Default::default(),
+            time_format: Default::default(),
+        }
+    }
+    pub fn min_length(mut self, len: usize) -> Self {
+        self.constraints.min_length = Some(len);
+        self
+    }
+    pub fn max_length(mut self, len: usize) -> Self {
+        self.constraints.max_length = Some(len);
+        self
+    }
+    pub fn pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.constraints.pattern = Some(pattern.into());
+        self
+    }
+    pub fn minimum(mut self, min: f64) -> Self {
+        self.constraints.minimum = Some(min);
+        self
+    }
+    pub fn maximum(mut self, max: f64) -> Self {
+        self.constraints.maximum = Some(max);
+        self
+    }
+    pub fn range(self, min: f64, max: f64) -> Self {
+        self.minimum(min).maximum(max)
+    }
+    pub fn format(mut self, format: StringFormat) -> Self {
+        self.constraints.format = Some(format);
+        self
+    }
+    pub fn non_empty(mut self) -> Self {
+        self.constraints.non_empty = true;
+        self
+    }
+    pub fn time_format(mut self, format: TimeFormat) -> Self {
+        self.time_format = format;
+        self
+    }
+}
+
+/// How a date/time field is represented in JSON
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeFormat {
+    /// RFC 3339 / ISO-8601 string, e.g. `2022-08-01T12:00:00Z` or `P1DT2H`
+    #[default]
+    Iso8601,
+    /// Milliseconds since the unix epoch, or the length in milliseconds for durations
+    EpochMillis,
+}
+impl TimeFormat {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StringFormat {
+    Email,
+    Phone,
+}
+impl StringFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StringFormat::Email => "email",
+            StringFormat::Phone => "phone",
+        }
+    }
+}
+
+/// Optional checks applied to a field on top of its type. Lengths count characters of a
+/// string or items of a vec, `minimum`/`maximum` apply to numbers
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldConstraints {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<StringFormat>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub non_empty: bool,
+}
+impl FieldConstraints {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+    /// Effective minimum length, taking `non_empty` into account
+    pub fn get_min_length(&self) -> Option<usize> {
+        match (self.min_length, self.non_empty) {
+            (Some(x), true) => Some(x.max(1)),
+            (None, true) => Some(1),
+            (x, false) => x,
         }
     }
 }
@@ -32,9 +135,16 @@ pub enum Type {
     Second,
     MilliSecond,
     Date,
+    Timestamp,
+    TimestampTz,
+    Duration,
     Int,
     BigInt,
-    Numeric,
+    /// Exact decimal with at most `precision` digits, `scale` of them after the point
+    Numeric {
+        precision: u32,
+        scale: u32,
+    },
     Boolean,
     String,
     Bytea,
@@ -69,6 +179,9 @@ impl Type {
             fields,
         }
     }
+    pub fn numeric(precision: u32, scale: u32) -> Self {
+        Self::Numeric { precision, scale }
+    }
     pub fn vec(ty: Type) -> Self {
         Self::Vec(Box::new(ty))
     }
@@ -88,26 +201,162 @@ impl Type {
         }
     }
 }
+/// How many rows a `ProceduralFunction` returns, decides which helpers the generated `DbClient`
+/// has besides the method returning every row
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cardinality {
+    /// No rows, no helper
+    None,
+    /// Exactly one row, `fun_x_one`
+    One,
+    /// At most one row, `fun_x_opt`
+    Optional,
+    /// Any number of rows, `fun_x_stream`
+    Many,
+}
+
 #[derive(Clone, Debug)]
 pub struct ProceduralFunction {
     pub name: String,
     pub parameters: Vec<Field>,
     pub returns: Vec<Field>,
     pub body: String,
+    pub cardinality: Cardinality,
 }
 
 impl ProceduralFunction {
+    /// Returns `Cardinality::Many` if it has return fields, `Cardinality::None` otherwise
     pub fn new(
         name: impl Into<String>,
         parameters: Vec<Field>,
         returns: Vec<Field>,
         body: impl Into<String>,
     ) -> Self {
+        let cardinality = if returns.is_empty() {
+            Cardinality::None
+        } else {
+            Cardinality::Many
+        };
         Self {
             name: name.into(),
             parameters,
             returns,
             body: body.into(),
+            cardinality,
+        }
+    }
+    pub fn cardinality(mut self, cardinality: Cardinality) -> Self {
+        self.cardinality = cardinality;
+        self
+    }
+}
+
+/// A column of a `Table`, nullable if its type is `Optional`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Column {
+    pub name: String,
+    pub ty: Type,
+    /// SQL expression used when no value is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Sequence in the `tbl` schema the column takes its default from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<String>,
+    /// SQL expression the column is set to by every update, e.g. `NOW()`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_update: Option<String>,
+    /// `(table, column)` this column is a foreign key to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub references: Option<(String, String)>,
+    /// Whether the generated list function can filter on this column
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub filter: bool,
+    #[serde(default, skip_serializing_if = "TimeFormat::is_default")]
+    pub time_format: TimeFormat,
+}
+impl Column {
+    pub fn new(name: impl Into<String>, ty: Type) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+            default: None,
+            sequence: None,
+            on_update: None,
+            references: None,
+            filter: false,
+            time_format: Default::default(),
         }
     }
+    pub fn default(mut self, expr: impl Into<String>) -> Self {
+        self.default = Some(expr.into());
+        self
+    }
+    pub fn sequence(mut self, name: impl Into<String>) -> Self {
+        self.sequence = Some(name.into());
+        self
+    }
+    pub fn on_update(mut self, expr: impl Into<String>) -> Self {
+        self.on_update = Some(expr.into());
+        self
+    }
+    pub fn references(mut self, table: impl Into<String>, column: impl Into<String>) -> Self {
+        self.references = Some((table.into(), column.into()));
+        self
+    }
+    pub fn filter(mut self) -> Self {
+        self.filter = true;
+        self
+    }
+    pub fn time_format(mut self, format: TimeFormat) -> Self {
+        self.time_format = format;
+        self
+    }
+    /// Default as a SQL expression, from `default` or `sequence`
+    pub fn get_default(&self) -> Option<String> {
+        match (&self.default, &self.sequence) {
+            (Some(expr), _) => Some(expr.clone()),
+            (None, Some(seq)) => Some(format!("nextval('tbl.{}')", seq)),
+            (None, None) => None,
+        }
+    }
+    pub fn to_field(&self) -> Field {
+        Field::new(&self.name, self.ty.clone()).time_format(self.time_format)
+    }
+}
+
+/// A table in the `tbl` schema. Codegen emits its DDL and, see `model::crud`, the standard
+/// pg functions and endpoints for it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub primary_key: String,
+    /// Code of the first generated endpoint, the others follow. `None` for no endpoints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_code: Option<u32>,
+}
+impl Table {
+    /// A table keyed by `pkey_id`
+    pub fn new(name: impl Into<String>, columns: Vec<Column>) -> Self {
+        Self {
+            name: name.into(),
+            columns,
+            primary_key: "pkey_id".to_owned(),
+            endpoint_code: None,
+        }
+    }
+    pub fn primary_key(mut self, column: impl Into<String>) -> Self {
+        self.primary_key = column.into();
+        self
+    }
+    pub fn endpoints(mut self, code: u32) -> Self {
+        self.endpoint_code = Some(code);
+        self
+    }
+    pub fn get_primary_key(&self) -> &Column {
+        self.columns
+            .iter()
+            .find(|x| x.name == self.primary_key)
+            .unwrap_or_else(|| panic!("table {} has no column {}", self.name, self.primary_key))
+    }
 }
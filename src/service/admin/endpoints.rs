@@ -1,24 +1,31 @@
 use model::endpoint::*;
-use model::types::{Field, Type};
+use model::types::{Field, TimeFormat, Type};
 
 pub fn endpoint_admin_list_users() -> EndpointSchema {
     EndpointSchema::new(
         "ListUsers",
         30010,
         vec![
-            Field::new("offset", Type::Int),
-            Field::new("limit", Type::Int),
+            Field::new("offset", Type::Int).minimum(0.0),
+            Field::new("limit", Type::Int).range(1.0, 1000.0),
         ],
         vec![Field::new(
             "users",
             Type::data_table(
-                "ListUsersResponseRow".to_owned(),
+                "ListUsersResponseRow",
                 vec![
                     Field::new("user_public_id", Type::BigInt),
                     Field::new("username", Type::String),
-                    Field::new("email", Type::String),
-                    Field::new("created_at", Type::Int),
-                    Field::new("updated_at", Type::Int),
+                    Field::new("email", Type::optional(Type::String)),
+                    Field::new("family_name", Type::optional(Type::String)),
+                    Field::new("given_name", Type::optional(Type::String)),
+                    Field::new("role", Type::enum_ref("role")),
+                    Field::new("last_login", Type::optional(Type::TimestampTz))
+                        .time_format(TimeFormat::EpochMillis),
+                    Field::new("created_at", Type::TimestampTz)
+                        .time_format(TimeFormat::EpochMillis),
+                    Field::new("updated_at", Type::TimestampTz)
+                        .time_format(TimeFormat::EpochMillis),
                 ],
             ),
         )],
@@ -35,6 +42,74 @@ pub fn endpoint_admin_assign_role() -> EndpointSchema {
         vec![Field::new("success", Type::Boolean)],
     )
 }
+/// Ends the sessions of a user, only those of `service` if given
+pub fn endpoint_admin_revoke_sessions() -> EndpointSchema {
+    EndpointSchema::new(
+        "RevokeSessions",
+        30030,
+        vec![
+            Field::new("user_public_id", Type::BigInt),
+            Field::new("service", Type::optional(Type::enum_ref("service"))),
+        ],
+        vec![Field::new("revoked_sessions", Type::Int)],
+    )
+}
+/// Usernames and addresses whose failed logins trigger a lockout policy
+pub fn endpoint_admin_list_login_lockouts() -> EndpointSchema {
+    EndpointSchema::new(
+        "ListLoginLockouts",
+        30040,
+        vec![],
+        vec![Field::new(
+            "lockouts",
+            Type::data_table(
+                "LoginLockoutRow",
+                vec![
+                    Field::new("username", Type::optional(Type::String)),
+                    Field::new("ip_address", Type::optional(Type::Inet)),
+                    Field::new("failures", Type::Int),
+                    Field::new("last_failure", Type::TimestampTz)
+                        .time_format(TimeFormat::EpochMillis),
+                    Field::new("blocked_until", Type::optional(Type::TimestampTz))
+                        .time_format(TimeFormat::EpochMillis),
+                    Field::new("captcha_required", Type::Boolean),
+                ],
+            ),
+        )],
+    )
+}
+/// Forgives the failed logins of a username, an address, or a username from an address
+pub fn endpoint_admin_clear_login_lockout() -> EndpointSchema {
+    EndpointSchema::new(
+        "ClearLoginLockout",
+        30050,
+        vec![
+            Field::new("username", Type::optional(Type::String)),
+            Field::new("ip_address", Type::optional(Type::Inet)),
+        ],
+        vec![Field::new("cleared", Type::Int)],
+    )
+}
+/// Requires two-factor authentication to log in to a service, ending the sessions of users
+/// without it
+pub fn endpoint_admin_set_two_factor_required() -> EndpointSchema {
+    EndpointSchema::new(
+        "SetTwoFactorRequired",
+        30060,
+        vec![
+            Field::new("service", Type::enum_ref("service")),
+            Field::new("required", Type::Boolean),
+        ],
+        vec![Field::new("revoked_sessions", Type::Int)],
+    )
+}
 pub fn get_admin_endpoints() -> Vec<EndpointSchema> {
-    vec![endpoint_admin_list_users(), endpoint_admin_assign_role()]
+    vec![
+        endpoint_admin_list_users(),
+        endpoint_admin_assign_role(),
+        endpoint_admin_revoke_sessions(),
+        endpoint_admin_list_login_lockouts(),
+        endpoint_admin_clear_login_lockout(),
+        endpoint_admin_set_two_factor_required(),
+    ]
 }
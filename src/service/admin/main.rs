@@ -1,18 +1,10 @@
-mod method;
-
-use crate::endpoints::endpoint_admin_list_users;
-use crate::method::ListUsersHandler;
-use iloverust::endpoints::endpoint_auth_authorize;
-use iloverust::method::AuthorizeHandler;
 use eyre::*;
-use gen::model::EnumService;
-use lib::config::load_config;
+use iloverust::servers::admin_server;
+use lib::config::{load_config, Command};
 use lib::database::connect_to_database;
+use lib::error_code::check_raised_error_codes;
 use lib::log::setup_logs;
-use lib::ws::{EndpointAuthController, WebsocketServer};
-use std::sync::Arc;
-
-pub mod endpoints;
+use lib::migration::migrate;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -20,17 +12,12 @@ async fn main() -> Result<()> {
     setup_logs(config.app.log_level)?;
 
     let db = connect_to_database(config.db).await?;
-    let mut server = WebsocketServer::new(config.app);
-    server.add_database(db);
-    let auth_controller = Arc::new(EndpointAuthController::new(server.get_toolbox()));
-    auth_controller.add_auth_endpoint(
-        endpoint_auth_authorize(),
-        AuthorizeHandler {
-            accept_service: EnumService::Admin,
-        },
-    );
-    server.add_auth_controller(auth_controller);
-    server.add_handler(endpoint_admin_list_users(), ListUsersHandler);
-    server.listen().await?;
+    if let Some(Command::Migrate(args)) = &config.command {
+        return migrate(&db, args).await;
+    }
+    check_raised_error_codes(&db).await?;
+    admin_server(config.app, config.lockout, db)?
+        .listen()
+        .await?;
     Ok(())
 }
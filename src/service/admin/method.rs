@@ -1,7 +1,10 @@
+use chrono::Utc;
 use eyre::Context;
 use gen::database::*;
 use gen::model::*;
 use lib::handler::RequestHandler;
+use lib::lockout::{Lockout, LockoutConfig, LockoutPolicy};
+use lib::time::Interval;
 use lib::toolbox::*;
 use lib::ws::*;
 use std::sync::Arc;
@@ -19,27 +22,18 @@ impl RequestHandler for ListUsersHandler {
         _conn: Arc<Connection>,
         req: Self::Request,
     ) {
-        let db: DbClient = toolbox.get_db();
+        let db = toolbox.get_db::<DbClient>();
         toolbox.spawn_response(ctx, async move {
+            let db = db?;
             let result = db
-                .fun_admin_list_users(FunAdminListUsersReq {
+                .fun_admin_list_users_one(FunAdminListUsersReq {
                     offset: req.offset as _,
                     limit: req.limit as _,
                 })
                 .await?;
 
             Ok(ListUsersResponse {
-                users: result
-                    .rows
-                    .into_iter()
-                    .map(|x| ListUsersResponseRow {
-                        user_public_id: x.user_public_id,
-                        username: x.username,
-                        email: x.email,
-                        created_at: x.created_at as _,
-                        updated_at: x.updated_at as _,
-                    })
-                    .collect(),
+                users: result.users,
             })
         });
     }
@@ -58,8 +52,9 @@ impl RequestHandler for AssignRoleHandler {
         _conn: Arc<Connection>,
         req: Self::Request,
     ) {
-        let db: DbClient = toolbox.get_db();
+        let db = toolbox.get_db::<DbClient>();
         toolbox.spawn_response(ctx, async move {
+            let db = db?;
             let result = db
                 .fun_admin_assign_role(FunAdminAssignRoleReq {
                     operator_user_id: _conn.get_user_id() as _,
@@ -72,3 +67,153 @@ impl RequestHandler for AssignRoleHandler {
         });
     }
 }
+
+pub struct RevokeSessionsHandler;
+
+impl RequestHandler for RevokeSessionsHandler {
+    type Request = RevokeSessionsRequest;
+    type Response = RevokeSessionsResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let row = db
+                .fun_admin_revoke_sessions_one(FunAdminRevokeSessionsReq {
+                    operator_user_id: conn.get_user_id(),
+                    user_public_id: req.user_public_id,
+                    service: req.service,
+                })
+                .await?;
+            Ok(RevokeSessionsResponse {
+                revoked_sessions: row.revoked_sessions,
+            })
+        });
+    }
+}
+
+pub struct ListLoginLockoutsHandler {
+    pub lockout: Arc<LockoutConfig>,
+}
+
+impl RequestHandler for ListLoginLockoutsHandler {
+    type Request = ListLoginLockoutsRequest;
+    type Response = ListLoginLockoutsResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        _req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let lockout = Arc::clone(&self.lockout);
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            // a disabled policy locks nothing out
+            let min_failures = |x: &LockoutPolicy| match x.is_enabled() {
+                true => x.max_failures as i32,
+                false => i32::MAX,
+            };
+            let rows = db
+                .fun_admin_list_login_failures(FunAdminListLoginFailuresReq {
+                    username_window: Interval::from_secs(lockout.username.window as _),
+                    ip_window: Interval::from_secs(lockout.ip.window as _),
+                    min_username_failures: min_failures(&lockout.username),
+                    min_ip_failures: min_failures(&lockout.ip),
+                })
+                .await?
+                .rows;
+            let now = Utc::now();
+            let lockouts = rows
+                .into_iter()
+                .map(|x| {
+                    let policy = match x.username {
+                        Some(_) => &lockout.username,
+                        None => &lockout.ip,
+                    };
+                    let state = policy.check(x.failures as _, Some(x.last_failure), now);
+                    LoginLockoutRow {
+                        username: x.username,
+                        ip_address: x.ip_address,
+                        failures: x.failures,
+                        last_failure: x.last_failure,
+                        blocked_until: match state {
+                            Lockout::Blocked { until } => Some(until),
+                            _ => None,
+                        },
+                        captcha_required: state == Lockout::CaptchaRequired,
+                    }
+                })
+                .collect();
+            Ok(ListLoginLockoutsResponse { lockouts })
+        });
+    }
+}
+
+pub struct ClearLoginLockoutHandler;
+
+impl RequestHandler for ClearLoginLockoutHandler {
+    type Request = ClearLoginLockoutRequest;
+    type Response = ClearLoginLockoutResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let row = db
+                .fun_admin_clear_login_failures_one(FunAdminClearLoginFailuresReq {
+                    operator_user_id: conn.get_user_id(),
+                    username: req.username.map(|x| x.trim().to_ascii_lowercase()),
+                    ip_address: req.ip_address,
+                })
+                .await?;
+            Ok(ClearLoginLockoutResponse {
+                cleared: row.cleared,
+            })
+        });
+    }
+}
+
+pub struct SetTwoFactorRequiredHandler;
+
+impl RequestHandler for SetTwoFactorRequiredHandler {
+    type Request = SetTwoFactorRequiredRequest;
+    type Response = SetTwoFactorRequiredResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let row = db
+                .fun_admin_set_two_factor_required_one(FunAdminSetTwoFactorRequiredReq {
+                    operator_user_id: conn.get_user_id(),
+                    service: req.service,
+                    required: req.required,
+                })
+                .await?;
+            Ok(SetTwoFactorRequiredResponse {
+                revoked_sessions: row.revoked_sessions,
+            })
+        });
+    }
+}
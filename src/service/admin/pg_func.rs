@@ -8,32 +8,49 @@ pub fn get_admin_pg_func() -> Vec<ProceduralFunction> {
                 Field::new("offset", Type::Int),
                 Field::new("limit", Type::Int),
             ],
-            vec![
-                Field::new("user_id", Type::BigInt),
-                Field::new("user_public_id", Type::BigInt),
-                Field::new("email", Type::String),
-                Field::new("username", Type::String),
-                Field::new("role", Type::enum_ref("role")),
-                Field::new("updated_at", Type::Int),
-                Field::new("created_at", Type::Int),
-            ],
+            vec![Field::new(
+                "users",
+                Type::data_table(
+                    "ListUsersResponseRow",
+                    vec![
+                        Field::new("user_public_id", Type::BigInt),
+                        Field::new("username", Type::String),
+                        Field::new("email", Type::optional(Type::String)),
+                        Field::new("family_name", Type::optional(Type::String)),
+                        Field::new("given_name", Type::optional(Type::String)),
+                        Field::new("role", Type::enum_ref("role")),
+                        Field::new("last_login", Type::optional(Type::TimestampTz))
+                            .time_format(TimeFormat::EpochMillis),
+                        Field::new("created_at", Type::TimestampTz)
+                            .time_format(TimeFormat::EpochMillis),
+                        Field::new("updated_at", Type::TimestampTz)
+                            .time_format(TimeFormat::EpochMillis),
+                    ],
+                ),
+            )],
             r#"
 BEGIN
-    RETURN QUERY SELECT
-        u.pkey_id,
+    RETURN QUERY SELECT COALESCE(array_agg((
         u.public_id,
-        u.email,
         u.username,
+        u.email,
+        u.family_name,
+        u.given_name,
         u.role,
-        u.updated_at::int,
-        u.created_at::int
-    FROM tbl.user AS u
-    ORDER BY user_id
-    OFFSET a_offset
-    LIMIT a_limit;
+        u.last_login,
+        u.created_at,
+        u.updated_at
+    )::struct_list_users_response_row ORDER BY u.pkey_id), '{}')
+    FROM (
+        SELECT * FROM tbl.user
+        ORDER BY pkey_id
+        OFFSET a_offset
+        LIMIT a_limit
+    ) AS u;
 END
         "#,
-        ),
+        )
+        .cardinality(Cardinality::One),
         ProceduralFunction::new(
             "fun_admin_assign_role",
             vec![
@@ -54,5 +71,154 @@ BEGIN
 END
         "#,
         ),
+        ProceduralFunction::new(
+            "fun_admin_revoke_sessions",
+            vec![
+                Field::new("operator_user_id", Type::BigInt),
+                Field::new("user_public_id", Type::BigInt),
+                Field::new("service", Type::optional(Type::enum_ref("service"))),
+            ],
+            vec![Field::new("revoked_sessions", Type::Int)],
+            r#"
+DECLARE
+    _operator_role enum_role;
+    _user_id       bigint;
+    _count         integer;
+BEGIN
+    SELECT role FROM tbl.user WHERE pkey_id = a_operator_user_id INTO STRICT _operator_role;
+    IF _operator_role <> 'admin' THEN
+        RAISE SQLSTATE 'R000S'; -- InvalidRole
+    END IF;
+    SELECT pkey_id FROM tbl.user WHERE public_id = a_user_public_id INTO _user_id;
+    IF _user_id ISNULL THEN
+        RAISE SQLSTATE 'R0007'; -- UnknownUser
+    END IF;
+    -- Deleting the sessions closes their connections.
+    DELETE
+    FROM tbl.session AS s
+    WHERE s.fkey_user = _user_id
+      AND (a_service ISNULL OR s.service = a_service);
+    GET DIAGNOSTICS _count := ROW_COUNT;
+    RETURN QUERY SELECT _count;
+END
+        "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_admin_list_login_failures",
+            vec![
+                Field::new("username_window", Type::Duration),
+                Field::new("ip_window", Type::Duration),
+                Field::new("min_username_failures", Type::Int),
+                Field::new("min_ip_failures", Type::Int),
+            ],
+            vec![
+                Field::new("username", Type::optional(Type::String)),
+                Field::new("ip_address", Type::optional(Type::Inet)),
+                Field::new("failures", Type::Int),
+                Field::new("last_failure", Type::TimestampTz),
+            ],
+            r#"
+BEGIN
+    -- Counted as in fun_auth_begin_login_attempt.
+    RETURN QUERY
+        SELECT a.username::varchar, NULL::inet, COUNT(*)::int, MAX(a.moment)
+        FROM tbl.login_attempt AS a
+        WHERE a.is_password_ok = FALSE
+          AND NOT a.is_cleared
+          AND a.moment > NOW() - a_username_window
+          AND a.moment > COALESCE((SELECT MAX(s.moment)
+                                   FROM tbl.login_attempt AS s
+                                   WHERE s.username = a.username
+                                     AND s.is_password_ok), '-infinity')
+        GROUP BY a.username
+        HAVING COUNT(*) >= a_min_username_failures
+        UNION ALL
+        SELECT NULL::varchar, a.ip_address, COUNT(*)::int, MAX(a.moment)
+        FROM tbl.login_attempt AS a
+        WHERE a.is_password_ok = FALSE
+          AND NOT a.is_cleared
+          AND a.moment > NOW() - a_ip_window
+        GROUP BY a.ip_address
+        HAVING COUNT(*) >= a_min_ip_failures
+        ORDER BY 4 DESC;
+END
+        "#,
+        ),
+        ProceduralFunction::new(
+            "fun_admin_clear_login_failures",
+            vec![
+                Field::new("operator_user_id", Type::BigInt),
+                Field::new("username", Type::optional(Type::String)),
+                Field::new("ip_address", Type::optional(Type::Inet)),
+            ],
+            vec![Field::new("cleared", Type::Int)],
+            r#"
+DECLARE
+    _operator_role enum_role;
+    _count         integer;
+BEGIN
+    SELECT role FROM tbl.user WHERE pkey_id = a_operator_user_id INTO STRICT _operator_role;
+    IF _operator_role <> 'admin' THEN
+        RAISE SQLSTATE 'R000S'; -- InvalidRole
+    END IF;
+    IF a_username ISNULL AND a_ip_address ISNULL THEN
+        RAISE SQLSTATE 'R0001'; -- InvalidArgument
+    END IF;
+    UPDATE tbl.login_attempt
+    SET is_cleared = TRUE
+    WHERE is_password_ok = FALSE
+      AND NOT is_cleared
+      AND (a_username ISNULL OR username = a_username)
+      AND (a_ip_address ISNULL OR ip_address = a_ip_address);
+    GET DIAGNOSTICS _count := ROW_COUNT;
+    RETURN QUERY SELECT _count;
+END
+        "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_admin_set_two_factor_required",
+            vec![
+                Field::new("operator_user_id", Type::BigInt),
+                Field::new("service", Type::enum_ref("service")),
+                Field::new("required", Type::Boolean),
+            ],
+            vec![Field::new("revoked_sessions", Type::Int)],
+            r#"
+DECLARE
+    _operator_role enum_role;
+    _operator_totp bytea;
+    _count         integer := 0;
+BEGIN
+    SELECT role, totp_secret
+    FROM tbl.user
+    WHERE pkey_id = a_operator_user_id
+    INTO STRICT _operator_role, _operator_totp;
+    IF _operator_role <> 'admin' THEN
+        RAISE SQLSTATE 'R000S'; -- InvalidRole
+    END IF;
+    -- Requiring what the operator has not enabled would lock them out.
+    IF a_required AND _operator_totp ISNULL THEN
+        RAISE SQLSTATE 'R0016'; -- TwoFactorRequired
+    END IF;
+    INSERT INTO tbl.service_setting(service, is_2fa_required, fkey_updated_by)
+    VALUES (a_service, a_required, a_operator_user_id)
+    ON CONFLICT (service) DO UPDATE SET is_2fa_required = a_required,
+                                        fkey_updated_by = a_operator_user_id,
+                                        updated_at      = NOW();
+    -- Ends the sessions opened without a second factor, even of users who enabled it since.
+    IF a_required THEN
+        DELETE
+        FROM tbl.session AS s
+        WHERE s.service = a_service
+          AND NOT s.is_two_factor_ok;
+        GET DIAGNOSTICS _count := ROW_COUNT;
+    END IF;
+    RETURN QUERY SELECT _count;
+END
+        "#,
+        )
+        .cardinality(Cardinality::One),
     ]
 }
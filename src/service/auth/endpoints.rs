@@ -1,15 +1,21 @@
 use model::endpoint::*;
-use model::types::{Field, Type};
+use model::types::{Field, StringFormat, TimeFormat, Type};
 
 pub fn endpoint_auth_signup() -> EndpointSchema {
     EndpointSchema::new(
         "Signup",
         10010,
         vec![
-            Field::new("username", Type::String),
-            Field::new("password", Type::String),
-            Field::new("email", Type::String),
-            Field::new("phone", Type::String),
+            Field::new("username", Type::String)
+                .non_empty()
+                .max_length(20),
+            Field::new("password", Type::String).non_empty(),
+            Field::new("email", Type::String)
+                .format(StringFormat::Email)
+                .max_length(320),
+            Field::new("phone", Type::String)
+                .format(StringFormat::Phone)
+                .max_length(15),
             Field::new("agreed_tos", Type::Boolean),
             Field::new("agreed_privacy", Type::Boolean),
         ],
@@ -19,22 +25,31 @@ pub fn endpoint_auth_signup() -> EndpointSchema {
         ],
     )
 }
+/// Either logs in, or with 2FA enabled returns a challenge to complete with `LoginVerify2FA`
 pub fn endpoint_auth_login() -> EndpointSchema {
     EndpointSchema::new(
         "Login",
         10020,
         vec![
-            Field::new("username", Type::String),
-            Field::new("password", Type::String),
+            Field::new("username", Type::String).non_empty(),
+            Field::new("password", Type::String).non_empty(),
             Field::new("service_code", Type::enum_ref("service")),
-            Field::new("device_id", Type::String),
-            Field::new("device_os", Type::String),
+            Field::new("device_id", Type::String).max_length(256),
+            Field::new("device_os", Type::String).max_length(64),
+            Field::new("captcha_token", Type::optional(Type::String)),
         ],
         vec![
             Field::new("username", Type::String),
             Field::new("user_public_id", Type::BigInt),
-            Field::new("user_token", Type::String),
-            Field::new("admin_token", Type::String),
+            Field::new("access_token", Type::optional(Type::String)),
+            Field::new("refresh_token", Type::optional(Type::String)),
+            Field::new("access_expires_at", Type::optional(Type::TimestampTz))
+                .time_format(TimeFormat::EpochMillis),
+            Field::new("expires_at", Type::optional(Type::TimestampTz))
+                .time_format(TimeFormat::EpochMillis),
+            Field::new("challenge_token", Type::optional(Type::String)),
+            Field::new("challenge_expires_at", Type::optional(Type::TimestampTz))
+                .time_format(TimeFormat::EpochMillis),
         ],
     )
 }
@@ -43,11 +58,215 @@ pub fn endpoint_auth_authorize() -> EndpointSchema {
         "Authorize",
         10030,
         vec![
-            Field::new("username", Type::String),
-            Field::new("token", Type::String),
+            Field::new("username", Type::String).non_empty(),
+            Field::new("token", Type::String).non_empty(),
             Field::new("service_code", Type::enum_ref("service")),
-            Field::new("device_id", Type::String),
-            Field::new("device_os", Type::String),
+            Field::new("device_id", Type::String).max_length(256),
+            Field::new("device_os", Type::String).max_length(64),
+        ],
+        vec![Field::new("success", Type::Boolean)],
+    )
+}
+
+/// Exchanges the refresh token of a session for new access and refresh tokens
+pub fn endpoint_auth_refresh() -> EndpointSchema {
+    EndpointSchema::new(
+        "Refresh",
+        10040,
+        vec![
+            Field::new("refresh_token", Type::String).non_empty(),
+            Field::new("device_id", Type::String).max_length(256),
+            Field::new("device_os", Type::String).max_length(64),
+        ],
+        vec![
+            Field::new("username", Type::String),
+            Field::new("access_token", Type::String),
+            Field::new("refresh_token", Type::String),
+            Field::new("access_expires_at", Type::TimestampTz).time_format(TimeFormat::EpochMillis),
+            Field::new("expires_at", Type::TimestampTz).time_format(TimeFormat::EpochMillis),
+        ],
+    )
+}
+
+/// Ends the session of an access token, which may have expired
+pub fn endpoint_auth_logout() -> EndpointSchema {
+    EndpointSchema::new(
+        "Logout",
+        10050,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("token", Type::String).non_empty(),
+        ],
+        vec![Field::new("success", Type::Boolean)],
+    )
+}
+/// Ends every session of the user, on all services and devices
+pub fn endpoint_auth_logout_all() -> EndpointSchema {
+    EndpointSchema::new(
+        "LogoutAll",
+        10060,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("token", Type::String).non_empty(),
+        ],
+        vec![Field::new("revoked_sessions", Type::Int)],
+    )
+}
+/// Replaces the password after checking the old one
+pub fn endpoint_auth_change_password() -> EndpointSchema {
+    EndpointSchema::new(
+        "ChangePassword",
+        10070,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("old_password", Type::String).non_empty(),
+            Field::new("new_password", Type::String).non_empty(),
+            Field::new("device_id", Type::String).max_length(256),
+            Field::new("device_os", Type::String).max_length(64),
+        ],
+        vec![Field::new("success", Type::Boolean)],
+    )
+}
+/// The questions users pick their recovery questions from
+pub fn endpoint_auth_list_recovery_question_catalog() -> EndpointSchema {
+    EndpointSchema::new(
+        "ListRecoveryQuestionCatalog",
+        10080,
+        vec![],
+        vec![Field::new(
+            "questions",
+            Type::data_table(
+                "RecoveryQuestionCatalogRow",
+                vec![
+                    Field::new("question_id", Type::Int),
+                    Field::new("content", Type::String),
+                    Field::new("category", Type::enum_ref("recovery_question_category")),
+                ],
+            ),
+        )],
+    )
+}
+/// Replaces the recovery questions of the user, answers in the same order as the questions
+pub fn endpoint_auth_set_recovery_questions() -> EndpointSchema {
+    EndpointSchema::new(
+        "SetRecoveryQuestions",
+        10090,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("password", Type::String).non_empty(),
+            Field::new("question_ids", Type::vec(Type::Int)),
+            Field::new("answers", Type::vec(Type::String)),
+        ],
+        vec![Field::new("success", Type::Boolean)],
+    )
+}
+/// The recovery questions a user has to answer to reset the password
+pub fn endpoint_auth_get_my_recovery_questions() -> EndpointSchema {
+    EndpointSchema::new(
+        "GetMyRecoveryQuestions",
+        10100,
+        vec![Field::new("username", Type::String).non_empty()],
+        vec![Field::new(
+            "questions",
+            Type::data_table(
+                "RecoveryQuestionRow",
+                vec![
+                    Field::new("question_id", Type::Int),
+                    Field::new("question", Type::String),
+                ],
+            ),
+        )],
+    )
+}
+/// Answers every recovery question of the user, for a token to `ResetPassword` with
+pub fn endpoint_auth_submit_recovery_answers() -> EndpointSchema {
+    EndpointSchema::new(
+        "SubmitRecoveryAnswers",
+        10110,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("question_ids", Type::vec(Type::Int)),
+            Field::new("answers", Type::vec(Type::String)),
+        ],
+        vec![
+            Field::new("reset_token", Type::String),
+            Field::new("reset_token_expires_at", Type::TimestampTz)
+                .time_format(TimeFormat::EpochMillis),
+        ],
+    )
+}
+/// Sets a new password with a token from `SubmitRecoveryAnswers`, ending every session
+pub fn endpoint_auth_reset_password() -> EndpointSchema {
+    EndpointSchema::new(
+        "ResetPassword",
+        10120,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("reset_token", Type::String).non_empty(),
+            Field::new("new_password", Type::String).non_empty(),
+        ],
+        vec![Field::new("success", Type::Boolean)],
+    )
+}
+
+/// Completes a login challenged for the second factor, with a TOTP code or a recovery code
+pub fn endpoint_auth_login_verify_2fa() -> EndpointSchema {
+    EndpointSchema::new(
+        "LoginVerify2FA",
+        10130,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("challenge_token", Type::String).non_empty(),
+            Field::new("code", Type::String).non_empty().max_length(64),
+        ],
+        vec![
+            Field::new("username", Type::String),
+            Field::new("user_public_id", Type::BigInt),
+            Field::new("access_token", Type::String),
+            Field::new("refresh_token", Type::String),
+            Field::new("access_expires_at", Type::TimestampTz).time_format(TimeFormat::EpochMillis),
+            Field::new("expires_at", Type::TimestampTz).time_format(TimeFormat::EpochMillis),
+        ],
+    )
+}
+/// Starts enabling TOTP 2FA, with a secret for the authenticator app to confirm with
+/// `ConfirmTotp`
+pub fn endpoint_auth_enroll_totp() -> EndpointSchema {
+    EndpointSchema::new(
+        "EnrollTotp",
+        10140,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("password", Type::String).non_empty(),
+        ],
+        vec![
+            Field::new("secret", Type::String),
+            Field::new("otpauth_uri", Type::String),
+        ],
+    )
+}
+/// Enables TOTP 2FA with a code from the enrolled secret, returning one-time recovery codes
+pub fn endpoint_auth_confirm_totp() -> EndpointSchema {
+    EndpointSchema::new(
+        "ConfirmTotp",
+        10150,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("password", Type::String).non_empty(),
+            Field::new("code", Type::String).non_empty().max_length(64),
+        ],
+        vec![Field::new("recovery_codes", Type::vec(Type::String))],
+    )
+}
+/// Disables TOTP 2FA with a TOTP code or a recovery code
+pub fn endpoint_auth_disable_totp() -> EndpointSchema {
+    EndpointSchema::new(
+        "DisableTotp",
+        10160,
+        vec![
+            Field::new("username", Type::String).non_empty(),
+            Field::new("password", Type::String).non_empty(),
+            Field::new("code", Type::String).non_empty().max_length(64),
         ],
         vec![Field::new("success", Type::Boolean)],
     )
@@ -58,5 +277,18 @@ pub fn get_auth_endpoints() -> Vec<EndpointSchema> {
         endpoint_auth_login(),
         endpoint_auth_signup(),
         endpoint_auth_authorize(),
+        endpoint_auth_refresh(),
+        endpoint_auth_logout(),
+        endpoint_auth_logout_all(),
+        endpoint_auth_change_password(),
+        endpoint_auth_list_recovery_question_catalog(),
+        endpoint_auth_set_recovery_questions(),
+        endpoint_auth_get_my_recovery_questions(),
+        endpoint_auth_submit_recovery_answers(),
+        endpoint_auth_reset_password(),
+        endpoint_auth_login_verify_2fa(),
+        endpoint_auth_enroll_totp(),
+        endpoint_auth_confirm_totp(),
+        endpoint_auth_disable_totp(),
     ]
 }
@@ -1,17 +1,28 @@
+use chrono::Utc;
 use eyre::*;
 use gen::database::*;
+use gen::error_code::EnumErrorCode;
 use gen::model::*;
+use lib::config::{RecoveryConfig, SessionConfig};
+use lib::database::IsolationLevel;
 use lib::handler::RequestHandler;
+use lib::locale::Locale;
+use lib::lockout::{Lockout, LockoutConfig};
+use lib::password::{PasswordCheck, PasswordConfig};
+use lib::time::Interval;
 use lib::toolbox::*;
+use lib::totp::{self, TotpConfig};
 use lib::ws::*;
 use reqwest::StatusCode;
-use sha2::{Digest, Sha256};
+use std::net::IpAddr;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use uuid::Uuid;
 
-pub struct SignupHandler;
+pub struct SignupHandler {
+    pub password: PasswordConfig,
+}
 
 impl RequestHandler for SignupHandler {
     type Request = SignupRequest;
@@ -24,14 +35,14 @@ impl RequestHandler for SignupHandler {
         conn: Arc<Connection>,
         req: Self::Request,
     ) {
-        let db: DbClient = toolbox.get_db();
+        let db = toolbox.get_db::<DbClient>();
+        let password = self.password;
         toolbox.spawn_response(ctx, async move {
+            let db = db?;
             let public_id = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_nanos() as i64;
-            let salt = Uuid::new_v4();
-            let password_hash = hash_password(&req.password, salt.as_bytes())?;
             let username = req.username.trim().to_ascii_lowercase();
 
             let agreed_tos = req.agreed_tos;
@@ -39,16 +50,17 @@ impl RequestHandler for SignupHandler {
 
             if !agreed_tos {
                 bail!(CustomError::new(
-                    StatusCode::BAD_REQUEST,
+                    EnumErrorCode::ConsentMissing,
                     format!("You must agree to the terms of service"),
                 ));
             }
             if !agreed_privacy {
                 bail!(CustomError::new(
-                    StatusCode::BAD_REQUEST,
+                    EnumErrorCode::ConsentMissing,
                     format!("You must agree to the privacy policy"),
                 ));
             }
+            let password_hash = password.hash_blocking(req.password).await?;
 
             db.fun_auth_signup(FunAuthSignupReq {
                 public_id,
@@ -56,9 +68,8 @@ impl RequestHandler for SignupHandler {
                 email: req.email,
                 phone: req.phone,
                 password_hash,
-                password_salt: salt.as_bytes().to_vec(),
                 age: 0,
-                preferred_language: "".to_string(),
+                preferred_language: ctx.locale.to_string(),
                 agreed_tos,
                 agreed_privacy,
                 ip_address: conn.address.clone(),
@@ -73,7 +84,12 @@ impl RequestHandler for SignupHandler {
     }
 }
 
-pub struct LoginHandler;
+pub struct LoginHandler {
+    pub password: PasswordConfig,
+    pub session: SessionConfig,
+    pub lockout: Arc<LockoutConfig>,
+    pub totp: Arc<TotpConfig>,
+}
 
 impl RequestHandler for LoginHandler {
     type Request = LoginRequest;
@@ -86,64 +102,153 @@ impl RequestHandler for LoginHandler {
         conn: Arc<Connection>,
         req: Self::Request,
     ) {
-        let db: DbClient = toolbox.get_db();
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        let session = self.session;
+        let lockout = Arc::clone(&self.lockout);
+        let totp = Arc::clone(&self.totp);
         toolbox.spawn_response(ctx, async move {
+            let db = db?;
             let username = req.username.trim().to_ascii_lowercase();
             let service_code = req.service_code;
-            let password = req.password;
-            let data = db
-                .fun_auth_get_password_salt(FunAuthGetPasswordSaltReq {
-                    username: username.clone(),
-                })
-                .await?;
-            let salt = data
-                .rows
-                .get(0)
-                .ok_or(eyre!("No salt found for user"))?
-                .salt
-                .clone();
-
-            let mut hasher = Sha256::new();
-
-            hasher.update(password.as_bytes());
-            hasher.update(salt.as_slice());
-            let password_hash = hasher.finalize().to_vec();
-            let data = db
-                .fun_auth_authenticate(FunAuthAuthenticateReq {
+            let attempt = PasswordAttempt {
+                username: username.clone(),
+                ip_address: conn.address,
+                device_id: Some(req.device_id.clone()),
+                device_os: Some(req.device_os.clone()),
+                captcha_token: req.captcha_token,
+            };
+            let (stored, check) =
+                verify_password(&db, config, &lockout, attempt, req.password.clone()).await?;
+            let new_password_hash = match check {
+                PasswordCheck::Outdated => Some(config.hash_blocking(req.password).await?),
+                _ => None,
+            };
+            let is_totp_enabled = stored.totp_secret.is_some();
+            let tx = db.begin(IsolationLevel::ReadCommitted).await?;
+            let row = tx
+                .fun_auth_authenticate_one(FunAuthAuthenticateReq {
                     username: username.clone(),
-                    password_hash: password_hash.clone(),
                     service_code: service_code as _,
                     device_id: req.device_id.clone(),
                     device_os: req.device_os.clone(),
                     ip_address: conn.address.clone(),
                 })
                 .await?;
-            let row = data.rows.get(0).ok_or(eyre!("No rows found for user"))?;
-            let user_token = Uuid::new_v4();
-            let admin_token = Uuid::new_v4();
-            db.fun_auth_set_token(FunAuthSetTokenReq {
-                user_id: row.user_id,
-                user_token: user_token.clone(),
-                admin_token: admin_token.clone(),
-                service_code: service_code as _,
-            })
-            .await?;
+            // existing users move off SHA-256 or old parameters as they log in
+            if let Some(new_password_hash) = new_password_hash {
+                tx.fun_auth_rehash_password(FunAuthRehashPasswordReq {
+                    user_id: row.user_id,
+                    old_password_hash: stored.password_hash,
+                    new_password_hash,
+                })
+                .await?;
+            }
+            // the session is only created once the second factor is verified
+            if is_totp_enabled {
+                let challenge_token = Uuid::new_v4();
+                let challenge = tx
+                    .fun_auth_create_login_challenge_one(FunAuthCreateLoginChallengeReq {
+                        user_id: row.user_id,
+                        service: service_code,
+                        device_id: req.device_id,
+                        device_os: req.device_os,
+                        ip_address: conn.address,
+                        token: challenge_token,
+                        lifetime: seconds(totp.challenge_lifetime),
+                    })
+                    .await?;
+                tx.commit().await?;
+                return Ok(LoginResponse {
+                    username,
+                    user_public_id: row.user_public_id,
+                    access_token: None,
+                    refresh_token: None,
+                    access_expires_at: None,
+                    expires_at: None,
+                    challenge_token: Some(challenge_token.to_string()),
+                    challenge_expires_at: Some(challenge.expires_at),
+                });
+            }
+            let access_token = Uuid::new_v4();
+            let refresh_token = Uuid::new_v4();
+            let created = tx
+                .fun_auth_create_session_one(FunAuthCreateSessionReq {
+                    user_id: row.user_id,
+                    service: service_code,
+                    device_id: req.device_id,
+                    device_os: req.device_os,
+                    ip_address: conn.address,
+                    access_token,
+                    refresh_token,
+                    access_token_lifetime: seconds(session.access_token_lifetime),
+                    idle_timeout: seconds(session.idle_timeout),
+                    absolute_timeout: seconds(session.absolute_timeout),
+                    is_two_factor_ok: false,
+                })
+                .await?;
+            tx.commit().await?;
             Ok(LoginResponse {
                 username: username.clone(),
                 user_public_id: row.user_public_id,
-                user_token: user_token.to_string(),
-                admin_token: admin_token.to_string(),
+                access_token: Some(access_token.to_string()),
+                refresh_token: Some(refresh_token.to_string()),
+                access_expires_at: Some(created.access_expires_at),
+                expires_at: Some(created.expires_at),
+                challenge_token: None,
+                challenge_expires_at: None,
             })
         })
     }
 }
-pub fn hash_password(password: &str, salt: impl AsRef<[u8]>) -> Result<Vec<u8>> {
-    let mut hasher = Sha256::new();
-    hasher.update(password.as_bytes());
-    hasher.update(salt.as_ref());
-    Ok(hasher.finalize().to_vec())
+
+fn seconds(x: u32) -> Interval {
+    Interval::from_secs(x as _)
+}
+
+pub struct RefreshHandler {
+    pub session: SessionConfig,
 }
 
+impl RequestHandler for RefreshHandler {
+    type Request = RefreshRequest;
+    type Response = RefreshResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let session = self.session;
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let refresh_token = parse_token(&req.refresh_token)?;
+            let access_token = Uuid::new_v4();
+            let new_refresh_token = Uuid::new_v4();
+            let row = db
+                .fun_auth_refresh_one(FunAuthRefreshReq {
+                    refresh_token,
+                    device_id: req.device_id,
+                    device_os: req.device_os,
+                    ip_address: conn.address,
+                    new_access_token: access_token,
+                    new_refresh_token,
+                    access_token_lifetime: seconds(session.access_token_lifetime),
+                })
+                .await?;
+            Ok(RefreshResponse {
+                username: row.username,
+                access_token: access_token.to_string(),
+                refresh_token: new_refresh_token.to_string(),
+                access_expires_at: row.access_expires_at,
+                expires_at: row.expires_at,
+            })
+        })
+    }
+}
 pub struct AuthorizeHandler {
     pub accept_service: EnumService,
 }
@@ -158,9 +263,10 @@ impl RequestHandler for AuthorizeHandler {
         conn: Arc<Connection>,
         req: Self::Request,
     ) {
-        let db: DbClient = toolbox.get_db();
+        let db = toolbox.get_db::<DbClient>();
         let accept_srv = self.accept_service;
         toolbox.spawn_response(ctx, async move {
+            let db = db?;
             if req.service_code != accept_srv {
                 bail!(CustomError::new(
                     StatusCode::FORBIDDEN,
@@ -171,21 +277,761 @@ impl RequestHandler for AuthorizeHandler {
                 ));
             }
             let auth_data = db
-                .fun_auth_authorize(FunAuthAuthorizeReq {
-                    username: req.username.to_string(),
-                    token: Uuid::from_str(&req.token)?,
+                .fun_auth_authorize_one(FunAuthAuthorizeReq {
+                    username: req.username.trim().to_ascii_lowercase(),
+                    token: parse_token(&req.token)?,
                     service: req.service_code,
                     device_id: req.device_id,
                     device_os: req.device_os,
                     ip_address: conn.address,
                 })
                 .await?;
-            let auth_data = &auth_data.rows[0];
 
             conn.user_id
                 .store(auth_data.user_id as _, Ordering::Relaxed);
+            conn.session_id
+                .store(auth_data.session_id, Ordering::Relaxed);
             conn.role.store(auth_data.role as _, Ordering::Relaxed);
+            if let Some(locale) = Locale::from_tag(&auth_data.preferred_language) {
+                conn.set_locale(locale);
+            }
             Ok(AuthorizeResponse { success: true })
         })
     }
 }
+
+fn parse_token(token: &str) -> Result<Uuid> {
+    Uuid::from_str(token)
+        .map_err(|_| CustomError::new(EnumErrorCode::InvalidToken, "Invalid token").into())
+}
+
+pub struct LogoutHandler;
+
+impl RequestHandler for LogoutHandler {
+    type Request = LogoutRequest;
+    type Response = LogoutResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            db.fun_auth_logout(FunAuthLogoutReq {
+                username: req.username.trim().to_ascii_lowercase(),
+                token: parse_token(&req.token)?,
+            })
+            .await?;
+            Ok(LogoutResponse { success: true })
+        })
+    }
+}
+
+pub struct LogoutAllHandler;
+
+impl RequestHandler for LogoutAllHandler {
+    type Request = LogoutAllRequest;
+    type Response = LogoutAllResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let row = db
+                .fun_auth_logout_all_one(FunAuthLogoutAllReq {
+                    username: req.username.trim().to_ascii_lowercase(),
+                    token: parse_token(&req.token)?,
+                })
+                .await?;
+            Ok(LogoutAllResponse {
+                revoked_sessions: row.revoked_sessions,
+            })
+        })
+    }
+}
+
+/// Who tries a password
+struct PasswordAttempt {
+    username: String,
+    ip_address: IpAddr,
+    device_id: Option<String>,
+    device_os: Option<String>,
+    captcha_token: Option<String>,
+}
+
+/// Records the attempt, counted as a failure until it is finished, and refuses it while its
+/// username or address is locked out, see `lib::lockout`. Returns the id of the attempt
+async fn begin_attempt(
+    db: &DbClient,
+    lockout: &LockoutConfig,
+    attempt: &PasswordAttempt,
+) -> Result<i64> {
+    // counted and recorded at once, so concurrent attempts cannot all pass the same count
+    let failures = db
+        .fun_auth_begin_login_attempt_one(FunAuthBeginLoginAttemptReq {
+            username: attempt.username.clone(),
+            ip_address: attempt.ip_address,
+            device_id: attempt.device_id.clone(),
+            device_os: attempt.device_os.clone(),
+            username_window: seconds(lockout.username.window),
+            ip_window: seconds(lockout.ip.window),
+        })
+        .await?;
+    let now = Utc::now();
+    let states = [
+        lockout.username.check(
+            failures.username_failures as _,
+            failures.username_last_failure,
+            now,
+        ),
+        lockout
+            .ip
+            .check(failures.ip_failures as _, failures.ip_last_failure, now),
+    ];
+    let blocked_until = states
+        .iter()
+        .filter_map(|x| match x {
+            Lockout::Blocked { until } => Some(*until),
+            _ => None,
+        })
+        .max();
+    let refusal = if let Some(until) = blocked_until {
+        let retry_after = ((until - now).num_milliseconds() + 999) / 1000;
+        Some(
+            CustomError::new(
+                EnumErrorCode::TemporarilyUnavailable,
+                "Too many failed logins",
+            )
+            .retry_after(retry_after as _),
+        )
+    } else if states.contains(&Lockout::CaptchaRequired) {
+        let solved = match (&lockout.captcha, &attempt.captcha_token) {
+            (Some(captcha), Some(token)) => captcha.verify(token, attempt.ip_address).await?,
+            _ => false,
+        };
+        (!solved).then(|| {
+            CustomError::new(
+                EnumErrorCode::CaptchaRequired,
+                "Too many failed logins, a CAPTCHA has to be solved",
+            )
+        })
+    } else {
+        None
+    };
+    if let Some(refusal) = refusal {
+        // refused attempts do not prolong the lockout
+        finish_attempt(db, failures.attempt_id, None).await?;
+        bail!(refusal);
+    }
+    Ok(failures.attempt_id)
+}
+
+async fn finish_attempt(
+    db: &DbClient,
+    attempt_id: i64,
+    is_password_ok: Option<bool>,
+) -> Result<()> {
+    db.fun_auth_finish_login_attempt(FunAuthFinishLoginAttemptReq {
+        attempt_id,
+        is_password_ok,
+    })
+    .await?;
+    Ok(())
+}
+
+/// Verifies a password unless the attempt is locked out. A wrong one stays recorded and fails
+/// with `InvalidPassword`, whether or not the user exists
+async fn verify_password(
+    db: &DbClient,
+    config: PasswordConfig,
+    lockout: &LockoutConfig,
+    attempt: PasswordAttempt,
+    password: String,
+) -> Result<(FunAuthGetPasswordHashRespRow, PasswordCheck)> {
+    let attempt_id = begin_attempt(db, lockout, &attempt).await?;
+    let stored = db
+        .fun_auth_get_password_hash_opt(FunAuthGetPasswordHashReq {
+            username: attempt.username.clone(),
+        })
+        .await?;
+    // verified here rather than in the database, which would only compare bytes
+    let check = match &stored {
+        Some(stored) => {
+            config
+                .verify_blocking(
+                    password,
+                    stored.password_hash.clone(),
+                    stored.password_salt.clone(),
+                )
+                .await?
+        }
+        None => config.verify_dummy_blocking(password).await?,
+    };
+    match stored {
+        Some(stored) if check.is_ok() => {
+            finish_attempt(db, attempt_id, Some(true)).await?;
+            Ok((stored, check))
+        }
+        // the same for unknown users, which are not revealed
+        _ => bail!(CustomError::new(
+            EnumErrorCode::InvalidPassword,
+            "Username or password is incorrect"
+        )),
+    }
+}
+
+pub struct ChangePasswordHandler {
+    pub password: PasswordConfig,
+    pub lockout: Arc<LockoutConfig>,
+}
+
+impl RequestHandler for ChangePasswordHandler {
+    type Request = ChangePasswordRequest;
+    type Response = ChangePasswordResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        let lockout = Arc::clone(&self.lockout);
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let username = req.username.trim().to_ascii_lowercase();
+            let attempt = PasswordAttempt {
+                username: username.clone(),
+                ip_address: conn.address,
+                device_id: Some(req.device_id.clone()),
+                device_os: Some(req.device_os.clone()),
+                captcha_token: None,
+            };
+            verify_password(&db, config, &lockout, attempt, req.old_password).await?;
+            db.fun_auth_change_password(FunAuthChangePasswordReq {
+                username,
+                is_old_password_ok: true,
+                new_password_hash: config.hash_blocking(req.new_password).await?,
+                device_id: req.device_id,
+                device_os: req.device_os,
+                ip_address: conn.address,
+            })
+            .await?;
+            Ok(ChangePasswordResponse { success: true })
+        })
+    }
+}
+
+pub struct ListRecoveryQuestionCatalogHandler;
+
+impl RequestHandler for ListRecoveryQuestionCatalogHandler {
+    type Request = ListRecoveryQuestionCatalogRequest;
+    type Response = ListRecoveryQuestionCatalogResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        _req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let questions = db
+                .fun_get_recovery_question_data(FunGetRecoveryQuestionDataReq {})
+                .await?
+                .rows
+                .into_iter()
+                .map(|x| RecoveryQuestionCatalogRow {
+                    question_id: x.question_id,
+                    content: x.content,
+                    category: x.category,
+                })
+                .collect();
+            Ok(ListRecoveryQuestionCatalogResponse { questions })
+        })
+    }
+}
+
+/// Answers match regardless of case and spacing
+fn normalize_answer(answer: &str) -> String {
+    answer
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+pub struct SetRecoveryQuestionsHandler {
+    pub password: PasswordConfig,
+    pub lockout: Arc<LockoutConfig>,
+}
+
+impl RequestHandler for SetRecoveryQuestionsHandler {
+    type Request = SetRecoveryQuestionsRequest;
+    type Response = SetRecoveryQuestionsResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        let lockout = Arc::clone(&self.lockout);
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let username = req.username.trim().to_ascii_lowercase();
+            if req.answers.iter().any(|x| normalize_answer(x).is_empty()) {
+                bail!(CustomError::new(
+                    EnumErrorCode::InvalidRecoveryQuestions,
+                    "Answers must not be empty",
+                ));
+            }
+            let attempt = PasswordAttempt {
+                username,
+                ip_address: conn.address,
+                device_id: None,
+                device_os: None,
+                captcha_token: None,
+            };
+            let (stored, _) = verify_password(&db, config, &lockout, attempt, req.password).await?;
+            // hashed like passwords, answers are just as easy to guess offline
+            let mut answer_hashes = vec![];
+            for answer in req.answers {
+                answer_hashes.push(config.hash_blocking(normalize_answer(&answer)).await?);
+            }
+            db.fun_auth_set_recovery_questions(FunAuthSetRecoveryQuestionsReq {
+                user_id: stored.user_id,
+                question_ids: req.question_ids,
+                answer_hashes,
+            })
+            .await?;
+            Ok(SetRecoveryQuestionsResponse { success: true })
+        })
+    }
+}
+
+pub struct GetMyRecoveryQuestionsHandler;
+
+impl RequestHandler for GetMyRecoveryQuestionsHandler {
+    type Request = GetMyRecoveryQuestionsRequest;
+    type Response = GetMyRecoveryQuestionsResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let questions = db
+                .fun_auth_get_recovery_questions(FunAuthGetRecoveryQuestionsReq {
+                    username: req.username.trim().to_ascii_lowercase(),
+                })
+                .await?
+                .rows
+                .into_iter()
+                .map(|x| RecoveryQuestionRow {
+                    question_id: x.question_id,
+                    question: x.question,
+                })
+                .collect();
+            Ok(GetMyRecoveryQuestionsResponse { questions })
+        })
+    }
+}
+
+pub struct SubmitRecoveryAnswersHandler {
+    pub password: PasswordConfig,
+    pub recovery: RecoveryConfig,
+}
+
+impl RequestHandler for SubmitRecoveryAnswersHandler {
+    type Request = SubmitRecoveryAnswersRequest;
+    type Response = SubmitRecoveryAnswersResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        let recovery = self.recovery;
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let stored = db
+                .fun_auth_begin_recovery_attempt(FunAuthBeginRecoveryAttemptReq {
+                    username: req.username.trim().to_ascii_lowercase(),
+                    ip_address: conn.address,
+                    max_failed_attempts: recovery.max_failed_attempts as _,
+                    attempt_window: seconds(recovery.attempt_window),
+                })
+                .await?
+                .rows;
+            let mut submitted = req.question_ids.clone();
+            let mut expected: Vec<i32> = stored.iter().map(|x| x.question_id).collect();
+            submitted.sort_unstable();
+            expected.sort_unstable();
+            if submitted != expected || req.question_ids.len() != req.answers.len() {
+                bail!(CustomError::new(
+                    EnumErrorCode::MustSubmitAllRecoveryQuestions,
+                    "Every recovery question must be answered once",
+                ));
+            }
+            let mut is_answers_ok = true;
+            for (question_id, answer) in req.question_ids.iter().zip(req.answers) {
+                let hash = stored
+                    .iter()
+                    .find(|x| x.question_id == *question_id)
+                    .unwrap();
+                let check = config
+                    .verify_blocking(normalize_answer(&answer), hash.answer_hash.clone(), None)
+                    .await?;
+                is_answers_ok &= check.is_ok();
+            }
+            let reset_token = Uuid::new_v4();
+            let row = db
+                .fun_submit_recovery_answers_one(FunSubmitRecoveryAnswersReq {
+                    attempt_id: stored[0].attempt_id,
+                    user_id: stored[0].user_id,
+                    is_answers_ok,
+                    password_reset_token: reset_token,
+                    token_lifetime: seconds(recovery.reset_token_lifetime),
+                })
+                .await?;
+            let reset_token_expires_at = match row.reset_token_valid {
+                Some(x) if is_answers_ok => x,
+                _ => bail!(CustomError::new(
+                    EnumErrorCode::WrongRecoveryAnswers,
+                    "Recovery answers do not match",
+                )),
+            };
+            Ok(SubmitRecoveryAnswersResponse {
+                reset_token: reset_token.to_string(),
+                reset_token_expires_at,
+            })
+        })
+    }
+}
+
+pub struct ResetPasswordHandler {
+    pub password: PasswordConfig,
+}
+
+impl RequestHandler for ResetPasswordHandler {
+    type Request = ResetPasswordRequest;
+    type Response = ResetPasswordResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        _conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let reset_token = Uuid::from_str(&req.reset_token).map_err(|_| {
+                CustomError::new(EnumErrorCode::InvalidRecoveryToken, "Invalid reset token")
+            })?;
+            db.fun_auth_reset_password(FunAuthResetPasswordReq {
+                username: req.username.trim().to_ascii_lowercase(),
+                new_password_hash: config.hash_blocking(req.new_password).await?,
+                reset_token,
+            })
+            .await?;
+            Ok(ResetPasswordResponse { success: true })
+        })
+    }
+}
+
+/// What `fun_auth_use_two_factor_code` is asked to accept for `code`, a TOTP code verified
+/// against `secret` or else a recovery code
+fn two_factor_code(
+    user_id: i64,
+    ip_address: IpAddr,
+    secret: &[u8],
+    code: &str,
+    config: &TotpConfig,
+) -> FunAuthUseTwoFactorCodeReq {
+    let code = code.trim();
+    let (totp_step, recovery_code_hash) = if totp::is_totp_code(code) {
+        (totp::verify(secret, code, Utc::now()), None)
+    } else {
+        (None, Some(totp::hash_recovery_code(code)))
+    };
+    FunAuthUseTwoFactorCodeReq {
+        user_id,
+        ip_address,
+        totp_step,
+        recovery_code_hash,
+        max_failed_attempts: config.max_failed_attempts as _,
+        attempt_window: seconds(config.attempt_window),
+    }
+}
+
+fn invalid_two_factor_code() -> Error {
+    CustomError::new(
+        EnumErrorCode::InvalidTwoFactorCode,
+        "Two-factor code is incorrect",
+    )
+    .into()
+}
+
+pub struct LoginVerify2FAHandler {
+    pub session: SessionConfig,
+    pub totp: Arc<TotpConfig>,
+}
+
+impl RequestHandler for LoginVerify2FAHandler {
+    type Request = LoginVerify2FARequest;
+    type Response = LoginVerify2FAResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let session = self.session;
+        let totp = Arc::clone(&self.totp);
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let username = req.username.trim().to_ascii_lowercase();
+            let token = Uuid::from_str(&req.challenge_token).map_err(|_| {
+                CustomError::new(
+                    EnumErrorCode::InvalidLoginChallenge,
+                    "Invalid challenge token",
+                )
+            })?;
+            let challenge = db
+                .fun_auth_get_login_challenge_one(FunAuthGetLoginChallengeReq {
+                    username: username.clone(),
+                    token,
+                })
+                .await?;
+            // on its own, so a wrong code stays recorded
+            let code = two_factor_code(
+                challenge.user_id,
+                conn.address,
+                &challenge.totp_secret,
+                &req.code,
+                &totp,
+            );
+            if !db.fun_auth_use_two_factor_code_one(code).await?.accepted {
+                return Err(invalid_two_factor_code());
+            }
+            let tx = db.begin(IsolationLevel::ReadCommitted).await?;
+            tx.fun_auth_complete_login_challenge(FunAuthCompleteLoginChallengeReq {
+                challenge_id: challenge.challenge_id,
+            })
+            .await?;
+            let access_token = Uuid::new_v4();
+            let refresh_token = Uuid::new_v4();
+            let created = tx
+                .fun_auth_create_session_one(FunAuthCreateSessionReq {
+                    user_id: challenge.user_id,
+                    service: challenge.service,
+                    device_id: challenge.device_id,
+                    device_os: challenge.device_os,
+                    ip_address: conn.address,
+                    access_token,
+                    refresh_token,
+                    access_token_lifetime: seconds(session.access_token_lifetime),
+                    idle_timeout: seconds(session.idle_timeout),
+                    absolute_timeout: seconds(session.absolute_timeout),
+                    is_two_factor_ok: true,
+                })
+                .await?;
+            tx.commit().await?;
+            Ok(LoginVerify2FAResponse {
+                username,
+                user_public_id: challenge.user_public_id,
+                access_token: access_token.to_string(),
+                refresh_token: refresh_token.to_string(),
+                access_expires_at: created.access_expires_at,
+                expires_at: created.expires_at,
+            })
+        })
+    }
+}
+
+pub struct EnrollTotpHandler {
+    pub password: PasswordConfig,
+    pub lockout: Arc<LockoutConfig>,
+    pub totp: Arc<TotpConfig>,
+}
+
+impl RequestHandler for EnrollTotpHandler {
+    type Request = EnrollTotpRequest;
+    type Response = EnrollTotpResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        let lockout = Arc::clone(&self.lockout);
+        let totp = Arc::clone(&self.totp);
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let username = req.username.trim().to_ascii_lowercase();
+            let attempt = PasswordAttempt {
+                username: username.clone(),
+                ip_address: conn.address,
+                device_id: None,
+                device_os: None,
+                captcha_token: None,
+            };
+            let (stored, _) = verify_password(&db, config, &lockout, attempt, req.password).await?;
+            let secret = totp::generate_secret();
+            db.fun_auth_enroll_totp(FunAuthEnrollTotpReq {
+                user_id: stored.user_id,
+                secret: secret.clone(),
+            })
+            .await?;
+            Ok(EnrollTotpResponse {
+                secret: totp::encode_secret(&secret),
+                otpauth_uri: totp.otpauth_uri(&username, &secret),
+            })
+        })
+    }
+}
+
+pub struct ConfirmTotpHandler {
+    pub password: PasswordConfig,
+    pub lockout: Arc<LockoutConfig>,
+    pub totp: Arc<TotpConfig>,
+}
+
+impl RequestHandler for ConfirmTotpHandler {
+    type Request = ConfirmTotpRequest;
+    type Response = ConfirmTotpResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        let lockout = Arc::clone(&self.lockout);
+        let totp = Arc::clone(&self.totp);
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let attempt = PasswordAttempt {
+                username: req.username.trim().to_ascii_lowercase(),
+                ip_address: conn.address,
+                device_id: None,
+                device_os: None,
+                captcha_token: None,
+            };
+            let (stored, _) = verify_password(&db, config, &lockout, attempt, req.password).await?;
+            let pending_secret = match stored.totp_pending_secret {
+                Some(x) if stored.totp_secret.is_none() => x,
+                _ => bail!(CustomError::new(
+                    EnumErrorCode::TwoFactorNotEnabled,
+                    "No enrollment to confirm",
+                )),
+            };
+            let step = totp::verify(&pending_secret, req.code.trim(), Utc::now())
+                .ok_or_else(invalid_two_factor_code)?;
+            let recovery_codes: Vec<String> = (0..totp.recovery_codes)
+                .map(|_| totp::generate_recovery_code())
+                .collect();
+            db.fun_auth_confirm_totp(FunAuthConfirmTotpReq {
+                user_id: stored.user_id,
+                pending_secret,
+                step,
+                recovery_code_hashes: recovery_codes
+                    .iter()
+                    .map(|x| totp::hash_recovery_code(x))
+                    .collect(),
+            })
+            .await?;
+            Ok(ConfirmTotpResponse { recovery_codes })
+        })
+    }
+}
+
+pub struct DisableTotpHandler {
+    pub password: PasswordConfig,
+    pub lockout: Arc<LockoutConfig>,
+    pub totp: Arc<TotpConfig>,
+}
+
+impl RequestHandler for DisableTotpHandler {
+    type Request = DisableTotpRequest;
+    type Response = DisableTotpResponse;
+
+    fn handle(
+        &self,
+        toolbox: &Toolbox,
+        ctx: RequestContext,
+        conn: Arc<Connection>,
+        req: Self::Request,
+    ) {
+        let db = toolbox.get_db::<DbClient>();
+        let config = self.password;
+        let lockout = Arc::clone(&self.lockout);
+        let totp = Arc::clone(&self.totp);
+        toolbox.spawn_response(ctx, async move {
+            let db = db?;
+            let attempt = PasswordAttempt {
+                username: req.username.trim().to_ascii_lowercase(),
+                ip_address: conn.address,
+                device_id: None,
+                device_os: None,
+                captcha_token: None,
+            };
+            let (stored, _) = verify_password(&db, config, &lockout, attempt, req.password).await?;
+            let secret = stored.totp_secret.ok_or_else(|| {
+                CustomError::new(
+                    EnumErrorCode::TwoFactorNotEnabled,
+                    "Two-factor authentication is not enabled",
+                )
+            })?;
+            let code = two_factor_code(stored.user_id, conn.address, &secret, &req.code, &totp);
+            if !db.fun_auth_use_two_factor_code_one(code).await?.accepted {
+                return Err(invalid_two_factor_code());
+            }
+            db.fun_auth_disable_totp(FunAuthDisableTotpReq {
+                user_id: stored.user_id,
+            })
+            .await?;
+            Ok(DisableTotpResponse { success: true })
+        })
+    }
+}
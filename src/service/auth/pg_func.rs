@@ -9,8 +9,7 @@ pub fn get_auth_pg_func() -> Vec<ProceduralFunction> {
                 Field::new("username", Type::String),
                 Field::new("email", Type::String),
                 Field::new("phone", Type::String),
-                Field::new("password_hash", Type::Bytea),
-                Field::new("password_salt", Type::Bytea),
+                Field::new("password_hash", Type::String),
                 Field::new("age", Type::Int),
                 Field::new("preferred_language", Type::String),
                 Field::new("agreed_tos", Type::Boolean),
@@ -34,7 +33,6 @@ BEGIN
                        email,
                        phone_number,
                        password_hash,
-                       password_salt,
                        age,
                        preferred_language,
                        agreed_tos,
@@ -45,7 +43,6 @@ BEGIN
           a_email,
           a_phone,
           a_password_hash,
-          a_password_salt,
           a_age,
           a_preferred_language,
           a_agreed_tos,
@@ -55,12 +52,12 @@ BEGIN
   RETURN QUERY SELECT id_;
 END
         "#,
-        ),
+        )
+        .cardinality(Cardinality::One),
         ProceduralFunction::new(
             "fun_auth_authenticate",
             vec![
                 Field::new("username", Type::String),
-                Field::new("password_hash", Type::Bytea),
                 Field::new("service_code", Type::Int),
                 Field::new("device_id", Type::String),
                 Field::new("device_os", Type::String),
@@ -73,33 +70,26 @@ END
             r#"
 DECLARE
     is_blocked_     boolean;
-    is_password_ok_ boolean;
     _user_id        bigint;
     _user_public_id bigint;
     _role           enum_role;
 BEGIN
     ASSERT (a_ip_address NOTNULL AND a_device_id NOTNULL AND a_device_os NOTNULL AND
-            a_username NOTNULL AND a_password_hash NOTNULL AND a_service_code NOTNULL);
+            a_username NOTNULL AND a_service_code NOTNULL);
 
-    -- Looking up the user.
-    SELECT pkey_id, u.public_id, is_blocked, (password_hash = a_password_hash), u.role
-    INTO _user_id, _user_public_id, is_blocked_, is_password_ok_, _role
+    -- Looking up the user. The password was verified against fun_auth_get_password_hash.
+    SELECT pkey_id, u.public_id, is_blocked, u.role
+    INTO _user_id, _user_public_id, is_blocked_, _role
     FROM tbl.user u
     WHERE username = a_username;
 
-    -- Log the login attempt.
-    INSERT INTO tbl.login_attempt(fkey_user, username, password_hash, ip_address,
-                                  is_password_ok)
-    VALUES (_user_id, a_username, a_password_hash, a_ip_address, is_password_ok_);
-
-    -- Checking the block status and password, and updating the login info if ok.
+    -- The attempt was logged by fun_auth_begin_login_attempt.
+    -- Checking the block status and role, and updating the login info if ok.
     IF (_user_id ISNULL) THEN
         RAISE SQLSTATE 'R0007'; -- UnknownUser
     END IF;
     IF (is_blocked_) THEN
         RAISE SQLSTATE 'R0008'; -- BlockedUser
-    ELSIF (NOT is_password_ok_) THEN
-        RAISE SQLSTATE 'R0009';
     ELSEIF (_role NOT IN ('admin', 'developer') AND
             a_service_code = (SELECT code FROM api.ADMIN_SERVICE())) OR
            (_role NOT IN ('user', 'admin', 'developer') AND
@@ -108,7 +98,7 @@ BEGIN
     END IF;
     UPDATE tbl.user -- ping
     SET last_ip      = a_ip_address,
-        last_login   = EXTRACT(EPOCH FROM (NOW()))::bigint,
+        last_login   = NOW(),
         logins_count = logins_count + 1
     WHERE pkey_id = _user_id;
 
@@ -121,46 +111,159 @@ BEGIN
     RETURN QUERY SELECT _user_id, _user_public_id;
 END
         "#,
-        ),
+        )
+        .cardinality(Cardinality::One),
         ProceduralFunction::new(
-            "fun_auth_get_password_salt",
+            "fun_auth_get_password_hash",
             vec![Field::new("username", Type::String)],
-            vec![Field::new("salt", Type::Bytea)],
+            vec![
+                Field::new("user_id", Type::BigInt),
+                Field::new("password_hash", Type::String),
+                Field::new("password_salt", Type::optional(Type::Bytea)),
+                Field::new("totp_secret", Type::optional(Type::Bytea)),
+                Field::new("totp_pending_secret", Type::optional(Type::Bytea)),
+            ],
             r#"
-DECLARE
-  user_id bigint;
 BEGIN
   ASSERT (a_username NOTNULL);
 
-  -- Looking up the user.
-  SELECT pkey_id, u.password_salt
-  INTO user_id, salt
-  FROM tbl.user u
-  WHERE username = a_username;
-
-  IF (user_id ISNULL) THEN
-    RAISE SQLSTATE 'R0007'; -- UnknownUser
+  -- Looking up the user, no row if unknown. The salt is only set for legacy SHA-256 hashes.
+  RETURN QUERY SELECT u.pkey_id, u.password_hash, u.password_salt, u.totp_secret,
+                      u.totp_pending_secret
+               FROM tbl.user u
+               WHERE u.username = a_username;
+END
+            "#,
+        )
+        .cardinality(Cardinality::Optional),
+        ProceduralFunction::new(
+            "fun_auth_begin_login_attempt",
+            vec![
+                Field::new("username", Type::String),
+                Field::new("ip_address", Type::Inet),
+                Field::new("device_id", Type::optional(Type::String)),
+                Field::new("device_os", Type::optional(Type::String)),
+                Field::new("username_window", Type::Duration),
+                Field::new("ip_window", Type::Duration),
+            ],
+            vec![
+                Field::new("attempt_id", Type::BigInt),
+                Field::new("username_failures", Type::Int),
+                Field::new("username_last_failure", Type::optional(Type::TimestampTz)),
+                Field::new("ip_failures", Type::Int),
+                Field::new("ip_last_failure", Type::optional(Type::TimestampTz)),
+            ],
+            r#"
+DECLARE
+  attempt_id_            bigint;
+  username_failures_     integer;
+  username_last_failure_ timestamptz;
+  ip_failures_           integer;
+  ip_last_failure_       timestamptz;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_ip_address NOTNULL AND a_username_window NOTNULL AND
+          a_ip_window NOTNULL);
+  -- Concurrent attempts on the username or from the address wait for each other, so each one
+  -- counts the attempts before it. Always locked in this order.
+  PERFORM pg_advisory_xact_lock(hashtext('login_attempt_username'), hashtext(a_username));
+  PERFORM pg_advisory_xact_lock(hashtext('login_attempt_ip'), hashtext(host(a_ip_address)));
+  -- A successful login forgives the earlier failures of the username, not of the address.
+  SELECT COUNT(*), MAX(moment)
+  INTO username_failures_, username_last_failure_
+  FROM tbl.login_attempt
+  WHERE username = a_username
+    AND is_password_ok = FALSE
+    AND NOT is_cleared
+    AND moment > NOW() - a_username_window
+    AND moment > COALESCE((SELECT MAX(moment)
+                           FROM tbl.login_attempt
+                           WHERE username = a_username
+                             AND is_password_ok), '-infinity');
+  SELECT COUNT(*), MAX(moment)
+  INTO ip_failures_, ip_last_failure_
+  FROM tbl.login_attempt
+  WHERE ip_address = a_ip_address
+    AND is_password_ok = FALSE
+    AND NOT is_cleared
+    AND moment > NOW() - a_ip_window;
+  -- Counted as a failure until fun_auth_finish_login_attempt tells otherwise.
+  INSERT INTO tbl.login_attempt(fkey_user, username, ip_address, device_id, device_os,
+                                is_password_ok)
+  VALUES ((SELECT pkey_id FROM tbl.user WHERE username = a_username), a_username,
+          a_ip_address, a_device_id, a_device_os, FALSE)
+  RETURNING pkey_id INTO attempt_id_;
+  RETURN QUERY SELECT attempt_id_, username_failures_, username_last_failure_, ip_failures_,
+                      ip_last_failure_;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_finish_login_attempt",
+            vec![
+                Field::new("attempt_id", Type::BigInt),
+                Field::new("is_password_ok", Type::optional(Type::Boolean)),
+            ],
+            vec![],
+            r#"
+BEGIN
+  ASSERT (a_attempt_id NOTNULL);
+  -- NULL when the attempt was refused before the password was checked, which is not kept.
+  IF (a_is_password_ok ISNULL) THEN
+    DELETE FROM tbl.login_attempt WHERE pkey_id = a_attempt_id;
+  ELSE
+    UPDATE tbl.login_attempt SET is_password_ok = a_is_password_ok WHERE pkey_id = a_attempt_id;
   END IF;
-  RETURN QUERY SELECT salt;
 END
             "#,
         ),
         ProceduralFunction::new(
-            "fun_auth_set_token",
+            "fun_auth_rehash_password",
             vec![
                 Field::new("user_id", Type::BigInt),
-                Field::new("user_token", Type::UUID),
-                Field::new("admin_token", Type::UUID),
-                Field::new("service_code", Type::Int),
+                Field::new("old_password_hash", Type::String),
+                Field::new("new_password_hash", Type::String),
             ],
             vec![],
             r#"
+BEGIN
+  ASSERT (a_user_id NOTNULL AND a_old_password_hash NOTNULL AND a_new_password_hash NOTNULL);
+  -- Skipped if the password changed since it was verified.
+  UPDATE tbl.user
+  SET password_hash = a_new_password_hash,
+      password_salt = NULL
+  WHERE pkey_id = a_user_id
+    AND password_hash = a_old_password_hash;
+END
+            "#,
+        ),
+        ProceduralFunction::new(
+            "fun_auth_create_session",
+            vec![
+                Field::new("user_id", Type::BigInt),
+                Field::new("service", Type::enum_ref("service")),
+                Field::new("device_id", Type::String),
+                Field::new("device_os", Type::String),
+                Field::new("ip_address", Type::Inet),
+                Field::new("access_token", Type::UUID),
+                Field::new("refresh_token", Type::UUID),
+                Field::new("access_token_lifetime", Type::Duration),
+                Field::new("idle_timeout", Type::Duration),
+                Field::new("absolute_timeout", Type::Duration),
+                Field::new("is_two_factor_ok", Type::Boolean),
+            ],
+            vec![
+                Field::new("access_expires_at", Type::TimestampTz),
+                Field::new("expires_at", Type::TimestampTz),
+            ],
+            r#"
 DECLARE
-  rc_         integer;
   is_blocked_ boolean;
 BEGIN
-  ASSERT (a_user_id NOTNULL AND a_service_code NOTNULL AND a_user_token NOTNULL AND
-          a_admin_token NOTNULL);
+  ASSERT (a_user_id NOTNULL AND a_service NOTNULL AND a_device_id NOTNULL AND
+          a_device_os NOTNULL AND a_ip_address NOTNULL AND a_access_token NOTNULL AND
+          a_refresh_token NOTNULL AND a_access_token_lifetime NOTNULL AND
+          a_idle_timeout NOTNULL AND a_absolute_timeout NOTNULL AND a_is_two_factor_ok NOTNULL);
   -- Looking up the user.
   SELECT is_blocked INTO is_blocked_ FROM tbl.user WHERE pkey_id = a_user_id;
   IF (is_blocked_ ISNULL) THEN
@@ -168,24 +271,34 @@ BEGIN
   ELSIF (is_blocked_) THEN
     RAISE SQLSTATE 'R0008'; -- BlockedUser
   END IF;
-
-  -- Setting up the token.
-  IF a_service_code = (SELECT code FROM api.USER_SERVICE()) THEN
-    UPDATE tbl.user
-    SET user_token = a_user_token
-    WHERE pkey_id = a_user_id;
-  END IF;
-  IF a_service_code = (SELECT code FROM api.ADMIN_SERVICE())  THEN
-    UPDATE tbl.user
-    SET admin_token = a_admin_token
-    WHERE pkey_id = a_user_id;
+  -- Admins may require the second factor for a service.
+  IF (NOT a_is_two_factor_ok AND
+      COALESCE((SELECT is_2fa_required FROM tbl.service_setting WHERE service = a_service),
+               FALSE)) THEN
+    RAISE SQLSTATE 'R0016'; -- TwoFactorRequired
   END IF;
 
-  GET DIAGNOSTICS rc_ := ROW_COUNT;
-  ASSERT (rc_ = 1);
+  -- Cleaning up the sessions of the user that ended, and the one of this device, which the new
+  -- session replaces. Deleting it closes its connections.
+  DELETE
+  FROM tbl.session AS s
+  WHERE s.fkey_user = a_user_id
+    AND (s.expires_at <= NOW() OR s.last_seen_at + s.idle_timeout <= NOW() OR
+         (s.service = a_service AND s.device_id = a_device_id));
+
+  RETURN QUERY INSERT INTO tbl.session AS s (fkey_user, service, device_id, device_os,
+                                             ip_address, access_token, refresh_token,
+                                             access_expires_at, idle_timeout, expires_at,
+                                             is_two_factor_ok)
+               VALUES (a_user_id, a_service, a_device_id, a_device_os, a_ip_address,
+                       a_access_token, a_refresh_token,
+                       LEAST(NOW() + a_access_token_lifetime, NOW() + a_absolute_timeout),
+                       a_idle_timeout, NOW() + a_absolute_timeout, a_is_two_factor_ok)
+               RETURNING s.access_expires_at, s.expires_at;
 END
             "#,
-        ),
+        )
+        .cardinality(Cardinality::One),
         ProceduralFunction::new(
             "fun_auth_authorize",
             vec![
@@ -199,62 +312,196 @@ END
             vec![
                 Field::new("user_id", Type::BigInt),
                 Field::new("role", Type::enum_ref("role")),
+                Field::new("preferred_language", Type::String),
+                Field::new("session_id", Type::BigInt),
             ],
             r#"
 DECLARE
-    rc_          integer;
-    is_token_ok_ boolean;
-    user_id_     bigint;
-    role_        enum_role;
+    user_id_            bigint;
+    role_               enum_role;
+    preferred_language_ varchar;
+    session_id_         bigint;
+    is_session_ok_      boolean;
+    is_token_ok_        boolean;
+    is_blocked_         boolean;
 
 BEGIN
     ASSERT (a_username NOTNULL AND a_token NOTNULL AND a_service NOTNULL AND
             a_device_id NOTNULL AND a_device_os NOTNULL);
 
-    -- Looking up the user
-    CASE a_service
-        WHEN 'user'::enum_service
-            THEN SELECT pkey_id, u.role, (user_token = a_token)
-                 INTO user_id_, role_, is_token_ok_
-                 FROM tbl.user AS u
-                 WHERE username = a_username;
-        WHEN 'admin'::enum_service
-            THEN SELECT pkey_id, u.role, (admin_token = a_token)
-                 INTO user_id_, role_, is_token_ok_
-                 FROM tbl.user AS u
-                 WHERE username = a_username;
-        ELSE RAISE SQLSTATE 'R0001'; -- InvalidArgument
-        END CASE;
-    GET DIAGNOSTICS rc_ := ROW_COUNT;
-    IF (rc_ <> 1) THEN
+    -- Looking up the user, a blocked one keeps no access even with a valid token
+    SELECT pkey_id, u.role, u.preferred_language, u.is_blocked
+    INTO user_id_, role_, preferred_language_, is_blocked_
+    FROM tbl.user AS u
+    WHERE username = a_username;
+    IF (user_id_ ISNULL) THEN
         RAISE SQLSTATE 'R0007'; -- UnknownUser
     END IF;
+    IF (is_blocked_) THEN
+        RAISE SQLSTATE 'R0008'; -- BlockedUser
+    END IF;
+
+    -- Looking up the session of the token
+    SELECT s.pkey_id,
+           (s.last_seen_at + s.idle_timeout > NOW() AND s.expires_at > NOW()),
+           (s.access_expires_at > NOW())
+    INTO session_id_, is_session_ok_, is_token_ok_
+    FROM tbl.session AS s
+    WHERE s.fkey_user = user_id_
+      AND s.service = a_service
+      AND s.access_token = a_token;
 
     -- Log the authorization attempt
     INSERT INTO tbl.authorization_attempt(fkey_user, ip_address, is_token_ok)
-    VALUES (user_id_, a_ip_address, is_token_ok_ NOTNULL AND is_token_ok_);
+    VALUES (user_id_, a_ip_address, session_id_ NOTNULL AND is_session_ok_ AND is_token_ok_);
 
     -- Validating the token
-    IF NOT is_token_ok_ OR is_token_ok_ IS NULL THEN
+    IF (session_id_ ISNULL OR NOT is_session_ok_) THEN
         RAISE SQLSTATE 'R000A'; -- InvalidToken
+    ELSIF (NOT is_token_ok_) THEN
+        RAISE SQLSTATE 'R0013'; -- TokenExpired
     END IF;
 
-    -- Updating the device info
-    CASE a_service
-        WHEN 'user'::enum_service
-            THEN UPDATE tbl.user
-                 SET user_device_id = a_device_id
-                 WHERE pkey_id = user_id_
-                   AND user_token = a_token;
-        WHEN 'admin'::enum_service
-            THEN UPDATE tbl.user
-                 SET admin_device_id = a_device_id
-                 WHERE pkey_id = user_id_
-                   AND admin_token = a_token;
-        END CASE;
-    GET DIAGNOSTICS rc_ := ROW_COUNT;
-    ASSERT (rc_ = 1);
-    RETURN QUERY SELECT user_id_, role_;
+    -- Keeping the session alive
+    UPDATE tbl.session
+    SET last_seen_at = NOW(),
+        device_os    = a_device_os,
+        ip_address   = a_ip_address
+    WHERE pkey_id = session_id_;
+    RETURN QUERY SELECT user_id_, role_, preferred_language_, session_id_;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_refresh",
+            vec![
+                Field::new("refresh_token", Type::UUID),
+                Field::new("device_id", Type::String),
+                Field::new("device_os", Type::String),
+                Field::new("ip_address", Type::Inet),
+                Field::new("new_access_token", Type::UUID),
+                Field::new("new_refresh_token", Type::UUID),
+                Field::new("access_token_lifetime", Type::Duration),
+            ],
+            vec![
+                Field::new("username", Type::String),
+                Field::new("access_expires_at", Type::TimestampTz),
+                Field::new("expires_at", Type::TimestampTz),
+            ],
+            r#"
+DECLARE
+  session_id_    bigint;
+  username_      varchar;
+  is_session_ok_ boolean;
+  is_blocked_    boolean;
+BEGIN
+  ASSERT (a_refresh_token NOTNULL AND a_device_id NOTNULL AND a_device_os NOTNULL AND
+          a_ip_address NOTNULL AND a_new_access_token NOTNULL AND
+          a_new_refresh_token NOTNULL AND a_access_token_lifetime NOTNULL);
+  -- Looking up the session, which only the device it belongs to can refresh. Concurrent
+  -- refreshes wait here and then find the token replaced.
+  SELECT s.pkey_id,
+         u.username,
+         (s.last_seen_at + s.idle_timeout > NOW() AND s.expires_at > NOW()),
+         u.is_blocked
+  INTO session_id_, username_, is_session_ok_, is_blocked_
+  FROM tbl.session AS s
+         JOIN tbl.user AS u ON u.pkey_id = s.fkey_user
+  WHERE s.refresh_token = a_refresh_token
+    AND s.device_id = a_device_id
+  FOR UPDATE OF s;
+
+  IF (session_id_ ISNULL OR NOT is_session_ok_) THEN
+    RAISE SQLSTATE 'R000A'; -- InvalidToken
+  ELSIF (is_blocked_) THEN
+    RAISE SQLSTATE 'R0008'; -- BlockedUser
+  END IF;
+
+  -- Both tokens are replaced, so a refresh token works once.
+  RETURN QUERY UPDATE tbl.session AS s
+               SET access_token      = a_new_access_token,
+                   refresh_token     = a_new_refresh_token,
+                   access_expires_at = LEAST(NOW() + a_access_token_lifetime, s.expires_at),
+                   last_seen_at      = NOW(),
+                   device_os         = a_device_os,
+                   ip_address        = a_ip_address
+               WHERE s.pkey_id = session_id_
+               RETURNING username_, s.access_expires_at, s.expires_at;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_logout",
+            vec![
+                Field::new("username", Type::String),
+                Field::new("token", Type::UUID),
+            ],
+            vec![],
+            r#"
+DECLARE
+  rc_ integer;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_token NOTNULL);
+  -- An expired access token still ends its session.
+  DELETE
+  FROM tbl.session AS s
+    USING tbl.user AS u
+  WHERE u.pkey_id = s.fkey_user
+    AND u.username = a_username
+    AND s.access_token = a_token;
+  GET DIAGNOSTICS rc_ := ROW_COUNT;
+  IF (rc_ <> 1) THEN
+    RAISE SQLSTATE 'R000A'; -- InvalidToken
+  END IF;
+END
+            "#,
+        ),
+        ProceduralFunction::new(
+            "fun_auth_logout_all",
+            vec![
+                Field::new("username", Type::String),
+                Field::new("token", Type::UUID),
+            ],
+            vec![Field::new("revoked_sessions", Type::Int)],
+            r#"
+DECLARE
+  user_id_ bigint;
+  count_   integer;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_token NOTNULL);
+  -- Any session of the user that has not ended, its access token may be expired.
+  SELECT s.fkey_user
+  INTO user_id_
+  FROM tbl.session AS s
+         JOIN tbl.user AS u ON u.pkey_id = s.fkey_user
+  WHERE u.username = a_username
+    AND s.access_token = a_token
+    AND s.last_seen_at + s.idle_timeout > NOW()
+    AND s.expires_at > NOW();
+  IF (user_id_ ISNULL) THEN
+    RAISE SQLSTATE 'R000A'; -- InvalidToken
+  END IF;
+
+  DELETE FROM tbl.session WHERE fkey_user = user_id_;
+  GET DIAGNOSTICS count_ := ROW_COUNT;
+  RETURN QUERY SELECT count_;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_filter_sessions",
+            vec![Field::new("session_ids", Type::vec(Type::BigInt))],
+            vec![Field::new("session_id", Type::BigInt)],
+            r#"
+BEGIN
+  ASSERT (a_session_ids NOTNULL);
+  -- The ones that still exist.
+  RETURN QUERY SELECT s.pkey_id
+               FROM tbl.session AS s
+               WHERE s.pkey_id = ANY (a_session_ids);
 END
             "#,
         ),
@@ -262,8 +509,8 @@ END
             "fun_auth_change_password",
             vec![
                 Field::new("username", Type::String),
-                Field::new("old_password_hash", Type::Bytea),
-                Field::new("new_password_hash", Type::Bytea),
+                Field::new("is_old_password_ok", Type::Boolean),
+                Field::new("new_password_hash", Type::String),
                 Field::new("device_id", Type::String),
                 Field::new("device_os", Type::String),
                 Field::new("ip_address", Type::Inet),
@@ -271,36 +518,30 @@ END
             vec![],
             r#"
 DECLARE
-  is_blocked_     boolean;
-  is_password_ok_ boolean;
-  user_id_        bigint;
+  is_blocked_ boolean;
+  user_id_    bigint;
 BEGIN
   ASSERT (a_ip_address NOTNULL AND a_device_id NOTNULL AND a_device_os NOTNULL AND
-          a_username NOTNULL AND a_old_password_hash NOTNULL AND
+          a_username NOTNULL AND a_is_old_password_ok NOTNULL AND
           a_new_password_hash NOTNULL);
-  -- Looking up the user.
-  SELECT pkey_id, is_blocked, (password_hash = a_old_password_hash)
-  INTO user_id_, is_blocked_, is_password_ok_
+  -- Looking up the user. The old password was verified against fun_auth_get_password_hash.
+  SELECT pkey_id, is_blocked
+  INTO user_id_, is_blocked_
   FROM tbl.user u
   WHERE username = a_username;
 
-  -- Log the login attempt.
-  INSERT INTO tbl.login_attempt(fkey_user, username, password_hash, ip_address,
-                                device_id, device_os, is_password_ok)
-  VALUES (user_id_, a_username, a_old_password_hash, a_ip_address, a_device_id,
-          a_device_os, is_password_ok_);
-
+  -- The attempt was logged by fun_auth_begin_login_attempt.
   -- Checking the block status and password, and updating the login info if ok.
   IF (user_id_ NOTNULL) THEN
     IF (is_blocked_) THEN
       RAISE SQLSTATE 'R0008'; -- BlockedUser
-    ELSIF (NOT is_password_ok_) THEN
+    ELSIF (NOT a_is_old_password_ok) THEN
       RAISE SQLSTATE 'R0009'; -- InvalidPassword
     END IF;
-    ASSERT (a_old_password_hash <> a_new_password_hash);
 
     UPDATE tbl.user
-    SET password_hash = a_new_password_hash
+    SET password_hash = a_new_password_hash,
+        password_salt = NULL
     WHERE username = a_username;
   ELSE
       RAISE SQLSTATE 'R0007'; -- UnknownUser
@@ -318,10 +559,11 @@ END
             ],
             r#"
 BEGIN
-  RETURN QUERY SELECT q.pkey_id,
+  RETURN QUERY SELECT q.pkey_id::int,
                       q.content,
                       q.category
-               FROM tbl.recovery_question_data q;
+               FROM tbl.recovery_question_data q
+               ORDER BY q.pkey_id;
 END
             "#,
         ),
@@ -329,20 +571,25 @@ END
             "fun_auth_set_recovery_questions",
             vec![
                 Field::new("user_id", Type::BigInt),
-                Field::new("question_ids", Type::Vec(Box::new(Type::Int))),
-                Field::new("answers", Type::Vec(Box::new(Type::Int))),
+                Field::new("question_ids", Type::vec(Type::Int)),
+                Field::new("answer_hashes", Type::vec(Type::String)),
             ],
             vec![],
             r#"
-DECLARE
-  rc_ integer;
 BEGIN
-  ASSERT (a_user_id NOTNULL AND a_question_ids NOTNULL AND a_answers NOTNULL);
+  ASSERT (a_user_id NOTNULL AND a_question_ids NOTNULL AND a_answer_hashes NOTNULL);
+  -- Every question has to exist and be picked once, with one answer each.
+  IF (CARDINALITY(a_question_ids) = 0 OR
+      CARDINALITY(a_question_ids) <> CARDINALITY(a_answer_hashes) OR
+      (SELECT COUNT(DISTINCT x) FROM UNNEST(a_question_ids) x) <> CARDINALITY(a_question_ids) OR
+      (SELECT COUNT(*) FROM tbl.recovery_question_data WHERE pkey_id = ANY (a_question_ids)) <>
+      CARDINALITY(a_question_ids)) THEN
+    RAISE SQLSTATE 'R000R'; -- InvalidRecoveryQuestions
+  END IF;
   DELETE FROM tbl.recovery_question WHERE fkey_user = a_user_id;
   INSERT INTO tbl.recovery_question(fkey_user, fkey_question, answer)
-  VALUES (a_user_id, UNNEST(a_question_ids), UNNEST(a_answers));
-  GET DIAGNOSTICS rc_ := ROW_COUNT;
-  ASSERT (rc_ > 0);
+  SELECT a_user_id, t.question_id, t.answer_hash
+  FROM UNNEST(a_question_ids, a_answer_hashes) t(question_id, answer_hash);
 END
             "#,
         ),
@@ -354,7 +601,7 @@ END
                 Field::new("device_os", Type::String),
                 Field::new("ip_address", Type::Inet),
             ],
-            vec![Field::new("user_id", Type::Inet)],
+            vec![Field::new("user_id", Type::BigInt)],
             r#"
 DECLARE
   is_blocked_ boolean;
@@ -366,9 +613,8 @@ BEGIN
   INTO user_id_, is_blocked_
   FROM tbl.user
   WHERE username = LOWER(a_username);
-  INSERT INTO tbl.login_attempt(fkey_user, username, password_hash, ip_address,
-                                device_id, device_os)
-  VALUES (user_id_, a_username, '', a_ip_address, a_device_id, a_device_os);
+  INSERT INTO tbl.login_attempt(fkey_user, username, ip_address, device_id, device_os)
+  VALUES (user_id_, a_username, a_ip_address, a_device_id, a_device_os);
   IF (user_id_ ISNULL) THEN
     RAISE SQLSTATE 'R0007'; -- UnknownUser
   ELSEIF (is_blocked_) THEN
@@ -377,86 +623,363 @@ BEGIN
   RETURN QUERY SELECT user_id_;
 END
             "#,
-        ),
+        )
+        .cardinality(Cardinality::One),
         ProceduralFunction::new(
             "fun_auth_get_recovery_questions",
-            vec![Field::new("user_id", Type::BigInt)],
+            vec![Field::new("username", Type::String)],
             vec![
                 Field::new("question_id", Type::Int),
                 Field::new("question", Type::String),
             ],
             r#"
+DECLARE
+  user_id_ bigint;
 BEGIN
-  ASSERT (a_user_id NOTNULL);
-  RETURN QUERY SELECT qd.pkey_id,
+  ASSERT (a_username NOTNULL);
+  SELECT pkey_id INTO user_id_ FROM tbl.user WHERE username = a_username;
+  IF (user_id_ ISNULL) THEN
+    RAISE SQLSTATE 'R0007'; -- UnknownUser
+  END IF;
+  IF NOT EXISTS(SELECT * FROM tbl.recovery_question WHERE fkey_user = user_id_) THEN
+    RAISE SQLSTATE 'R0010'; -- RecoveryQuestionsNotSet
+  END IF;
+  RETURN QUERY SELECT qd.pkey_id::int,
                       qd.content
                FROM tbl.recovery_question_data qd
                       JOIN tbl.recovery_question q ON qd.pkey_id = q.fkey_question
-               WHERE q.fkey_user = a_user_id;
+               WHERE q.fkey_user = user_id_
+               ORDER BY q.pkey_id;
+END
+            "#,
+        ),
+        ProceduralFunction::new(
+            "fun_auth_begin_recovery_attempt",
+            vec![
+                Field::new("username", Type::String),
+                Field::new("ip_address", Type::Inet),
+                Field::new("max_failed_attempts", Type::Int),
+                Field::new("attempt_window", Type::Duration),
+            ],
+            vec![
+                Field::new("attempt_id", Type::BigInt),
+                Field::new("user_id", Type::BigInt),
+                Field::new("question_id", Type::Int),
+                Field::new("answer_hash", Type::String),
+            ],
+            r#"
+DECLARE
+  user_id_    bigint;
+  attempt_id_ bigint;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_ip_address NOTNULL AND a_max_failed_attempts NOTNULL AND
+          a_attempt_window NOTNULL);
+  -- Locked, so concurrent submissions for the user each count the ones before them.
+  SELECT pkey_id INTO user_id_ FROM tbl.user WHERE username = a_username FOR UPDATE;
+  IF (user_id_ ISNULL) THEN
+    RAISE SQLSTATE 'R0007'; -- UnknownUser
+  END IF;
+  -- Guessing answers is limited per user, whatever the address.
+  IF (SELECT COUNT(*)
+      FROM tbl.password_reset_attempt
+      WHERE fkey_user = user_id_
+        AND NOT is_answers_ok
+        AND initiated_at > NOW() - a_attempt_window) >= a_max_failed_attempts THEN
+    RAISE SQLSTATE 'R000B'; -- TemporarilyUnavailable
+  END IF;
+  IF NOT EXISTS(SELECT * FROM tbl.recovery_question WHERE fkey_user = user_id_) THEN
+    RAISE SQLSTATE 'R0010'; -- RecoveryQuestionsNotSet
+  END IF;
+  -- Counted as wrong answers until fun_submit_recovery_answers tells otherwise.
+  INSERT INTO tbl.password_reset_attempt(fkey_user, ip_address, is_answers_ok)
+  VALUES (user_id_, a_ip_address, FALSE)
+  RETURNING pkey_id INTO attempt_id_;
+  RETURN QUERY SELECT attempt_id_, user_id_, q.fkey_question::int, q.answer
+               FROM tbl.recovery_question q
+               WHERE q.fkey_user = user_id_;
 END
             "#,
         ),
         ProceduralFunction::new(
             "fun_submit_recovery_answers",
             vec![
+                Field::new("attempt_id", Type::BigInt),
                 Field::new("user_id", Type::BigInt),
-                Field::new("question_ids", Type::Vec(Box::new(Type::Int))),
-                Field::new("answers", Type::Vec(Box::new(Type::String))),
+                Field::new("is_answers_ok", Type::Boolean),
                 Field::new("password_reset_token", Type::UUID),
-                Field::new("token_valid", Type::Int),
+                Field::new("token_lifetime", Type::Duration),
+            ],
+            vec![Field::new(
+                "reset_token_valid",
+                Type::optional(Type::TimestampTz),
+            )],
+            r#"
+DECLARE
+  valid_until_ timestamptz;
+BEGIN
+  ASSERT (a_attempt_id NOTNULL AND a_user_id NOTNULL AND a_is_answers_ok NOTNULL AND
+          a_password_reset_token NOTNULL AND a_token_lifetime NOTNULL);
+  -- The answers were verified against fun_auth_begin_recovery_attempt, which recorded the
+  -- attempt as wrong.
+  IF (a_is_answers_ok) THEN
+    UPDATE tbl.password_reset_attempt
+    SET is_answers_ok = TRUE
+    WHERE pkey_id = a_attempt_id
+      AND fkey_user = a_user_id;
+    valid_until_ := NOW() + a_token_lifetime;
+    UPDATE tbl.user
+    SET password_reset_token = a_password_reset_token,
+        reset_token_valid    = valid_until_
+    WHERE pkey_id = a_user_id;
+  END IF;
+  RETURN QUERY SELECT valid_until_;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_reset_password",
+            vec![
+                Field::new("username", Type::String),
+                Field::new("new_password_hash", Type::String),
+                Field::new("reset_token", Type::UUID),
             ],
             vec![],
             r#"
 DECLARE
-  correct_answers_ varchar[];
+  user_id_ bigint;
 BEGIN
-  IF (SELECT COUNT(pkey_id) FROM tbl.recovery_question WHERE fkey_user = a_user_id) !=
-     CARDINALITY(a_question_ids) THEN
-    RAISE SQLSTATE 'R0011'; -- MustSubmitAllRecoveryQuestions
+  ASSERT (a_username NOTNULL AND a_new_password_hash NOTNULL AND a_reset_token NOTNULL);
+  UPDATE tbl.user
+  SET password_hash        = a_new_password_hash,
+      password_salt        = NULL,
+      password_reset_token = NULL,
+      reset_token_valid    = NULL,
+      last_password_reset  = NOW()
+  WHERE username = a_username
+    AND password_reset_token = a_reset_token
+    AND reset_token_valid > NOW()
+  RETURNING pkey_id INTO user_id_;
+  IF (user_id_ ISNULL) THEN
+    RAISE SQLSTATE 'R0012'; -- InvalidRecoveryToken
   END IF;
-  SELECT ARRAY_AGG(result.answer)
-  INTO correct_answers_
-  FROM (SELECT q.answer AS answer
-        FROM tbl.recovery_question q
-               JOIN UNNEST(a_question_ids) WITH ORDINALITY t(fkey_question, ord)
-                    USING (fkey_question)
-        WHERE fkey_user = a_user_id
-        ORDER BY t.ord) result;
-  IF a_answers != correct_answers_ THEN
-    RAISE SQLSTATE 'R000T'; -- WrongRecoveryAnswers
+  -- Whoever knew the old password is logged out.
+  DELETE FROM tbl.session WHERE fkey_user = user_id_;
+END
+            "#,
+        ),
+        ProceduralFunction::new(
+            "fun_auth_enroll_totp",
+            vec![
+                Field::new("user_id", Type::BigInt),
+                Field::new("secret", Type::Bytea),
+            ],
+            vec![],
+            r#"
+DECLARE
+  totp_secret_ bytea;
+BEGIN
+  ASSERT (a_user_id NOTNULL AND a_secret NOTNULL);
+  SELECT totp_secret INTO STRICT totp_secret_ FROM tbl.user WHERE pkey_id = a_user_id;
+  IF (totp_secret_ NOTNULL) THEN
+    RAISE SQLSTATE 'R001C'; -- TwoFactorAlreadyEnabled
   END IF;
-  UPDATE tbl.user
-  SET password_reset_token = a_password_reset_token,
-      reset_token_valid    = a_token_valid
-  WHERE pkey_id = a_user_id;
+  -- Replaces an enrollment that was never confirmed.
+  UPDATE tbl.user SET totp_pending_secret = a_secret WHERE pkey_id = a_user_id;
 END
             "#,
         ),
         ProceduralFunction::new(
-            "fun_auth_reset_password",
+            "fun_auth_confirm_totp",
             vec![
                 Field::new("user_id", Type::BigInt),
-                Field::new("new_password_hash", Type::Bytea),
-                Field::new("new_password_salt", Type::Bytea),
-                Field::new("reset_token", Type::UUID),
+                Field::new("pending_secret", Type::Bytea),
+                Field::new("step", Type::BigInt),
+                Field::new("recovery_code_hashes", Type::vec(Type::String)),
             ],
             vec![],
             r#"
 DECLARE
   rc_ integer;
 BEGIN
-  ASSERT (a_user_id NOTNULL AND a_new_password_hash NOTNULL AND a_reset_token NOTNULL);
+  ASSERT (a_user_id NOTNULL AND a_pending_secret NOTNULL AND a_step NOTNULL AND
+          a_recovery_code_hashes NOTNULL);
+  -- The code was verified against the pending secret, which may have been replaced since.
   UPDATE tbl.user
-  SET password_hash        = a_new_password_hash,
-      password_salt        = a_new_password_salt,
-      password_reset_token = NULL,
-      reset_token_valid    = NULL
+  SET totp_secret         = totp_pending_secret,
+      totp_pending_secret = NULL,
+      totp_last_step      = a_step
   WHERE pkey_id = a_user_id
-    AND password_reset_token = a_reset_token
-    AND reset_token_valid > EXTRACT(EPOCH FROM NOW())::bigint;
+    AND totp_secret ISNULL
+    AND totp_pending_secret = a_pending_secret;
   GET DIAGNOSTICS rc_ := ROW_COUNT;
   IF (rc_ <> 1) THEN
-    RAISE SQLSTATE 'R0012'; -- InvalidRecoveryToken
+    RAISE SQLSTATE 'R001D'; -- TwoFactorNotEnabled
+  END IF;
+  DELETE FROM tbl.totp_recovery_code WHERE fkey_user = a_user_id;
+  INSERT INTO tbl.totp_recovery_code(fkey_user, code_hash)
+  SELECT a_user_id, x
+  FROM UNNEST(a_recovery_code_hashes) x;
+END
+            "#,
+        ),
+        ProceduralFunction::new(
+            "fun_auth_disable_totp",
+            vec![Field::new("user_id", Type::BigInt)],
+            vec![],
+            r#"
+BEGIN
+  ASSERT (a_user_id NOTNULL);
+  UPDATE tbl.user
+  SET totp_secret         = NULL,
+      totp_pending_secret = NULL,
+      totp_last_step      = NULL
+  WHERE pkey_id = a_user_id;
+  DELETE FROM tbl.totp_recovery_code WHERE fkey_user = a_user_id;
+  DELETE FROM tbl.login_challenge WHERE fkey_user = a_user_id;
+  -- Sessions on services requiring the second factor no longer qualify.
+  DELETE
+  FROM tbl.session AS s
+  WHERE s.fkey_user = a_user_id
+    AND s.service IN (SELECT service FROM tbl.service_setting WHERE is_2fa_required);
+END
+            "#,
+        ),
+        ProceduralFunction::new(
+            "fun_auth_use_two_factor_code",
+            vec![
+                Field::new("user_id", Type::BigInt),
+                Field::new("ip_address", Type::Inet),
+                Field::new("totp_step", Type::optional(Type::BigInt)),
+                Field::new("recovery_code_hash", Type::optional(Type::String)),
+                Field::new("max_failed_attempts", Type::Int),
+                Field::new("attempt_window", Type::Duration),
+            ],
+            vec![Field::new("accepted", Type::Boolean)],
+            r#"
+DECLARE
+  rc_ integer := 0;
+BEGIN
+  ASSERT (a_user_id NOTNULL AND a_ip_address NOTNULL AND a_max_failed_attempts NOTNULL AND
+          a_attempt_window NOTNULL);
+  -- Locked, so concurrent codes for the user each count the failures before them.
+  PERFORM 1 FROM tbl.user WHERE pkey_id = a_user_id FOR UPDATE;
+  -- Guessing codes is limited per user, whatever the address or the login.
+  IF (SELECT COUNT(*)
+      FROM tbl.two_factor_failure
+      WHERE fkey_user = a_user_id
+        AND moment > NOW() - a_attempt_window) >= a_max_failed_attempts THEN
+    RAISE SQLSTATE 'R000B'; -- TemporarilyUnavailable
+  END IF;
+  -- A TOTP code verified for a step is accepted once, later steps only. A recovery code is
+  -- used up. Without either the code was wrong.
+  IF (a_totp_step NOTNULL) THEN
+    UPDATE tbl.user
+    SET totp_last_step = a_totp_step
+    WHERE pkey_id = a_user_id
+      AND totp_secret NOTNULL
+      AND (totp_last_step ISNULL OR totp_last_step < a_totp_step);
+    GET DIAGNOSTICS rc_ := ROW_COUNT;
+  ELSIF (a_recovery_code_hash NOTNULL) THEN
+    UPDATE tbl.totp_recovery_code
+    SET used_at = NOW()
+    WHERE fkey_user = a_user_id
+      AND code_hash = a_recovery_code_hash
+      AND used_at ISNULL;
+    GET DIAGNOSTICS rc_ := ROW_COUNT;
+  END IF;
+  -- Reported rather than raised, which would roll the record back.
+  IF (rc_ <> 1) THEN
+    INSERT INTO tbl.two_factor_failure(fkey_user, ip_address) VALUES (a_user_id, a_ip_address);
+  END IF;
+  RETURN QUERY SELECT rc_ = 1;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_create_login_challenge",
+            vec![
+                Field::new("user_id", Type::BigInt),
+                Field::new("service", Type::enum_ref("service")),
+                Field::new("device_id", Type::String),
+                Field::new("device_os", Type::String),
+                Field::new("ip_address", Type::Inet),
+                Field::new("token", Type::UUID),
+                Field::new("lifetime", Type::Duration),
+            ],
+            vec![Field::new("expires_at", Type::TimestampTz)],
+            r#"
+BEGIN
+  ASSERT (a_user_id NOTNULL AND a_service NOTNULL AND a_device_id NOTNULL AND
+          a_device_os NOTNULL AND a_ip_address NOTNULL AND a_token NOTNULL AND
+          a_lifetime NOTNULL);
+  DELETE FROM tbl.login_challenge AS c WHERE c.fkey_user = a_user_id AND c.expires_at <= NOW();
+  RETURN QUERY INSERT INTO tbl.login_challenge AS c (fkey_user, token, service, device_id,
+                                                     device_os, ip_address, expires_at)
+               VALUES (a_user_id, a_token, a_service, a_device_id, a_device_os, a_ip_address,
+                       NOW() + a_lifetime)
+               RETURNING c.expires_at;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_get_login_challenge",
+            vec![
+                Field::new("username", Type::String),
+                Field::new("token", Type::UUID),
+            ],
+            vec![
+                Field::new("challenge_id", Type::BigInt),
+                Field::new("user_id", Type::BigInt),
+                Field::new("user_public_id", Type::BigInt),
+                Field::new("service", Type::enum_ref("service")),
+                Field::new("device_id", Type::String),
+                Field::new("device_os", Type::String),
+                Field::new("totp_secret", Type::Bytea),
+            ],
+            r#"
+DECLARE
+  challenge_id_   bigint;
+  user_id_        bigint;
+  user_public_id_ bigint;
+  service_        enum_service;
+  device_id_      varchar;
+  device_os_      varchar;
+  totp_secret_    bytea;
+BEGIN
+  ASSERT (a_username NOTNULL AND a_token NOTNULL);
+  SELECT c.pkey_id, u.pkey_id, u.public_id, c.service, c.device_id, c.device_os, u.totp_secret
+  INTO challenge_id_, user_id_, user_public_id_, service_, device_id_, device_os_, totp_secret_
+  FROM tbl.login_challenge AS c
+         JOIN tbl.user AS u ON u.pkey_id = c.fkey_user
+  WHERE u.username = a_username
+    AND c.token = a_token
+    AND c.expires_at > NOW();
+  IF (challenge_id_ ISNULL OR totp_secret_ ISNULL) THEN
+    RAISE SQLSTATE 'R0017'; -- InvalidLoginChallenge
+  END IF;
+  RETURN QUERY SELECT challenge_id_, user_id_, user_public_id_, service_, device_id_,
+                      device_os_, totp_secret_;
+END
+            "#,
+        )
+        .cardinality(Cardinality::One),
+        ProceduralFunction::new(
+            "fun_auth_complete_login_challenge",
+            vec![Field::new("challenge_id", Type::BigInt)],
+            vec![],
+            r#"
+DECLARE
+  rc_ integer;
+BEGIN
+  ASSERT (a_challenge_id NOTNULL);
+  -- A challenge completes once.
+  DELETE FROM tbl.login_challenge WHERE pkey_id = a_challenge_id;
+  GET DIAGNOSTICS rc_ := ROW_COUNT;
+  IF (rc_ <> 1) THEN
+    RAISE SQLSTATE 'R0017'; -- InvalidLoginChallenge
   END IF;
 END
             "#,
@@ -1,5 +1,13 @@
 #[path = "auth/method.rs"]
 pub mod method;
+#[path = "user/method.rs"]
+pub mod user_method;
+#[path = "admin/method.rs"]
+pub mod admin_method;
 
-#[path = "auth/endpoints.rs"]
-pub mod endpoints;
+pub mod enums;
+pub mod servers;
+pub mod services;
+pub mod tables;
+
+pub use services::auth_endpoints as endpoints;
@@ -0,0 +1,18 @@
+use eyre::*;
+use lib::config::{load_config, Command, MigrateArgs};
+use lib::database::connect_to_database;
+use lib::log::setup_logs;
+use lib::migration::migrate;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let config = load_config("migrate".to_owned())?;
+    setup_logs(config.app.log_level)?;
+
+    let db = connect_to_database(config.db).await?;
+    let args = match config.command {
+        Some(Command::Migrate(args)) => args,
+        None => MigrateArgs::default(),
+    };
+    migrate(&db, &args).await
+}
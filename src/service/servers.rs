@@ -0,0 +1,247 @@
+use crate::admin_method::{
+    ClearLoginLockoutHandler, ListLoginLockoutsHandler, ListUsersHandler, RevokeSessionsHandler,
+    SetTwoFactorRequiredHandler,
+};
+use crate::enums::get_enums;
+use crate::method::{
+    AuthorizeHandler, ChangePasswordHandler, ConfirmTotpHandler, DisableTotpHandler,
+    EnrollTotpHandler, GetMyRecoveryQuestionsHandler, ListRecoveryQuestionCatalogHandler,
+    LoginHandler, LoginVerify2FAHandler, LogoutAllHandler, LogoutHandler, RefreshHandler,
+    ResetPasswordHandler, SetRecoveryQuestionsHandler, SignupHandler, SubmitRecoveryAnswersHandler,
+};
+use crate::services::admin_endpoints::{
+    endpoint_admin_clear_login_lockout, endpoint_admin_list_login_lockouts,
+    endpoint_admin_list_users, endpoint_admin_revoke_sessions,
+    endpoint_admin_set_two_factor_required,
+};
+use crate::services::auth_endpoints::{
+    endpoint_auth_authorize, endpoint_auth_change_password, endpoint_auth_confirm_totp,
+    endpoint_auth_disable_totp, endpoint_auth_enroll_totp, endpoint_auth_get_my_recovery_questions,
+    endpoint_auth_list_recovery_question_catalog, endpoint_auth_login,
+    endpoint_auth_login_verify_2fa, endpoint_auth_logout, endpoint_auth_logout_all,
+    endpoint_auth_refresh, endpoint_auth_reset_password, endpoint_auth_set_recovery_questions,
+    endpoint_auth_signup, endpoint_auth_submit_recovery_answers,
+};
+use crate::services::user_endpoints::endpoint_user_foo;
+use crate::tables::get_tables;
+use crate::user_method::FooHandler;
+use eyre::*;
+use gen::crud::get_crud_handler;
+use gen::database::{DbClient, FunAuthFilterSessionsReq};
+use gen::model::EnumService;
+use lib::config::{AppConfig, RecoveryConfig, SessionConfig};
+use lib::database::SimpleDbClient;
+use lib::lockout::LockoutConfig;
+use lib::password::PasswordConfig;
+use lib::toolbox::Toolbox;
+use lib::totp::TotpConfig;
+use lib::ws::{EndpointAuthController, WebsocketServer};
+use model::crud::get_crud_endpoints;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::*;
+
+/// Postgres channel notified with the id of every deleted session
+const SESSION_REVOKED_CHANNEL: &str = "session_revoked";
+
+/// The auth service with its handlers registered, ready to `listen`
+pub fn auth_server(
+    config: AppConfig,
+    password: PasswordConfig,
+    session: SessionConfig,
+    recovery: RecoveryConfig,
+    lockout: LockoutConfig,
+    totp: TotpConfig,
+    db: SimpleDbClient,
+) -> Result<WebsocketServer> {
+    let lockout = Arc::new(lockout);
+    let totp = Arc::new(totp);
+    let mut server = WebsocketServer::new(config);
+    server.add_database(db);
+    let auth_controller =
+        Arc::new(EndpointAuthController::new(server.get_toolbox()).with_enums(get_enums()));
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_login(),
+        LoginHandler {
+            password,
+            session,
+            lockout: Arc::clone(&lockout),
+            totp: Arc::clone(&totp),
+        },
+    );
+    auth_controller.add_auth_endpoint(endpoint_auth_refresh(), RefreshHandler { session });
+    auth_controller.add_auth_endpoint(endpoint_auth_signup(), SignupHandler { password });
+    auth_controller.add_auth_endpoint(endpoint_auth_logout(), LogoutHandler);
+    auth_controller.add_auth_endpoint(endpoint_auth_logout_all(), LogoutAllHandler);
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_change_password(),
+        ChangePasswordHandler {
+            password,
+            lockout: Arc::clone(&lockout),
+        },
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_list_recovery_question_catalog(),
+        ListRecoveryQuestionCatalogHandler,
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_set_recovery_questions(),
+        SetRecoveryQuestionsHandler {
+            password,
+            lockout: Arc::clone(&lockout),
+        },
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_get_my_recovery_questions(),
+        GetMyRecoveryQuestionsHandler,
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_submit_recovery_answers(),
+        SubmitRecoveryAnswersHandler { password, recovery },
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_reset_password(),
+        ResetPasswordHandler { password },
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_login_verify_2fa(),
+        LoginVerify2FAHandler {
+            session,
+            totp: Arc::clone(&totp),
+        },
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_enroll_totp(),
+        EnrollTotpHandler {
+            password,
+            lockout: Arc::clone(&lockout),
+            totp: Arc::clone(&totp),
+        },
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_confirm_totp(),
+        ConfirmTotpHandler {
+            password,
+            lockout: Arc::clone(&lockout),
+            totp: Arc::clone(&totp),
+        },
+    );
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_disable_totp(),
+        DisableTotpHandler {
+            password,
+            lockout,
+            totp,
+        },
+    );
+    server.add_auth_controller(auth_controller);
+    Ok(server)
+}
+
+/// Connections are authorized by `AuthorizeHandler` for `service`, and closed when their session
+/// ends
+fn authorized_server(
+    config: AppConfig,
+    db: SimpleDbClient,
+    service: EnumService,
+) -> WebsocketServer {
+    let mut server = WebsocketServer::new(config);
+    server.add_database(db.clone());
+    server.add_background_task(close_revoked_sessions(db, server.get_toolbox()));
+    let auth_controller =
+        Arc::new(EndpointAuthController::new(server.get_toolbox()).with_enums(get_enums()));
+    auth_controller.add_auth_endpoint(
+        endpoint_auth_authorize(),
+        AuthorizeHandler {
+            accept_service: service,
+        },
+    );
+    server.add_auth_controller(auth_controller);
+    server
+}
+
+pub fn user_server(config: AppConfig, db: SimpleDbClient) -> Result<WebsocketServer> {
+    let mut server = authorized_server(config, db, EnumService::User);
+    server.add_handler(endpoint_user_foo(), FooHandler);
+    Ok(server)
+}
+
+pub fn admin_server(
+    config: AppConfig,
+    lockout: LockoutConfig,
+    db: SimpleDbClient,
+) -> Result<WebsocketServer> {
+    let mut server = authorized_server(config, db, EnumService::Admin);
+    server.add_handler(endpoint_admin_list_users(), ListUsersHandler);
+    server.add_handler(endpoint_admin_revoke_sessions(), RevokeSessionsHandler);
+    server.add_handler(
+        endpoint_admin_list_login_lockouts(),
+        ListLoginLockoutsHandler {
+            lockout: Arc::new(lockout),
+        },
+    );
+    server.add_handler(
+        endpoint_admin_clear_login_lockout(),
+        ClearLoginLockoutHandler,
+    );
+    server.add_handler(
+        endpoint_admin_set_two_factor_required(),
+        SetTwoFactorRequiredHandler,
+    );
+    for schema in get_tables().iter().flat_map(get_crud_endpoints) {
+        let handler = get_crud_handler(&schema.name)
+            .with_context(|| format!("no handler generated for {}", schema.name))?;
+        server.add_handler_erased(schema, handler);
+    }
+    Ok(server)
+}
+
+/// Closes the connections whose session is deleted, for as long as the server runs
+async fn close_revoked_sessions(db: SimpleDbClient, toolbox: Toolbox) -> Result<()> {
+    loop {
+        if let Err(err) = listen_revoked_sessions(&db, &toolbox).await {
+            error!("Failed to listen for revoked sessions: {:?}", err);
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+async fn listen_revoked_sessions(db: &SimpleDbClient, toolbox: &Toolbox) -> Result<()> {
+    let mut notifications = db.listen(SESSION_REVOKED_CHANNEL).await?;
+    // sessions may have ended while not listening
+    let session_ids: Vec<i64> = toolbox
+        .get_connections()
+        .iter()
+        .map(|x| x.get_session_id())
+        .filter(|x| *x != 0)
+        .collect();
+    if !session_ids.is_empty() {
+        let alive: HashSet<i64> = DbClient::from(db.clone())
+            .fun_auth_filter_sessions(FunAuthFilterSessionsReq { session_ids })
+            .await?
+            .rows
+            .into_iter()
+            .map(|x| x.session_id)
+            .collect();
+        toolbox
+            .close_connections(|x| x.get_session_id() != 0 && !alive.contains(&x.get_session_id()));
+    }
+    while let Some(payload) = notifications.recv().await {
+        let session_id: i64 = match payload.parse() {
+            Ok(x) => x,
+            Err(_) => {
+                warn!("Invalid session id notified: {}", payload);
+                continue;
+            }
+        };
+        let closed = toolbox.close_connections(|x| x.get_session_id() == session_id);
+        if closed > 0 {
+            info!(
+                session_id,
+                closed, "Closed connections of a revoked session"
+            );
+        }
+    }
+    bail!("Stopped receiving notifications")
+}
@@ -0,0 +1,71 @@
+use model::types::*;
+
+/// Tables with generated DDL and CRUD functions, see `model::crud`. A table has to be declared
+/// after the tables it references
+pub fn get_tables() -> Vec<Table> {
+    vec![
+        Table::new(
+            "organization",
+            vec![
+                Column::new("pkey_id", Type::BigInt).sequence("seq_organization_id"),
+                Column::new("name", Type::String).filter(),
+                Column::new("country", Type::String).filter(),
+                Column::new("tax_id", Type::String),
+                Column::new("address", Type::String),
+                Column::new("note", Type::String),
+            ],
+        )
+        .endpoints(31000),
+        Table::new(
+            "asset",
+            vec![
+                Column::new("pkey_id", Type::BigInt).sequence("seq_asset_id"),
+                Column::new("short_name", Type::String).filter(),
+                Column::new("long_name", Type::String),
+                Column::new("description", Type::String),
+                Column::new("network_type", Type::String).filter(),
+                Column::new("contract_address", Type::String),
+                Column::new("precision", Type::Int),
+                Column::new("fkey_prototype", Type::optional(Type::BigInt))
+                    .references("asset_prototype", "pkey_id")
+                    .filter(),
+            ],
+        )
+        .endpoints(31010),
+        Table::new(
+            "support_ticket",
+            vec![
+                Column::new("pkey_id", Type::BigInt).sequence("seq_support_ticket_id"),
+                Column::new("fkey_user", Type::BigInt)
+                    .references("user", "pkey_id")
+                    .filter(),
+                Column::new("fkey_handler_user", Type::optional(Type::BigInt))
+                    .references("user", "pkey_id")
+                    .filter(),
+                Column::new("content", Type::String),
+                Column::new("response", Type::String),
+                Column::new("created_at", Type::TimestampTz)
+                    .default("NOW()")
+                    .time_format(TimeFormat::EpochMillis),
+                Column::new("updated_at", Type::TimestampTz)
+                    .default("NOW()")
+                    .on_update("NOW()")
+                    .time_format(TimeFormat::EpochMillis),
+            ],
+        )
+        .endpoints(31020),
+        Table::new(
+            "favorite_wallet",
+            vec![
+                Column::new("pkey_id", Type::BigInt).sequence("seq_favorite_wallet_id"),
+                Column::new("fkey_user", Type::BigInt)
+                    .references("user", "pkey_id")
+                    .filter(),
+                Column::new("fkey_wallet", Type::BigInt)
+                    .references("vault_wallet", "pkey_id")
+                    .filter(),
+                Column::new("user_pkey_id", Type::BigInt),
+            ],
+        ),
+    ]
+}
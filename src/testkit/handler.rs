@@ -0,0 +1,167 @@
+//! Calls a `RequestHandler` directly, capturing what it sends instead of writing to a connection
+use eyre::*;
+use lib::database::SimpleDbClient;
+use lib::handler::{RequestHandler, RequestHandlerErased};
+use lib::locale::Locale;
+use lib::toolbox::{RequestContext, Toolbox};
+use lib::ws::*;
+use model::endpoint::EndpointSchema;
+use serde::de::DeserializeOwned;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Everything a handler sent during one call, grouped by kind
+#[derive(Debug)]
+pub struct Captured<Resp> {
+    pub responses: Vec<Resp>,
+    pub streams: Vec<WsStreamResponse>,
+    pub errors: Vec<WsResponseError>,
+    pub logs: Vec<WsLogResponse>,
+    pub forwarded: Vec<WsForwardedResponse>,
+}
+impl<Resp> Captured<Resp> {
+    /// The response, failing if the handler sent an error or not exactly one response
+    pub fn into_response(mut self) -> Result<Resp> {
+        if let Some(err) = self.errors.first() {
+            bail!("handler failed with {} {}", err.code, err.reason);
+        }
+        ensure!(
+            self.responses.len() == 1,
+            "expected 1 response, got {}",
+            self.responses.len()
+        );
+        Ok(self.responses.remove(0))
+    }
+    /// The error, failing if the handler did not send exactly one
+    pub fn into_error(mut self) -> Result<WsResponseError> {
+        ensure!(
+            self.errors.len() == 1,
+            "expected 1 error, got {} and {} responses",
+            self.errors.len(),
+            self.responses.len()
+        );
+        Ok(self.errors.remove(0))
+    }
+}
+
+/// A `Toolbox` whose messages are recorded, with a fake connection from 127.0.0.1
+pub struct HandlerTest {
+    toolbox: Toolbox,
+    receiver: mpsc::Receiver<WsMessage>,
+    pub conn: Arc<Connection>,
+    seq: u32,
+}
+impl Default for HandlerTest {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel(1024);
+        Self {
+            toolbox: Toolbox::new(sender),
+            receiver,
+            conn: Arc::new(Connection::new(
+                1,
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                1,
+                Locale::default(),
+            )),
+            seq: 0,
+        }
+    }
+}
+impl HandlerTest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_db(mut self, db: SimpleDbClient) -> Self {
+        self.toolbox.set_db(db);
+        self
+    }
+    /// See `Toolbox::set_mock_db`
+    pub fn with_mock_db<T: Clone + Send + Sync + 'static>(mut self, db: T) -> Self {
+        self.toolbox.set_mock_db(db);
+        self
+    }
+    /// As if the connection had been authorized by the user
+    pub fn with_user(self, user_id: i64, role: u32) -> Self {
+        self.conn.user_id.store(user_id, Ordering::Relaxed);
+        self.conn.role.store(role, Ordering::Relaxed);
+        self
+    }
+    pub fn with_locale(self, locale: Locale) -> Self {
+        self.conn.set_locale(locale);
+        self
+    }
+    pub fn toolbox_mut(&mut self) -> &mut Toolbox {
+        &mut self.toolbox
+    }
+    fn next_context(&mut self, method: u32) -> RequestContext {
+        self.seq += 1;
+        RequestContext {
+            connection_id: self.conn.connection_id,
+            user_id: self.conn.get_user_id(),
+            seq: self.seq,
+            method,
+            log_id: self.conn.log_id,
+            locale: self.conn.get_locale(),
+        }
+    }
+    /// Calls the handler with a request that skips validation, then waits for the tasks it
+    /// spawned with `spawn_response`
+    pub async fn call<H>(&mut self, handler: &H, req: H::Request) -> Result<Captured<H::Response>>
+    where
+        H: RequestHandler,
+        H::Response: DeserializeOwned,
+    {
+        let ctx = self.next_context(0);
+        let conn = Arc::clone(&self.conn);
+        let tasks = self
+            .toolbox
+            .clone()
+            .collect_tasks(|toolbox| handler.handle(toolbox, ctx, conn, req));
+        self.capture(tasks).await
+    }
+    /// Calls the handler the way the server does, validating `params` against `schema` first
+    pub async fn call_erased(
+        &mut self,
+        handler: &dyn RequestHandlerErased,
+        schema: &EndpointSchema,
+        params: serde_json::Value,
+    ) -> Result<Captured<serde_json::Value>> {
+        let ctx = self.next_context(schema.code);
+        let conn = Arc::clone(&self.conn);
+        let tasks = self
+            .toolbox
+            .clone()
+            .collect_tasks(|toolbox| handler.handle(toolbox, ctx, conn, schema, params));
+        self.capture(tasks).await
+    }
+    async fn capture<Resp: DeserializeOwned>(
+        &mut self,
+        tasks: Vec<JoinHandle<()>>,
+    ) -> Result<Captured<Resp>> {
+        for task in tasks {
+            task.await?;
+        }
+        let mut captured = Captured {
+            responses: vec![],
+            streams: vec![],
+            errors: vec![],
+            logs: vec![],
+            forwarded: vec![],
+        };
+        while let Ok(msg) = self.receiver.try_recv() {
+            match msg.message {
+                WsResponse::Immediate(x) => {
+                    captured.responses.push(serde_json::from_value(x.params)?)
+                }
+                WsResponse::Stream(x) => captured.streams.push(x),
+                WsResponse::Error(x) => captured.errors.push(x),
+                WsResponse::Log(x) => captured.logs.push(x),
+                WsResponse::Forwarded(x) => captured.forwarded.push(x),
+            }
+        }
+        Ok(captured)
+    }
+}
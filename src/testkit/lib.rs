@@ -0,0 +1,529 @@
+//! Runs the services in-process against a throwaway database, for integration tests
+pub mod handler;
+
+use chrono::{DateTime, Utc};
+use eyre::*;
+use gen::error_code::EnumErrorCode;
+use gen::model::*;
+use iloverust::servers::{admin_server, auth_server, user_server};
+use iloverust::services::auth_endpoints::{
+    endpoint_auth_authorize, endpoint_auth_login, endpoint_auth_login_verify_2fa,
+    endpoint_auth_logout, endpoint_auth_logout_all, endpoint_auth_refresh, endpoint_auth_signup,
+};
+use lib::config::{AppConfig, RecoveryConfig, SessionConfig};
+use lib::database::{connect_to_database, DatabaseConfig, SimpleDbClient};
+use lib::error_code::check_raised_error_codes;
+use lib::lockout::LockoutConfig;
+use lib::migration::{load_migrations, run_migrations};
+use lib::password::PasswordConfig;
+use lib::totp::TotpConfig;
+use lib::ws::{encode_header, WebsocketServer, WsClient};
+use model::endpoint::EndpointSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_postgres::config::Host;
+use tokio_postgres::NoTls;
+
+/// Server to create the test databases on, in the libpq `key=value` format. Without it a cluster
+/// is initialized for each test, which `initdb` refuses to do as root
+pub const DATABASE_ENV: &str = "TESTKIT_DATABASE";
+/// Directory of `initdb` and `postgres`, defaults to `pg_config --bindir` and then `PATH`
+pub const PG_BIN_ENV: &str = "PG_BIN";
+
+pub const DEVICE_ID: &str = "testkit";
+pub const DEVICE_OS: &str = "linux";
+/// Password the tests sign up with
+pub const TEST_PASSWORD: &str = "AHJQ6X1H68SK8D9P6WW0";
+/// Cheap hashing for the auth service, the defaults take seconds in debug builds
+pub const PASSWORD_CONFIG: PasswordConfig = PasswordConfig {
+    memory_kib: 1024,
+    iterations: 1,
+    parallelism: 1,
+};
+
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// Fails unless `result` is the error `code` sent by a server
+pub fn ensure_code<T>(result: Result<T>, code: EnumErrorCode) -> Result<()> {
+    get_error(result, code)?;
+    Ok(())
+}
+/// Fails unless `result` is `TemporarilyUnavailable`, returning its `retry_after` in seconds
+pub fn ensure_retry_after<T>(result: Result<T>) -> Result<u32> {
+    let err = get_error(result, EnumErrorCode::TemporarilyUnavailable)?;
+    let (_, retry_after) = err.split_once("retry_after=").context("no retry_after")?;
+    Ok(retry_after.trim().parse()?)
+}
+fn get_error<T>(result: Result<T>, code: EnumErrorCode) -> Result<String> {
+    let err = match result {
+        Ok(_) => bail!("expected {:?}, but it succeeded", code),
+        Err(err) => err.to_string(),
+    };
+    ensure!(
+        err.contains(&format!("Error: {} ", code as u32)),
+        "expected {:?}, got {}",
+        code,
+        err
+    );
+    Ok(err)
+}
+
+fn unique_name(prefix: &str) -> String {
+    format!(
+        "{}_{}_{}",
+        prefix,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+fn pg_bin(name: &str) -> PathBuf {
+    if let Some(dir) = std::env::var_os(PG_BIN_ENV) {
+        return PathBuf::from(dir).join(name);
+    }
+    // distributions keep the server binaries out of PATH
+    if let Ok(output) = Command::new("pg_config").arg("--bindir").output() {
+        let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        if output.status.success() && dir.join(name).exists() {
+            return dir.join(name);
+        }
+    }
+    PathBuf::from(name)
+}
+
+fn to_database_config(config: &tokio_postgres::Config) -> DatabaseConfig {
+    let mut db = DatabaseConfig::new();
+    db.host = config.get_hosts().first().map(|x| match x {
+        Host::Tcp(host) => host.clone(),
+        Host::Unix(path) => path.display().to_string(),
+    });
+    db.port = Some(config.get_ports().first().copied().unwrap_or(5432));
+    db.user = config.get_user().map(|x| x.to_owned());
+    db.password = config
+        .get_password()
+        .map(|x| String::from_utf8_lossy(x).into_owned());
+    db.dbname = config.get_dbname().map(|x| x.to_owned());
+    db
+}
+
+async fn connect(config: &tokio_postgres::Config) -> Result<tokio_postgres::Client> {
+    let (client, connection) = config.connect(NoTls).await?;
+    tokio::spawn(connection);
+    Ok(client)
+}
+
+enum Backend {
+    /// A cluster of our own, stopped and deleted on drop
+    Cluster { dir: PathBuf, process: Child },
+    /// A database created on the server of `TESTKIT_DATABASE`, dropped on drop
+    Server {
+        admin: tokio_postgres::Config,
+        name: String,
+    },
+}
+
+/// A database with the migrations of `db/` applied, removed on drop
+pub struct TestDatabase {
+    pub config: DatabaseConfig,
+    backend: Backend,
+}
+impl TestDatabase {
+    pub async fn create() -> Result<Self> {
+        let this = match std::env::var(DATABASE_ENV) {
+            Ok(admin) => Self::create_on_server(admin.parse()?).await?,
+            Err(_) => Self::create_cluster().await?,
+        };
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../db"));
+        run_migrations(&this.connect().await?, &load_migrations(dir)?, false).await?;
+        Ok(this)
+    }
+    async fn create_on_server(mut admin: tokio_postgres::Config) -> Result<Self> {
+        if admin.get_dbname().is_none() {
+            admin.dbname("postgres");
+        }
+        let name = unique_name("testkit");
+        connect(&admin)
+            .await
+            .with_context(|| format!("failed to connect to {}", DATABASE_ENV))?
+            .batch_execute(&format!("CREATE DATABASE {}", name))
+            .await?;
+        let mut config = admin.clone();
+        config.dbname(&name);
+        Ok(Self {
+            config: to_database_config(&config),
+            backend: Backend::Server { admin, name },
+        })
+    }
+    async fn create_cluster() -> Result<Self> {
+        let dir = std::env::temp_dir().join(unique_name("testkit"));
+        std::fs::create_dir_all(&dir)?;
+        let data = dir.join("data");
+        let initdb = Command::new(pg_bin("initdb"))
+            .arg("-D")
+            .arg(&data)
+            .args(["-A", "trust", "-U", "postgres", "-E", "UTF8", "--no-sync"])
+            .output();
+        match initdb {
+            Ok(output) if output.status.success() => {}
+            result => {
+                let _ = std::fs::remove_dir_all(&dir);
+                let reason = match result {
+                    Ok(output) => String::from_utf8_lossy(&output.stderr).into_owned(),
+                    Err(err) => err.to_string(),
+                };
+                bail!(
+                    "initdb failed, install postgres or set {}: {}",
+                    DATABASE_ENV,
+                    reason.trim()
+                );
+            }
+        }
+        let log = std::fs::File::create(dir.join("postgres.log"))?;
+        // only listens on a socket in `dir`, so the port cannot clash
+        let process = Command::new(pg_bin("postgres"))
+            .arg("-D")
+            .arg(&data)
+            .arg("-k")
+            .arg(&dir)
+            .args(["-c", "listen_addresses=", "-p", "5432", "-F"])
+            .stdout(Stdio::null())
+            .stderr(log)
+            .spawn()?;
+        let mut config = tokio_postgres::Config::new();
+        config
+            .host_path(&dir)
+            .port(5432)
+            .user("postgres")
+            .dbname("postgres");
+        let mut this = Self {
+            config: to_database_config(&config),
+            backend: Backend::Cluster { dir, process },
+        };
+        this.wait_ready(&config).await?;
+        Ok(this)
+    }
+    async fn wait_ready(&mut self, config: &tokio_postgres::Config) -> Result<()> {
+        let Backend::Cluster { dir, process } = &mut self.backend else {
+            return Ok(());
+        };
+        for _ in 0..100 {
+            if let Some(status) = process.try_wait()? {
+                bail!(
+                    "postgres exited with {}: {}",
+                    status,
+                    std::fs::read_to_string(dir.join("postgres.log")).unwrap_or_default()
+                );
+            }
+            if connect(config).await.is_ok() {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        bail!("postgres did not accept connections in {}", dir.display())
+    }
+    pub async fn connect(&self) -> Result<SimpleDbClient> {
+        connect_to_database(self.config.clone()).await
+    }
+}
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        match &mut self.backend {
+            Backend::Cluster { dir, process } => {
+                // SIGINT is the fast shutdown, closing open connections
+                unsafe {
+                    libc::kill(process.id() as libc::pid_t, libc::SIGINT);
+                }
+                let _ = process.wait();
+                let _ = std::fs::remove_dir_all(dir);
+            }
+            Backend::Server { admin, name } => {
+                let admin = admin.clone();
+                let sql = format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", name);
+                // drop may run inside a runtime, which cannot be blocked on
+                let result = std::thread::spawn(move || -> Result<()> {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()?
+                        .block_on(async { Ok(connect(&admin).await?.batch_execute(&sql).await?) })
+                })
+                .join();
+                if let Ok(Err(err)) = result {
+                    eprintln!("failed to drop test database {}: {:?}", name, err);
+                }
+            }
+        }
+    }
+}
+
+/// The tokens of a completed login, from `Login` or `LoginVerify2FA`
+#[derive(Debug, Clone)]
+pub struct LoginSession {
+    pub username: String,
+    pub user_public_id: i64,
+    pub access_token: String,
+    pub refresh_token: String,
+    pub access_expires_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+impl TryFrom<LoginResponse> for LoginSession {
+    type Error = Error;
+    fn try_from(resp: LoginResponse) -> Result<Self> {
+        ensure!(
+            resp.challenge_token.is_none(),
+            "login of {} is challenged for a second factor",
+            resp.username
+        );
+        Ok(Self {
+            username: resp.username,
+            user_public_id: resp.user_public_id,
+            access_token: resp.access_token.context("no access_token")?,
+            refresh_token: resp.refresh_token.context("no refresh_token")?,
+            access_expires_at: resp.access_expires_at.context("no access_expires_at")?,
+            expires_at: resp.expires_at.context("no expires_at")?,
+        })
+    }
+}
+impl From<LoginVerify2FAResponse> for LoginSession {
+    fn from(resp: LoginVerify2FAResponse) -> Self {
+        Self {
+            username: resp.username,
+            user_public_id: resp.user_public_id,
+            access_token: resp.access_token,
+            refresh_token: resp.refresh_token,
+            access_expires_at: resp.access_expires_at,
+            expires_at: resp.expires_at,
+        }
+    }
+}
+
+/// The auth, user and admin services listening on ephemeral ports of 127.0.0.1, with the real
+/// handlers and a `TestDatabase`. Stopped on drop
+pub struct TestApp {
+    pub auth_url: String,
+    pub user_url: String,
+    pub admin_url: String,
+    pub db: TestDatabase,
+    tasks: Vec<JoinHandle<Result<()>>>,
+}
+impl TestApp {
+    pub async fn start() -> Result<Self> {
+        Self::start_with_lockout(LockoutConfig::default()).await
+    }
+    /// With other lockout policies than the default ones, for the auth and admin services
+    pub async fn start_with_lockout(lockout: LockoutConfig) -> Result<Self> {
+        let mut this = Self {
+            auth_url: String::new(),
+            user_url: String::new(),
+            admin_url: String::new(),
+            db: TestDatabase::create().await?,
+            tasks: vec![],
+        };
+        check_raised_error_codes(&this.db.connect().await?).await?;
+        this.auth_url = this
+            .spawn("auth", |config, db| {
+                auth_server(
+                    config,
+                    PASSWORD_CONFIG,
+                    SessionConfig::default(),
+                    RecoveryConfig::default(),
+                    lockout.clone(),
+                    TotpConfig::default(),
+                    db,
+                )
+            })
+            .await?;
+        this.user_url = this.spawn("user", user_server).await?;
+        this.admin_url = this
+            .spawn("admin", |config, db| admin_server(config, lockout, db))
+            .await?;
+        Ok(this)
+    }
+    async fn spawn(
+        &mut self,
+        name: &str,
+        build: impl FnOnce(AppConfig, SimpleDbClient) -> Result<WebsocketServer>,
+    ) -> Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let config = AppConfig {
+            name: name.to_owned(),
+            host: addr.ip().to_string(),
+            port: addr.port(),
+            ..Default::default()
+        };
+        let server = build(config, self.db.connect().await?)?;
+        self.tasks.push(tokio::spawn(server.listen_on(listener)));
+        Ok(format!("ws://{}", addr))
+    }
+    /// Connects to the auth service, the header being an encoded request as sent by
+    /// `encode_header`
+    pub async fn auth_client(&self, header: &str) -> Result<WsClient> {
+        WsClient::new(&self.auth_url, header).await
+    }
+    /// Calls an auth endpoint with `req` in the handshake header
+    pub async fn auth_request<Req: Serialize, Resp: DeserializeOwned>(
+        &self,
+        req: Req,
+        schema: EndpointSchema,
+    ) -> Result<Resp> {
+        let header = encode_header(req, schema)?;
+        self.auth_client(&header).await?.recv_resp().await
+    }
+    pub async fn signup(&self, username: &str, password: &str) -> Result<SignupResponse> {
+        let header = encode_header(
+            SignupRequest {
+                username: username.to_owned(),
+                password: password.to_owned(),
+                email: format!("{}@example.com", username),
+                phone: "+00123456".to_owned(),
+                agreed_tos: true,
+                agreed_privacy: true,
+            },
+            endpoint_auth_signup(),
+        )?;
+        self.auth_client(&header).await?.recv_resp().await
+    }
+    pub async fn login(
+        &self,
+        username: &str,
+        password: &str,
+        service: EnumService,
+    ) -> Result<LoginSession> {
+        self.login_on(DEVICE_ID, username, password, service).await
+    }
+    /// Logs in from another device than `DEVICE_ID`, which keeps the sessions of other devices
+    pub async fn login_on(
+        &self,
+        device_id: &str,
+        username: &str,
+        password: &str,
+        service: EnumService,
+    ) -> Result<LoginSession> {
+        self.login_challenged(device_id, username, password, service)
+            .await?
+            .try_into()
+    }
+    /// The response of `Login` as is, which may be a challenge for the second factor
+    pub async fn login_challenged(
+        &self,
+        device_id: &str,
+        username: &str,
+        password: &str,
+        service: EnumService,
+    ) -> Result<LoginResponse> {
+        let header = encode_header(
+            LoginRequest {
+                username: username.to_owned(),
+                password: password.to_owned(),
+                service_code: service,
+                device_id: device_id.to_owned(),
+                device_os: DEVICE_OS.to_owned(),
+                captcha_token: None,
+            },
+            endpoint_auth_login(),
+        )?;
+        self.auth_client(&header).await?.recv_resp().await
+    }
+    /// Completes a challenged login with a TOTP code or a recovery code
+    pub async fn login_verify_2fa(
+        &self,
+        challenge: &LoginResponse,
+        code: &str,
+    ) -> Result<LoginSession> {
+        let resp: LoginVerify2FAResponse = self
+            .auth_request(
+                LoginVerify2FARequest {
+                    username: challenge.username.clone(),
+                    challenge_token: challenge
+                        .challenge_token
+                        .clone()
+                        .context("login is not challenged")?,
+                    code: code.to_owned(),
+                },
+                endpoint_auth_login_verify_2fa(),
+            )
+            .await?;
+        Ok(resp.into())
+    }
+    pub async fn refresh(&self, refresh_token: &str) -> Result<RefreshResponse> {
+        let header = encode_header(
+            RefreshRequest {
+                refresh_token: refresh_token.to_owned(),
+                device_id: DEVICE_ID.to_owned(),
+                device_os: DEVICE_OS.to_owned(),
+            },
+            endpoint_auth_refresh(),
+        )?;
+        self.auth_client(&header).await?.recv_resp().await
+    }
+    pub async fn logout(&self, username: &str, token: &str) -> Result<LogoutResponse> {
+        let header = encode_header(
+            LogoutRequest {
+                username: username.to_owned(),
+                token: token.to_owned(),
+            },
+            endpoint_auth_logout(),
+        )?;
+        self.auth_client(&header).await?.recv_resp().await
+    }
+    pub async fn logout_all(&self, username: &str, token: &str) -> Result<LogoutAllResponse> {
+        let header = encode_header(
+            LogoutAllRequest {
+                username: username.to_owned(),
+                token: token.to_owned(),
+            },
+            endpoint_auth_logout_all(),
+        )?;
+        self.auth_client(&header).await?.recv_resp().await
+    }
+    /// A client of the user service, authorized with the token of a user login
+    pub async fn user_client(&self, login: &LoginSession) -> Result<WsClient> {
+        self.authorized_client(EnumService::User, &login.username, &login.access_token)
+            .await
+    }
+    /// A client of the admin service, authorized with the token of an admin login
+    pub async fn admin_client(&self, login: &LoginSession) -> Result<WsClient> {
+        self.authorized_client(EnumService::Admin, &login.username, &login.access_token)
+            .await
+    }
+    /// A client of the user or admin service, authorized with an access token of `username`
+    pub async fn authorized_client(
+        &self,
+        service: EnumService,
+        username: &str,
+        token: &str,
+    ) -> Result<WsClient> {
+        let url = match service {
+            EnumService::User => &self.user_url,
+            EnumService::Admin => &self.admin_url,
+            EnumService::Auth => bail!("the auth service takes no authorization"),
+        };
+        let header = encode_header(
+            AuthorizeRequest {
+                username: username.to_owned(),
+                token: token.to_owned(),
+                service_code: service,
+                device_id: DEVICE_ID.to_owned(),
+                device_os: DEVICE_OS.to_owned(),
+            },
+            endpoint_auth_authorize(),
+        )?;
+        let mut client = WsClient::new(url, &header).await?;
+        let resp: AuthorizeResponse = client.recv_resp().await?;
+        ensure!(resp.success, "authorization to {} failed", url);
+        Ok(client)
+    }
+}
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
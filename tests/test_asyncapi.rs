@@ -0,0 +1,105 @@
+use eyre::*;
+use serde_json::Value;
+
+fn read(name: &str) -> Result<Value> {
+    let path = format!("{}/docs/{}", env!("CARGO_MANIFEST_DIR"), name);
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+fn doc() -> Result<Value> {
+    read("asyncapi.json")
+}
+
+fn refs<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(x)) = map.get("$ref") {
+                found.push(x);
+            }
+            map.values().for_each(|x| refs(x, found));
+        }
+        Value::Array(items) => items.iter().for_each(|x| refs(x, found)),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_every_endpoint_has_messages() -> Result<()> {
+    let doc = doc()?;
+    assert_eq!(doc["asyncapi"], "2.6.0");
+    let services = read("services.json")?;
+    let services = services["services"].as_array().context("no services")?;
+    for service in services {
+        let name = service["name"].as_str().context("service has no name")?;
+        let endpoints = service["endpoints"]
+            .as_array()
+            .context("service has no endpoints")?;
+        let channel = &doc["channels"][format!("/{}", name)];
+        assert_eq!(channel["servers"][0], name);
+        let publish = channel["publish"]["message"]["oneOf"]
+            .as_array()
+            .context("publish has no messages")?;
+        let subscribe = channel["subscribe"]["message"]["oneOf"]
+            .as_array()
+            .context("subscribe has no messages")?;
+        assert_eq!(publish.len(), endpoints.len());
+        // a response per endpoint, then the error and log messages
+        assert_eq!(subscribe.len(), endpoints.len() + 2);
+        for endpoint in endpoints {
+            let endpoint_name = endpoint["name"].as_str().context("endpoint has no name")?;
+            for kind in ["Request", "Response"] {
+                let name = format!("{}{}", endpoint_name, kind);
+                let message = &doc["components"]["messages"][&name];
+                assert_eq!(message["x-method-code"], endpoint["code"], "{}", name);
+                assert_eq!(
+                    message["payload"]["properties"]["method"]["const"],
+                    endpoint["code"]
+                );
+                assert_eq!(message["payload"]["properties"]["params"]["title"], name);
+            }
+            let request = format!("#/components/messages/{}Request", endpoint_name);
+            assert!(publish.iter().any(|x| x["$ref"] == request.as_str()));
+        }
+        assert!(subscribe
+            .iter()
+            .any(|x| x["$ref"] == "#/components/messages/Error"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_refs_resolve() -> Result<()> {
+    let doc = doc()?;
+    let mut found = vec![];
+    refs(&doc, &mut found);
+    assert!(!found.is_empty());
+    for x in found {
+        let pointer = x
+            .strip_prefix('#')
+            .with_context(|| format!("{} is not a local reference", x))?;
+        ensure!(doc.pointer(pointer).is_some(), "{} does not resolve", x);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_error_envelope() -> Result<()> {
+    let doc = doc()?;
+    let payload = &doc["components"]["messages"]["Error"]["payload"];
+    for key in [
+        "method",
+        "code",
+        "seq",
+        "reason",
+        "log_id",
+        "retry_after",
+        "details",
+    ] {
+        assert!(
+            payload["properties"].get(key).is_some(),
+            "error has no {}",
+            key
+        );
+    }
+    assert_eq!(payload["properties"]["details"]["type"], "array");
+    Ok(())
+}
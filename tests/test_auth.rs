@@ -1,53 +1,101 @@
-pub mod tools;
-use crate::endpoints::*;
 use eyre::*;
+use gen::error_code::EnumErrorCode;
 use gen::model::*;
-use lib::utils::encode_header;
-use tools::*;
+use sha2::{Digest, Sha256};
+use testkit::{ensure_code, TestApp, TEST_PASSWORD};
 
-#[path = "../src/service/auth/endpoints.rs"]
-pub mod endpoints;
 #[tokio::test]
 async fn test_bad_login() -> Result<()> {
-    let mut client = get_ws_auth_client("").await?;
-    let res: LoginResponse = client.recv_resp().await?;
-    println!("{:?}", res);
+    let app = TestApp::start().await?;
+    let mut client = app.auth_client("").await?;
+    let res = client.recv_resp::<LoginResponse>().await;
+    ensure!(res.is_err(), "empty header accepted: {:?}", res);
     Ok(())
 }
 
 #[tokio::test]
-async fn test_login() -> Result<()> {
-    let mut client = get_ws_auth_client(&encode_header(
-        LoginRequest {
-            username: "pepe_pablo".to_string(),
-            password: "AHJQ6X1H68SK8D9P6WW0".to_string(),
-            service_code: EnumService::User as _,
-            device_id: "24787297130491616".to_string(),
-            device_os: "android".to_string(),
-        },
-        endpoint_auth_login(),
-    )?)
-    .await?;
-    let res: LoginResponse = client.recv_resp().await?;
-    println!("{:?}", res);
+async fn test_login_unknown_user() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    // an unknown user is not told apart from a wrong password
+    ensure_code(
+        app.login("pepe_nobody", TEST_PASSWORD, EnumService::User)
+            .await,
+        EnumErrorCode::InvalidPassword,
+    )?;
+    ensure_code(
+        app.login("pepe_pablo", "wrong password", EnumService::User)
+            .await,
+        EnumErrorCode::InvalidPassword,
+    )?;
     Ok(())
 }
 
 #[tokio::test]
 async fn test_signup() -> Result<()> {
-    let mut client = get_ws_auth_client(&encode_header(
-        SignupRequest {
-            username: "pepe_pablo".to_string(),
-            password: "AHJQ6X1H68SK8D9P6WW0".to_string(),
-            email: "pepe_pablo@defi.digital".to_string(),
-            phone: "+00123456".to_string(),
-            agreed_tos: true,
-            agreed_privacy: true,
-        },
-        endpoint_auth_signup(),
-    )?)
+    let app = TestApp::start().await?;
+    let res = app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    assert_eq!(res.username, "pepe_pablo");
+    ensure!(
+        app.signup("pepe_pablo", TEST_PASSWORD).await.is_err(),
+        "signed up the same username twice"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_login() -> Result<()> {
+    let app = TestApp::start().await?;
+    let signup = app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let res = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    assert_eq!(res.user_public_id, signup.user_public_id);
+    ensure!(
+        app.login("pepe_pablo", "wrong password", EnumService::User)
+            .await
+            .is_err(),
+        "logged in with a wrong password"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_login_upgrades_legacy_hash() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    // as stored before argon2id, a SHA-256 of the password and salt
+    let salt = b"0123456789abcdef";
+    let mut hasher = Sha256::new();
+    hasher.update(TEST_PASSWORD.as_bytes());
+    hasher.update(salt);
+    let legacy = hex::encode(hasher.finalize());
+    let db = app.db.connect().await?;
+    db.query(
+        "UPDATE tbl.user SET password_hash = $1, password_salt = $2 WHERE username = $3",
+        &[&legacy, &salt.as_slice(), &"pepe_pablo"],
+    )
     .await?;
-    let res: SignupResponse = client.recv_resp().await?;
-    println!("{:?}", res);
+
+    ensure!(
+        app.login("pepe_pablo", "wrong password", EnumService::User)
+            .await
+            .is_err(),
+        "logged in with a wrong password"
+    );
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let rows = db
+        .query(
+            "SELECT password_hash, password_salt FROM tbl.user WHERE username = $1",
+            &[&"pepe_pablo"],
+        )
+        .await?;
+    let hash: String = rows[0].get(0);
+    let salt: Option<Vec<u8>> = rows[0].get(1);
+    ensure!(hash.starts_with("$argon2id$"), "not rehashed: {}", hash);
+    ensure!(salt.is_none(), "legacy salt kept");
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
     Ok(())
 }
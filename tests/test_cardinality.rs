@@ -0,0 +1,64 @@
+use eyre::*;
+use gen::database::*;
+use lib::database::{expect_one, expect_optional};
+use std::sync::Arc;
+
+#[test]
+fn test_expect_one() -> Result<()> {
+    assert_eq!(expect_one("fun_a", vec![1])?, 1);
+    assert_eq!(
+        expect_one::<i32>("fun_a", vec![]).unwrap_err().to_string(),
+        "fun_a returned 0 rows, expected one"
+    );
+    assert_eq!(
+        expect_one("fun_a", vec![1, 2]).unwrap_err().to_string(),
+        "fun_a returned 2 rows, expected one"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_expect_optional() -> Result<()> {
+    assert_eq!(expect_optional::<i32>("fun_a", vec![])?, None);
+    assert_eq!(expect_optional("fun_a", vec![1])?, Some(1));
+    assert_eq!(
+        expect_optional("fun_a", vec![1, 2, 3])
+            .unwrap_err()
+            .to_string(),
+        "fun_a returned 3 rows, expected at most one"
+    );
+    Ok(())
+}
+
+fn row(pkey_id: i64) -> FunOrganizationGetRespRow {
+    FunOrganizationGetRespRow {
+        pkey_id,
+        name: "a".to_owned(),
+        country: "ES".to_owned(),
+        tax_id: "B00000000".to_owned(),
+        address: "Main 1".to_owned(),
+        note: "".to_owned(),
+    }
+}
+
+#[tokio::test]
+async fn test_generated_one() -> Result<()> {
+    // the mock answers with as many rows as the requested id
+    let mock = MockDbClient::new().on_fun_organization_get(|req| {
+        Ok(FunOrganizationGetResp {
+            rows: (0..req.pkey_id).map(row).collect(),
+        })
+    });
+    let db = DbClient::with_api(Arc::new(mock));
+    let get = |pkey_id| db.fun_organization_get_one(FunOrganizationGetReq { pkey_id });
+    assert_eq!(get(1).await?.pkey_id, 0);
+    assert_eq!(
+        get(0).await.err().context("0 rows")?.to_string(),
+        "fun_organization_get returned 0 rows, expected one"
+    );
+    assert_eq!(
+        get(2).await.err().context("2 rows")?.to_string(),
+        "fun_organization_get returned 2 rows, expected one"
+    );
+    Ok(())
+}
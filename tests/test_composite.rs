@@ -0,0 +1,61 @@
+use eyre::*;
+use gen::database::*;
+use gen::model::*;
+use testkit::TestDatabase;
+
+#[tokio::test]
+async fn test_composite_rows() -> Result<()> {
+    let database = TestDatabase::create().await?;
+    let db = DbClient::new(database.connect().await?);
+    for (public_id, username) in [(1, "pepe_a"), (2, "pepe_b")] {
+        db.fun_auth_signup_one(FunAuthSignupReq {
+            public_id,
+            username: username.to_owned(),
+            email: format!("{}@example.com", username),
+            phone: "+34600000000".to_owned(),
+            password_hash: "".to_owned(),
+            age: 30,
+            preferred_language: "en".to_owned(),
+            agreed_tos: true,
+            agreed_privacy: true,
+            ip_address: "127.0.0.1".parse()?,
+        })
+        .await?;
+    }
+    // a struct_list_users_response_row[] column reads as a Vec of the generated struct
+    let users = db
+        .fun_admin_list_users(FunAdminListUsersReq {
+            offset: 0,
+            limit: 10,
+        })
+        .await?
+        .rows
+        .pop()
+        .context("no row")?
+        .users;
+    assert_eq!(
+        users
+            .iter()
+            .map(|x| (x.user_public_id, x.username.as_str()))
+            .collect::<Vec<_>>(),
+        [(1, "pepe_a"), (2, "pepe_b")]
+    );
+    assert_eq!(users[0].email.as_deref(), Some("pepe_a@example.com"));
+    assert_eq!(users[0].given_name, None);
+    assert_eq!(users[0].last_login, None);
+    assert_eq!(users[0].role, EnumRole::User);
+
+    // and an empty page as an empty one
+    let users = db
+        .fun_admin_list_users(FunAdminListUsersReq {
+            offset: 10,
+            limit: 10,
+        })
+        .await?
+        .rows
+        .pop()
+        .context("no row")?
+        .users;
+    assert!(users.is_empty());
+    Ok(())
+}
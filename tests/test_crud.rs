@@ -0,0 +1,193 @@
+use eyre::*;
+use gen::crud::get_crud_handler;
+use gen::error_code::EnumErrorCode;
+use gen::model::*;
+use iloverust::tables::get_tables;
+use model::crud::*;
+use model::types::*;
+use testkit::{ensure_code, TestApp};
+
+fn widget() -> Table {
+    Table::new(
+        "widget",
+        vec![
+            Column::new("pkey_id", Type::BigInt).sequence("seq_widget_id"),
+            Column::new("name", Type::String).filter(),
+            Column::new("note", Type::optional(Type::String)),
+            Column::new("size", Type::Int).default("1"),
+            Column::new("updated_at", Type::TimestampTz).on_update("NOW()"),
+        ],
+    )
+    .endpoints(50000)
+}
+
+fn names(fields: &[Field]) -> Vec<String> {
+    fields.iter().map(|x| x.name.clone()).collect()
+}
+
+#[test]
+fn test_crud_functions() {
+    let table = widget();
+    let funcs = get_crud_functions(&table);
+    assert_eq!(
+        funcs.iter().map(|x| x.name.as_str()).collect::<Vec<_>>(),
+        [
+            "fun_widget_get",
+            "fun_widget_list",
+            "fun_widget_insert",
+            "fun_widget_update",
+            "fun_widget_delete"
+        ]
+    );
+    let [get, list, insert, update, delete] = &funcs[..] else {
+        unreachable!()
+    };
+    assert_eq!(names(&get.parameters), ["pkey_id"]);
+    assert!(matches!(get.cardinality, Cardinality::One));
+    // filters are optional, NULL matching every row
+    assert_eq!(names(&list.parameters), ["offset", "limit", "name"]);
+    assert!(matches!(list.parameters[2].ty, Type::Optional(_)));
+    assert!(matches!(list.cardinality, Cardinality::Many));
+    assert!(list.body.contains("($name ISNULL OR t.\"name\" = $name)"));
+    // columns with a sequence or default are left to the database, nullable ones come last
+    assert_eq!(names(&insert.parameters), ["name", "updated_at", "note"]);
+    assert!(matches!(insert.parameters[2].ty, Type::Optional(_)));
+    assert!(insert.body.contains("(\"name\", \"note\", \"updated_at\")"));
+    // set by every update rather than by the caller
+    assert_eq!(
+        names(&update.parameters),
+        ["pkey_id", "name", "note", "size"]
+    );
+    assert!(update.body.contains("\"updated_at\" = NOW()"));
+    assert!(update
+        .body
+        .contains("\"name\" = COALESCE($name, t.\"name\")"));
+    assert_eq!(names(&delete.returns), ["pkey_id"]);
+    for func in [get, update, delete] {
+        assert!(
+            func.body.contains("RAISE SQLSTATE 'R001H'"),
+            "{}",
+            func.name
+        );
+    }
+}
+
+#[test]
+fn test_crud_endpoints() {
+    let endpoints = get_crud_endpoints(&widget());
+    assert_eq!(
+        endpoints
+            .iter()
+            .map(|x| (x.name.as_str(), x.code))
+            .collect::<Vec<_>>(),
+        [
+            ("GetWidget", 50000),
+            ("ListWidgets", 50001),
+            ("CreateWidget", 50002),
+            ("UpdateWidget", 50003),
+            ("DeleteWidget", 50004)
+        ]
+    );
+    let list = &endpoints[1];
+    assert_eq!(list.parameters[1].constraints.maximum, Some(1000.0));
+    assert!(matches!(
+        &list.returns[0].ty,
+        Type::DataTable { name, .. } if name == "WidgetRow"
+    ));
+    assert!(get_crud_endpoints(&Table::new("secret", widget().columns)).is_empty());
+}
+
+#[test]
+fn test_crud_handlers() {
+    for table in get_tables() {
+        for endpoint in get_crud_endpoints(&table) {
+            assert!(
+                get_crud_handler(&endpoint.name).is_some(),
+                "{}",
+                endpoint.name
+            );
+        }
+    }
+    assert!(get_crud_handler("GetFavoriteWallet").is_none());
+}
+
+#[tokio::test]
+async fn test_organization_crud() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("admin_crud", testkit::TEST_PASSWORD).await?;
+    app.db
+        .connect()
+        .await?
+        .query(
+            "UPDATE tbl.user SET role = 'admin' WHERE username = 'admin_crud'",
+            &[],
+        )
+        .await?;
+    let admin = app
+        .login("admin_crud", testkit::TEST_PASSWORD, EnumService::Admin)
+        .await?;
+    let mut client = app.admin_client(&admin).await?;
+    let create = |name: &str, country: &str| CreateOrganizationRequest {
+        name: name.to_owned(),
+        country: country.to_owned(),
+        tax_id: "T1".to_owned(),
+        address: "Main 1".to_owned(),
+        note: "".to_owned(),
+    };
+    let acme: CreateOrganizationResponse = client.request(31002, create("acme", "es")).await?;
+    client
+        .request::<CreateOrganizationResponse>(31002, create("globex", "us"))
+        .await?;
+
+    let resp: ListOrganizationsResponse = client
+        .request(
+            31001,
+            ListOrganizationsRequest {
+                offset: 0,
+                limit: 10,
+                name: None,
+                country: Some("es".to_owned()),
+            },
+        )
+        .await?;
+    assert_eq!(resp.rows.len(), 1);
+    assert_eq!(resp.rows[0].name, "acme");
+
+    let resp: UpdateOrganizationResponse = client
+        .request(
+            31003,
+            UpdateOrganizationRequest {
+                pkey_id: acme.pkey_id,
+                name: None,
+                country: None,
+                tax_id: None,
+                address: None,
+                note: Some("customer".to_owned()),
+            },
+        )
+        .await?;
+    // only the given columns change
+    assert_eq!(resp.note, "customer");
+    assert_eq!(resp.name, "acme");
+
+    let get = || GetOrganizationRequest {
+        pkey_id: acme.pkey_id,
+    };
+    let resp: GetOrganizationResponse = client.request(31000, get()).await?;
+    assert_eq!(resp.note, "customer");
+    client
+        .request::<DeleteOrganizationResponse>(
+            31004,
+            DeleteOrganizationRequest {
+                pkey_id: acme.pkey_id,
+            },
+        )
+        .await?;
+    ensure_code(
+        client
+            .request::<GetOrganizationResponse>(31000, get())
+            .await,
+        EnumErrorCode::NotFound,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use eyre::*;
+use lib::json_schema::{validate_json, SchemaError};
+use lib::validation::check_decimal_range;
+use model::json_schema::ToJsonSchema;
+use model::types::{Field, Type};
+use rust_decimal::Decimal;
+use serde_json::json;
+use std::str::FromStr;
+use testkit::TestDatabase;
+
+fn errors(value: serde_json::Value) -> Vec<String> {
+    let schema =
+        vec![Field::new("amount", Type::numeric(10, 2)).range(0.0, 1000.0)].to_json_schema();
+    match validate_json(&schema, &json!({ "amount": value })) {
+        Ok(()) => vec![],
+        Err(err) => err.0.iter().map(|x| x.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_decimal_serde() -> Result<()> {
+    // sent as strings, so no digit is lost to a float
+    let amount = Decimal::from_str("0.100000000000000001")?;
+    assert_eq!(serde_json::to_value(amount)?, json!("0.100000000000000001"));
+    assert_eq!(
+        serde_json::from_value::<Decimal>(json!("0.100000000000000001"))?,
+        amount
+    );
+    assert_eq!(
+        serde_json::to_value(Decimal::from_str("1.50")?)?,
+        json!("1.50")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decimal_schema() {
+    assert!(errors(json!("12.5")).is_empty());
+    assert!(errors(json!("-0")).is_empty());
+    assert_eq!(
+        errors(json!(12.5)),
+        vec!["amount: expected string, found number"]
+    );
+    // the precision and scale of numeric(10, 2)
+    assert_eq!(errors(json!("12.345")).len(), 1);
+    // too many digits, and so also out of range
+    assert_eq!(errors(json!("123456789")).len(), 2);
+    assert_eq!(errors(json!("1e3")).len(), 1);
+    assert_eq!(
+        errors(json!("1000.01")),
+        vec!["amount: must be at most 1000"]
+    );
+    assert_eq!(errors(json!("-0.01")), vec!["amount: must be at least 0"]);
+}
+
+#[test]
+fn test_check_decimal_range() -> Result<()> {
+    let mut errors: Vec<SchemaError> = vec![];
+    let check = |x: &str, errors: &mut Vec<SchemaError>| -> Result<()> {
+        check_decimal_range("x", &Decimal::from_str(x)?, Some(0.5), Some(1.5), errors);
+        Ok(())
+    };
+    for x in ["0.5", "1", "1.5"] {
+        check(x, &mut errors)?;
+    }
+    assert!(errors.is_empty());
+    check("0.4999999999999999999", &mut errors)?;
+    check("1.5000000000000000001", &mut errors)?;
+    check_decimal_range("x", &Decimal::MAX, None, None, &mut errors);
+    let errors = errors.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        ["x: must be at least 0.5", "x: must be at most 1.5"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decimal_column() -> Result<()> {
+    let database = TestDatabase::create().await?;
+    let db = database.connect().await?;
+    let amount = Decimal::from_str("12345678901234567890.123456789012345678")?;
+    let rows = db.query("SELECT $1::numeric(38,18)", &[&amount]).await?;
+    assert_eq!(rows[0].get::<_, Decimal>(0), amount);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use eyre::*;
+use lib::error_code::check_raised_error_codes;
+use testkit::TestApp;
+
+#[tokio::test]
+async fn test_check_raised_error_codes() -> Result<()> {
+    let app = TestApp::start().await?;
+    let db = app.db.connect().await?;
+    check_raised_error_codes(&db).await?;
+
+    // as if deployed by a newer build with another error code
+    db.query(
+        "CREATE FUNCTION api.fun_from_the_future() RETURNS void LANGUAGE plpgsql AS $$
+         BEGIN
+           RAISE SQLSTATE 'R00ZZ';
+         END
+         $$",
+        &[],
+    )
+    .await?;
+    let err = check_raised_error_codes(&db)
+        .await
+        .err()
+        .context("unknown SQLSTATE accepted")?;
+    ensure!(
+        err.to_string()
+            .contains("api.fun_from_the_future raises SQLSTATE R00ZZ"),
+        "{}",
+        err
+    );
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use eyre::*;
+use gen::database::*;
+use gen::error_code::EnumErrorCode;
+use gen::model::*;
+use iloverust::endpoints::endpoint_auth_signup;
+use iloverust::method::{AuthorizeHandler, LoginHandler, SignupHandler};
+use std::sync::{Arc, Mutex};
+use testkit::handler::HandlerTest;
+use testkit::PASSWORD_CONFIG;
+
+fn mock_db(mock: MockDbClient) -> DbClient {
+    DbClient::with_api(Arc::new(mock))
+}
+
+fn no_failures() -> FunAuthBeginLoginAttemptResp {
+    FunAuthBeginLoginAttemptResp {
+        rows: vec![FunAuthBeginLoginAttemptRespRow {
+            attempt_id: 7,
+            username_failures: 0,
+            username_last_failure: None,
+            ip_failures: 0,
+            ip_last_failure: None,
+        }],
+    }
+}
+
+#[tokio::test]
+async fn test_login_handler() -> Result<()> {
+    // hashed with other parameters, so it is replaced
+    let stored = lib::password::PasswordConfig {
+        iterations: 2,
+        ..PASSWORD_CONFIG
+    }
+    .hash("secret")?;
+    let rehashed = Arc::new(Mutex::new(None));
+    let rehashed_by_mock = Arc::clone(&rehashed);
+    let mock = MockDbClient::new()
+        .on_fun_auth_begin_login_attempt(|_| Ok(no_failures()))
+        .on_fun_auth_finish_login_attempt(|req| {
+            assert_eq!((req.attempt_id, req.is_password_ok), (7, Some(true)));
+            Ok(FunAuthFinishLoginAttemptResp { rows: vec![] })
+        })
+        .on_fun_auth_get_password_hash(move |_| {
+            Ok(FunAuthGetPasswordHashResp {
+                rows: vec![FunAuthGetPasswordHashRespRow {
+                    user_id: 1,
+                    password_hash: stored.clone(),
+                    password_salt: None,
+                    totp_secret: None,
+                    totp_pending_secret: None,
+                }],
+            })
+        })
+        .on_fun_auth_authenticate(|_| {
+            Ok(FunAuthAuthenticateResp {
+                rows: vec![FunAuthAuthenticateRespRow {
+                    user_id: 1,
+                    user_public_id: 42,
+                }],
+            })
+        })
+        .on_fun_auth_rehash_password(move |req| {
+            *rehashed_by_mock.lock().unwrap() = Some(req.new_password_hash);
+            Ok(FunAuthRehashPasswordResp { rows: vec![] })
+        })
+        .on_fun_auth_create_session(|req| {
+            assert_eq!(req.service, EnumService::User);
+            assert_eq!(req.device_id, "device");
+            let now = chrono::Utc::now();
+            Ok(FunAuthCreateSessionResp {
+                rows: vec![FunAuthCreateSessionRespRow {
+                    access_expires_at: now,
+                    expires_at: now,
+                }],
+            })
+        });
+    let mut test = HandlerTest::new().with_mock_db(mock_db(mock));
+    let req = LoginRequest {
+        username: "Pepe_Pablo".to_string(),
+        password: "secret".to_string(),
+        service_code: EnumService::User,
+        device_id: "device".to_string(),
+        device_os: "android".to_string(),
+        captcha_token: None,
+    };
+    let handler = LoginHandler {
+        password: PASSWORD_CONFIG,
+        session: Default::default(),
+        lockout: Default::default(),
+        totp: Default::default(),
+    };
+    let resp = test.call(&handler, req).await?.into_response()?;
+    assert_eq!(resp.username, "pepe_pablo");
+    assert_eq!(resp.user_public_id, 42);
+    let rehashed = rehashed
+        .lock()
+        .unwrap()
+        .take()
+        .context("password not rehashed")?;
+    assert_eq!(
+        PASSWORD_CONFIG.verify("secret", &rehashed, None)?,
+        lib::password::PasswordCheck::Ok
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_login_handler_locked_out() -> Result<()> {
+    // the password is not even looked up
+    let mock = MockDbClient::new()
+        .on_fun_auth_begin_login_attempt(|_| {
+            Ok(FunAuthBeginLoginAttemptResp {
+                rows: vec![FunAuthBeginLoginAttemptRespRow {
+                    username_failures: 6,
+                    username_last_failure: Some(chrono::Utc::now()),
+                    ..no_failures().rows.remove(0)
+                }],
+            })
+        })
+        // a refused attempt is not kept
+        .on_fun_auth_finish_login_attempt(|req| {
+            assert_eq!(req.is_password_ok, None);
+            Ok(FunAuthFinishLoginAttemptResp { rows: vec![] })
+        });
+    let mut test = HandlerTest::new().with_mock_db(mock_db(mock));
+    let req = LoginRequest {
+        username: "pepe_pablo".to_string(),
+        password: "secret".to_string(),
+        service_code: EnumService::User,
+        device_id: "device".to_string(),
+        device_os: "android".to_string(),
+        captcha_token: None,
+    };
+    let handler = LoginHandler {
+        password: PASSWORD_CONFIG,
+        session: Default::default(),
+        lockout: Default::default(),
+        totp: Default::default(),
+    };
+    let err = test.call(&handler, req).await?.into_error()?;
+    assert_eq!(err.code, EnumErrorCode::TemporarilyUnavailable as u32);
+    // the second failure after the limit of 5 doubles the block of 30 seconds
+    let retry_after = err.retry_after.context("no retry_after")?;
+    ensure!(
+        (59..=60).contains(&retry_after),
+        "retry_after {}",
+        retry_after
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_authorize_handler_wrong_service() -> Result<()> {
+    let mut test = HandlerTest::new().with_mock_db(mock_db(MockDbClient::new()));
+    let handler = AuthorizeHandler {
+        accept_service: EnumService::Admin,
+    };
+    let req = AuthorizeRequest {
+        username: "pepe_pablo".to_string(),
+        token: "token".to_string(),
+        service_code: EnumService::User,
+        device_id: "device".to_string(),
+        device_os: "android".to_string(),
+    };
+    let err = test.call(&handler, req).await?.into_error()?;
+    assert_eq!(err.code, 403);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signup_handler_validation() -> Result<()> {
+    let mut test = HandlerTest::new().with_mock_db(mock_db(MockDbClient::new()));
+    let params = serde_json::json!({
+        "username": "pepe_pablo",
+        "password": "secret",
+        "email": "not an email",
+        "phone": "+00123456",
+        "agreedTos": true,
+        "agreedPrivacy": true,
+    });
+    let captured = test
+        .call_erased(
+            &SignupHandler {
+                password: PASSWORD_CONFIG,
+            },
+            &endpoint_auth_signup(),
+            params,
+        )
+        .await?;
+    let err = captured.into_error()?;
+    assert_eq!(err.code, 400);
+    // the violations are sent along, each with the path of its field
+    assert_eq!(err.details.len(), 1, "{:?}", err.details);
+    assert_eq!(err.details[0].path, "email");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_db_of_another_type() -> Result<()> {
+    // the mock itself rather than a DbClient around it
+    let mut test = HandlerTest::new().with_mock_db(Arc::new(MockDbClient::new()));
+    let handler = AuthorizeHandler {
+        accept_service: EnumService::User,
+    };
+    let req = AuthorizeRequest {
+        username: "pepe_pablo".to_string(),
+        token: "token".to_string(),
+        service_code: EnumService::User,
+        device_id: "device".to_string(),
+        device_os: "android".to_string(),
+    };
+    let err = test.call(&handler, req).await?.into_error()?;
+    assert_eq!(err.code, 500);
+    Ok(())
+}
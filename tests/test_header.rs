@@ -0,0 +1,139 @@
+use eyre::*;
+use iloverust::enums::get_enums;
+use iloverust::method::LogoutHandler;
+use iloverust::services::auth_endpoints::endpoint_auth_authorize;
+use lib::toolbox::Toolbox;
+use lib::ws::{decode_header, encode_header, EndpointAuthController, RawHeader};
+use model::endpoint::EndpointSchema;
+use model::types::{Field, TimeFormat, Type};
+use proptest::prelude::*;
+use schema::schema;
+use serde_json::{json, Map, Value};
+
+#[path = "../fuzz/schema.rs"]
+mod schema;
+
+fn request() -> impl Strategy<Value = Map<String, Value>> {
+    (
+        any::<String>(),
+        any::<i64>(),
+        any::<i32>(),
+        any::<bool>(),
+        proptest::option::of(any::<String>()),
+        proptest::option::of(any::<i64>()),
+        prop::sample::select(vec!["User", "Admin"]),
+        prop::collection::vec(any::<String>(), 0..4),
+        prop::collection::vec(any::<u8>(), 0..16),
+    )
+        .prop_map(
+            |(name, id, count, flag, nickname, created_at, service, tags, salt)| {
+                let mut req = json!({
+                    "name": name,
+                    "id": id,
+                    "count": count,
+                    "flag": flag,
+                    "service": service,
+                    "tags": tags,
+                    "salt": salt,
+                });
+                let req = req.as_object_mut().unwrap();
+                if let Some(nickname) = nickname {
+                    req.insert("nickname".into(), nickname.into());
+                }
+                if let Some(created_at) = created_at {
+                    req.insert("createdAt".into(), created_at.into());
+                }
+                req.clone()
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn round_trip(req in request()) {
+        let header = encode_header(&req, schema()).unwrap();
+        prop_assert_eq!(decode_header(&header, &schema()).unwrap(), req);
+    }
+
+    #[test]
+    fn decode_does_not_panic(header in any::<String>()) {
+        let _ = decode_header(&header, &schema());
+    }
+
+    #[test]
+    fn decode_does_not_panic_on_fields(
+        fields in prop::collection::vec(("[0-9]", any::<String>()), 0..12)
+    ) {
+        let header = fields
+            .iter()
+            .map(|(index, value)| format!("{}{}", index, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Ok(header) = RawHeader::parse(&header) {
+            let _ = header.decode_params(&schema());
+        }
+    }
+}
+
+#[test]
+fn test_decode_tolerates_spacing_and_order() -> Result<()> {
+    let params = decode_header(
+        "2123,0everything ,  1x%2C%22y%22, 3-1,4true,7user,  8%5B%5D,9%5B1%5D,",
+        &schema(),
+    )?;
+    assert_eq!(params["name"], "x,\"y\"");
+    assert_eq!(params["service"], "User");
+    assert!(params.get("nickname").is_none());
+    assert!(decode_header("0everything, 1a, 1b", &schema()).is_err());
+    assert!(decode_header("é", &schema()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_empty_optional_string() -> Result<()> {
+    let header = "0everything, 1, 20, 30, 4false, 5, 6, 7user, 8%5B%5D, 9%5B%5D";
+    let params = decode_header(header, &schema())?;
+    assert_eq!(params["name"], "");
+    assert_eq!(params["nickname"], "");
+    // left out, as encode_header leaves out null optionals
+    assert!(params.get("createdAt").is_none());
+    assert_eq!(
+        decode_header(&encode_header(&params, schema())?, &schema())?,
+        params
+    );
+    Ok(())
+}
+
+#[test]
+fn test_time_formats() -> Result<()> {
+    let schema = EndpointSchema::new(
+        "Times",
+        1,
+        vec![
+            Field::new("day", Type::Date),
+            Field::new("at", Type::TimestampTz).time_format(TimeFormat::EpochMillis),
+        ],
+        vec![],
+    );
+    // ISO-8601 values stay text even when they look like numbers
+    let params = decode_header("0times, 120220801, 21659355200000", &schema)?;
+    assert_eq!(params["day"], "20220801");
+    assert_eq!(params["at"], 1659355200000i64);
+    assert!(decode_header("0times, 12022-08-01, 22022-08-01T12%3A00%3A00Z", &schema).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_enums_set_after_endpoints() -> Result<()> {
+    let (sender, _) = tokio::sync::mpsc::channel(1);
+    let controller = EndpointAuthController::new(Toolbox::new(sender));
+    controller.add_auth_endpoint(endpoint_auth_authorize(), LogoutHandler);
+    let controller = controller.with_enums(get_enums());
+    let endpoint = controller.auth_endpoints.get("authorize").unwrap();
+    let header = RawHeader::parse("0authorize, 1pepe, 2token, 3auth, 4device, 5os")?;
+    assert_eq!(
+        header.decode_params(&endpoint.schema)?["serviceCode"],
+        "Auth"
+    );
+    Ok(())
+}
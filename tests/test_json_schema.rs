@@ -0,0 +1,110 @@
+use eyre::*;
+use lib::json_schema::validate_json;
+use model::json_schema::ToJsonSchema;
+use model::types::{EnumVariant, Field, Type};
+use serde_json::{json, Value};
+
+fn schema() -> Value {
+    vec![
+        Field::new("name", Type::String),
+        Field::new("age", Type::Int),
+        Field::new("nickname", Type::optional(Type::String)),
+        Field::new(
+            "role",
+            Type::enum_(
+                "role",
+                vec![EnumVariant::new("user", 1), EnumVariant::new("admin", 2)],
+            ),
+        ),
+        Field::new(
+            "address",
+            Type::object(
+                "Address",
+                vec![
+                    Field::new("street", Type::String),
+                    Field::new("number", Type::Int),
+                ],
+            ),
+        ),
+        Field::new("tags", Type::vec(Type::String)),
+    ]
+    .to_json_schema()
+}
+
+fn errors(value: Value) -> Vec<String> {
+    match validate_json(&schema(), &value) {
+        Ok(()) => vec![],
+        Err(err) => err.0.iter().map(|x| x.to_string()).collect(),
+    }
+}
+
+fn paths(value: Value) -> Vec<String> {
+    match validate_json(&schema(), &value) {
+        Ok(()) => vec![],
+        Err(err) => err.0.into_iter().map(|x| x.path).collect(),
+    }
+}
+
+#[test]
+fn test_valid() -> Result<()> {
+    let value = json!({
+        "name": "pepe",
+        "age": 30,
+        "nickname": null,
+        "role": "Admin",
+        "address": {"street": "Main", "number": 1},
+        "tags": ["a", "b"]
+    });
+    validate_json(&schema(), &value)?;
+    Ok(())
+}
+
+#[test]
+fn test_collects_every_error() {
+    // not only the first one, with the path of each field
+    assert_eq!(
+        errors(json!({
+            "age": "30",
+            "nickname": 1,
+            "role": "Root",
+            "address": {"street": 1},
+            "tags": ["a", 2]
+        })),
+        vec![
+            "name: missing required field",
+            "address.number: missing required field",
+            "address.street: expected string, found integer",
+            "age: expected integer, found string",
+            "nickname: expected string, found integer",
+            "role: expected one of \"User\", \"Admin\"",
+            "tags[1]: expected string, found integer",
+        ]
+    );
+}
+
+#[test]
+fn test_root_and_type_errors() {
+    assert_eq!(errors(json!([])), vec!["$: expected object, found array"]);
+    // a float is not an integer
+    assert_eq!(
+        paths(json!({
+            "name": "pepe",
+            "age": 1.5,
+            "role": "User",
+            "address": {"street": "Main", "number": 1},
+            "tags": []
+        })),
+        vec!["age"]
+    );
+    // nor is null a required value
+    assert_eq!(
+        paths(json!({
+            "name": null,
+            "age": 1,
+            "role": "User",
+            "address": {"street": "Main", "number": 1},
+            "tags": null
+        })),
+        vec!["name", "tags"]
+    );
+}
@@ -0,0 +1,32 @@
+use lib::locale::Locale;
+
+fn pick(header: &str) -> Option<&'static str> {
+    Locale::from_accept_language(header).map(|x| x.as_str())
+}
+
+#[test]
+fn test_from_accept_language() {
+    assert_eq!(pick("zh-CN,zh;q=0.9,en;q=0.8"), Some("zh"));
+    assert_eq!(pick("fr-FR, en;q=0.5"), Some("en"));
+    assert_eq!(pick("en;q=0.2, zh_TW;q=0.8"), Some("zh"));
+    assert_eq!(pick("ZH"), Some("zh"));
+    // the first of equal quality
+    assert_eq!(pick("en, zh"), Some("en"));
+    // an invalid quality skips the language
+    assert_eq!(pick("zh;q=high, en;q=0.1"), Some("en"));
+    assert_eq!(pick("zh;q=0"), None);
+    assert_eq!(pick("fr, de;q=0.9"), None);
+    assert_eq!(pick(""), None);
+}
+
+#[test]
+fn test_from_tag() {
+    assert_eq!(Locale::from_tag(" en-US ").map(|x| x.as_str()), Some("en"));
+    assert_eq!(Locale::from_tag("eng"), None);
+    assert_eq!(Locale::default().as_str(), "en");
+    assert_eq!(
+        Locale::from_u8(Locale::default().to_u8()),
+        Some(Locale::default())
+    );
+    assert_eq!(Locale::from_u8(u8::MAX), None);
+}
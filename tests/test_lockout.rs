@@ -0,0 +1,270 @@
+use chrono::{Duration, Utc};
+use eyre::*;
+use gen::error_code::EnumErrorCode;
+use gen::model::*;
+use iloverust::services::auth_endpoints::endpoint_auth_login;
+use lib::lockout::*;
+use testkit::{ensure_code, ensure_retry_after, TestApp, DEVICE_ID, DEVICE_OS, TEST_PASSWORD};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const WRONG_PASSWORD: &str = "Q3V9ZK2M7HB4XW8TR1PD";
+
+async fn login_with_captcha(
+    app: &TestApp,
+    password: &str,
+    captcha_token: Option<&str>,
+) -> Result<LoginResponse> {
+    app.auth_request(
+        LoginRequest {
+            username: "pepe_pablo".to_owned(),
+            password: password.to_owned(),
+            service_code: EnumService::User,
+            device_id: DEVICE_ID.to_owned(),
+            device_os: DEVICE_OS.to_owned(),
+            captcha_token: captcha_token.map(|x| x.to_owned()),
+        },
+        endpoint_auth_login(),
+    )
+    .await
+}
+
+/// A siteverify endpoint accepting every token, answering one request per connection
+async fn captcha_provider() -> Result<CaptchaConfig> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let verify_url = format!("http://{}/siteverify", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).await;
+            let body = r#"{"success":true}"#;
+            let resp = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(resp.as_bytes()).await;
+        }
+    });
+    Ok(CaptchaConfig {
+        verify_url,
+        secret: "secret".to_owned(),
+    })
+}
+
+#[test]
+fn test_lockout_policy() {
+    let policy = LockoutPolicy {
+        max_failures: 5,
+        window: 900,
+        action: LockoutAction::Block,
+        base_delay: 30,
+        max_delay: 100,
+    };
+    assert_eq!(policy.delay(5), Duration::seconds(30));
+    assert_eq!(policy.delay(6), Duration::seconds(60));
+    assert_eq!(policy.delay(7), Duration::seconds(100));
+    assert_eq!(policy.delay(70), Duration::seconds(100));
+
+    let now = Utc::now();
+    assert_eq!(policy.check(4, Some(now), now), Lockout::None);
+    assert_eq!(
+        policy.check(5, Some(now), now),
+        Lockout::Blocked {
+            until: now + Duration::seconds(30)
+        }
+    );
+    assert_eq!(
+        policy.check(5, Some(now - Duration::seconds(30)), now),
+        Lockout::None
+    );
+    let captcha = LockoutPolicy {
+        action: LockoutAction::Captcha,
+        ..policy
+    };
+    assert_eq!(captcha.check(5, Some(now), now), Lockout::CaptchaRequired);
+    let disabled = LockoutPolicy {
+        max_failures: 0,
+        ..policy
+    };
+    assert_eq!(disabled.check(100, Some(now), now), Lockout::None);
+}
+
+#[tokio::test]
+async fn test_username_lockout() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    for _ in 0..5 {
+        ensure_code(
+            app.login("pepe_pablo", WRONG_PASSWORD, EnumService::User)
+                .await,
+            EnumErrorCode::InvalidPassword,
+        )?;
+    }
+    // refused before the password is checked
+    let retry = ensure_retry_after(
+        app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+            .await,
+    )?;
+    ensure!((1..=30).contains(&retry), "retry_after {}", retry);
+
+    let db = app.db.connect().await?;
+    db.query(
+        "UPDATE tbl.login_attempt SET moment = moment - interval '30 seconds'",
+        &[],
+    )
+    .await?;
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    // a successful login forgives the earlier failures
+    ensure_code(
+        app.login("pepe_pablo", WRONG_PASSWORD, EnumService::User)
+            .await,
+        EnumErrorCode::InvalidPassword,
+    )?;
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_failures() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let results = futures::future::join_all(
+        (0..10).map(|_| app.login("pepe_pablo", WRONG_PASSWORD, EnumService::User)),
+    )
+    .await;
+    // each attempt counts the ones before it, however many run at once
+    let checked = results
+        .into_iter()
+        .filter_map(|x| ensure_code(x, EnumErrorCode::InvalidPassword).ok())
+        .count();
+    assert_eq!(checked, 5);
+    ensure_retry_after(
+        app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+            .await,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ip_lockout() -> Result<()> {
+    let app = TestApp::start_with_lockout(LockoutConfig {
+        ip: LockoutPolicy {
+            max_failures: 3,
+            ..LockoutPolicy::default()
+        },
+        ..LockoutConfig::default()
+    })
+    .await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    // unknown users fail like wrong passwords
+    for username in ["pepe_a", "pepe_b", "pepe_c"] {
+        ensure_code(
+            app.login(username, TEST_PASSWORD, EnumService::User).await,
+            EnumErrorCode::InvalidPassword,
+        )?;
+    }
+    ensure_retry_after(
+        app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+            .await,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_captcha_required() -> Result<()> {
+    let captcha_after_two = LockoutConfig {
+        username: LockoutPolicy {
+            max_failures: 2,
+            action: LockoutAction::Captcha,
+            ..LockoutPolicy::default()
+        },
+        ..LockoutConfig::default()
+    };
+    let app = TestApp::start_with_lockout(LockoutConfig {
+        captcha: Some(captcha_provider().await?),
+        ..captcha_after_two.clone()
+    })
+    .await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    for _ in 0..2 {
+        ensure_code(
+            login_with_captcha(&app, WRONG_PASSWORD, None).await,
+            EnumErrorCode::InvalidPassword,
+        )?;
+    }
+    ensure_code(
+        login_with_captcha(&app, TEST_PASSWORD, None).await,
+        EnumErrorCode::CaptchaRequired,
+    )?;
+    login_with_captcha(&app, TEST_PASSWORD, Some("solved")).await?;
+
+    // without a provider a CAPTCHA cannot be solved
+    let app = TestApp::start_with_lockout(captcha_after_two).await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    for _ in 0..2 {
+        ensure_code(
+            login_with_captcha(&app, WRONG_PASSWORD, None).await,
+            EnumErrorCode::InvalidPassword,
+        )?;
+    }
+    ensure_code(
+        login_with_captcha(&app, TEST_PASSWORD, Some("solved")).await,
+        EnumErrorCode::CaptchaRequired,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_admin_lockouts() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    app.signup("admin_pablo", TEST_PASSWORD).await?;
+    let db = app.db.connect().await?;
+    db.query(
+        "UPDATE tbl.user SET role = 'admin' WHERE username = 'admin_pablo'",
+        &[],
+    )
+    .await?;
+    let admin = app
+        .login("admin_pablo", TEST_PASSWORD, EnumService::Admin)
+        .await?;
+    let mut admin_client = app.admin_client(&admin).await?;
+    for _ in 0..5 {
+        ensure_code(
+            app.login("pepe_pablo", WRONG_PASSWORD, EnumService::User)
+                .await,
+            EnumErrorCode::InvalidPassword,
+        )?;
+    }
+
+    let resp: ListLoginLockoutsResponse = admin_client
+        .request(30040, ListLoginLockoutsRequest {})
+        .await?;
+    assert_eq!(resp.lockouts.len(), 1);
+    let lockout = &resp.lockouts[0];
+    assert_eq!(lockout.username.as_deref(), Some("pepe_pablo"));
+    assert_eq!(lockout.failures, 5);
+    ensure!(lockout.blocked_until.is_some(), "{:?}", lockout);
+    assert!(!lockout.captcha_required);
+
+    let resp: ClearLoginLockoutResponse = admin_client
+        .request(
+            30050,
+            ClearLoginLockoutRequest {
+                username: Some("pepe_pablo".to_owned()),
+                ip_address: None,
+            },
+        )
+        .await?;
+    assert_eq!(resp.cleared, 5);
+    let resp: ListLoginLockoutsResponse = admin_client
+        .request(30040, ListLoginLockoutsRequest {})
+        .await?;
+    assert!(resp.lockouts.is_empty());
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    Ok(())
+}
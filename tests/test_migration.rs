@@ -0,0 +1,164 @@
+use eyre::*;
+use lib::migration::*;
+use std::collections::HashMap;
+use std::path::Path;
+use testkit::TestDatabase;
+use MigrationAction::*;
+
+fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::new("model.sql", None, "CREATE TYPE t AS (x int);"),
+        Migration::new("V0001__baseline.sql", Some(1), "CREATE TABLE a (x int);"),
+        Migration::new("V0002__b.sql", Some(2), "CREATE TABLE b (x int);"),
+        Migration::new("api.sql", None, "CREATE FUNCTION f() ..."),
+    ]
+}
+
+fn applied(migrations: &[Migration]) -> HashMap<String, String> {
+    migrations
+        .iter()
+        .map(|x| (x.name.clone(), x.checksum.clone()))
+        .collect()
+}
+
+#[test]
+fn test_plan_fresh_and_up_to_date() -> Result<()> {
+    let migrations = migrations();
+    assert_eq!(
+        plan_migrations(&migrations, &HashMap::new())?,
+        vec![Apply; 4]
+    );
+    assert_eq!(
+        plan_migrations(&migrations, &applied(&migrations))?,
+        vec![Skip; 4]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_plan_new_migration() -> Result<()> {
+    let migrations = migrations();
+    let applied = applied(&migrations[..2]);
+    // api.sql was never applied, so it is applied rather than reapplied
+    assert_eq!(
+        plan_migrations(&migrations, &applied)?,
+        vec![Skip, Skip, Apply, Apply]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_plan_changed_repeatable() -> Result<()> {
+    let mut migrations = migrations();
+    let applied = applied(&migrations);
+    migrations[0] = Migration::new("model.sql", None, "CREATE TYPE u AS (x int);");
+    // the ones after a reapplied repeatable migration may depend on it
+    assert_eq!(
+        plan_migrations(&migrations, &applied)?,
+        vec![Reapply, Skip, Skip, Reapply]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_plan_checksum_mismatch() {
+    let mut migrations = migrations();
+    let applied = applied(&migrations);
+    migrations[1] = Migration::new("V0001__baseline.sql", Some(1), "CREATE TABLE a (y int);");
+    let err = plan_migrations(&migrations, &applied).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("migration V0001__baseline.sql was modified after being applied"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_plan_out_of_order() {
+    let migrations = migrations();
+    // V0001 was added after V0002 had been applied
+    let mut applied = applied(&migrations);
+    applied.remove("V0001__baseline.sql");
+    let err = plan_migrations(&migrations, &applied).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("migration V0001__baseline.sql is older than the latest applied one, V2"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_load_migrations() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("test_load_migrations_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("migrations"))?;
+    std::fs::write(dir.join("model.sql"), "")?;
+    std::fs::write(dir.join("tables.sql"), "")?;
+    std::fs::write(dir.join("api.sql"), "")?;
+    std::fs::write(dir.join("migrations/V0010__later.sql"), "")?;
+    std::fs::write(dir.join("migrations/V0002__earlier.sql"), "")?;
+    std::fs::write(dir.join("migrations/README"), "")?;
+    let names = load_migrations(&dir)?
+        .into_iter()
+        .map(|x| x.name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "model.sql",
+            "V0002__earlier.sql",
+            "V0010__later.sql",
+            "tables.sql",
+            "api.sql"
+        ]
+    );
+    std::fs::write(dir.join("migrations/V2__same.sql"), "")?;
+    assert!(load_migrations(&dir).is_err());
+    std::fs::remove_file(dir.join("migrations/V2__same.sql"))?;
+    std::fs::write(dir.join("migrations/baseline.sql"), "")?;
+    assert!(load_migrations(&dir).is_err());
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_baseline() -> Result<()> {
+    let database = TestDatabase::create().await?;
+    let db = database.connect().await?;
+    let migrations = load_migrations(&Path::new(env!("CARGO_MANIFEST_DIR")).join("db"))?;
+    assert!(baseline_migrations(&db, &migrations).await.is_err());
+
+    // what the old setup_database.sh left: the tables of V0001 and no record of them
+    for statement in [
+        "DROP SCHEMA tbl CASCADE",
+        "DROP SCHEMA api CASCADE",
+        "DROP TABLE public.schema_migrations",
+    ] {
+        db.query(statement, &[]).await?;
+    }
+    run_migrations(&db, &migrations[..2], false).await?;
+    db.query("DROP TABLE public.schema_migrations", &[]).await?;
+    db.query(
+        "INSERT INTO tbl.bad_request (ip_address, error_code, moment)
+VALUES ('127.0.0.1', 1, 1700000000)",
+        &[],
+    )
+    .await?;
+    assert!(run_migrations(&db, &migrations, false).await.is_err());
+
+    baseline_migrations(&db, &migrations).await?;
+    let plan = run_migrations(&db, &migrations, false).await?;
+    assert_eq!(plan[1], Skip);
+    assert!(plan[2..].iter().all(|x| *x == Apply));
+    let rows = db
+        .query(
+            "SELECT EXTRACT(EPOCH FROM moment)::bigint FROM tbl.bad_request",
+            &[],
+        )
+        .await?;
+    let moment: i64 = rows[0].get(0);
+    assert_eq!(moment, 1700000000);
+    Ok(())
+}
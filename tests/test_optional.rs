@@ -0,0 +1,125 @@
+use eyre::*;
+use gen::database::*;
+use gen::model::*;
+use lib::ws::{decode_header, encode_header};
+use schema::schema;
+use serde_json::json;
+use testkit::TestDatabase;
+
+#[path = "../fuzz/schema.rs"]
+mod schema;
+
+fn login_response() -> LoginResponse {
+    LoginResponse {
+        username: "pepe_pablo".to_owned(),
+        user_public_id: 1,
+        access_token: None,
+        refresh_token: None,
+        access_expires_at: None,
+        expires_at: None,
+        challenge_token: None,
+        challenge_expires_at: None,
+    }
+}
+
+#[test]
+fn test_optional_fields() -> Result<()> {
+    // absent fields are left out rather than sent as null
+    assert_eq!(
+        serde_json::to_value(login_response())?,
+        json!({"username": "pepe_pablo", "userPublicId": 1})
+    );
+    let resp = LoginResponse {
+        access_token: Some("token".to_owned()),
+        ..login_response()
+    };
+    assert_eq!(serde_json::to_value(&resp)?["accessToken"], "token");
+    // both null and a missing field read as None
+    let resp: LoginResponse = serde_json::from_value(json!({
+        "username": "pepe_pablo",
+        "userPublicId": 1,
+        "accessToken": null,
+        "accessExpiresAt": null,
+    }))?;
+    assert_eq!(resp.access_token, None);
+    assert_eq!(resp.access_expires_at, None);
+    assert_eq!(resp.refresh_token, None);
+    let resp: LoginResponse = serde_json::from_value(json!({
+        "username": "pepe_pablo",
+        "userPublicId": 1,
+        "accessExpiresAt": 1659355200000i64,
+    }))?;
+    assert_eq!(
+        resp.access_expires_at.map(|x| x.timestamp()),
+        Some(1659355200)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_optional_header_params() -> Result<()> {
+    let params = json!({
+        "name": "pepe",
+        "id": 1,
+        "count": 2,
+        "flag": true,
+        "nickname": null,
+        "service": "User",
+        "tags": [],
+        "salt": [],
+    });
+    // null optionals are left out, and read back as missing
+    let header = encode_header(&params, schema())?;
+    let decoded = decode_header(&header, &schema())?;
+    assert!(decoded.get("nickname").is_none());
+    assert!(decoded.get("createdAt").is_none());
+    assert_eq!(decoded["name"], "pepe");
+    // but a required one cannot be
+    assert!(decode_header("0everything, 1pepe, 21, 32, 4true", &schema()).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_optional_rows() -> Result<()> {
+    let database = TestDatabase::create().await?;
+    let db = DbClient::new(database.connect().await?);
+    // no row is None rather than an error
+    let stored = db
+        .fun_auth_get_password_hash_opt(FunAuthGetPasswordHashReq {
+            username: "pepe_nobody".to_owned(),
+        })
+        .await?;
+    assert!(stored.is_none());
+
+    // NULL columns and arguments
+    let asset = db
+        .fun_asset_insert_one(FunAssetInsertReq {
+            short_name: "BTC".to_owned(),
+            long_name: "Bitcoin".to_owned(),
+            description: "".to_owned(),
+            network_type: "bitcoin".to_owned(),
+            contract_address: "".to_owned(),
+            precision: 8,
+            fkey_prototype: None,
+        })
+        .await?;
+    assert_eq!(asset.fkey_prototype, None);
+    let row = db
+        .fun_asset_get_one(FunAssetGetReq {
+            pkey_id: asset.pkey_id,
+        })
+        .await?;
+    assert_eq!(row.fkey_prototype, None);
+    let rows = db
+        .fun_asset_list(FunAssetListReq {
+            offset: 0,
+            limit: 10,
+            short_name: None,
+            network_type: None,
+            fkey_prototype: None,
+        })
+        .await?
+        .rows;
+    assert_eq!(rows.len(), 1);
+    Ok(())
+}
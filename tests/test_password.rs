@@ -0,0 +1,53 @@
+use eyre::*;
+use lib::password::{PasswordCheck, PasswordConfig};
+use sha2::{Digest, Sha256};
+use testkit::PASSWORD_CONFIG;
+
+#[test]
+fn test_hash_and_verify() -> Result<()> {
+    let hash = PASSWORD_CONFIG.hash("secret")?;
+    ensure!(hash.starts_with("$argon2id$"), "not a PHC string: {}", hash);
+    assert_ne!(hash, PASSWORD_CONFIG.hash("secret")?, "salt reused");
+    assert_eq!(
+        PASSWORD_CONFIG.verify("secret", &hash, None)?,
+        PasswordCheck::Ok
+    );
+    assert_eq!(
+        PASSWORD_CONFIG.verify("Secret", &hash, None)?,
+        PasswordCheck::Wrong
+    );
+    Ok(())
+}
+
+#[test]
+fn test_changed_parameters_are_outdated() -> Result<()> {
+    let hash = PASSWORD_CONFIG.hash("secret")?;
+    let stronger = PasswordConfig {
+        memory_kib: PASSWORD_CONFIG.memory_kib * 2,
+        ..PASSWORD_CONFIG
+    };
+    assert_eq!(
+        stronger.verify("secret", &hash, None)?,
+        PasswordCheck::Outdated
+    );
+    assert_eq!(stronger.verify("other", &hash, None)?, PasswordCheck::Wrong);
+    Ok(())
+}
+
+#[test]
+fn test_legacy_sha256() -> Result<()> {
+    let salt = b"salt";
+    let mut hasher = Sha256::new();
+    hasher.update(b"secret");
+    hasher.update(salt);
+    let legacy = hex::encode(hasher.finalize());
+    assert_eq!(
+        PASSWORD_CONFIG.verify("secret", &legacy, Some(salt))?,
+        PasswordCheck::Outdated
+    );
+    assert_eq!(
+        PASSWORD_CONFIG.verify("secret", &legacy, Some(b"other salt"))?,
+        PasswordCheck::Wrong
+    );
+    Ok(())
+}
@@ -0,0 +1,254 @@
+use eyre::*;
+use gen::error_code::EnumErrorCode;
+use gen::model::*;
+use iloverust::services::auth_endpoints::*;
+use lib::database::SimpleDbClient;
+use testkit::{ensure_code, TestApp, TEST_PASSWORD};
+
+const NEW_PASSWORD: &str = "Q3V9ZK2M7HB4XW8TR1PD";
+
+async fn add_questions(db: &SimpleDbClient) -> Result<()> {
+    db.query(
+        "INSERT INTO tbl.recovery_question_data(pkey_id, content, category)
+         VALUES (1, 'Name of your first pet?', 'pet'),
+                (2, 'Your primary school?', 'education'),
+                (3, 'City you were born in?', 'personal')",
+        &[],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn set_questions(
+    app: &TestApp,
+    password: &str,
+    question_ids: Vec<i32>,
+    answers: Vec<&str>,
+) -> Result<SetRecoveryQuestionsResponse> {
+    app.auth_request(
+        SetRecoveryQuestionsRequest {
+            username: "pepe_pablo".to_owned(),
+            password: password.to_owned(),
+            question_ids,
+            answers: answers.into_iter().map(|x| x.to_owned()).collect(),
+        },
+        endpoint_auth_set_recovery_questions(),
+    )
+    .await
+}
+
+async fn submit_answers(
+    app: &TestApp,
+    question_ids: Vec<i32>,
+    answers: Vec<&str>,
+) -> Result<SubmitRecoveryAnswersResponse> {
+    app.auth_request(
+        SubmitRecoveryAnswersRequest {
+            username: "pepe_pablo".to_owned(),
+            question_ids,
+            answers: answers.into_iter().map(|x| x.to_owned()).collect(),
+        },
+        endpoint_auth_submit_recovery_answers(),
+    )
+    .await
+}
+
+async fn reset_password(app: &TestApp, reset_token: &str) -> Result<ResetPasswordResponse> {
+    app.auth_request(
+        ResetPasswordRequest {
+            username: "pepe_pablo".to_owned(),
+            reset_token: reset_token.to_owned(),
+            new_password: NEW_PASSWORD.to_owned(),
+        },
+        endpoint_auth_reset_password(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_change_password() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let change = |old_password: &str| ChangePasswordRequest {
+        username: "pepe_pablo".to_owned(),
+        old_password: old_password.to_owned(),
+        new_password: NEW_PASSWORD.to_owned(),
+        device_id: "phone".to_owned(),
+        device_os: "android".to_owned(),
+    };
+    ensure_code(
+        app.auth_request::<_, ChangePasswordResponse>(
+            change(NEW_PASSWORD),
+            endpoint_auth_change_password(),
+        )
+        .await,
+        EnumErrorCode::InvalidPassword,
+    )?;
+    let resp: ChangePasswordResponse = app
+        .auth_request(change(TEST_PASSWORD), endpoint_auth_change_password())
+        .await?;
+    assert!(resp.success);
+    ensure_code(
+        app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+            .await,
+        EnumErrorCode::InvalidPassword,
+    )?;
+    app.login("pepe_pablo", NEW_PASSWORD, EnumService::User)
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_recovery_questions() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let db = app.db.connect().await?;
+    add_questions(&db).await?;
+
+    let catalog: ListRecoveryQuestionCatalogResponse = app
+        .auth_request(
+            ListRecoveryQuestionCatalogRequest {},
+            endpoint_auth_list_recovery_question_catalog(),
+        )
+        .await?;
+    assert_eq!(catalog.questions.len(), 3);
+    assert_eq!(
+        catalog.questions[0].category,
+        EnumRecoveryQuestionCategory::Pet
+    );
+
+    ensure_code(
+        set_questions(&app, NEW_PASSWORD, vec![1, 2], vec!["rex", "hill"]).await,
+        EnumErrorCode::InvalidPassword,
+    )?;
+    for (question_ids, answers) in [
+        (vec![1, 1], vec!["rex", "hill"]),
+        (vec![1, 4], vec!["rex", "hill"]),
+        (vec![1, 2], vec!["rex"]),
+        (vec![1, 2], vec!["rex", "  "]),
+    ] {
+        ensure_code(
+            set_questions(&app, TEST_PASSWORD, question_ids, answers).await,
+            EnumErrorCode::InvalidRecoveryQuestions,
+        )?;
+    }
+    ensure_code(
+        app.auth_request::<_, GetMyRecoveryQuestionsResponse>(
+            GetMyRecoveryQuestionsRequest {
+                username: "pepe_pablo".to_owned(),
+            },
+            endpoint_auth_get_my_recovery_questions(),
+        )
+        .await,
+        EnumErrorCode::RecoveryQuestionsNotSet,
+    )?;
+
+    set_questions(&app, TEST_PASSWORD, vec![2, 1], vec!["Hill Park", "Rex"]).await?;
+    let mine: GetMyRecoveryQuestionsResponse = app
+        .auth_request(
+            GetMyRecoveryQuestionsRequest {
+                username: "pepe_pablo".to_owned(),
+            },
+            endpoint_auth_get_my_recovery_questions(),
+        )
+        .await?;
+    let ids: Vec<i32> = mine.questions.iter().map(|x| x.question_id).collect();
+    assert_eq!(ids, vec![2, 1]);
+    let rows = db
+        .query("SELECT answer FROM tbl.recovery_question", &[])
+        .await?;
+    for row in rows {
+        let answer: String = row.get(0);
+        ensure!(answer.starts_with("$argon2id$"), "answer not hashed");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reset_password() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let db = app.db.connect().await?;
+    add_questions(&db).await?;
+    set_questions(&app, TEST_PASSWORD, vec![1, 2], vec!["Rex", "Hill Park"]).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+
+    ensure_code(
+        submit_answers(&app, vec![1], vec!["rex"]).await,
+        EnumErrorCode::MustSubmitAllRecoveryQuestions,
+    )?;
+    ensure_code(
+        submit_answers(&app, vec![1, 2], vec!["rex", "hill"]).await,
+        EnumErrorCode::WrongRecoveryAnswers,
+    )?;
+    // answers match regardless of order, case and spacing
+    let submitted = submit_answers(&app, vec![2, 1], vec!["  hill   PARK ", "rex"]).await?;
+
+    ensure_code(
+        reset_password(&app, "e0c4b0bf-4d2a-4a55-9a7b-5e6c7d0f7f11").await,
+        EnumErrorCode::InvalidRecoveryToken,
+    )?;
+    reset_password(&app, &submitted.reset_token).await?;
+    ensure_code(
+        reset_password(&app, &submitted.reset_token).await,
+        EnumErrorCode::InvalidRecoveryToken,
+    )?;
+    ensure_code(app.user_client(&login).await, EnumErrorCode::InvalidToken)?;
+    ensure_code(
+        app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+            .await,
+        EnumErrorCode::InvalidPassword,
+    )?;
+    app.login("pepe_pablo", NEW_PASSWORD, EnumService::User)
+        .await?;
+
+    let submitted = submit_answers(&app, vec![1, 2], vec!["rex", "hill park"]).await?;
+    db.query("UPDATE tbl.user SET reset_token_valid = NOW()", &[])
+        .await?;
+    ensure_code(
+        reset_password(&app, &submitted.reset_token).await,
+        EnumErrorCode::InvalidRecoveryToken,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_recovery_rate_limit() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    add_questions(&app.db.connect().await?).await?;
+    set_questions(&app, TEST_PASSWORD, vec![1], vec!["Rex"]).await?;
+
+    for _ in 0..5 {
+        ensure_code(
+            submit_answers(&app, vec![1], vec!["max"]).await,
+            EnumErrorCode::WrongRecoveryAnswers,
+        )?;
+    }
+    ensure_code(
+        submit_answers(&app, vec![1], vec!["rex"]).await,
+        EnumErrorCode::TemporarilyUnavailable,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_recovery_answers() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    add_questions(&app.db.connect().await?).await?;
+    set_questions(&app, TEST_PASSWORD, vec![1], vec!["Rex"]).await?;
+
+    let results =
+        futures::future::join_all((0..10).map(|_| submit_answers(&app, vec![1], vec!["max"])))
+            .await;
+    // each submission counts the ones before it, however many run at once
+    let checked = results
+        .into_iter()
+        .filter_map(|x| ensure_code(x, EnumErrorCode::WrongRecoveryAnswers).ok())
+        .count();
+    assert_eq!(checked, 5);
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use eyre::*;
+use gen::error_code::EnumErrorCode;
+use gen::model::*;
+use lib::ws::WsClient;
+use std::time::Duration;
+use testkit::{ensure_code, TestApp, TEST_PASSWORD};
+
+/// Fails unless the server closes `client` soon
+async fn ensure_closed(client: &mut WsClient) -> Result<()> {
+    let result = tokio::time::timeout(Duration::from_secs(5), client.recv_resp::<()>())
+        .await
+        .context("the connection was not closed")?;
+    match result {
+        Ok(()) => bail!("expected the connection to be closed, got a response"),
+        Err(err) => ensure!(err.to_string().contains("Connection closed"), "{}", err),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_refresh() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    ensure!(login.access_expires_at <= login.expires_at, "{:?}", login);
+
+    let refreshed = app.refresh(&login.refresh_token).await?;
+    assert_eq!(refreshed.username, "pepe_pablo");
+    assert_eq!(refreshed.expires_at, login.expires_at);
+    app.authorized_client(EnumService::User, "pepe_pablo", &refreshed.access_token)
+        .await?;
+    // both tokens were replaced
+    ensure_code(
+        app.refresh(&login.refresh_token).await,
+        EnumErrorCode::InvalidToken,
+    )?;
+    ensure_code(
+        app.authorized_client(EnumService::User, "pepe_pablo", &login.access_token)
+            .await,
+        EnumErrorCode::InvalidToken,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_expired_access_token() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let db = app.db.connect().await?;
+    db.query(
+        "UPDATE tbl.session SET access_expires_at = NOW() - interval '1 second'",
+        &[],
+    )
+    .await?;
+    ensure_code(app.user_client(&login).await, EnumErrorCode::TokenExpired)?;
+    let refreshed = app.refresh(&login.refresh_token).await?;
+    app.authorized_client(EnumService::User, "pepe_pablo", &refreshed.access_token)
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_timeouts() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let db = app.db.connect().await?;
+
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    db.query(
+        "UPDATE tbl.session SET last_seen_at = NOW() - idle_timeout",
+        &[],
+    )
+    .await?;
+    ensure_code(app.user_client(&login).await, EnumErrorCode::InvalidToken)?;
+    ensure_code(
+        app.refresh(&login.refresh_token).await,
+        EnumErrorCode::InvalidToken,
+    )?;
+
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    db.query("UPDATE tbl.session SET expires_at = NOW()", &[])
+        .await?;
+    ensure_code(app.user_client(&login).await, EnumErrorCode::InvalidToken)?;
+    ensure_code(
+        app.refresh(&login.refresh_token).await,
+        EnumErrorCode::InvalidToken,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_sessions() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let phone = app
+        .login_on("phone", "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let laptop = app
+        .login_on("laptop", "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    app.user_client(&phone).await?;
+    app.user_client(&laptop).await?;
+
+    // a new login on the same device replaces its session
+    let again = app
+        .login_on("phone", "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    app.user_client(&again).await?;
+    ensure_code(app.user_client(&phone).await, EnumErrorCode::InvalidToken)?;
+    app.user_client(&laptop).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_logout() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let mut client = app.user_client(&login).await?;
+
+    let resp = app.logout("pepe_pablo", &login.access_token).await?;
+    assert!(resp.success);
+    ensure_closed(&mut client).await?;
+    ensure_code(app.user_client(&login).await, EnumErrorCode::InvalidToken)?;
+    ensure_code(
+        app.refresh(&login.refresh_token).await,
+        EnumErrorCode::InvalidToken,
+    )?;
+    ensure_code(
+        app.logout("pepe_pablo", &login.access_token).await,
+        EnumErrorCode::InvalidToken,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_logout_all() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let phone = app
+        .login_on("phone", "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let laptop = app
+        .login_on("laptop", "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let mut phone_client = app.user_client(&phone).await?;
+    let mut laptop_client = app.user_client(&laptop).await?;
+
+    let resp = app.logout_all("pepe_pablo", &phone.access_token).await?;
+    assert_eq!(resp.revoked_sessions, 2);
+    ensure_closed(&mut phone_client).await?;
+    ensure_closed(&mut laptop_client).await?;
+    ensure_code(app.user_client(&laptop).await, EnumErrorCode::InvalidToken)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_admin_revoke_sessions() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    app.signup("admin_pablo", TEST_PASSWORD).await?;
+    let db = app.db.connect().await?;
+    db.query(
+        "UPDATE tbl.user SET role = 'admin' WHERE username = 'admin_pablo'",
+        &[],
+    )
+    .await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let mut client = app.user_client(&login).await?;
+    let admin = app
+        .login("admin_pablo", TEST_PASSWORD, EnumService::Admin)
+        .await?;
+    let mut admin_client = app.admin_client(&admin).await?;
+
+    let resp: RevokeSessionsResponse = admin_client
+        .request(
+            30030,
+            RevokeSessionsRequest {
+                user_public_id: login.user_public_id,
+                service: Some(EnumService::Admin),
+            },
+        )
+        .await?;
+    assert_eq!(resp.revoked_sessions, 0);
+    let resp: RevokeSessionsResponse = admin_client
+        .request(
+            30030,
+            RevokeSessionsRequest {
+                user_public_id: login.user_public_id,
+                service: None,
+            },
+        )
+        .await?;
+    assert_eq!(resp.revoked_sessions, 1);
+    ensure_closed(&mut client).await?;
+    ensure_code(app.user_client(&login).await, EnumErrorCode::InvalidToken)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_login_closes_replaced_session() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let mut client = app.user_client(&login).await?;
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    ensure_closed(&mut client).await?;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use eyre::*;
+use lib::time::{EpochMillis, Interval};
+
+fn interval(months: i32, days: i32, microseconds: i64) -> Interval {
+    Interval {
+        months,
+        days,
+        microseconds,
+    }
+}
+
+#[test]
+fn test_interval_format() {
+    let cases = [
+        (interval(0, 0, 0), "PT0S"),
+        (interval(14, 3, 14_706_500_000), "P1Y2M3DT4H5M6.5S"),
+        (interval(0, 3, 0), "P3D"),
+        (interval(0, 0, 90_000_000), "PT1M30S"),
+        (interval(0, 0, 1), "PT0.000001S"),
+        (interval(0, 0, -1_500_000), "PT-1.5S"),
+        (interval(-1, -2, -3_630_000_000), "P-1M-2DT-1H-30S"),
+    ];
+    for (interval, text) in cases {
+        assert_eq!(interval.to_string(), text);
+        assert_eq!(text.parse::<Interval>().unwrap(), interval, "{}", text);
+    }
+}
+
+#[test]
+fn test_interval_parse() -> Result<()> {
+    assert_eq!("P2W".parse::<Interval>()?, interval(0, 14, 0));
+    assert_eq!(
+        "PT1H".parse::<Interval>()?,
+        Interval::from_millis(3_600_000)
+    );
+    assert_eq!(
+        "P+1DT-1H".parse::<Interval>()?,
+        interval(0, 1, -3_600_000_000)
+    );
+    assert_eq!("PT0.25S".parse::<Interval>()?, Interval::from_millis(250));
+    for text in [
+        "", "P", "PT", "P1DT", "1D", "P1H", "PT1D", "P1.5D", "PX", "P1", "PTT1S",
+    ] {
+        assert!(text.parse::<Interval>().is_err(), "{}", text);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_interval_millis() -> Result<()> {
+    // a month counts as 30 days
+    assert_eq!(interval(1, 1, 1_000).to_millis(), 31 * 86_400_000 + 1);
+    assert_eq!(
+        Interval::from_epoch_millis(1500),
+        Some(Interval::from_millis(1500))
+    );
+    assert_eq!(
+        serde_json::to_string(&Interval::from_millis(90_000))?,
+        "\"PT1M30S\""
+    );
+    assert_eq!(
+        serde_json::from_str::<Interval>("\"P1D\"")?,
+        interval(0, 1, 0)
+    );
+    assert!(serde_json::from_str::<Interval>("\"1 day\"").is_err());
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use chrono::{TimeZone, Utc};
+use eyre::*;
+use gen::error_code::EnumErrorCode;
+use gen::model::*;
+use iloverust::services::auth_endpoints::*;
+use lib::totp;
+use testkit::{ensure_code, TestApp, DEVICE_ID, TEST_PASSWORD};
+
+async fn enroll(app: &TestApp, username: &str) -> Result<EnrollTotpResponse> {
+    app.auth_request(
+        EnrollTotpRequest {
+            username: username.to_owned(),
+            password: TEST_PASSWORD.to_owned(),
+        },
+        endpoint_auth_enroll_totp(),
+    )
+    .await
+}
+
+async fn confirm(app: &TestApp, username: &str, code: &str) -> Result<ConfirmTotpResponse> {
+    app.auth_request(
+        ConfirmTotpRequest {
+            username: username.to_owned(),
+            password: TEST_PASSWORD.to_owned(),
+            code: code.to_owned(),
+        },
+        endpoint_auth_confirm_totp(),
+    )
+    .await
+}
+
+async fn disable(app: &TestApp, username: &str, code: &str) -> Result<DisableTotpResponse> {
+    app.auth_request(
+        DisableTotpRequest {
+            username: username.to_owned(),
+            password: TEST_PASSWORD.to_owned(),
+            code: code.to_owned(),
+        },
+        endpoint_auth_disable_totp(),
+    )
+    .await
+}
+
+/// Enables 2FA, returning the secret and the recovery codes
+async fn enable_totp(app: &TestApp, username: &str) -> Result<(Vec<u8>, Vec<String>)> {
+    let enrolled = enroll(app, username).await?;
+    let secret = data_encoding::BASE32_NOPAD.decode(enrolled.secret.as_bytes())?;
+    let confirmed = confirm(app, username, &totp::code_at(&secret, Utc::now())).await?;
+    Ok((secret, confirmed.recovery_codes))
+}
+
+/// A code of the step after the current one, as the current one is used up by enabling
+fn next_code(secret: &[u8]) -> String {
+    totp::code_at_step(secret, totp::step_at(Utc::now()) + 1)
+}
+
+#[test]
+fn test_totp_rfc6238() {
+    // the SHA-1 test vectors of RFC 6238, truncated to 6 digits
+    let secret = b"12345678901234567890";
+    for (time, code) in [
+        (59, "287082"),
+        (1111111109, "081804"),
+        (1111111111, "050471"),
+        (1234567890, "005924"),
+        (2000000000, "279037"),
+        (20000000000, "353130"),
+    ] {
+        let time = Utc.timestamp_opt(time, 0).unwrap();
+        assert_eq!(totp::code_at(secret, time), code);
+        assert_eq!(totp::verify(secret, code, time), Some(totp::step_at(time)));
+    }
+    let time = Utc.timestamp_opt(1111111109, 0).unwrap();
+    assert!(totp::verify(secret, "081804", time + chrono::Duration::seconds(30)).is_some());
+    assert!(totp::verify(secret, "081804", time + chrono::Duration::seconds(90)).is_none());
+    assert!(totp::verify(secret, "081805", time).is_none());
+
+    let code = totp::generate_recovery_code();
+    assert_eq!(code.len(), 11);
+    assert_eq!(
+        totp::hash_recovery_code(&code),
+        totp::hash_recovery_code(&format!(" {} ", code.to_uppercase().replace('-', "")))
+    );
+    assert!(!totp::is_totp_code(&code));
+}
+
+#[tokio::test]
+async fn test_enroll_totp() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    ensure_code(
+        confirm(&app, "pepe_pablo", "123456").await,
+        EnumErrorCode::TwoFactorNotEnabled,
+    )?;
+    let enrolled = enroll(&app, "pepe_pablo").await?;
+    assert!(enrolled
+        .otpauth_uri
+        .starts_with("otpauth://totp/iloverust:pepe_pablo?secret="));
+    assert!(enrolled.otpauth_uri.contains(&enrolled.secret));
+    // until confirmed, logins are not challenged
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+
+    // a second enrollment replaces the first
+    let enrolled = enroll(&app, "pepe_pablo").await?;
+    let secret = data_encoding::BASE32_NOPAD.decode(enrolled.secret.as_bytes())?;
+    let wrong = totp::code_at(&secret, Utc::now() + chrono::Duration::minutes(5));
+    ensure_code(
+        confirm(&app, "pepe_pablo", &wrong).await,
+        EnumErrorCode::InvalidTwoFactorCode,
+    )?;
+    let confirmed = confirm(&app, "pepe_pablo", &totp::code_at(&secret, Utc::now())).await?;
+    assert_eq!(confirmed.recovery_codes.len(), 10);
+    ensure_code(
+        enroll(&app, "pepe_pablo").await,
+        EnumErrorCode::TwoFactorAlreadyEnabled,
+    )?;
+    let challenge = app
+        .login_challenged(DEVICE_ID, "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    assert!(challenge.access_token.is_none());
+    assert!(challenge.challenge_expires_at.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_login_verify_2fa() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let (secret, recovery_codes) = enable_totp(&app, "pepe_pablo").await?;
+
+    let challenge = app
+        .login_challenged(DEVICE_ID, "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let code = next_code(&secret);
+    let login = app.login_verify_2fa(&challenge, &code).await?;
+    app.user_client(&login).await?;
+    // a challenge completes once, and a code is used once
+    ensure_code(
+        app.login_verify_2fa(&challenge, &code).await,
+        EnumErrorCode::InvalidLoginChallenge,
+    )?;
+    let challenge = app
+        .login_challenged(DEVICE_ID, "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    ensure_code(
+        app.login_verify_2fa(&challenge, &code).await,
+        EnumErrorCode::InvalidTwoFactorCode,
+    )?;
+
+    // recovery codes work once each, regardless of case and dashes
+    let recovery = recovery_codes[0].to_uppercase().replace('-', "");
+    app.login_verify_2fa(&challenge, &recovery).await?;
+    let challenge = app
+        .login_challenged(DEVICE_ID, "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    ensure_code(
+        app.login_verify_2fa(&challenge, &recovery_codes[0]).await,
+        EnumErrorCode::InvalidTwoFactorCode,
+    )?;
+
+    let db = app.db.connect().await?;
+    db.query(
+        "UPDATE tbl.login_challenge SET expires_at = NOW() - interval '1 second'",
+        &[],
+    )
+    .await?;
+    ensure_code(
+        app.login_verify_2fa(&challenge, &recovery_codes[1]).await,
+        EnumErrorCode::InvalidLoginChallenge,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_two_factor_rate_limit() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let (secret, _) = enable_totp(&app, "pepe_pablo").await?;
+    // new challenges do not reset the count
+    for _ in 0..5 {
+        let challenge = app
+            .login_challenged(DEVICE_ID, "pepe_pablo", TEST_PASSWORD, EnumService::User)
+            .await?;
+        ensure_code(
+            app.login_verify_2fa(&challenge, "000000-wrong").await,
+            EnumErrorCode::InvalidTwoFactorCode,
+        )?;
+    }
+    let challenge = app
+        .login_challenged(DEVICE_ID, "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    ensure_code(
+        app.login_verify_2fa(&challenge, &next_code(&secret)).await,
+        EnumErrorCode::TemporarilyUnavailable,
+    )?;
+    ensure_code(
+        disable(&app, "pepe_pablo", &next_code(&secret)).await,
+        EnumErrorCode::TemporarilyUnavailable,
+    )?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_two_factor_codes() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    enable_totp(&app, "pepe_pablo").await?;
+    let challenge = app
+        .login_challenged(DEVICE_ID, "pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let results = futures::future::join_all(
+        (0..10).map(|_| app.login_verify_2fa(&challenge, "000000-wrong")),
+    )
+    .await;
+    // each code counts the failures before it, however many run at once
+    let checked = results
+        .into_iter()
+        .filter_map(|x| ensure_code(x, EnumErrorCode::InvalidTwoFactorCode).ok())
+        .count();
+    assert_eq!(checked, 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_disable_totp() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    ensure_code(
+        disable(&app, "pepe_pablo", "123456").await,
+        EnumErrorCode::TwoFactorNotEnabled,
+    )?;
+    let (_, recovery_codes) = enable_totp(&app, "pepe_pablo").await?;
+    ensure_code(
+        disable(&app, "pepe_pablo", "wrong-code").await,
+        EnumErrorCode::InvalidTwoFactorCode,
+    )?;
+    disable(&app, "pepe_pablo", &recovery_codes[0]).await?;
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+
+    // enabling again hands out new recovery codes
+    let (_, new_codes) = enable_totp(&app, "pepe_pablo").await?;
+    ensure_code(
+        disable(&app, "pepe_pablo", &recovery_codes[1]).await,
+        EnumErrorCode::InvalidTwoFactorCode,
+    )?;
+    disable(&app, "pepe_pablo", &new_codes[0]).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_admin_requires_2fa() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("admin_pablo", TEST_PASSWORD).await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let db = app.db.connect().await?;
+    db.query(
+        "UPDATE tbl.user SET role = 'admin' WHERE username IN ('admin_pablo', 'pepe_pablo')",
+        &[],
+    )
+    .await?;
+    let admin = app
+        .login("admin_pablo", TEST_PASSWORD, EnumService::Admin)
+        .await?;
+    let mut admin_client = app.admin_client(&admin).await?;
+    let require = |required| SetTwoFactorRequiredRequest {
+        service: EnumService::Admin,
+        required,
+    };
+    // not before the operator has enabled it
+    ensure_code(
+        admin_client
+            .request::<SetTwoFactorRequiredResponse>(30060, require(true))
+            .await,
+        EnumErrorCode::TwoFactorRequired,
+    )?;
+    let pepe = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::Admin)
+        .await?;
+    let (secret, _) = enable_totp(&app, "admin_pablo").await?;
+    // opened with the password only, the session of the operator ends as well, which may close
+    // its connection before the response arrives
+    match admin_client
+        .request::<SetTwoFactorRequiredResponse>(30060, require(true))
+        .await
+    {
+        Ok(resp) => assert_eq!(resp.revoked_sessions, 2),
+        Err(err) => ensure!(err.to_string() == "Connection closed", err),
+    }
+    ensure_code(app.admin_client(&admin).await, EnumErrorCode::InvalidToken)?;
+    ensure_code(app.admin_client(&pepe).await, EnumErrorCode::InvalidToken)?;
+
+    ensure_code(
+        app.login("pepe_pablo", TEST_PASSWORD, EnumService::Admin)
+            .await,
+        EnumErrorCode::TwoFactorRequired,
+    )?;
+    // other services are not affected
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    let challenge = app
+        .login_challenged(DEVICE_ID, "admin_pablo", TEST_PASSWORD, EnumService::Admin)
+        .await?;
+    let admin = app
+        .login_verify_2fa(&challenge, &next_code(&secret))
+        .await?;
+    let mut admin_client = app.admin_client(&admin).await?;
+    // sessions opened with the second factor are kept
+    let resp: SetTwoFactorRequiredResponse = admin_client.request(30060, require(true)).await?;
+    assert_eq!(resp.revoked_sessions, 0);
+    let resp: SetTwoFactorRequiredResponse = admin_client.request(30060, require(false)).await?;
+    assert_eq!(resp.revoked_sessions, 0);
+    app.login("pepe_pablo", TEST_PASSWORD, EnumService::Admin)
+        .await?;
+    Ok(())
+}
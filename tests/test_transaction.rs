@@ -0,0 +1,106 @@
+use eyre::*;
+use gen::database::*;
+use lib::database::IsolationLevel;
+use std::sync::Arc;
+use testkit::TestDatabase;
+
+fn organization(name: &str) -> FunOrganizationInsertReq {
+    FunOrganizationInsertReq {
+        name: name.to_owned(),
+        country: "ES".to_owned(),
+        tax_id: "B00000000".to_owned(),
+        address: "Main 1".to_owned(),
+        note: "".to_owned(),
+    }
+}
+
+async fn exists(db: &DbClient, name: &str) -> Result<bool> {
+    let resp = db
+        .fun_organization_list(FunOrganizationListReq {
+            offset: 0,
+            limit: 10,
+            name: Some(name.to_owned()),
+            country: None,
+        })
+        .await?;
+    Ok(!resp.rows.is_empty())
+}
+
+#[tokio::test]
+async fn test_commit_and_rollback() -> Result<()> {
+    let database = TestDatabase::create().await?;
+    let db = DbClient::new(database.connect().await?);
+
+    let tx = db.begin(IsolationLevel::ReadCommitted).await?;
+    let row = tx.fun_organization_insert_one(organization("a")).await?;
+    // visible inside the transaction only
+    tx.fun_organization_get_one(FunOrganizationGetReq {
+        pkey_id: row.pkey_id,
+    })
+    .await?;
+    assert!(!exists(&db, "a").await?);
+    tx.rollback().await?;
+    assert!(!exists(&db, "a").await?);
+
+    // dropping without commit rolls back as well
+    let tx = db.begin(IsolationLevel::ReadCommitted).await?;
+    tx.fun_organization_insert_one(organization("b")).await?;
+    drop(tx);
+    assert!(!exists(&db, "b").await?);
+
+    let tx = db.begin(IsolationLevel::Serializable).await?;
+    tx.fun_organization_insert_one(organization("c")).await?;
+    tx.fun_organization_insert_one(organization("d")).await?;
+    tx.commit().await?;
+    assert!(exists(&db, "c").await?);
+    assert!(exists(&db, "d").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_statement_rolls_back() -> Result<()> {
+    let database = TestDatabase::create().await?;
+    let db = DbClient::new(database.connect().await?);
+    let tx = db.begin(IsolationLevel::ReadCommitted).await?;
+    let row = tx.fun_organization_insert_one(organization("a")).await?;
+    // a missing row raises NotFound
+    let result = tx
+        .fun_organization_delete(FunOrganizationDeleteReq {
+            pkey_id: row.pkey_id + 1,
+        })
+        .await;
+    assert!(result.is_err());
+    // postgres aborts the transaction, so even a commit keeps nothing
+    let _ = tx.commit().await;
+    assert!(!exists(&db, "a").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_transaction() -> Result<()> {
+    let mock = MockDbClient::new().on_fun_organization_get(|req| {
+        Ok(FunOrganizationGetResp {
+            rows: vec![FunOrganizationGetRespRow {
+                pkey_id: req.pkey_id,
+                name: "a".to_owned(),
+                country: "ES".to_owned(),
+                tax_id: "B00000000".to_owned(),
+                address: "Main 1".to_owned(),
+                note: "".to_owned(),
+            }],
+        })
+    });
+    let db = DbClient::with_api(Arc::new(mock));
+    // calls go to the mock, commit and rollback do nothing
+    let tx = db.begin(IsolationLevel::ReadCommitted).await?;
+    let row = tx
+        .fun_organization_get_one(FunOrganizationGetReq { pkey_id: 7 })
+        .await?;
+    assert_eq!(row.pkey_id, 7);
+    tx.commit().await?;
+    db.begin(IsolationLevel::ReadCommitted)
+        .await?
+        .rollback()
+        .await?;
+    Ok(())
+}
@@ -1,39 +1,56 @@
-pub mod tools;
-use crate::endpoints::*;
 use eyre::*;
+use gen::error_code::EnumErrorCode;
 use gen::model::*;
-use lib::utils::encode_header;
-use tools::*;
+use testkit::{ensure_code, TestApp, TEST_PASSWORD};
 
-#[path = "../src/service/auth/endpoints.rs"]
-pub mod endpoints;
 #[tokio::test]
 async fn test_authorize() -> Result<()> {
-    let mut client = get_ws_auth_client(&encode_header(
-        &LoginRequest {
-            username: "pepe_pablo".to_string(),
-            password: "AHJQ6X1H68SK8D9P6WW0".to_string(),
-            service_code: EnumService::User as _,
-            device_id: "24787297130491616".to_string(),
-            device_os: "android".to_string(),
-        },
-        endpoint_auth_login(),
-    )?)
-    .await?;
-    let res: LoginResponse = client.recv_resp().await?;
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    app.user_client(&login).await?;
+    // the username is matched like on login
+    app.authorized_client(EnumService::User, " Pepe_Pablo ", &login.access_token)
+        .await?;
+    ensure_code(
+        app.authorized_client(EnumService::User, "pepe_pablo", "not a token")
+            .await,
+        EnumErrorCode::InvalidToken,
+    )?;
+    Ok(())
+}
 
-    let mut client = get_ws_user_client(&encode_header(
-        AuthorizeRequest {
-            username: res.username,
-            token: res.user_token,
-            service_code: EnumService::User as _,
-            device_id: "24787297130491616".to_string(),
-            device_os: "android".to_string(),
-        },
-        endpoint_auth_authorize(),
-    )?)
-    .await?;
-    let res: AuthorizeResponse = client.recv_resp().await?;
-    println!("{:?}", res);
+#[tokio::test]
+async fn test_authorize_blocked_user() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    app.db
+        .connect()
+        .await?
+        .query(
+            "UPDATE tbl.user SET is_blocked = TRUE WHERE username = 'pepe_pablo'",
+            &[],
+        )
+        .await?;
+    ensure_code(app.user_client(&login).await, EnumErrorCode::BlockedUser)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_authorize_wrong_service() -> Result<()> {
+    let app = TestApp::start().await?;
+    app.signup("pepe_pablo", TEST_PASSWORD).await?;
+    let login = app
+        .login("pepe_pablo", TEST_PASSWORD, EnumService::User)
+        .await?;
+    ensure!(
+        app.admin_client(&login).await.is_err(),
+        "a user token authorized the admin service"
+    );
     Ok(())
 }
@@ -0,0 +1,123 @@
+use eyre::*;
+use gen::model::*;
+use iloverust::endpoints::endpoint_auth_signup;
+use lib::json_schema::{validate_json, SchemaError};
+use lib::validation::*;
+
+fn signup() -> SignupRequest {
+    SignupRequest {
+        username: "pepe".to_owned(),
+        password: "secret".to_owned(),
+        email: "pepe@example.com".to_owned(),
+        phone: "+34600000000".to_owned(),
+        agreed_tos: true,
+        agreed_privacy: true,
+    }
+}
+
+fn errors(req: &impl Validate) -> Vec<String> {
+    match req.validate() {
+        Ok(()) => vec![],
+        Err(err) => err.0.iter().map(|x| x.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_signup_constraints() -> Result<()> {
+    signup().validate()?;
+    let req = SignupRequest {
+        username: "".to_owned(),
+        password: "".to_owned(),
+        email: "pepe".to_owned(),
+        phone: "600-000".to_owned(),
+        ..signup()
+    };
+    assert_eq!(
+        errors(&req),
+        vec![
+            "username: must be at least 1 characters",
+            "password: must be at least 1 characters",
+            "email: must be a valid email",
+            "phone: must be a valid phone",
+        ]
+    );
+    // lengths are counted in characters, not bytes
+    let req = SignupRequest {
+        username: "ñ".repeat(20),
+        ..signup()
+    };
+    assert!(errors(&req).is_empty());
+    let req = SignupRequest {
+        username: "a".repeat(21),
+        email: format!("{}@example.com", "a".repeat(320)),
+        ..signup()
+    };
+    assert_eq!(
+        errors(&req),
+        vec![
+            "username: must be at most 20 characters",
+            "email: must be at most 320 characters",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_schema_agrees() -> Result<()> {
+    // the constraints in the endpoint's json schema are the ones of the generated type
+    let schema = &endpoint_auth_signup().json_schema["parameters"];
+    let req = SignupRequest {
+        username: "a".repeat(21),
+        email: "pepe".to_owned(),
+        ..signup()
+    };
+    let mut paths = validate_json(schema, &serde_json::to_value(&req)?)
+        .unwrap_err()
+        .0
+        .into_iter()
+        .map(|x| x.path)
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert_eq!(paths, vec!["email", "username"]);
+    assert_eq!(req.validate().unwrap_err().0.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_range_constraints() -> Result<()> {
+    ListUsersRequest {
+        offset: 0,
+        limit: 1000,
+    }
+    .validate()?;
+    assert_eq!(
+        errors(&ListUsersRequest {
+            offset: -1,
+            limit: 1001
+        }),
+        vec!["offset: must be at least 0", "limit: must be at most 1000"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_checks() {
+    let mut errors: Vec<SchemaError> = vec![];
+    check_pattern("code", "12a", "^[0-9]+$", &mut errors);
+    check_pattern("code", "123", "^[0-9]+$", &mut errors);
+    check_pattern("code", "123", "(", &mut errors);
+    check_items("tags", 0, Some(1), None, &mut errors);
+    check_format("email", "a b@example.com", "email", &mut errors);
+    // unknown formats are not checked
+    check_format("x", "anything", "uuid", &mut errors);
+    let errors = errors.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            "code: must match ^[0-9]+$",
+            "code: invalid pattern (",
+            "tags: must have at least 1 items",
+            "email: must be a valid email",
+        ]
+    );
+}